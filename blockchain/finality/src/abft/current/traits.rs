@@ -41,8 +41,8 @@ where
     fn data_finalized(
         &mut self,
         data: AlephData<H::Unverified>,
-        _creator: current_aleph_bft::NodeIndex,
+        creator: current_aleph_bft::NodeIndex,
     ) {
-        OrderedDataInterpreter::data_finalized(self, data)
+        OrderedDataInterpreter::data_finalized(self, data, creator.into())
     }
 }
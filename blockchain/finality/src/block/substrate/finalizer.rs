@@ -29,6 +29,7 @@ use crate::{
         Finalizer,
     },
     finalization::{AlephFinalizer, BlockFinalizer},
+    BlockId,
 };
 
 impl<BE, C> Finalizer<Justification> for AlephFinalizer<Block, BE, C>
@@ -40,10 +41,13 @@ where
 
     fn finalize(&self, justification: Justification) -> Result<(), Self::Error> {
         match justification.inner_justification {
-            InnerJustification::AlephJustification(aleph_justification) => self.finalize_block(
-                (justification.header.hash(), *justification.header.number()).into(),
-                aleph_justification.into(),
-            ),
+            InnerJustification::AlephJustification(aleph_justification) => {
+                let block_id: BlockId =
+                    (justification.header.hash(), *justification.header.number()).into();
+                self.finalize_block(block_id.clone(), aleph_justification.clone().into())?;
+                self.notify_finalized(block_id, aleph_justification);
+                Ok(())
+            }
             _ => Err(Self::Error::BadJustification(
                 "Trying fo finalize the genesis block using virtual sync justification."
                     .to_string(),
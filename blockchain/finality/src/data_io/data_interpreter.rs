@@ -23,6 +23,8 @@ use std::{default::Default, marker::PhantomData};
 use futures::channel::mpsc;
 use log::{debug, error, warn};
 
+use primitives::BlockNumber;
+
 use crate::{
     block::{Header, HeaderVerifier},
     data_io::{
@@ -31,8 +33,9 @@ use crate::{
         status_provider::get_proposal_status,
         AlephData, ChainInfoProvider,
     },
+    metrics::SessionScoreboard,
     mpsc::TrySendError,
-    BlockId, SessionBoundaries,
+    BlockId, NodeIndex, SessionBoundaries,
 };
 
 type InterpretersChainInfoProvider<CIP> =
@@ -51,7 +54,10 @@ where
     chain_info_provider: InterpretersChainInfoProvider<CIP>,
     verifier: V,
     last_finalized_by_aleph: BlockId,
+    // The highest block proposed by the units ordered so far, the lag of proposals is measured against it.
+    round_head: BlockNumber,
     session_boundaries: SessionBoundaries,
+    scoreboard: SessionScoreboard,
     _phantom: PhantomData<H>,
 }
 
@@ -87,6 +93,7 @@ where
         mut chain_info: CIP,
         verifier: V,
         session_boundaries: SessionBoundaries,
+        scoreboard: SessionScoreboard,
     ) -> Self {
         let last_finalized_by_aleph =
             get_last_block_prev_session(session_boundaries.clone(), &mut chain_info);
@@ -98,9 +105,11 @@ where
         OrderedDataInterpreter {
             blocks_to_finalize_tx,
             chain_info_provider,
+            round_head: last_finalized_by_aleph.number(),
             last_finalized_by_aleph,
             session_boundaries,
             verifier,
+            scoreboard,
             _phantom: PhantomData,
         }
     }
@@ -120,6 +129,7 @@ where
     pub fn blocks_to_finalize_from_data(
         &mut self,
         new_data: AlephData<H::Unverified>,
+        creator: NodeIndex,
     ) -> Vec<BlockId> {
         let unvalidated_proposal = new_data.head_proposal;
         let proposal = match unvalidated_proposal.validate_bounds(&self.session_boundaries) {
@@ -134,12 +144,20 @@ where
         // analyzed for possible safety violations.
 
         use ProposalStatus::*;
+        self.round_head = self.round_head.max(proposal.number_top_block());
+        let lag = self.round_head - proposal.number_top_block();
         let status = get_proposal_status(
             &mut self.chain_info_provider,
             &mut self.verifier,
             &proposal,
             None,
         );
+        let finalized_blocks = match &status {
+            Finalize(blocks) => blocks.len(),
+            _ => 0,
+        };
+        self.scoreboard
+            .report_ordered_unit(creator, lag, finalized_blocks);
         match status {
             Finalize(blocks) => blocks,
             Ignore => {
@@ -154,8 +172,8 @@ where
         }
     }
 
-    pub fn data_finalized(&mut self, data: AlephData<H::Unverified>) {
-        for block in self.blocks_to_finalize_from_data(data) {
+    pub fn data_finalized(&mut self, data: AlephData<H::Unverified>, creator: NodeIndex) {
+        for block in self.blocks_to_finalize_from_data(data, creator) {
             self.set_last_finalized(block.clone());
             self.chain_info_provider()
                 .inner()
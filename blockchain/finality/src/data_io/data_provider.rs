@@ -28,8 +28,12 @@ use sp_runtime::{traits::Zero, SaturatedConversion};
 use crate::{
     primitives ::BlockNumber,
     block::{BestBlockSelector, Header, HeaderBackend, UnverifiedHeader},
-    data_io::{proposal::UnvalidatedAlephProposal, AlephData, MAX_DATA_BRANCH_LEN},
-    metrics::{AllBlockMetrics, Checkpoint},
+    data_io::{
+        proposal::UnvalidatedAlephProposal,
+        proposal_policy::{BestBlockPolicy, ProposalPolicy},
+        AlephData, MAX_DATA_BRANCH_LEN,
+    },
+    metrics::{AllBlockMetrics, Checkpoint, SessionScoreboard},
     party::manager::Runnable,
     BlockId, SessionBoundaries,
 };
@@ -79,6 +83,19 @@ where
     }
 }
 
+// Returns the ancestor of block at the level given by num, or None if some header on the way is unknown.
+pub fn get_ancestor<H, C>(client: &C, block: &BlockId, num: BlockNumber) -> Option<BlockId>
+where
+    H: Header,
+    C: HeaderBackend<H>,
+{
+    let mut curr_block = block.clone();
+    while curr_block.number() > num {
+        curr_block = get_parent(client, &curr_block)?;
+    }
+    Some(curr_block)
+}
+
 pub enum ProposalPreparationError {
     MissingHeader,
     BestContradictsFinalized,
@@ -149,19 +166,22 @@ impl Default for ChainTrackerConfig {
 struct ChainInfo {
     best_block_in_session: BlockId,
     highest_finalized: BlockId,
+    proposal_target: Option<BlockId>,
 }
 
 /// ChainTracker keeps track of the best_block in a given session and allows to generate `AlephData`.
 /// Internally it frequently updates a `data_to_propose` field that is shared with a `DataProvider`, which
 /// in turn is a tiny wrapper around this single shared resource that takes out `data_to_propose` whenever
-/// `get_data` is called.
-pub struct ChainTracker<H, BBS, C>
+/// `get_data` is called. Which branch ends up in `data_to_propose` is decided by the `ProposalPolicy`.
+pub struct ChainTracker<H, BBS, C, P = BestBlockPolicy>
 where
     H: Header,
     C: HeaderBackend<H>,
     BBS: BestBlockSelector<H> + 'static,
+    P: ProposalPolicy<H, C>,
 {
     chain_tip_selections_strategy: BBS,
+    proposal_policy: P,
     client: C,
     data_to_propose: Arc<Mutex<Option<AlephData<H::Unverified>>>>,
     session_boundaries: SessionBoundaries,
@@ -170,14 +190,16 @@ where
     _phantom: PhantomData<H>,
 }
 
-impl<H, BBS, C> ChainTracker<H, BBS, C>
+impl<H, BBS, C, P> ChainTracker<H, BBS, C, P>
 where
     H: Header,
     C: HeaderBackend<H>,
     BBS: BestBlockSelector<H> + 'static,
+    P: ProposalPolicy<H, C>,
 {
     pub fn new(
         chain_tip_selections_strategy: BBS,
+        proposal_policy: P,
         client: C,
         session_boundaries: SessionBoundaries,
         config: ChainTrackerConfig,
@@ -187,6 +209,7 @@ where
         (
             ChainTracker {
                 chain_tip_selections_strategy,
+                proposal_policy,
                 client,
                 data_to_propose: data_to_propose.clone(),
                 session_boundaries,
@@ -197,15 +220,31 @@ where
             DataProvider {
                 data_to_propose,
                 metrics,
+                scoreboard: SessionScoreboard::Noop,
             },
         )
     }
 
+    // Returns the block chosen by the proposal policy, reduced to the last block of the session if needed.
+    fn proposal_target(&self, best_block_in_session: &BlockId, finalized_block: &BlockId) -> BlockId {
+        let target = self.proposal_policy.proposal_target(
+            &self.client,
+            best_block_in_session,
+            finalized_block,
+        );
+        let last_block = self.session_boundaries.last_block();
+        if target.number() <= last_block {
+            return target;
+        }
+        get_ancestor(&self.client, &target, last_block)
+            .unwrap_or_else(|| best_block_in_session.clone())
+    }
+
     fn update_data(&mut self, best_block_in_session: &BlockId) {
-        // We use best_block_in_session argument and the top_finalized block from the client and compute
-        // the corresponding `AlephData<B>` in `data_to_propose` for AlephBFT. To not recompute this many
-        // times we remember these "inputs" in `prev_chain_info` and upon match we leave the old value
-        // of `data_to_propose` unaffected.
+        // We use best_block_in_session argument and the top_finalized block from the client to let the
+        // proposal policy choose a target, and compute the corresponding `AlephData<B>` in `data_to_propose`
+        // for AlephBFT. To not recompute this many times we remember these "inputs" in `prev_chain_info`
+        // and upon match we leave the old value of `data_to_propose` unaffected.
         let finalized_block = self.client.top_finalized_id();
         if finalized_block.number() >= self.session_boundaries.last_block() {
             // This session is already finished, but this instance of ChainTracker has not been terminated yet.
@@ -214,9 +253,14 @@ where
             return;
         }
 
+        // The target is only meaningful if the best block is not below the finalized one, see below.
+        let proposal_target = (best_block_in_session.number() >= finalized_block.number())
+            .then(|| self.proposal_target(best_block_in_session, &finalized_block));
+
         if let Some(prev) = &self.prev_chain_info {
             if prev.best_block_in_session == *best_block_in_session
                 && prev.highest_finalized == finalized_block
+                && prev.proposal_target == proposal_target
             {
                 // This is exactly the same state that we processed last time in update_data.
                 // No point in recomputing.
@@ -227,22 +271,24 @@ where
         self.prev_chain_info = Some(ChainInfo {
             best_block_in_session: best_block_in_session.clone(),
             highest_finalized: finalized_block.clone(),
+            proposal_target: proposal_target.clone(),
         });
 
-        if best_block_in_session.number() < finalized_block.number() {
-            // Because of the client synchronization, in extremely rare cases this could happen.
-            warn!(
-                target: LOG_TARGET,
-                "Error updating data. best_block {:?} is lower than finalized {:?}.",
-                best_block_in_session,
-                finalized_block
-            );
-            return;
-        }
+        let proposal_target = match proposal_target {
+            Some(target) => target,
+            None => {
+                // Because of the client synchronization, in extremely rare cases this could happen.
+                warn!(
+                    target: LOG_TARGET,
+                    "Error updating data. best_block {:?} is lower than finalized {:?}.",
+                    best_block_in_session,
+                    finalized_block
+                );
+                return;
+            }
+        };
 
-        if let Ok(proposal) =
-            get_proposal(&self.client, best_block_in_session.clone(), finalized_block)
-        {
+        if let Ok(proposal) = get_proposal(&self.client, proposal_target, finalized_block) {
             *self.data_to_propose.lock() = proposal;
         }
     }
@@ -319,11 +365,12 @@ where
 }
 
 #[async_trait::async_trait]
-impl<H, BBS, C> Runnable for ChainTracker<H, BBS, C>
+impl<H, BBS, C, P> Runnable for ChainTracker<H, BBS, C, P>
 where
     H: Header,
     C: HeaderBackend<H> + 'static,
     BBS: BestBlockSelector<H> + 'static,
+    P: ProposalPolicy<H, C>,
 {
     async fn run(mut self, exit: oneshot::Receiver<()>) {
         tokio::select! {
@@ -338,6 +385,7 @@ where
 pub struct DataProvider<UH: UnverifiedHeader> {
     data_to_propose: Arc<Mutex<Option<AlephData<UH>>>>,
     metrics: AllBlockMetrics,
+    scoreboard: SessionScoreboard,
 }
 
 // Honest nodes propose data in session `k` as follows:
@@ -348,9 +396,19 @@ pub struct DataProvider<UH: UnverifiedHeader> {
 //    then the node proposes `Empty`, otherwise the node proposes a branch extending from one block above
 //    last finalized till `best_block` with the restriction that the branch must be truncated to length
 //    at most MAX_DATA_BRANCH_LEN.
+// This is the behaviour of the default `BestBlockPolicy`, other `ProposalPolicy`s may propose a different
+// branch above last finalized.
 impl<UH: UnverifiedHeader> DataProvider<UH> {
+    /// Reports every unit created by this node, i.e. every call to `get_data`, to the scoreboard.
+    pub fn with_scoreboard(mut self, scoreboard: SessionScoreboard) -> Self {
+        self.scoreboard = scoreboard;
+        self
+    }
+
     pub async fn get_data(&mut self) -> Option<AlephData<UH>> {
         let data_to_propose = (*self.data_to_propose.lock()).take();
+        // AlephBFT asks for data exactly once per unit it creates.
+        self.scoreboard.report_unit_created();
 
         if let Some(data) = &data_to_propose {
             let top_block = data.head_proposal.top_block();
@@ -365,23 +423,29 @@ impl<UH: UnverifiedHeader> DataProvider<UH> {
 
 #[cfg(test)]
 mod tests {
-    use std::{future::Future, sync::Arc, time::Duration};
+    use std::{collections::HashMap, future::Future, sync::Arc, time::Duration};
 
     use futures::channel::oneshot;
+    use parking_lot::Mutex;
     use tokio::time::sleep;
 
     use crate::{
+        block::UnverifiedHeader,
         data_io::{
             data_provider::{ChainTracker, ChainTrackerConfig},
-            AlephData, DataProvider, MAX_DATA_BRANCH_LEN,
+            AlephData, BestBlockPolicy, DataProvider, ImportCoverage, ImportCoveragePolicy,
+            MaxLagPolicy, PinnedBranch, PinnedBranchPolicy, ProposalPolicy, MAX_DATA_BRANCH_LEN,
         },
         metrics::AllBlockMetrics,
         party::manager::Runnable,
         testing::{
             client_chain_builder::ClientChainBuilder,
-            mocks::{aleph_data_from_blocks, THeader, TestClientBuilder, TestClientBuilderExt},
+            mocks::{
+                aleph_data_from_blocks, THeader, TestClient, TestClientBuilder,
+                TestClientBuilderExt,
+            },
         },
-        SessionBoundaryInfo, SessionId, SessionPeriod,
+        BlockId, SessionBoundaryInfo, SessionId, SessionPeriod,
     };
 
     const SESSION_LEN: u32 = 100;
@@ -391,7 +455,28 @@ mod tests {
     //  Sleep time that's usually enough for the internal refreshing in ChainTracker to finish.
     const SLEEP_TIME: Duration = Duration::from_millis(15);
 
-    fn prepare_chain_tracker_test() -> (
+    #[derive(Clone, Default)]
+    struct MockImportCoverage(Arc<Mutex<HashMap<BlockId, usize>>>);
+
+    impl MockImportCoverage {
+        fn set(&self, block: BlockId, peers: usize) {
+            self.0.lock().insert(block, peers);
+        }
+
+        fn clear(&self) {
+            self.0.lock().clear();
+        }
+    }
+
+    impl ImportCoverage for MockImportCoverage {
+        fn peers_with_block(&self, block: &BlockId) -> usize {
+            self.0.lock().get(block).copied().unwrap_or(0)
+        }
+    }
+
+    fn prepare_chain_tracker_test<P: ProposalPolicy<THeader, Arc<TestClient>>>(
+        proposal_policy: P,
+    ) -> (
         impl Future<Output = ()>,
         oneshot::Sender<()>,
         ClientChainBuilder,
@@ -411,6 +496,7 @@ mod tests {
 
         let (chain_tracker, data_provider) = ChainTracker::new(
             select_chain,
+            proposal_policy,
             client,
             session_boundaries,
             config,
@@ -453,7 +539,17 @@ mod tests {
         F: Future,
         S: FnOnce(ClientChainBuilder, DataProvider<THeader>) -> F,
     {
-        let (task_handle, exit, chain_builder, data_provider) = prepare_chain_tracker_test();
+        run_test_with_policy(BestBlockPolicy, scenario).await
+    }
+
+    async fn run_test_with_policy<P, F, S>(proposal_policy: P, scenario: S)
+    where
+        P: ProposalPolicy<THeader, Arc<TestClient>>,
+        F: Future,
+        S: FnOnce(ClientChainBuilder, DataProvider<THeader>) -> F,
+    {
+        let (task_handle, exit, chain_builder, data_provider) =
+            prepare_chain_tracker_test(proposal_policy);
         let chain_tracker_handle = tokio::spawn(task_handle);
 
         scenario(chain_builder, data_provider).await;
@@ -533,4 +629,100 @@ mod tests {
         })
         .await;
     }
+    #[tokio::test(flavor = "multi_thread")]
+    async fn max_lag_policy_caps_proposal() {
+        const MAX_LAG: u32 = 3;
+        let policy = MaxLagPolicy::new(BestBlockPolicy, MAX_LAG);
+        run_test_with_policy(policy, |mut chain_builder, mut data_provider| async move {
+            let blocks = chain_builder
+                .initialize_single_branch_and_import(2 * MAX_DATA_BRANCH_LEN)
+                .await;
+            let data = sleep_until_data_available(&mut data_provider).await;
+            let expected_data = aleph_data_from_blocks(blocks[..MAX_LAG as usize].to_vec());
+            assert_eq!(data, expected_data);
+
+            chain_builder.finalize_block(&blocks[0].header.hash());
+            let data = sleep_until_data_available(&mut data_provider).await;
+            let expected_data = aleph_data_from_blocks(blocks[1..=MAX_LAG as usize].to_vec());
+            assert_eq!(data, expected_data);
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn follows_pinned_branch() {
+        let pinned = PinnedBranch::default();
+        let policy = PinnedBranchPolicy::new(BestBlockPolicy, pinned.clone());
+        run_test_with_policy(policy, |mut chain_builder, mut data_provider| async move {
+            let genesis = chain_builder.genesis_hash();
+            let best_branch = chain_builder
+                .build_and_import_branch_above(&genesis, 5)
+                .await;
+            let data = sleep_until_data_available(&mut data_provider).await;
+            assert_eq!(data, aleph_data_from_blocks(best_branch.clone()));
+
+            let pinned_branch = chain_builder
+                .build_and_import_branch_above(&genesis, 3)
+                .await;
+            pinned.pin(pinned_branch.last().unwrap().header.id());
+            let data = sleep_until_data_available(&mut data_provider).await;
+            assert_eq!(data, aleph_data_from_blocks(pinned_branch));
+
+            pinned.unpin();
+            let data = sleep_until_data_available(&mut data_provider).await;
+            assert_eq!(data, aleph_data_from_blocks(best_branch));
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ignores_pinned_branch_conflicting_with_finalized() {
+        let pinned = PinnedBranch::default();
+        let policy = PinnedBranchPolicy::new(BestBlockPolicy, pinned.clone());
+        run_test_with_policy(policy, |mut chain_builder, mut data_provider| async move {
+            let genesis = chain_builder.genesis_hash();
+            let best_branch = chain_builder
+                .build_and_import_branch_above(&genesis, 5)
+                .await;
+            let data = sleep_until_data_available(&mut data_provider).await;
+            assert_eq!(data, aleph_data_from_blocks(best_branch.clone()));
+
+            let pinned_branch = chain_builder
+                .build_and_import_branch_above(&genesis, 3)
+                .await;
+            chain_builder.finalize_block(&best_branch[0].header.hash());
+            pinned.pin(pinned_branch.last().unwrap().header.id());
+
+            let data = sleep_until_data_available(&mut data_provider).await;
+            assert_eq!(data, aleph_data_from_blocks(best_branch[1..].to_vec()));
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn prefers_blocks_covered_by_peers() {
+        const MIN_PEERS: usize = 2;
+        let coverage = MockImportCoverage::default();
+        let policy = ImportCoveragePolicy::new(BestBlockPolicy, coverage.clone(), MIN_PEERS);
+        run_test_with_policy(policy, |mut chain_builder, mut data_provider| async move {
+            let blocks = chain_builder
+                .initialize_single_branch(2 * MAX_DATA_BRANCH_LEN)
+                .await;
+            for block in &blocks[..3] {
+                coverage.set(block.header.id(), MIN_PEERS);
+            }
+            coverage.set(blocks[3].header.id(), MIN_PEERS - 1);
+            chain_builder.import_branch(blocks.clone()).await;
+
+            let data = sleep_until_data_available(&mut data_provider).await;
+            assert_eq!(data, aleph_data_from_blocks(blocks[..3].to_vec()));
+
+            // Without any coverage data the best block is proposed as usual.
+            coverage.clear();
+            let data = sleep_until_data_available(&mut data_provider).await;
+            let expected_data = aleph_data_from_blocks(blocks[..MAX_DATA_BRANCH_LEN].to_vec());
+            assert_eq!(data, expected_data);
+        })
+        .await;
+    }
 }
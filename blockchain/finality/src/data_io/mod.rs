@@ -33,6 +33,7 @@ mod data_interpreter;
 mod data_provider;
 mod data_store;
 mod proposal;
+mod proposal_policy;
 mod status_provider;
 
 /// TODO: This is only temporary so we can change the proposal type once. Should be removed after that is done, and only the current version should be used.
@@ -43,6 +44,10 @@ pub use data_interpreter::OrderedDataInterpreter;
 pub use data_provider::{ChainTracker, DataProvider};
 pub use data_store::{DataStore, DataStoreConfig};
 pub use proposal::UnvalidatedAlephProposal;
+pub use proposal_policy::{
+    BestBlockPolicy, ImportCoverage, ImportCoveragePolicy, MaxLagPolicy, PinnedBranch,
+    PinnedBranchPolicy, ProposalPolicy, ProposalPolicyConfig,
+};
 
 // Maximum number of blocks above the last finalized allowed in an AlephBFT proposal.
 pub const MAX_DATA_BRANCH_LEN: usize = 7;
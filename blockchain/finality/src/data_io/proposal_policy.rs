@@ -0,0 +1,320 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{cmp::min, sync::Arc};
+
+use log::{debug, warn};
+use parking_lot::Mutex;
+use sp_runtime::SaturatedConversion;
+
+use crate::{
+    block::{Header, HeaderBackend},
+    data_io::{
+        data_provider::{get_ancestor, get_parent},
+        MAX_DATA_BRANCH_LEN,
+    },
+    primitives::BlockNumber,
+    BlockId,
+};
+
+const LOG_TARGET: &str = "aleph-data-store";
+
+/// Decides which branch the node proposes to AlephBFT.
+///
+/// Given the best block in the current session and the highest finalized block, a policy returns
+/// the block up to which the branch above `finalized_block` should be proposed. The returned block
+/// should be a descendant of `finalized_block`, returning `finalized_block` itself results in an empty
+/// proposal. Targets above the end of the session are reduced to the last block of the session.
+pub trait ProposalPolicy<H, C>: Send + Sync + 'static
+where
+    H: Header,
+    C: HeaderBackend<H>,
+{
+    fn proposal_target(
+        &self,
+        client: &C,
+        best_block_in_session: &BlockId,
+        finalized_block: &BlockId,
+    ) -> BlockId;
+}
+
+impl<H, C> ProposalPolicy<H, C> for Box<dyn ProposalPolicy<H, C>>
+where
+    H: Header,
+    C: HeaderBackend<H> + 'static,
+{
+    fn proposal_target(
+        &self,
+        client: &C,
+        best_block_in_session: &BlockId,
+        finalized_block: &BlockId,
+    ) -> BlockId {
+        self.as_ref()
+            .proposal_target(client, best_block_in_session, finalized_block)
+    }
+}
+
+/// The default policy -- always proposes the best block in the session.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BestBlockPolicy;
+
+impl<H, C> ProposalPolicy<H, C> for BestBlockPolicy
+where
+    H: Header,
+    C: HeaderBackend<H>,
+{
+    fn proposal_target(
+        &self,
+        _client: &C,
+        best_block_in_session: &BlockId,
+        _finalized_block: &BlockId,
+    ) -> BlockId {
+        best_block_in_session.clone()
+    }
+}
+
+/// Caps the target of the wrapped policy to at most `max_lag` blocks above the highest finalized block.
+#[derive(Clone, Debug)]
+pub struct MaxLagPolicy<P> {
+    inner: P,
+    max_lag: BlockNumber,
+}
+
+impl<P> MaxLagPolicy<P> {
+    pub fn new(inner: P, max_lag: BlockNumber) -> Self {
+        MaxLagPolicy { inner, max_lag }
+    }
+}
+
+impl<H, C, P> ProposalPolicy<H, C> for MaxLagPolicy<P>
+where
+    H: Header,
+    C: HeaderBackend<H>,
+    P: ProposalPolicy<H, C>,
+{
+    fn proposal_target(
+        &self,
+        client: &C,
+        best_block_in_session: &BlockId,
+        finalized_block: &BlockId,
+    ) -> BlockId {
+        let target = self
+            .inner
+            .proposal_target(client, best_block_in_session, finalized_block);
+        let limit = finalized_block.number().saturating_add(self.max_lag);
+        if target.number() <= limit {
+            return target;
+        }
+        get_ancestor(client, &target, limit).unwrap_or_else(|| finalized_block.clone())
+    }
+}
+
+/// Handle for pinning the branch followed by a `PinnedBranchPolicy`.
+#[derive(Clone, Debug, Default)]
+pub struct PinnedBranch(Arc<Mutex<Option<BlockId>>>);
+
+impl PinnedBranch {
+    /// Start following the branch ending in `block`.
+    pub fn pin(&self, block: BlockId) {
+        *self.0.lock() = Some(block);
+    }
+
+    /// Go back to the wrapped policy.
+    pub fn unpin(&self) {
+        *self.0.lock() = None;
+    }
+
+    pub fn pinned(&self) -> Option<BlockId> {
+        self.0.lock().clone()
+    }
+}
+
+/// Follows the branch pinned by the operator, e.g. during an incident, and the wrapped policy otherwise.
+///
+/// While the pinned block is not known to the client nothing above the finalized block is proposed.
+/// A pin which conflicts with finalization, or is already finalized, is ignored.
+#[derive(Clone, Debug)]
+pub struct PinnedBranchPolicy<P> {
+    inner: P,
+    pinned: PinnedBranch,
+}
+
+impl<P> PinnedBranchPolicy<P> {
+    pub fn new(inner: P, pinned: PinnedBranch) -> Self {
+        PinnedBranchPolicy { inner, pinned }
+    }
+}
+
+impl<H, C, P> ProposalPolicy<H, C> for PinnedBranchPolicy<P>
+where
+    H: Header,
+    C: HeaderBackend<H>,
+    P: ProposalPolicy<H, C>,
+{
+    fn proposal_target(
+        &self,
+        client: &C,
+        best_block_in_session: &BlockId,
+        finalized_block: &BlockId,
+    ) -> BlockId {
+        let pinned = match self.pinned.pinned() {
+            Some(pinned) if pinned.number() > finalized_block.number() => pinned,
+            _ => {
+                return self
+                    .inner
+                    .proposal_target(client, best_block_in_session, finalized_block)
+            }
+        };
+        match client.header(&pinned) {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Pinned block {:?} is not imported yet, proposing nothing.", pinned
+                );
+                return finalized_block.clone();
+            }
+            Err(e) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Failed to read the pinned block {:?}: {:?}, proposing nothing.", pinned, e
+                );
+                return finalized_block.clone();
+            }
+        }
+        match get_ancestor(client, &pinned, finalized_block.number()) {
+            Some(ancestor) if ancestor == *finalized_block => pinned,
+            _ => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Pinned block {:?} does not extend finalized {:?}, ignoring the pin.",
+                    pinned,
+                    finalized_block
+                );
+                self.inner
+                    .proposal_target(client, best_block_in_session, finalized_block)
+            }
+        }
+    }
+}
+
+/// Source of knowledge about which blocks have been imported by our peers.
+pub trait ImportCoverage: Send + Sync + 'static {
+    /// The number of peers known to have imported the given block.
+    fn peers_with_block(&self, block: &BlockId) -> usize;
+}
+
+impl ImportCoverage for Arc<dyn ImportCoverage> {
+    fn peers_with_block(&self, block: &BlockId) -> usize {
+        self.as_ref().peers_with_block(block)
+    }
+}
+
+/// Reduces the target of the wrapped policy to its highest ancestor that is imported by at least
+/// `min_peers` peers. If no block above the finalized one is covered well enough, the target of the
+/// wrapped policy is proposed as is, so that missing coverage data never stalls finalization.
+#[derive(Clone, Debug)]
+pub struct ImportCoveragePolicy<P, IC> {
+    inner: P,
+    coverage: IC,
+    min_peers: usize,
+}
+
+impl<P, IC> ImportCoveragePolicy<P, IC> {
+    pub fn new(inner: P, coverage: IC, min_peers: usize) -> Self {
+        ImportCoveragePolicy {
+            inner,
+            coverage,
+            min_peers,
+        }
+    }
+}
+
+impl<H, C, P, IC> ProposalPolicy<H, C> for ImportCoveragePolicy<P, IC>
+where
+    H: Header,
+    C: HeaderBackend<H>,
+    P: ProposalPolicy<H, C>,
+    IC: ImportCoverage,
+{
+    fn proposal_target(
+        &self,
+        client: &C,
+        best_block_in_session: &BlockId,
+        finalized_block: &BlockId,
+    ) -> BlockId {
+        let target = self
+            .inner
+            .proposal_target(client, best_block_in_session, finalized_block);
+        // Blocks higher than this would not make it into the proposal anyway.
+        let highest_proposable = finalized_block
+            .number()
+            .saturating_add(<BlockNumber>::saturated_from(MAX_DATA_BRANCH_LEN));
+        let mut current =
+            match get_ancestor(client, &target, min(target.number(), highest_proposable)) {
+                Some(block) => block,
+                None => return target,
+            };
+        while current.number() > finalized_block.number() {
+            if self.coverage.peers_with_block(&current) >= self.min_peers {
+                return current;
+            }
+            current = match get_parent(client, &current) {
+                Some(parent) => parent,
+                None => break,
+            };
+        }
+        target
+    }
+}
+
+/// Configuration of the proposal policy used by the node in every session.
+///
+/// With nothing set this is the `BestBlockPolicy`. Otherwise the import coverage preference is applied
+/// first, then the pinned branch and the lag cap is applied last, so that it also bounds a pinned branch.
+#[derive(Clone, Default)]
+pub struct ProposalPolicyConfig {
+    /// The maximal number of blocks above the highest finalized one that get proposed.
+    pub max_lag: Option<BlockNumber>,
+    /// Handle through which the operator can pin the proposed branch.
+    pub pinned_branch: Option<PinnedBranch>,
+    /// Source of peer import coverage, together with the number of peers that is considered enough.
+    pub import_coverage: Option<(Arc<dyn ImportCoverage>, usize)>,
+}
+
+impl ProposalPolicyConfig {
+    pub fn build<H, C>(&self) -> Box<dyn ProposalPolicy<H, C>>
+    where
+        H: Header,
+        C: HeaderBackend<H> + 'static,
+    {
+        let mut policy: Box<dyn ProposalPolicy<H, C>> = Box::new(BestBlockPolicy);
+        if let Some((coverage, min_peers)) = &self.import_coverage {
+            policy = Box::new(ImportCoveragePolicy::new(policy, coverage.clone(), *min_peers));
+        }
+        if let Some(pinned) = &self.pinned_branch {
+            policy = Box::new(PinnedBranchPolicy::new(policy, pinned.clone()));
+        }
+        if let Some(max_lag) = self.max_lag {
+            policy = Box::new(MaxLagPolicy::new(policy, max_lag));
+        }
+        policy
+    }
+}
@@ -0,0 +1,96 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Emergency finalization for nodes that finalize blocks without running the Aleph gadget.
+
+use std::sync::Arc;
+
+use futures::{channel::mpsc::UnboundedReceiver, StreamExt};
+use log::{debug, info, warn};
+use parity_scale_codec::Encode;
+use sc_client_api::{Backend, Finalizer as SubstrateFinalizer, HeaderBackend, LockImportRun};
+use sp_api::ProvideRuntimeApi;
+use sp_runtime::traits::Header as SubstrateHeader;
+
+use crate::{
+    block::{
+        substrate::{InnerJustification, Justification, SessionVerifier},
+        Finalizer,
+    },
+    finalization::AlephFinalizer,
+    justification::{AlephJustification, JustificationNotifications},
+    metrics::AllBlockMetrics,
+    primitives::{AlephSessionApi, Block},
+};
+
+const LOG_TARGET: &str = "aleph-emergency";
+
+/// Finalizes blocks with the emergency justifications received from `justifications`, after
+/// checking them against the emergency finalizer of the session of the block. Other
+/// justifications are dropped, as only the full gadget is able to verify them.
+pub async fn run_emergency_finalization<C, BE>(
+    client: Arc<C>,
+    mut justifications: UnboundedReceiver<Justification>,
+    justification_notifications: JustificationNotifications,
+) where
+    C: HeaderBackend<Block>
+        + LockImportRun<Block, BE>
+        + SubstrateFinalizer<Block, BE>
+        + ProvideRuntimeApi<Block>
+        + Send
+        + Sync
+        + 'static,
+    C::Api: AlephSessionApi<Block>,
+    BE: Backend<Block> + 'static,
+{
+    let finalizer = AlephFinalizer::new(
+        client.clone(),
+        AllBlockMetrics::new(None),
+        justification_notifications,
+    );
+    while let Some(justification) = justifications.next().await {
+        let hash = justification.header.hash();
+        let number = *justification.header.number();
+        let aleph_justification = match &justification.inner_justification {
+            InnerJustification::AlephJustification(
+                aleph_justification @ AlephJustification::EmergencySignature(_),
+            ) => aleph_justification,
+            _ => {
+                debug!(target: LOG_TARGET, "Ignoring a non-emergency justification of block #{number}.");
+                continue;
+            }
+        };
+        let verifier = match client.runtime_api().authority_data(hash) {
+            Ok(authority_data) => SessionVerifier::from(authority_data),
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Failed to read the authority data at block #{number}: {e}.");
+                continue;
+            }
+        };
+        if let Err(e) = verifier.verify_bytes(aleph_justification, hash.encode()) {
+            warn!(target: LOG_TARGET, "Rejecting the emergency justification of block #{number}: {e}.");
+            continue;
+        }
+        match finalizer.finalize(justification) {
+            Ok(()) => info!(target: LOG_TARGET, "Emergency finalized block #{number} {hash:?}."),
+            Err(e) => warn!(target: LOG_TARGET, "Failed to emergency finalize block #{number}: {e}."),
+        }
+    }
+}
@@ -31,6 +31,7 @@ use sp_runtime::{
 
 use crate::{
     primitives ::{BlockHash, BlockNumber},
+    justification::{AlephJustification, JustificationNotification, JustificationNotifications},
     metrics::{AllBlockMetrics, Checkpoint},
     BlockId,
 };
@@ -47,6 +48,7 @@ where
 {
     client: Arc<C>,
     metrics: AllBlockMetrics,
+    justification_notifications: JustificationNotifications,
     phantom: PhantomData<(B, BE)>,
 }
 
@@ -56,13 +58,26 @@ where
     BE: Backend<B>,
     C: HeaderBackend<B> + LockImportRun<B, BE> + Finalizer<B, BE>,
 {
-    pub(crate) fn new(client: Arc<C>, metrics: AllBlockMetrics) -> Self {
+    pub(crate) fn new(
+        client: Arc<C>,
+        metrics: AllBlockMetrics,
+        justification_notifications: JustificationNotifications,
+    ) -> Self {
         AlephFinalizer {
             client,
             metrics,
+            justification_notifications,
             phantom: PhantomData,
         }
     }
+
+    /// Lets the subscribers know that the block got finalized with the given justification.
+    pub(crate) fn notify_finalized(&self, block: BlockId, justification: AlephJustification) {
+        self.justification_notifications.notify(JustificationNotification {
+            block,
+            justification,
+        });
+    }
 }
 
 impl<B, BE, C> BlockFinalizer for AlephFinalizer<B, BE, C>
@@ -28,8 +28,10 @@ use crate::{
 };
 
 mod compatibility;
+mod notification;
 
 pub use compatibility::{backwards_compatible_decode, versioned_encode, Error as DecodeError};
+pub use notification::{JustificationNotification, JustificationNotifications};
 
 const LOG_TARGET: &str = "aleph-justification";
 
@@ -0,0 +1,100 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use parking_lot::Mutex;
+
+use crate::{justification::AlephJustification, BlockId};
+
+/// A block finalized by this node together with the justification it was finalized with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JustificationNotification {
+    pub block: BlockId,
+    pub justification: AlephJustification,
+}
+
+/// Distributes notifications about justifications of finalized blocks, e.g. to RPC subscribers.
+/// Subscribers that dropped their receivers are forgotten on the next notification.
+#[derive(Clone, Default)]
+pub struct JustificationNotifications {
+    subscribers: Arc<Mutex<Vec<UnboundedSender<JustificationNotification>>>>,
+}
+
+impl JustificationNotifications {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe(&self) -> UnboundedReceiver<JustificationNotification> {
+        let (sender, receiver) = unbounded();
+        self.subscribers.lock().push(sender);
+        receiver
+    }
+
+    pub(crate) fn notify(&self, notification: JustificationNotification) {
+        self.subscribers
+            .lock()
+            .retain(|subscriber| subscriber.unbounded_send(notification.clone()).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use sp_core::Pair;
+
+    use super::{JustificationNotification, JustificationNotifications};
+    use crate::{justification::AlephJustification, primitives::AuthorityPair, BlockId};
+
+    fn notification(number: u32) -> JustificationNotification {
+        let signature = AuthorityPair::generate().0.sign(&number.to_le_bytes());
+        JustificationNotification {
+            block: BlockId::new_random(number),
+            justification: AlephJustification::EmergencySignature(signature),
+        }
+    }
+
+    #[tokio::test]
+    async fn delivers_notifications_to_all_subscribers() {
+        let notifications = JustificationNotifications::new();
+        let mut first = notifications.subscribe();
+        let mut second = notifications.subscribe();
+        let notification = notification(1);
+
+        notifications.notify(notification.clone());
+
+        assert_eq!(first.next().await, Some(notification.clone()));
+        assert_eq!(second.next().await, Some(notification));
+    }
+
+    #[tokio::test]
+    async fn forgets_dropped_subscribers() {
+        let notifications = JustificationNotifications::new();
+        let mut subscriber = notifications.subscribe();
+        drop(notifications.subscribe());
+
+        notifications.notify(notification(1));
+
+        assert_eq!(notifications.subscribers.lock().len(), 1);
+        assert!(subscriber.next().await.is_some());
+    }
+}
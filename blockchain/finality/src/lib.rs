@@ -70,6 +70,7 @@ mod block;
 mod compatibility;
 mod crypto;
 mod data_io;
+mod emergency;
 mod finalization;
 mod idx_to_account;
 mod import;
@@ -88,18 +89,25 @@ pub mod testing;
 
 pub use crate::{
     block::{
-        substrate::{BlockImporter, Justification, JustificationTranslator, SubstrateChainStatus},
+        substrate::{
+            BlockImporter, Justification, JustificationTranslator, SubstrateChainStatus,
+            TranslateError,
+        },
         BlockId,
     },
+    data_io::{ImportCoverage, PinnedBranch, ProposalPolicyConfig},
+    emergency::run_emergency_finalization,
     import::{AlephBlockImport, RedirectingBlockImport, TracingBlockImport},
-    justification::AlephJustification,
+    justification::{AlephJustification, JustificationNotification, JustificationNotifications},
     metrics::{AllBlockMetrics, DefaultClock, FinalityRateMetrics, TimingBlockMetrics},
     network::{
         address_cache::{ValidatorAddressCache, ValidatorAddressingInfo},
+        tcp::ExternalAddresses,
         Protocol, ProtocolNaming, SubstrateNetwork, SubstrateNetworkEventStream,
     },
     nodes::run_validator_node,
     session::SessionPeriod,
+    sync::BudgetConfig as SyncBudgetConfig,
     sync_oracle::SyncOracle,
 };
 
@@ -317,10 +325,17 @@ pub struct AlephConfig<C, SC, T> {
     pub millisecs_per_block: MillisecsPerBlock,
     pub unit_creation_delay: UnitCreationDelay,
     pub backup_saving_path: Option<PathBuf>,
-    pub external_addresses: Vec<String>,
+    pub external_addresses: ExternalAddresses,
     pub validator_port: u16,
     pub rate_limiter_config: RateLimiterConfig,
     pub sync_oracle: SyncOracle,
     pub validator_address_cache: Option<ValidatorAddressCache>,
+    /// File in which the validator address cache is kept across restarts, used if no
+    /// `validator_address_cache` is provided.
+    pub validator_address_cache_path: Option<PathBuf>,
+    pub proposal_policy: ProposalPolicyConfig,
+    /// Limits on the data peers can request through the block sync.
+    pub sync_budget: SyncBudgetConfig,
+    pub justification_notifications: JustificationNotifications,
     pub transaction_pool: Arc<T>,
 }
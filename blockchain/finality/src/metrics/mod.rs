@@ -21,12 +21,14 @@
 mod all_block;
 mod chain_state;
 mod finality_rate;
+mod scoreboard;
 mod timing;
 pub mod transaction_pool;
 
 pub use all_block::AllBlockMetrics;
 pub use chain_state::run_chain_state_metrics;
 pub use finality_rate::FinalityRateMetrics;
+pub use scoreboard::{ScoreboardMetrics, SessionScoreboard};
 use substrate_prometheus_endpoint::{exponential_buckets, prometheus};
 pub use timing::{Checkpoint, DefaultClock, TimingBlockMetrics};
 pub use transaction_pool::TransactionPoolInfoProvider;
@@ -0,0 +1,406 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Per-validator performance metrics, labelled by the account of the validator.
+//! The counters accumulate over sessions, the multisignature share describes the current session only.
+
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use primitives::BlockNumber;
+use substrate_prometheus_endpoint::{
+    exponential_buckets, register, CounterVec, Gauge, GaugeVec, HistogramOpts, HistogramVec, Opts,
+    PrometheusError, Registry, F64, U64,
+};
+
+use crate::{
+    abft::{NodeIndex, SignatureSet},
+    crypto::Signature,
+    idx_to_account::ValidatorIndexToAccountIdConverter,
+    SessionId,
+};
+
+const ACCOUNT_LABEL: &str = "account";
+
+fn counter_vec(
+    registry: &Registry,
+    name: &str,
+    help: &str,
+) -> Result<CounterVec<U64>, PrometheusError> {
+    register(
+        CounterVec::new(Opts::new(name, help), &[ACCOUNT_LABEL])?,
+        registry,
+    )
+}
+
+pub struct Metrics {
+    session: Gauge<U64>,
+    units_created: CounterVec<U64>,
+    units_received: CounterVec<U64>,
+    units_ordered: CounterVec<U64>,
+    finalizing_proposals: CounterVec<U64>,
+    proposal_lag: HistogramVec,
+    multisignature_signatures: CounterVec<U64>,
+    multisignature_share: GaugeVec<F64>,
+}
+
+impl Metrics {
+    fn new(registry: &Registry) -> Result<Self, PrometheusError> {
+        Ok(Metrics {
+            session: register(
+                Gauge::new(
+                    "aleph_scoreboard_session",
+                    "Session described by the validator scoreboard",
+                )?,
+                registry,
+            )?,
+            units_created: counter_vec(
+                registry,
+                "aleph_validator_units_created",
+                "Units created by this validator",
+            )?,
+            units_received: counter_vec(
+                registry,
+                "aleph_validator_units_received",
+                "Units of other validators received by this validator and ordered",
+            )?,
+            units_ordered: counter_vec(
+                registry,
+                "aleph_validator_units_ordered",
+                "Units created by the validator and ordered",
+            )?,
+            finalizing_proposals: counter_vec(
+                registry,
+                "aleph_validator_finalizing_proposals",
+                "Proposals of the validator that finalized blocks",
+            )?,
+            proposal_lag: register(
+                HistogramVec::new(
+                    HistogramOpts::new(
+                        "aleph_validator_proposal_lag",
+                        "How many blocks the ordered proposals of the validator were behind the round head, the highest block proposed by the units ordered so far",
+                    )
+                    .buckets(exponential_buckets(1.0, 2.0, 10)?),
+                    &[ACCOUNT_LABEL],
+                )?,
+                registry,
+            )?,
+            multisignature_signatures: counter_vec(
+                registry,
+                "aleph_validator_multisignature_signatures",
+                "Signatures of the validator included in multisignatures",
+            )?,
+            multisignature_share: register(
+                GaugeVec::new(
+                    Opts::new(
+                        "aleph_validator_multisignature_share",
+                        "Share of multisignatures in the current session including a signature of the validator",
+                    ),
+                    &[ACCOUNT_LABEL],
+                )?,
+                registry,
+            )?,
+        })
+    }
+
+    fn reset(&self, session_id: SessionId) {
+        self.session.set(session_id.0.into());
+        self.multisignature_share.reset();
+    }
+}
+
+/// Creates scoreboards for sessions, translating validator indices to accounts.
+#[derive(Clone)]
+pub enum ScoreboardMetrics {
+    Prometheus {
+        metrics: Arc<Metrics>,
+        converter: Arc<dyn ValidatorIndexToAccountIdConverter + Send + Sync>,
+    },
+    Noop,
+}
+
+impl ScoreboardMetrics {
+    pub fn new<C: ValidatorIndexToAccountIdConverter + Send + Sync + 'static>(
+        registry: Option<&Registry>,
+        converter: C,
+    ) -> Result<Self, PrometheusError> {
+        let registry = match registry {
+            None => return Ok(ScoreboardMetrics::Noop),
+            Some(registry) => registry,
+        };
+
+        Ok(ScoreboardMetrics::Prometheus {
+            metrics: Arc::new(Metrics::new(registry)?),
+            converter: Arc::new(converter),
+        })
+    }
+
+    pub fn noop() -> Self {
+        ScoreboardMetrics::Noop
+    }
+
+    /// Starts describing the given session, in which this validator has index `own_index`,
+    /// discarding the multisignature share of the previous one.
+    pub fn start_session(
+        &self,
+        session_id: SessionId,
+        n_members: usize,
+        own_index: NodeIndex,
+    ) -> SessionScoreboard {
+        let (metrics, converter) = match self {
+            ScoreboardMetrics::Prometheus { metrics, converter } => (metrics, converter),
+            ScoreboardMetrics::Noop => return SessionScoreboard::Noop,
+        };
+        let accounts = (0..n_members)
+            .map(|index| match converter.account(session_id, NodeIndex(index)) {
+                Some(account) => account.to_string(),
+                None => format!("unknown/{index}"),
+            })
+            .collect();
+        metrics.reset(session_id);
+        SessionScoreboard::Prometheus {
+            metrics: metrics.clone(),
+            session_id,
+            own_index,
+            accounts: Arc::new(accounts),
+            scores: Arc::new(Mutex::new(Scores::new(n_members))),
+        }
+    }
+}
+
+pub struct Scores {
+    multisignatures: u64,
+    signatures: Vec<u64>,
+}
+
+impl Scores {
+    fn new(n_members: usize) -> Self {
+        Scores {
+            multisignatures: 0,
+            signatures: vec![0; n_members],
+        }
+    }
+}
+
+/// Reports the performance of validators in a single session.
+#[derive(Clone)]
+pub enum SessionScoreboard {
+    Prometheus {
+        metrics: Arc<Metrics>,
+        session_id: SessionId,
+        own_index: NodeIndex,
+        accounts: Arc<Vec<String>>,
+        scores: Arc<Mutex<Scores>>,
+    },
+    Noop,
+}
+
+impl SessionScoreboard {
+    /// Returns the metrics and the account label of the validator with the given index.
+    fn account(&self, index: NodeIndex) -> Option<(&Metrics, &str)> {
+        match self {
+            SessionScoreboard::Prometheus {
+                metrics, accounts, ..
+            } => accounts
+                .get(index.0)
+                .map(|account| (metrics.as_ref(), account.as_str())),
+            SessionScoreboard::Noop => None,
+        }
+    }
+
+    /// Reports that this validator created a unit.
+    pub fn report_unit_created(&self) {
+        let own_index = match self {
+            SessionScoreboard::Prometheus { own_index, .. } => *own_index,
+            SessionScoreboard::Noop => return,
+        };
+        if let Some((metrics, account)) = self.account(own_index) {
+            metrics.units_created.with_label_values(&[account]).inc();
+        }
+    }
+
+    /// Reports that a unit created by `creator` was ordered. The proposal it contained was
+    /// `lag` blocks behind the round head and finalized `finalized_blocks` new blocks.
+    pub fn report_ordered_unit(
+        &self,
+        creator: NodeIndex,
+        lag: BlockNumber,
+        finalized_blocks: usize,
+    ) {
+        let own_index = match self {
+            SessionScoreboard::Prometheus { own_index, .. } => *own_index,
+            SessionScoreboard::Noop => return,
+        };
+        let (metrics, account) = match self.account(creator) {
+            Some(account) => account,
+            None => return,
+        };
+        metrics.units_ordered.with_label_values(&[account]).inc();
+        if creator != own_index {
+            metrics.units_received.with_label_values(&[account]).inc();
+        }
+        metrics
+            .proposal_lag
+            .with_label_values(&[account])
+            .observe(lag.into());
+        if finalized_blocks > 0 {
+            metrics
+                .finalizing_proposals
+                .with_label_values(&[account])
+                .inc();
+        }
+    }
+
+    /// Reports a completed multisignature, crediting all the validators that contributed to it.
+    /// The share is only updated while the session is the one described by the scoreboard.
+    pub fn report_multisignature(&self, multisignature: &SignatureSet<Signature>) {
+        let (metrics, session_id, accounts, scores) = match self {
+            SessionScoreboard::Prometheus {
+                metrics,
+                session_id,
+                accounts,
+                scores,
+                ..
+            } => (metrics, session_id, accounts, scores),
+            SessionScoreboard::Noop => return,
+        };
+        let mut scores = scores.lock();
+        scores.multisignatures += 1;
+        for (index, _) in multisignature.iter() {
+            if let Some(signatures) = scores.signatures.get_mut(index.0) {
+                *signatures += 1;
+            }
+            if let Some(account) = accounts.get(index.0) {
+                metrics
+                    .multisignature_signatures
+                    .with_label_values(&[account])
+                    .inc();
+            }
+        }
+        if metrics.session.get() != u64::from(session_id.0) {
+            return;
+        }
+        for (account, signatures) in accounts.iter().zip(scores.signatures.iter()) {
+            metrics
+                .multisignature_share
+                .with_label_values(&[account])
+                .set(*signatures as f64 / scores.multisignatures as f64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use substrate_prometheus_endpoint::Registry;
+
+    use super::{ScoreboardMetrics, SessionScoreboard};
+    use crate::{
+        abft::{NodeCount, NodeIndex, SignatureSet},
+        idx_to_account::MockConverter,
+        network::mock::crypto_basics,
+        SessionId,
+    };
+
+    fn scoreboard() -> ScoreboardMetrics {
+        ScoreboardMetrics::new(Some(&Registry::new()), MockConverter)
+            .expect("metrics should register")
+    }
+
+    fn gauge(scoreboard: &SessionScoreboard, name: &str, account: &str) -> f64 {
+        let metrics = match scoreboard {
+            SessionScoreboard::Prometheus { metrics, .. } => metrics,
+            SessionScoreboard::Noop => panic!("metrics should have been initialized properly"),
+        };
+        match name {
+            "units_created" => metrics.units_created.with_label_values(&[account]).get() as f64,
+            "units_received" => metrics.units_received.with_label_values(&[account]).get() as f64,
+            "units_ordered" => metrics.units_ordered.with_label_values(&[account]).get() as f64,
+            "finalizing_proposals" => metrics
+                .finalizing_proposals
+                .with_label_values(&[account])
+                .get() as f64,
+            "signatures" => metrics
+                .multisignature_signatures
+                .with_label_values(&[account])
+                .get() as f64,
+            "share" => metrics
+                .multisignature_share
+                .with_label_values(&[account])
+                .get(),
+            _ => panic!("unknown metric"),
+        }
+    }
+
+    #[test]
+    fn counts_ordered_units_per_creator() {
+        let session = scoreboard().start_session(SessionId(7), 3, NodeIndex(0));
+        session.report_ordered_unit(NodeIndex(1), 0, 2);
+        session.report_ordered_unit(NodeIndex(1), 3, 0);
+        session.report_ordered_unit(NodeIndex(2), 0, 1);
+        session.report_ordered_unit(NodeIndex(0), 0, 1);
+        assert_eq!(gauge(&session, "units_ordered", "unknown/1"), 2.0);
+        assert_eq!(gauge(&session, "units_received", "unknown/1"), 2.0);
+        assert_eq!(gauge(&session, "finalizing_proposals", "unknown/1"), 1.0);
+        assert_eq!(gauge(&session, "units_ordered", "unknown/2"), 1.0);
+        assert_eq!(gauge(&session, "units_ordered", "unknown/0"), 1.0);
+        assert_eq!(gauge(&session, "units_received", "unknown/0"), 0.0);
+    }
+
+    #[test]
+    fn counts_own_created_units() {
+        let session = scoreboard().start_session(SessionId(7), 2, NodeIndex(1));
+        session.report_unit_created();
+        session.report_unit_created();
+        assert_eq!(gauge(&session, "units_created", "unknown/1"), 2.0);
+        assert_eq!(gauge(&session, "units_created", "unknown/0"), 0.0);
+    }
+
+    #[test]
+    fn computes_multisignature_share() {
+        let session = scoreboard().start_session(SessionId(7), 2, NodeIndex(0));
+        let (validators, _) = crypto_basics(2);
+        let signature = validators[0].1.sign(b"block");
+        let full = SignatureSet::with_size(NodeCount(2))
+            .add_signature(&signature, NodeIndex(0))
+            .add_signature(&signature, NodeIndex(1));
+        let partial = SignatureSet::with_size(NodeCount(2)).add_signature(&signature, NodeIndex(0));
+        session.report_multisignature(&full);
+        session.report_multisignature(&partial);
+        assert_eq!(gauge(&session, "signatures", "unknown/0"), 2.0);
+        assert_eq!(gauge(&session, "share", "unknown/0"), 1.0);
+        assert_eq!(gauge(&session, "signatures", "unknown/1"), 1.0);
+        assert_eq!(gauge(&session, "share", "unknown/1"), 0.5);
+    }
+
+    #[test]
+    fn keeps_counting_but_ignores_share_from_previous_session() {
+        let metrics = scoreboard();
+        let old_session = metrics.start_session(SessionId(7), 2, NodeIndex(0));
+        let new_session = metrics.start_session(SessionId(8), 2, NodeIndex(0));
+        let (validators, _) = crypto_basics(2);
+        let signature = validators[0].1.sign(b"block");
+        old_session.report_ordered_unit(NodeIndex(1), 0, 1);
+        old_session.report_multisignature(
+            &SignatureSet::with_size(NodeCount(2)).add_signature(&signature, NodeIndex(1)),
+        );
+        assert_eq!(gauge(&new_session, "units_ordered", "unknown/1"), 1.0);
+        assert_eq!(gauge(&new_session, "signatures", "unknown/1"), 1.0);
+        assert_eq!(gauge(&new_session, "share", "unknown/1"), 0.0);
+    }
+}
@@ -18,52 +18,198 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{collections::HashMap, fmt::Debug, num::NonZeroUsize, sync::Arc};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    fs, io,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
+use futures_timer::Delay;
+use log::warn;
 use lru::LruCache;
+use parity_scale_codec::{Decode, Encode};
 use parking_lot::Mutex;
 use primitives::AccountId;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    abft::NodeIndex, idx_to_account::ValidatorIndexToAccountIdConverter, session::SessionId,
+    abft::NodeIndex, idx_to_account::ValidatorIndexToAccountIdConverter,
+    network::session::SequenceNumber, session::SessionId,
 };
 
 /// Network details for a given validator in a given session.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
 pub struct ValidatorAddressingInfo {
     /// Session to which given information applies.
     pub session: SessionId,
+    /// Sequence number of the signed record this information comes from, newer records
+    /// within a session have higher numbers.
+    pub sequence_number: SequenceNumber,
     /// Network level address of the validator, i.e. IP address (for validator network)
     pub network_level_address: String,
     /// PeerId of the validator used in validator (clique) network
     pub validator_network_peer_id: String,
 }
 
+impl ValidatorAddressingInfo {
+    fn is_newer_than(&self, other: &ValidatorAddressingInfo) -> bool {
+        (self.session, self.sequence_number) >= (other.session, other.sequence_number)
+    }
+}
+
+struct CacheData {
+    entries: LruCache<AccountId, ValidatorAddressingInfo>,
+    /// Increased on every change, so that unchanged caches are not written again.
+    generation: u64,
+}
+
+struct Persistence {
+    path: PathBuf,
+    /// Generation of the last snapshot written to the file, held while writing.
+    written_generation: Mutex<u64>,
+}
+
 /// Stores most recent information about validator addresses.
+/// Optionally persists it to a file, so that it survives restarts.
 #[derive(Clone)]
 pub struct ValidatorAddressCache {
-    data: Arc<Mutex<LruCache<AccountId, ValidatorAddressingInfo>>>,
+    data: Arc<Mutex<CacheData>>,
+    persistence: Option<Arc<Persistence>>,
 }
 
 const VALIDATOR_ADDRESS_CACHE_SIZE: usize = 1000;
 
+/// How often the changes of a persisted cache are written to its file.
+pub const VALIDATOR_ADDRESS_CACHE_PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
+fn load_entries(path: &Path) -> io::Result<Vec<(AccountId, ValidatorAddressingInfo)>> {
+    let encoded = match fs::read(path) {
+        Ok(encoded) => encoded,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    Vec::decode(&mut encoded.as_slice())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+}
+
+fn store_entries(path: &Path, entries: &[(AccountId, ValidatorAddressingInfo)]) -> io::Result<()> {
+    // Write to a temporary file first, so that a crash never leaves a corrupted cache behind.
+    let temporary_path = path.with_extension("tmp");
+    fs::write(&temporary_path, entries.encode())?;
+    fs::rename(temporary_path, path)
+}
+
+impl Persistence {
+    fn store(&self, entries: &[(AccountId, ValidatorAddressingInfo)], generation: u64) {
+        let mut written_generation = self.written_generation.lock();
+        // A concurrent call already wrote a more recent snapshot.
+        if *written_generation >= generation {
+            return;
+        }
+        match store_entries(&self.path, entries) {
+            Ok(()) => *written_generation = generation,
+            Err(e) => warn!(
+                target: "aleph-network",
+                "Failed to persist validator address cache at {:?}: {}", self.path, e
+            ),
+        }
+    }
+}
+
 impl ValidatorAddressCache {
     pub fn new() -> Self {
         Self {
-            data: Arc::new(Mutex::new(LruCache::new(
-                NonZeroUsize::try_from(VALIDATOR_ADDRESS_CACHE_SIZE)
-                    .expect("the cache size is a non-zero constant"),
-            ))),
+            data: Arc::new(Mutex::new(CacheData {
+                entries: LruCache::new(
+                    NonZeroUsize::try_from(VALIDATOR_ADDRESS_CACHE_SIZE)
+                        .expect("the cache size is a non-zero constant"),
+                ),
+                generation: 0,
+            })),
+            persistence: None,
+        }
+    }
+
+    /// Creates a cache backed by the file at `path`, loading its contents if the file exists.
+    /// Changes of the cache are written back to the file by `persist`.
+    pub fn with_persistence(path: PathBuf) -> io::Result<Self> {
+        let entries = load_entries(&path)?;
+        let cache = Self {
+            persistence: Some(Arc::new(Persistence {
+                path,
+                written_generation: Mutex::new(0),
+            })),
+            ..Self::new()
+        };
+        {
+            let mut data = cache.data.lock();
+            // The entries are stored from the most to the least recently used one.
+            for (validator_stash, info) in entries.into_iter().rev() {
+                data.entries.put(validator_stash, info);
+            }
         }
+        Ok(cache)
     }
 
+    /// Inserts the information, unless we already know more recent information
+    /// about the same validator.
     pub fn insert(&self, validator_stash: AccountId, info: ValidatorAddressingInfo) {
-        self.data.lock().put(validator_stash, info);
+        let mut data = self.data.lock();
+        match data.entries.get(&validator_stash) {
+            Some(known) if known == &info => return,
+            Some(known) if !info.is_newer_than(known) => return,
+            _ => {}
+        }
+        data.entries.put(validator_stash, info);
+        data.generation += 1;
+    }
+
+    /// Writes the cache to its file, if it changed since it was last written.
+    pub fn persist(&self) {
+        let persistence = match &self.persistence {
+            Some(persistence) => persistence,
+            None => return,
+        };
+        let (entries, generation) = {
+            let data = self.data.lock();
+            if *persistence.written_generation.lock() >= data.generation {
+                return;
+            }
+            let entries: Vec<_> = data
+                .entries
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+            (entries, data.generation)
+        };
+        // The file is written without holding the cache lock, so that readers are not blocked on IO.
+        persistence.store(&entries, generation);
+    }
+
+    /// Periodically writes the changes of the cache to its file, so that a burst of updates
+    /// results in a single write.
+    pub async fn run_persistence(self) {
+        if self.persistence.is_none() {
+            return;
+        }
+        loop {
+            Delay::new(VALIDATOR_ADDRESS_CACHE_PERSIST_INTERVAL).await;
+            self.persist();
+        }
     }
 
     pub fn snapshot(&self) -> HashMap<AccountId, ValidatorAddressingInfo> {
-        HashMap::from_iter(self.data.lock().iter().map(|(k, v)| (k.clone(), v.clone())))
+        HashMap::from_iter(
+            self.data
+                .lock()
+                .entries
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone())),
+        )
     }
 }
 
@@ -121,12 +267,70 @@ impl<C: ValidatorIndexToAccountIdConverter> ValidatorAddressCacheUpdater
 
 #[cfg(test)]
 pub mod test {
+    use std::{env, fs, process};
+
+    use primitives::AccountId;
+
     use crate::{
         idx_to_account::MockConverter,
-        network::address_cache::{ValidatorAddressCacheUpdater, ValidatorAddressCacheUpdaterImpl},
+        network::address_cache::{
+            ValidatorAddressCache, ValidatorAddressCacheUpdater, ValidatorAddressCacheUpdaterImpl,
+            ValidatorAddressingInfo,
+        },
+        SessionId,
     };
 
     pub fn noop_updater() -> impl ValidatorAddressCacheUpdater {
         ValidatorAddressCacheUpdaterImpl::<MockConverter>::Noop
     }
+
+    fn info(session: u32, sequence_number: u64, address: &str) -> ValidatorAddressingInfo {
+        ValidatorAddressingInfo {
+            session: SessionId(session),
+            sequence_number,
+            network_level_address: address.to_string(),
+            validator_network_peer_id: String::from("peer"),
+        }
+    }
+
+    #[test]
+    fn keeps_most_recent_info() {
+        let cache = ValidatorAddressCache::new();
+        let validator = AccountId::new([1; 32]);
+        cache.insert(validator.clone(), info(43, 2, "new"));
+        cache.insert(validator.clone(), info(43, 1, "old"));
+        assert_eq!(cache.snapshot()[&validator], info(43, 2, "new"));
+        cache.insert(validator.clone(), info(43, 3, "newer"));
+        assert_eq!(cache.snapshot()[&validator], info(43, 3, "newer"));
+        cache.insert(validator.clone(), info(44, 1, "next session"));
+        assert_eq!(cache.snapshot()[&validator], info(44, 1, "next session"));
+    }
+
+    #[test]
+    fn survives_restart_when_persisted() {
+        let path = env::temp_dir().join(format!("validator-address-cache-{}", process::id()));
+        let validator = AccountId::new([2; 32]);
+        {
+            let cache = ValidatorAddressCache::with_persistence(path.clone())
+                .expect("no cache file yet is fine");
+            cache.insert(validator.clone(), info(43, 7, "address"));
+            cache.persist();
+        }
+        let cache =
+            ValidatorAddressCache::with_persistence(path.clone()).expect("the file is valid");
+        assert_eq!(cache.snapshot()[&validator], info(43, 7, "address"));
+        fs::remove_file(path).expect("the file was created");
+    }
+
+    #[test]
+    fn writes_only_when_persisted() {
+        let path = env::temp_dir().join(format!("validator-address-cache-writes-{}", process::id()));
+        let cache =
+            ValidatorAddressCache::with_persistence(path.clone()).expect("no cache file yet is fine");
+        cache.insert(AccountId::new([3; 32]), info(43, 1, "address"));
+        assert!(!path.exists());
+        cache.persist();
+        assert!(path.exists());
+        fs::remove_file(path).expect("the file was created");
+    }
 }
@@ -27,8 +27,12 @@ use log::warn;
 use parity_scale_codec::{Decode, Encode, Error as CodecError, Input as CodecInput};
 
 use crate::{
-    network::{session::Authentication, AddressingInformation},
-    SessionId, Version,
+    crypto::Signature,
+    network::{
+        session::{AuthData, Authentication, LEGACY_SEQUENCE_NUMBER},
+        AddressingInformation,
+    },
+    NodeIndex, SessionId, Version,
 };
 
 type ByteCount = u16;
@@ -40,15 +44,49 @@ const MAX_AUTHENTICATION_SIZE: u16 = 16 * 1024;
 pub enum VersionedAuthentication<A: AddressingInformation> {
     // Most likely from the future.
     Other(Version, Vec<u8>),
+    // Only legacy authentications, without sequence numbers, can be represented in this version.
     V2(Authentication<A>),
+    V3(Authentication<A>),
 }
 
 impl<A: AddressingInformation> From<Authentication<A>> for Vec<VersionedAuthentication<A>> {
     fn from(authentication: Authentication<A>) -> Self {
-        vec![VersionedAuthentication::V2(authentication)]
+        match authentication.0.sequence_number() {
+            LEGACY_SEQUENCE_NUMBER => vec![VersionedAuthentication::V2(authentication)],
+            _ => vec![VersionedAuthentication::V3(authentication)],
+        }
     }
 }
 
+/// Encodes the authentication the way it was encoded before sequence numbers were introduced.
+fn encode_legacy<A: AddressingInformation>(authentication: &Authentication<A>) -> Vec<u8> {
+    let Authentication(auth_data, signature) = authentication;
+    (
+        &auth_data.address,
+        auth_data.node_id,
+        auth_data.session_id,
+        signature,
+    )
+        .encode()
+}
+
+/// Decodes an authentication encoded before sequence numbers were introduced.
+fn decode_legacy<A: AddressingInformation, I: CodecInput>(
+    input: &mut I,
+) -> Result<Authentication<A>, CodecError> {
+    let (address, node_id, session_id, signature) =
+        <(A, NodeIndex, SessionId, Signature)>::decode(input)?;
+    Ok(Authentication(
+        AuthData {
+            address,
+            node_id,
+            session_id,
+            sequence_number: LEGACY_SEQUENCE_NUMBER,
+        },
+        signature,
+    ))
+}
+
 pub type DiscoveryMessage<A> = Authentication<A>;
 
 impl<A: AddressingInformation> DiscoveryMessage<A> {
@@ -64,7 +102,7 @@ impl<A: AddressingInformation> TryInto<DiscoveryMessage<A>> for VersionedAuthent
     fn try_into(self) -> Result<DiscoveryMessage<A>, Self::Error> {
         use VersionedAuthentication::*;
         match self {
-            V2(authentication) => Ok(authentication),
+            V2(authentication) | V3(authentication) => Ok(authentication),
             Other(v, _) => Err(Error::UnknownVersion(v)),
         }
     }
@@ -109,7 +147,7 @@ impl<A: AddressingInformation> Encode for VersionedAuthentication<A> {
             + byte_count_size
             + match self {
                 Other(_, payload) => payload.len(),
-                V2(data) => data.size_hint(),
+                V2(data) | V3(data) => data.size_hint(),
             }
     }
 
@@ -117,7 +155,8 @@ impl<A: AddressingInformation> Encode for VersionedAuthentication<A> {
         use VersionedAuthentication::*;
         match self {
             Other(version, payload) => encode_with_version(*version, payload),
-            V2(data) => encode_with_version(Version(2), &data.encode()),
+            V2(data) => encode_with_version(Version(2), &encode_legacy(data)),
+            V3(data) => encode_with_version(Version(3), &data.encode()),
         }
     }
 }
@@ -128,7 +167,8 @@ impl<A: AddressingInformation> Decode for VersionedAuthentication<A> {
         let version = Version::decode(input)?;
         let num_bytes = ByteCount::decode(input)?;
         match version {
-            Version(2) => Ok(V2(Authentication::decode(input)?)),
+            Version(2) => Ok(V2(decode_legacy(input)?)),
+            Version(3) => Ok(V3(Authentication::decode(input)?)),
             _ => {
                 if num_bytes > MAX_AUTHENTICATION_SIZE {
                     Err("Authentication has unknown version and is encoded as more than 16KiB.")?;
@@ -202,6 +242,16 @@ mod test {
         handler: SessionHandler<SignedTcpAddressingInformation>,
     ) -> VersionedAuthentication<SignedTcpAddressingInformation> {
         VersionedAuthentication::V2(
+            handler
+                .legacy_authentication()
+                .expect("should have authentication"),
+        )
+    }
+
+    fn authentication_v3(
+        handler: SessionHandler<SignedTcpAddressingInformation>,
+    ) -> VersionedAuthentication<SignedTcpAddressingInformation> {
+        VersionedAuthentication::V3(
             handler
                 .authentication()
                 .expect("should have authentication"),
@@ -259,6 +309,33 @@ mod test {
         assert_eq!(decoded, Ok(authentication_v2))
     }
 
+    #[test]
+    fn correctly_decodes_v3_roundtrip() {
+        let handler = handler();
+        let authentication_v3 = authentication_v3(handler);
+
+        let encoded = authentication_v3.encode();
+        let decoded = VersionedAuthentication::decode(&mut encoded.as_slice());
+
+        assert_eq!(decoded, Ok(authentication_v3))
+    }
+
+    #[test]
+    fn legacy_authentication_is_sent_as_v2() {
+        let handler = handler();
+        let legacy = handler
+            .legacy_authentication()
+            .expect("should have authentication");
+        let current = handler
+            .authentication()
+            .expect("should have authentication");
+
+        let versioned: Vec<VersionedAuthentication<_>> = legacy.clone().into();
+        assert_eq!(versioned, vec![VersionedAuthentication::V2(legacy)]);
+        let versioned: Vec<VersionedAuthentication<_>> = current.clone().into();
+        assert_eq!(versioned, vec![VersionedAuthentication::V3(current)]);
+    }
+
     #[test]
     fn correctly_decodes_other() {
         let other =
@@ -28,16 +28,18 @@ use log::{debug, info, trace};
 
 use crate::{
     network::{
-        session::{Authentication, SessionHandler},
+        session::{Authentication, SequenceNumber, SessionHandler},
         AddressingInformation,
     },
     NodeIndex,
 };
 
 /// Handles creating and rebroadcasting discovery messages.
+/// Authentications superseding the last one rebroadcast for a node are rebroadcast immediately,
+/// others only after a cooldown.
 pub struct Discovery<A: AddressingInformation> {
     cooldown: Duration,
-    last_broadcast: HashMap<NodeIndex, Instant>,
+    last_broadcast: HashMap<NodeIndex, (Instant, SequenceNumber)>,
     _phantom: PhantomData<A>,
 }
 
@@ -67,9 +69,12 @@ impl<A: AddressingInformation> Discovery<A> {
         Some(authentication)
     }
 
-    fn should_rebroadcast(&self, node_id: &NodeIndex) -> bool {
+    fn should_rebroadcast(&self, node_id: &NodeIndex, sequence_number: SequenceNumber) -> bool {
         match self.last_broadcast.get(node_id) {
-            Some(instant) => Instant::now() > *instant + self.cooldown,
+            Some((instant, last_sequence_number)) => {
+                sequence_number > *last_sequence_number
+                    || Instant::now() > *instant + self.cooldown
+            }
             None => true,
         }
     }
@@ -88,11 +93,13 @@ impl<A: AddressingInformation> Discovery<A> {
             None => return (None, None),
         };
         let node_id = authentication.0.creator();
-        if !self.should_rebroadcast(&node_id) {
+        let sequence_number = authentication.0.sequence_number();
+        if !self.should_rebroadcast(&node_id, sequence_number) {
             return (address, None);
         }
         trace!(target: "aleph-network", "Rebroadcasting {:?}.", authentication);
-        self.last_broadcast.insert(node_id, Instant::now());
+        self.last_broadcast
+            .insert(node_id, (Instant::now(), sequence_number));
         (address, Some(authentication))
     }
 }
@@ -211,6 +218,25 @@ mod tests {
         assert!(command.is_none());
     }
 
+    #[test]
+    fn rebroadcasts_newer_authentication_immediately() {
+        let (mut discovery, mut handlers, _) = build();
+        let authentication = authentication(&handlers[1]);
+        let new_authentication = handlers[1]
+            .update_own_address(random_address())
+            .expect("the address changed");
+        let handler = &mut handlers[0];
+        discovery.handle_authentication(authentication.clone(), handler);
+        let (_, command) = discovery.handle_authentication(authentication, handler);
+        assert!(command.is_none());
+        let (address, command) =
+            discovery.handle_authentication(new_authentication.clone(), handler);
+        assert_eq!(address, Some(new_authentication.0.address()));
+        assert!(matches!(command, Some(
+                rebroadcast_authentication,
+            ) if rebroadcast_authentication == new_authentication));
+    }
+
     #[test]
     fn rebroadcasts_after_cooldown() {
         let (mut discovery, mut handlers, _) = build();
@@ -18,7 +18,11 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::collections::HashMap;
+use std::{
+    cmp,
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use parity_scale_codec::Encode;
 
@@ -26,7 +30,7 @@ use crate::{
     abft::NodeCount,
     crypto::{AuthorityPen, AuthorityVerifier},
     network::{
-        session::{AuthData, Authentication},
+        session::{AuthData, Authentication, SequenceNumber, LEGACY_SEQUENCE_NUMBER},
         AddressingInformation,
     },
     NodeIndex, SessionId,
@@ -35,15 +39,25 @@ use crate::{
 #[derive(Debug)]
 pub enum SessionInfo<A: AddressingInformation> {
     SessionId(SessionId),
-    OwnAuthentication(Authentication<A>),
+    OwnAuthentication {
+        authentication: Authentication<A>,
+        legacy_authentication: Authentication<A>,
+    },
 }
 
 impl<A: AddressingInformation> SessionInfo<A> {
     fn session_id(&self) -> SessionId {
         match self {
             SessionInfo::SessionId(session_id) => *session_id,
-            SessionInfo::OwnAuthentication(peer_authentications) => {
-                peer_authentications.session_id()
+            SessionInfo::OwnAuthentication { authentication, .. } => authentication.session_id(),
+        }
+    }
+
+    fn sequence_number(&self) -> Option<SequenceNumber> {
+        match self {
+            SessionInfo::SessionId(_) => None,
+            SessionInfo::OwnAuthentication { authentication, .. } => {
+                Some(authentication.0.sequence_number())
             }
         }
     }
@@ -55,7 +69,7 @@ pub struct Handler<A: AddressingInformation> {
     peers_by_node: HashMap<NodeIndex, A::PeerId>,
     authentications: HashMap<A::PeerId, Authentication<A>>,
     session_info: SessionInfo<A>,
-    own_peer_id: A::PeerId,
+    own_address: A,
     authority_index_and_pen: Option<(NodeIndex, AuthorityPen)>,
     authority_verifier: AuthorityVerifier,
 }
@@ -67,24 +81,50 @@ pub enum HandlerError {
     TypeChange,
 }
 
+/// Returns a sequence number for a newly created own record, strictly greater than the
+/// `previous` one if provided. Based on wall clock time, so that records created after a restart
+/// supersede the ones created before it.
+fn next_sequence_number(previous: Option<SequenceNumber>) -> SequenceNumber {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|duration| duration.as_millis().try_into().ok())
+        .unwrap_or(LEGACY_SEQUENCE_NUMBER);
+    cmp::max(now, previous.unwrap_or(LEGACY_SEQUENCE_NUMBER).saturating_add(1))
+}
+
+fn sign<A: AddressingInformation>(
+    authority_pen: &AuthorityPen,
+    auth_data: AuthData<A>,
+) -> Authentication<A> {
+    let signature = authority_pen.sign(&auth_data.signed_payload());
+    Authentication(auth_data, signature)
+}
+
 fn construct_session_info<A: AddressingInformation>(
     authority_index_and_pen: &Option<(NodeIndex, AuthorityPen)>,
     session_id: SessionId,
     address: A,
-) -> (SessionInfo<A>, A::PeerId) {
-    let peer_id = address.peer_id();
+    sequence_number: SequenceNumber,
+) -> SessionInfo<A> {
     match authority_index_and_pen {
         Some((node_index, authority_pen)) => {
             let auth_data = AuthData {
                 address,
                 node_id: *node_index,
                 session_id,
+                sequence_number,
+            };
+            let legacy_auth_data = AuthData {
+                sequence_number: LEGACY_SEQUENCE_NUMBER,
+                ..auth_data.clone()
             };
-            let signature = authority_pen.sign(&auth_data.encode());
-            let authentications = Authentication(auth_data, signature);
-            (SessionInfo::OwnAuthentication(authentications), peer_id)
+            SessionInfo::OwnAuthentication {
+                authentication: sign(authority_pen, auth_data),
+                legacy_authentication: sign(authority_pen, legacy_auth_data),
+            }
         }
-        None => (SessionInfo::SessionId(session_id), peer_id),
+        None => SessionInfo::SessionId(session_id),
     }
 }
 
@@ -97,15 +137,35 @@ impl<A: AddressingInformation> Handler<A> {
         session_id: SessionId,
         address: A,
     ) -> Handler<A> {
-        let (session_info, own_peer_id) =
-            construct_session_info(&authority_index_and_pen, session_id, address);
+        Self::new_with_sequence_number(
+            authority_index_and_pen,
+            authority_verifier,
+            session_id,
+            address,
+            next_sequence_number(None),
+        )
+    }
+
+    fn new_with_sequence_number(
+        authority_index_and_pen: Option<(NodeIndex, AuthorityPen)>,
+        authority_verifier: AuthorityVerifier,
+        session_id: SessionId,
+        address: A,
+        sequence_number: SequenceNumber,
+    ) -> Handler<A> {
+        let session_info = construct_session_info(
+            &authority_index_and_pen,
+            session_id,
+            address.clone(),
+            sequence_number,
+        );
         Handler {
             peers_by_node: HashMap::new(),
             authentications: HashMap::new(),
             session_info,
+            own_address: address,
             authority_index_and_pen,
             authority_verifier,
-            own_peer_id,
         }
     }
 
@@ -132,9 +192,19 @@ impl<A: AddressingInformation> Handler<A> {
     pub fn authentication(&self) -> Option<Authentication<A>> {
         match &self.session_info {
             SessionInfo::SessionId(_) => None,
-            SessionInfo::OwnAuthentication(own_authentications) => {
-                Some(own_authentications.clone())
-            }
+            SessionInfo::OwnAuthentication { authentication, .. } => Some(authentication.clone()),
+        }
+    }
+
+    /// Returns the authentication without a sequence number, understood by nodes that do not
+    /// version their addresses yet.
+    pub fn legacy_authentication(&self) -> Option<Authentication<A>> {
+        match &self.session_info {
+            SessionInfo::SessionId(_) => None,
+            SessionInfo::OwnAuthentication {
+                legacy_authentication,
+                ..
+            } => Some(legacy_authentication.clone()),
         }
     }
 
@@ -152,30 +222,50 @@ impl<A: AddressingInformation> Handler<A> {
             .collect()
     }
 
+    /// Returns the sequence number of the most recent authentication of the given node, if any.
+    pub fn known_sequence_number(&self, node_id: &NodeIndex) -> Option<SequenceNumber> {
+        self.peers_by_node
+            .get(node_id)
+            .and_then(|peer_id| self.authentications.get(peer_id))
+            .map(|authentication| authentication.0.sequence_number())
+    }
+
     /// Verifies the authentication, uses it to update mappings, and returns the address we
-    /// should stay connected to if any.
+    /// should stay connected to if any. Authentications older than the most recent one we know
+    /// for the same node are ignored, newer ones replace it.
     pub fn handle_authentication(&mut self, authentication: Authentication<A>) -> Option<A> {
         if authentication.0.session() != self.session_id() {
             return None;
         }
         let Authentication(auth_data, signature) = &authentication;
 
+        if let Some(known) = self.known_sequence_number(&auth_data.creator()) {
+            if known > auth_data.sequence_number() {
+                return None;
+            }
+        }
         let address = auth_data.address();
         if !address.verify() {
             return None;
         }
         let peer_id = address.peer_id();
-        if peer_id == self.own_peer_id {
+        if peer_id == self.own_address.peer_id() {
             return None;
         }
         if !self
             .authority_verifier
-            .verify(&auth_data.encode(), signature, auth_data.creator())
+            .verify(&auth_data.signed_payload(), signature, auth_data.creator())
         {
             return None;
         }
-        self.peers_by_node
-            .insert(auth_data.creator(), peer_id.clone());
+        if let Some(old_peer_id) = self
+            .peers_by_node
+            .insert(auth_data.creator(), peer_id.clone())
+        {
+            if old_peer_id != peer_id {
+                self.authentications.remove(&old_peer_id);
+            }
+        }
         self.authentications.insert(peer_id, authentication);
         Some(address)
     }
@@ -207,11 +297,12 @@ impl<A: AddressingInformation> Handler<A> {
 
         let authentications = self.authentications.clone();
 
-        *self = Handler::new(
+        *self = Handler::new_with_sequence_number(
             authority_index_and_pen,
             authority_verifier,
             self.session_id(),
             address,
+            next_sequence_number(self.session_info.sequence_number()),
         );
 
         for (_, authentication) in authentications {
@@ -223,6 +314,23 @@ impl<A: AddressingInformation> Handler<A> {
             .map(|authentication| authentication.0.address())
             .collect())
     }
+
+    /// Updates own address, e.g. after the external addresses of the node changed.
+    /// Returns the new own authentication with a bumped sequence number, if the address
+    /// actually changed and we are a validator in this session.
+    pub fn update_own_address(&mut self, address: A) -> Option<Authentication<A>> {
+        if address == self.own_address {
+            return None;
+        }
+        self.session_info = construct_session_info(
+            &self.authority_index_and_pen,
+            self.session_id(),
+            address.clone(),
+            next_sequence_number(self.session_info.sequence_number()),
+        );
+        self.own_address = address;
+        self.authentication()
+    }
 }
 
 #[cfg(test)]
@@ -243,6 +351,14 @@ pub mod tests {
             .expect("this is a validator handler")
     }
 
+    pub fn legacy_authentication(
+        handler: &Handler<MockAddressingInformation>,
+    ) -> Authentication<MockAddressingInformation> {
+        handler
+            .legacy_authentication()
+            .expect("this is a validator handler")
+    }
+
     const NUM_NODES: usize = 7;
 
     #[test]
@@ -476,6 +592,105 @@ pub mod tests {
         assert_eq!(missing_nodes, expected_missing);
     }
 
+    #[test]
+    fn newer_authentication_replaces_older() {
+        let crypto_basics = crypto_basics(NUM_NODES);
+        let mut handler0 = Handler::new(
+            Some(crypto_basics.0[0].clone()),
+            crypto_basics.1.clone(),
+            SessionId(43),
+            random_address(),
+        );
+        let mut handler1 = Handler::new(
+            Some(crypto_basics.0[1].clone()),
+            crypto_basics.1.clone(),
+            SessionId(43),
+            random_address(),
+        );
+        let old_authentication = authentication(&handler1);
+        let address = random_address();
+        let new_authentication = handler1
+            .update_own_address(address.clone())
+            .expect("the address changed");
+        assert!(new_authentication.0.sequence_number() > old_authentication.0.sequence_number());
+        assert!(handler0
+            .handle_authentication(old_authentication)
+            .is_some());
+        assert_eq!(
+            handler0.handle_authentication(new_authentication),
+            Some(address.clone())
+        );
+        assert_eq!(handler0.peer_id(&NodeIndex(1)), Some(address.peer_id()));
+    }
+
+    #[test]
+    fn ignores_older_authentication() {
+        let crypto_basics = crypto_basics(NUM_NODES);
+        let mut handler0 = Handler::new(
+            Some(crypto_basics.0[0].clone()),
+            crypto_basics.1.clone(),
+            SessionId(43),
+            random_address(),
+        );
+        let mut handler1 = Handler::new(
+            Some(crypto_basics.0[1].clone()),
+            crypto_basics.1.clone(),
+            SessionId(43),
+            random_address(),
+        );
+        let old_authentication = authentication(&handler1);
+        let address = random_address();
+        let new_authentication = handler1
+            .update_own_address(address.clone())
+            .expect("the address changed");
+        assert!(handler0
+            .handle_authentication(new_authentication)
+            .is_some());
+        assert!(handler0
+            .handle_authentication(old_authentication)
+            .is_none());
+        assert!(handler0
+            .handle_authentication(legacy_authentication(&handler1))
+            .is_none());
+        assert_eq!(handler0.peer_id(&NodeIndex(1)), Some(address.peer_id()));
+    }
+
+    #[test]
+    fn accepts_legacy_authentication() {
+        let crypto_basics = crypto_basics(NUM_NODES);
+        let mut handler0 = Handler::new(
+            Some(crypto_basics.0[0].clone()),
+            crypto_basics.1.clone(),
+            SessionId(43),
+            random_address(),
+        );
+        let handler1 = Handler::new(
+            Some(crypto_basics.0[1].clone()),
+            crypto_basics.1.clone(),
+            SessionId(43),
+            random_address(),
+        );
+        assert!(handler0
+            .handle_authentication(legacy_authentication(&handler1))
+            .is_some());
+        assert!(handler0
+            .handle_authentication(authentication(&handler1))
+            .is_some());
+    }
+
+    #[test]
+    fn does_not_refresh_unchanged_address() {
+        let crypto_basics = crypto_basics(NUM_NODES);
+        let address = random_address();
+        let mut handler0 = Handler::new(
+            Some(crypto_basics.0[0].clone()),
+            crypto_basics.1,
+            SessionId(43),
+            address.clone(),
+        );
+        assert!(handler0.update_own_address(address).is_none());
+    }
+
     #[test]
     fn invalidates_obsolete_authentication() {
         let ed_crypto_basics = crypto_basics(NUM_NODES);
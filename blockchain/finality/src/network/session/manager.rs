@@ -73,6 +73,18 @@ pub struct PreNonvalidatorSession {
     pub verifier: AuthorityVerifier,
 }
 
+fn addressing_info<A: AddressingInformation>(
+    authentication: &Authentication<A>,
+) -> ValidatorAddressingInfo {
+    let address = authentication.0.address();
+    ValidatorAddressingInfo {
+        session: authentication.session_id(),
+        sequence_number: authentication.0.sequence_number(),
+        network_level_address: address.address(),
+        validator_network_peer_id: address.peer_id().to_string(),
+    }
+}
+
 /// Actions that the manager wants to take as the result of some information. Might contain a
 /// command for connecting to or disconnecting from some peers or a message to broadcast for
 /// discovery  purposes.
@@ -163,14 +175,41 @@ impl<NI: NetworkIdentity, D: Data, VCU: ValidatorAddressCacheUpdater> Manager<NI
     }
 
     /// Returns all the network messages that should be sent as part of discovery at this moment.
+    /// Includes our legacy authentications, so that nodes not supporting sequence numbers can
+    /// still find us.
     pub fn discovery(&mut self) -> Vec<Authentication<NI::AddressingInformation>> {
         let sessions: Vec<_> = self.sessions.keys().cloned().collect();
+        let legacy_authentications: Vec<_> = self
+            .sessions
+            .values()
+            .filter_map(|session| session.handler.legacy_authentication())
+            .collect();
         sessions
             .iter()
             .flat_map(|session_id| self.discover_authorities(session_id))
+            .chain(legacy_authentications)
             .collect()
     }
 
+    /// Checks whether our own addressing information changed, e.g. because the external
+    /// addresses of the node changed. If it did, all our authentications are replaced with
+    /// ones with higher sequence numbers, which are returned so that they can be broadcast
+    /// right away instead of waiting for the next discovery round.
+    pub fn refresh_own_address(&mut self) -> Vec<Authentication<NI::AddressingInformation>> {
+        let address = self.network_identity.identity();
+        let authentications: Vec<_> = self
+            .sessions
+            .values_mut()
+            .filter_map(|session| session.handler.update_own_address(address.clone()))
+            .collect();
+        for authentication in &authentications {
+            info!(target: "aleph-network", "Own address changed, broadcasting new authentication for session {:?}.", authentication.session_id());
+            self.validator_address_cache_updater
+                .update(authentication.0.creator(), addressing_info(authentication));
+        }
+        authentications
+    }
+
     fn start_validator_session(
         &mut self,
         pre_session: PreValidatorSession,
@@ -232,14 +271,6 @@ impl<NI: NetworkIdentity, D: Data, VCU: ValidatorAddressCacheUpdater> Manager<NI
             node_id,
             pen,
         } = pre_session;
-        self.validator_address_cache_updater.update(
-            node_id,
-            ValidatorAddressingInfo {
-                session: session_id,
-                network_level_address: address.address(),
-                validator_network_peer_id: address.peer_id().to_string(),
-            },
-        );
 
         let peers_to_stay = session
             .handler
@@ -247,6 +278,10 @@ impl<NI: NetworkIdentity, D: Data, VCU: ValidatorAddressCacheUpdater> Manager<NI
             .iter()
             .map(|address| address.peer_id())
             .collect();
+        if let Some(authentication) = session.handler.authentication() {
+            self.validator_address_cache_updater
+                .update(node_id, addressing_info(&authentication));
+        }
         let maybe_command = Self::delete_reserved(
             self.connections
                 .remove_session(session_id)
@@ -345,6 +380,7 @@ impl<NI: NetworkIdentity, D: Data, VCU: ValidatorAddressCacheUpdater> Manager<NI
     ) -> ManagerActions<NI::AddressingInformation> {
         let session_id = message.session_id();
         let creator = message.0.creator();
+        let info = addressing_info(&message);
         match self.sessions.get_mut(&session_id) {
             Some(Session {
                 handler, discovery, ..
@@ -353,14 +389,7 @@ impl<NI: NetworkIdentity, D: Data, VCU: ValidatorAddressCacheUpdater> Manager<NI
                     discovery.handle_authentication(message, handler);
                 let mut maybe_command = None;
                 if let Some(address) = maybe_address {
-                    self.validator_address_cache_updater.update(
-                        creator,
-                        ValidatorAddressingInfo {
-                            session: session_id,
-                            network_level_address: address.address(),
-                            validator_network_peer_id: address.peer_id().to_string(),
-                        },
-                    );
+                    self.validator_address_cache_updater.update(creator, info);
                     if handler.is_validator() {
                         debug!(target: "aleph-network", "Adding addresses for session {:?} to reserved: {:?}", session_id, address);
                         self.connections.add_peers(session_id, [address.peer_id()]);
@@ -487,7 +516,7 @@ mod tests {
         network::{
             address_cache::{test::noop_updater, ValidatorAddressCacheUpdater},
             mock::crypto_basics,
-            session::data::DataInSession,
+            session::{data::DataInSession, LEGACY_SEQUENCE_NUMBER},
         },
         Recipient, SessionId,
     };
@@ -622,6 +651,42 @@ mod tests {
         assert!(maybe_message.is_some());
     }
 
+    #[test]
+    fn does_not_refresh_unchanged_address() {
+        let mut manager = build();
+        let (validator_data, verifier) = crypto_basics(NUM_NODES);
+        let (node_id, pen) = validator_data[0].clone();
+        manager
+            .update_validator_session(PreValidatorSession {
+                session_id: SessionId(43),
+                verifier,
+                node_id,
+                pen,
+            })
+            .unwrap();
+        assert!(manager.refresh_own_address().is_empty());
+    }
+
+    #[test]
+    fn discovery_includes_legacy_authentication() {
+        let mut manager = build();
+        let (validator_data, verifier) = crypto_basics(NUM_NODES);
+        let (node_id, pen) = validator_data[0].clone();
+        manager
+            .update_validator_session(PreValidatorSession {
+                session_id: SessionId(43),
+                verifier,
+                node_id,
+                pen,
+            })
+            .unwrap();
+        let messages = manager.discovery();
+        assert_eq!(messages.len(), 2);
+        assert!(messages
+            .iter()
+            .any(|message| message.0.sequence_number() == LEGACY_SEQUENCE_NUMBER));
+    }
+
     #[test]
     fn sends_user_data() {
         let mut manager = build();
@@ -60,6 +60,14 @@ pub use service::{Config as ConnectionManagerConfig, ManagerError, Service as Co
 /// at most ~260 and no one should need more than a couple of these.
 pub const MAX_MESSAGE_SIZE: u64 = 1024 * 1024;
 
+/// Sequence number of an address record. Within a session, a record with a higher sequence
+/// number supersedes all records of the same validator with lower ones.
+pub type SequenceNumber = u64;
+
+/// The sequence number of records coming from nodes that do not version their addresses.
+/// Such records are signed without the sequence number and never supersede versioned ones.
+pub const LEGACY_SEQUENCE_NUMBER: SequenceNumber = 0;
+
 /// Data validators use to authenticate themselves for a single session
 /// and disseminate their addresses.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Encode, Decode)]
@@ -67,6 +75,7 @@ pub struct AuthData<A: AddressingInformation> {
     address: A,
     node_id: NodeIndex,
     session_id: SessionId,
+    sequence_number: SequenceNumber,
 }
 
 impl<A: AddressingInformation> AuthData<A> {
@@ -81,6 +90,19 @@ impl<A: AddressingInformation> AuthData<A> {
     pub fn address(&self) -> A {
         self.address.clone()
     }
+
+    pub fn sequence_number(&self) -> SequenceNumber {
+        self.sequence_number
+    }
+
+    /// The data that is actually signed. Legacy records are signed without the sequence number,
+    /// so that they can still be verified after being translated from the old format.
+    fn signed_payload(&self) -> Vec<u8> {
+        match self.sequence_number {
+            LEGACY_SEQUENCE_NUMBER => (&self.address, self.node_id, self.session_id).encode(),
+            _ => self.encode(),
+        }
+    }
 }
 
 /// A full authentication, consisting of a signed AuthData.
@@ -154,12 +154,17 @@ impl<D: Data> SessionManager<D> for ManagerInterface<D> {
     }
 }
 
+/// How often we check whether our own addressing information changed, at most.
+const MAX_ADDRESS_REFRESH_PERIOD: Duration = Duration::from_secs(10);
+
 /// Configuration for the session manager. Controls how often the maintenance and
-/// rebroadcasts are triggerred. Also controls when maintenance starts.
+/// rebroadcasts are triggerred. Also controls when maintenance starts and how often
+/// we check for changes of our own address.
 pub struct Config {
     discovery_cooldown: Duration,
     maintenance_period: Duration,
     initial_delay: Duration,
+    address_refresh_period: Duration,
 }
 
 impl Config {
@@ -172,6 +177,7 @@ impl Config {
             discovery_cooldown,
             maintenance_period,
             initial_delay,
+            address_refresh_period: cmp::min(maintenance_period, MAX_ADDRESS_REFRESH_PERIOD),
         }
     }
 
@@ -208,6 +214,7 @@ pub struct Service<
     gossip_network: GN,
     maintenance_period: Duration,
     initial_delay: Duration,
+    address_refresh_period: Duration,
 }
 
 /// Errors that can happen during the network service operations.
@@ -255,6 +262,7 @@ where
             discovery_cooldown,
             maintenance_period,
             initial_delay,
+            address_refresh_period,
         } = config;
         let manager = Manager::new(
             network_identity,
@@ -272,6 +280,7 @@ where
                 gossip_network,
                 maintenance_period,
                 initial_delay,
+                address_refresh_period,
             },
             ManagerInterface {
                 commands_for_service,
@@ -373,6 +382,11 @@ where
         let mut maintenance =
             time::interval_at(Instant::now() + self.initial_delay, self.maintenance_period);
 
+        let mut address_refresh = time::interval_at(
+            Instant::now() + self.address_refresh_period,
+            self.address_refresh_period,
+        );
+
         let mut status_ticker = time::interval(STATUS_REPORT_INTERVAL);
         loop {
             trace!(target: "aleph-network", "Manager Loop started a next iteration");
@@ -419,6 +433,12 @@ where
                         self.send_authentications(to_send.into())?;
                     }
                 },
+                _ = address_refresh.tick() => {
+                    trace!(target: "aleph-network", "Manager checks own address");
+                    for to_send in self.manager.refresh_own_address() {
+                        self.send_authentications(to_send.into())?;
+                    }
+                },
                 _ = status_ticker.tick() => {
                     self.manager.status_report();
                 }
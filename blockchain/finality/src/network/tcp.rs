@@ -18,12 +18,13 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{io::Error as IoError, iter, net::ToSocketAddrs as _};
+use std::{io::Error as IoError, iter, net::ToSocketAddrs as _, sync::Arc};
 
 use derive_more::{AsRef, Display};
-use log::info;
+use log::{info, warn};
 use network_clique::{Dialer, Listener, PeerId, PublicKey, SecretKey};
 use parity_scale_codec::{Decode, Encode};
+use parking_lot::Mutex;
 use sp_core::crypto::KeyTypeId;
 use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
 
@@ -106,6 +107,12 @@ impl TcpAddressingInformation {
     fn peer_id(&self) -> AuthorityId {
         self.peer_id.clone()
     }
+
+    fn addresses(&self) -> Vec<String> {
+        iter::once(self.primary_address.clone())
+            .chain(self.other_addresses.iter().cloned())
+            .collect()
+    }
 }
 
 /// A representation of TCP addressing information with an associated peer ID, self-signed.
@@ -156,6 +163,72 @@ impl SignedTcpAddressingInformation {
     }
 }
 
+/// External addresses of the node, which can be changed while the node is running.
+#[derive(Clone, Debug, Default)]
+pub struct ExternalAddresses(Arc<Mutex<Vec<String>>>);
+
+impl ExternalAddresses {
+    pub fn new(addresses: Vec<String>) -> Self {
+        ExternalAddresses(Arc::new(Mutex::new(addresses)))
+    }
+
+    /// Replaces the addresses, other validators learn about them on the next address refresh.
+    pub fn set(&self, addresses: Vec<String>) {
+        *self.0.lock() = addresses;
+    }
+
+    fn get(&self) -> Vec<String> {
+        self.0.lock().clone()
+    }
+}
+
+impl From<Vec<String>> for ExternalAddresses {
+    fn from(addresses: Vec<String>) -> Self {
+        ExternalAddresses::new(addresses)
+    }
+}
+
+/// Identity of the node, signed anew whenever its external addresses change.
+struct TcpNetworkIdentity {
+    external_addresses: ExternalAddresses,
+    authority_pen: AuthorityPen,
+    current: Mutex<SignedTcpAddressingInformation>,
+}
+
+impl TcpNetworkIdentity {
+    fn new(
+        external_addresses: ExternalAddresses,
+        authority_pen: &AuthorityPen,
+    ) -> Result<Self, AddressingInformationError> {
+        let current = SignedTcpAddressingInformation::new(external_addresses.get(), authority_pen)?;
+        Ok(TcpNetworkIdentity {
+            external_addresses,
+            authority_pen: authority_pen.clone(),
+            current: Mutex::new(current),
+        })
+    }
+}
+
+impl NetworkIdentity for TcpNetworkIdentity {
+    type PeerId = AuthorityIdWrapper;
+    type AddressingInformation = SignedTcpAddressingInformation;
+
+    fn identity(&self) -> Self::AddressingInformation {
+        let addresses = self.external_addresses.get();
+        let mut current = self.current.lock();
+        if current.addressing_information.addresses() != addresses {
+            match SignedTcpAddressingInformation::new(addresses, &self.authority_pen) {
+                Ok(identity) => *current = identity,
+                Err(e) => warn!(
+                    target: LOG_TARGET,
+                    "Keeping the previous external addresses, the new ones are invalid: {:?}.", e
+                ),
+            }
+        }
+        current.clone()
+    }
+}
+
 #[derive(Clone)]
 struct TcpDialer;
 
@@ -213,7 +286,7 @@ impl From<AddressingInformationError> for Error {
 /// authentications for other peers.
 pub async fn new_tcp_network<A: ToSocketAddrs>(
     listening_addresses: A,
-    external_addresses: Vec<String>,
+    external_addresses: ExternalAddresses,
     authority_pen: &AuthorityPen,
 ) -> Result<
     (
@@ -227,7 +300,7 @@ pub async fn new_tcp_network<A: ToSocketAddrs>(
     Error,
 > {
     let listener = TcpListener::bind(listening_addresses).await?;
-    let identity = SignedTcpAddressingInformation::new(external_addresses, authority_pen)?;
+    let identity = TcpNetworkIdentity::new(external_addresses, authority_pen)?;
     Ok((TcpDialer {}, listener, identity))
 }
 
@@ -249,3 +322,41 @@ pub mod testing {
             .expect("the provided addresses are fine")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use sc_keystore::LocalKeystore;
+
+    use super::{ExternalAddresses, TcpNetworkIdentity};
+    use crate::{
+        network::{AddressingInformation, NetworkIdentity},
+        nodes::new_pen,
+    };
+
+    fn identity(external_addresses: ExternalAddresses) -> TcpNetworkIdentity {
+        let mnemonic = "ring cool spatial rookie need wing opinion pond fork garbage more april";
+        let pen = new_pen(mnemonic, Arc::new(LocalKeystore::in_memory()));
+        TcpNetworkIdentity::new(external_addresses, &pen).expect("the addresses are fine")
+    }
+
+    #[test]
+    fn identity_follows_external_addresses() {
+        let external_addresses = ExternalAddresses::new(vec![String::from("old")]);
+        let identity = identity(external_addresses.clone());
+        assert_eq!(identity.identity().address(), "old");
+        external_addresses.set(vec![String::from("new")]);
+        let refreshed = identity.identity();
+        assert_eq!(refreshed.address(), "new");
+        assert!(refreshed.verify());
+    }
+
+    #[test]
+    fn identity_keeps_addresses_when_new_ones_are_empty() {
+        let external_addresses = ExternalAddresses::new(vec![String::from("old")]);
+        let identity = identity(external_addresses.clone());
+        external_addresses.set(Vec::new());
+        assert_eq!(identity.identity().address(), "old");
+    }
+}
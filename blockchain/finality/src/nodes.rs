@@ -22,7 +22,7 @@ use std::{marker::PhantomData, sync::Arc};
 
 use bip39::{Language, Mnemonic, MnemonicType};
 use futures::channel::oneshot;
-use log::{debug, error};
+use log::{debug, error, warn};
 use network_clique::{RateLimitingDialer, RateLimitingListener, Service, SpawnHandleT};
 use rate_limiter::SleepingRateLimiter;
 use sc_client_api::Backend;
@@ -40,7 +40,9 @@ use crate::{
     crypto::AuthorityPen,
     finalization::AlephFinalizer,
     idx_to_account::ValidatorIndexToAccountIdConverterImpl,
-    metrics::{run_chain_state_metrics, transaction_pool::TransactionPoolWrapper},
+    metrics::{
+        run_chain_state_metrics, transaction_pool::TransactionPoolWrapper, ScoreboardMetrics,
+    },
     network::{
         address_cache::validator_address_cache_updater,
         session::{ConnectionManager, ConnectionManagerConfig},
@@ -55,7 +57,7 @@ use crate::{
     session::SessionBoundaryInfo,
     session_map::{AuthorityProviderImpl, FinalityNotifierImpl, SessionMapUpdater},
     sync::{DatabaseIO as SyncDatabaseIO, Service as SyncService, IO as SyncIO},
-    AlephConfig,
+    AlephConfig, ValidatorAddressCache,
 };
 
 // How many sessions we remember.
@@ -103,6 +105,10 @@ where
         rate_limiter_config,
         sync_oracle,
         validator_address_cache,
+        validator_address_cache_path,
+        proposal_policy,
+        sync_budget,
+        justification_notifications,
         transaction_pool,
     } = aleph_config;
 
@@ -215,7 +221,11 @@ where
         VERIFIER_CACHE_SIZE,
         genesis_header,
     );
-    let finalizer = AlephFinalizer::new(client.clone(), metrics.clone());
+    let finalizer = AlephFinalizer::new(
+        client.clone(),
+        metrics.clone(),
+        justification_notifications,
+    );
     import_queue_handle.attach_metrics(metrics.clone());
     let justifications_for_sync = justification_channel_provider.get_sender();
     let sync_io = SyncIO::new(
@@ -231,6 +241,7 @@ where
         session_info.clone(),
         sync_io,
         registry.clone(),
+        sync_budget,
     ) {
         Ok(x) => x,
         Err(e) => panic!("Failed to initialize Sync service: {e}"),
@@ -244,6 +255,25 @@ where
         }
     };
 
+    let validator_address_cache = validator_address_cache.or_else(|| {
+        let path = validator_address_cache_path?;
+        match ValidatorAddressCache::with_persistence(path.clone()) {
+            Ok(cache) => Some(cache),
+            Err(e) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Failed to load the validator address cache from {path:?}, starting empty: {e}."
+                );
+                Some(ValidatorAddressCache::new())
+            }
+        }
+    });
+    if let Some(validator_address_cache) = &validator_address_cache {
+        spawn_handle.spawn(
+            "aleph/validator-address-cache",
+            validator_address_cache.clone().run_persistence(),
+        );
+    }
     let validator_address_cache_updater = validator_address_cache_updater(
         validator_address_cache,
         ValidatorIndexToAccountIdConverterImpl::new(
@@ -253,6 +283,24 @@ where
         ),
     );
 
+    let scoreboard = match ScoreboardMetrics::new(
+        registry.as_ref(),
+        ValidatorIndexToAccountIdConverterImpl::new(
+            client.clone(),
+            session_info.clone(),
+            RuntimeApiImpl::new(client.clone()),
+        ),
+    ) {
+        Ok(scoreboard) => scoreboard,
+        Err(e) => {
+            warn!(
+                target: LOG_TARGET,
+                "Failed to register Prometheus validator scoreboard metrics: {:?}.", e
+            );
+            ScoreboardMetrics::noop()
+        }
+    };
+
     let (connection_manager_service, connection_manager) = ConnectionManager::new(
         network_identity,
         validator_network,
@@ -293,6 +341,8 @@ where
             JustificationTranslator::new(chain_status.clone()),
             request_block,
             metrics,
+            scoreboard,
+            proposal_policy,
             spawn_handle,
             connection_manager,
             keystore,
@@ -39,7 +39,7 @@ use crate::{
     },
     crypto::Signature,
     justification::AlephJustification,
-    metrics::{AllBlockMetrics, Checkpoint},
+    metrics::{AllBlockMetrics, Checkpoint, SessionScoreboard},
     network::data::Network,
     party::{
         manager::aggregator::AggregatorVersion::{Current, Legacy},
@@ -129,6 +129,7 @@ async fn run_aggregator<H, C, CN, LN, JS>(
     client: C,
     session_boundaries: &SessionBoundaries,
     mut metrics: AllBlockMetrics,
+    scoreboard: SessionScoreboard,
     mut exit_rx: oneshot::Receiver<()>,
 ) -> Result<(), Error>
 where
@@ -178,6 +179,7 @@ where
             },
             multisigned_hash = aggregator.next_multisigned_hash() => {
                 let (hash, multisignature) = multisigned_hash.ok_or(Error::MultisignaturesStreamTerminated)?;
+                scoreboard.report_multisignature(&multisignature);
                 process_hash(hash, multisignature, &mut justifications_for_chain, &justification_translator, &client).map_err(|_| Error::UnableToProcessHash)?;
                 if Some(hash) == hash_of_last_block {
                     hash_of_last_block = None;
@@ -212,6 +214,7 @@ pub fn task<H, C, CN, LN, JS>(
     io: IO<JS>,
     session_boundaries: SessionBoundaries,
     metrics: AllBlockMetrics,
+    scoreboard: SessionScoreboard,
     multikeychain: Keychain,
     version: AggregatorVersion<CN, LN>,
 ) -> Task
@@ -240,6 +243,7 @@ where
                 client,
                 &session_boundaries,
                 metrics,
+                scoreboard,
                 exit,
             )
             .await;
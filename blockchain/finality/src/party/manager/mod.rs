@@ -44,9 +44,10 @@ use crate::{
             ChainTracker as LegacyChainTracker, DataStore as LegacyDataStore,
             OrderedDataInterpreter as LegacyOrderedDataInterpreter,
         },
-        ChainTracker, DataStore, OrderedDataInterpreter, SubstrateChainInfoProvider,
+        ChainTracker, DataStore, OrderedDataInterpreter, ProposalPolicyConfig,
+        SubstrateChainInfoProvider,
     },
-    metrics::AllBlockMetrics,
+    metrics::{AllBlockMetrics, ScoreboardMetrics, SessionScoreboard},
     mpsc,
     network::{
         data::{
@@ -110,6 +111,7 @@ where
     multikeychain: Keychain,
     exit_rx: oneshot::Receiver<()>,
     backup: ABFTBackup,
+    scoreboard: SessionScoreboard,
 }
 
 pub struct NodeSessionManagerImpl<H, C, HB, BBS, B, RB, SM, JS, V>
@@ -136,6 +138,8 @@ where
     justification_translator: JustificationTranslator,
     block_requester: RB,
     metrics: AllBlockMetrics,
+    scoreboard: ScoreboardMetrics,
+    proposal_policy: ProposalPolicyConfig,
     spawn_handle: SpawnHandle,
     session_manager: SM,
     keystore: Arc<LocalKeystore>,
@@ -168,6 +172,8 @@ where
         justification_translator: JustificationTranslator,
         block_requester: RB,
         metrics: AllBlockMetrics,
+        scoreboard: ScoreboardMetrics,
+        proposal_policy: ProposalPolicyConfig,
         spawn_handle: SpawnHandle,
         session_manager: SM,
         keystore: Arc<LocalKeystore>,
@@ -183,6 +189,8 @@ where
             justification_translator,
             block_requester,
             metrics,
+            scoreboard,
+            proposal_policy,
             spawn_handle,
             session_manager,
             keystore,
@@ -207,6 +215,7 @@ where
             multikeychain,
             exit_rx,
             backup,
+            scoreboard,
             ..
         } = params;
         let (chain_tracker, data_provider) = LegacyChainTracker::new(
@@ -252,6 +261,7 @@ where
                 aggregator_io,
                 session_boundaries,
                 self.metrics.clone(),
+                scoreboard,
                 multikeychain,
                 AggregatorVersion::<CurrentNetworkType, _>::Legacy(rmc_network),
             ),
@@ -277,20 +287,24 @@ where
             multikeychain,
             exit_rx,
             backup,
+            scoreboard,
             ..
         } = params;
         let (chain_tracker, data_provider) = ChainTracker::new(
             self.best_block_selection_strategy.clone(),
+            self.proposal_policy.build(),
             self.header_backend.clone(),
             session_boundaries.clone(),
             Default::default(),
             self.metrics.clone(),
         );
+        let data_provider = data_provider.with_scoreboard(scoreboard.clone());
         let ordered_data_interpreter = OrderedDataInterpreter::new(
             blocks_for_aggregator,
             chain_info,
             self.verifier.clone(),
             session_boundaries.clone(),
+            scoreboard.clone(),
         );
         let consensus_config =
             current_create_aleph_config(n_members, node_id, session_id, self.unit_creation_delay);
@@ -324,6 +338,7 @@ where
                 aggregator_io,
                 session_boundaries,
                 self.metrics.clone(),
+                scoreboard,
                 multikeychain,
                 AggregatorVersion::<_, LegacyNetworkType>::Current(rmc_network),
             ),
@@ -395,6 +410,9 @@ where
             multikeychain,
             exit_rx,
             backup,
+            scoreboard: self
+                .scoreboard
+                .start_session(session_id, authorities.len(), node_id),
         };
 
         match self
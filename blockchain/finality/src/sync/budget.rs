@@ -0,0 +1,301 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{cmp::min, collections::HashMap};
+
+use parity_scale_codec::Encode;
+use tokio::time::{Duration, Instant};
+
+use crate::{
+    block::{Block, Justification, UnverifiedHeaderFor},
+    sync::{data::ResponseItem, PeerId},
+};
+
+/// Cost of handling a request, charged before anything is read from the database.
+const REQUEST_COST: u64 = 1;
+/// Cost of sending a single justification.
+const JUSTIFICATION_COST: u64 = 1;
+/// Cost of sending a single header.
+const HEADER_COST: u64 = 1;
+/// Cost of sending a single block, on top of the cost of its size.
+const BLOCK_COST: u64 = 1;
+/// Every this many bytes of an encoded block cost one additional unit.
+const BLOCK_BYTES_PER_COST: u64 = 1024;
+
+// With the above, a unit of cost is roughly a KiB of data, so by default peers can request around
+// 32MiB at once and 4MiB per second in the long run.
+const DEFAULT_CAPACITY: u64 = 32 * 1024;
+const DEFAULT_REFILL_PER_SECOND: u64 = 4 * 1024;
+
+/// The cost of sending the item in response to a request.
+pub fn response_item_cost<B, J>(item: &ResponseItem<B, J>) -> u64
+where
+    J: Justification,
+    B: Block<UnverifiedHeader = UnverifiedHeaderFor<J>>,
+{
+    match item {
+        ResponseItem::Justification(_) => JUSTIFICATION_COST,
+        ResponseItem::Header(_) => HEADER_COST,
+        ResponseItem::Block(block) => BLOCK_COST + block.encoded_size() as u64 / BLOCK_BYTES_PER_COST,
+    }
+}
+
+/// Limits on how much data every peer can request from us through the block sync.
+#[derive(Clone, Debug)]
+pub struct BudgetConfig {
+    /// The maximal budget a peer can accumulate.
+    pub capacity: u64,
+    /// How much budget a peer regains every second.
+    pub refill_per_second: u64,
+}
+
+impl Default for BudgetConfig {
+    fn default() -> Self {
+        BudgetConfig {
+            capacity: DEFAULT_CAPACITY,
+            refill_per_second: DEFAULT_REFILL_PER_SECOND,
+        }
+    }
+}
+
+struct PeerBudget {
+    available: u64,
+    last_refill: Instant,
+}
+
+impl PeerBudget {
+    fn new(capacity: u64) -> Self {
+        PeerBudget {
+            available: capacity,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, config: &BudgetConfig) {
+        let now = Instant::now();
+        if self.available >= config.capacity || config.refill_per_second == 0 {
+            self.last_refill = now;
+            return;
+        }
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let refill = (elapsed.as_micros() * config.refill_per_second as u128 / 1_000_000) as u64;
+        if refill == 0 {
+            return;
+        }
+        self.available = min(config.capacity, self.available.saturating_add(refill));
+        // Only move forward by the time that was actually used up, so that no budget is lost to rounding.
+        self.last_refill += Duration::from_micros(refill * 1_000_000 / config.refill_per_second);
+    }
+}
+
+/// Keeps track of how much each peer can still request from us, so that no single peer can make us
+/// read and send arbitrary amounts of data. The budget of every peer is a token bucket, refilled at
+/// a constant rate, responses are charged according to `response_item_cost`.
+pub struct RequestBudget<I: PeerId> {
+    config: BudgetConfig,
+    peers: HashMap<I, PeerBudget>,
+}
+
+impl<I: PeerId> RequestBudget<I> {
+    pub fn new(config: BudgetConfig) -> Self {
+        RequestBudget {
+            config,
+            peers: HashMap::new(),
+        }
+    }
+
+    fn budget(&mut self, peer: &I) -> &mut PeerBudget {
+        let config = &self.config;
+        let budget = self
+            .peers
+            .entry(peer.clone())
+            .or_insert_with(|| PeerBudget::new(config.capacity));
+        budget.refill(config);
+        budget
+    }
+
+    /// Whether the peer has used up all of its budget.
+    #[cfg(test)]
+    pub fn exhausted(&mut self, peer: &I) -> bool {
+        self.budget(peer).available == 0
+    }
+
+    /// Charges the peer for handling a request, before any work is done for it. Returns whether the
+    /// peer could afford it, requests of peers that could not should not be handled.
+    pub fn charge_request(&mut self, peer: &I) -> bool {
+        let budget = self.budget(peer);
+        if budget.available < REQUEST_COST {
+            return false;
+        }
+        budget.available -= REQUEST_COST;
+        true
+    }
+
+    /// Charges the peer for the longest prefix of the response that fits in its budget and returns
+    /// the length of that prefix. Only this prefix should be sent.
+    pub fn charge<B, J>(&mut self, peer: &I, response_items: &[ResponseItem<B, J>]) -> usize
+    where
+        J: Justification,
+        B: Block<UnverifiedHeader = UnverifiedHeaderFor<J>>,
+    {
+        let budget = self.budget(peer);
+        for (idx, item) in response_items.iter().enumerate() {
+            let cost = response_item_cost(item);
+            if cost > budget.available {
+                budget.available = 0;
+                return idx;
+            }
+            budget.available -= cost;
+        }
+        response_items.len()
+    }
+
+    /// Forgets about peers that have their full budget available, as they are indistinguishable
+    /// from new ones.
+    pub fn prune(&mut self) {
+        let config = &self.config;
+        self.peers.retain(|_, budget| {
+            budget.refill(config);
+            budget.available < config.capacity
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::sleep;
+
+    use super::{response_item_cost, BudgetConfig, RequestBudget};
+    use crate::{
+        block::mock::{MockBlock, MockHeader, MockJustification},
+        sync::data::ResponseItem,
+    };
+
+    type MockResponseItem = ResponseItem<MockBlock, MockJustification>;
+
+    fn response_items(len: usize) -> Vec<MockResponseItem> {
+        MockHeader::genesis()
+            .random_branch()
+            .take(len)
+            .map(|header| ResponseItem::Block(MockBlock::new(header, true)))
+            .collect()
+    }
+
+    fn total_cost(response_items: &[MockResponseItem]) -> u64 {
+        response_items.iter().map(response_item_cost).sum()
+    }
+
+    #[test]
+    fn charges_according_to_cost_model() {
+        let header = MockHeader::genesis().random_child();
+        let block = MockBlock::new(header.clone(), true);
+        assert_eq!(
+            response_item_cost(&MockResponseItem::Header(header.clone())),
+            1
+        );
+        assert_eq!(
+            response_item_cost(&MockResponseItem::Justification(
+                MockJustification::for_header(header)
+            )),
+            1
+        );
+        assert!(response_item_cost(&MockResponseItem::Block(block)) >= 1);
+    }
+
+    #[test]
+    fn serves_everything_within_budget() {
+        let items = response_items(10);
+        let mut budget = RequestBudget::new(BudgetConfig {
+            capacity: 2 * total_cost(&items),
+            refill_per_second: 0,
+        });
+        assert_eq!(budget.charge(&0, &items), items.len());
+        assert_eq!(budget.charge(&0, &items), items.len());
+        assert!(budget.exhausted(&0));
+    }
+
+    #[test]
+    fn truncates_response_above_budget() {
+        let items = response_items(10);
+        let mut budget = RequestBudget::new(BudgetConfig {
+            capacity: total_cost(&items[..4]),
+            refill_per_second: 0,
+        });
+        assert_eq!(budget.charge(&0, &items), 4);
+        assert!(budget.exhausted(&0));
+        assert_eq!(budget.charge(&0, &items), 0);
+    }
+
+    #[test]
+    fn charges_requests_before_serving() {
+        let mut budget = RequestBudget::new(BudgetConfig {
+            capacity: 2,
+            refill_per_second: 0,
+        });
+        assert!(budget.charge_request(&0));
+        assert!(budget.charge_request(&0));
+        assert!(budget.exhausted(&0));
+        assert!(!budget.charge_request(&0));
+        assert!(budget.charge_request(&1));
+    }
+
+    #[test]
+    fn peers_have_separate_budgets() {
+        let items = response_items(10);
+        let mut budget = RequestBudget::new(BudgetConfig {
+            capacity: total_cost(&items),
+            refill_per_second: 0,
+        });
+        assert_eq!(budget.charge(&0, &items), items.len());
+        assert!(budget.exhausted(&0));
+        assert!(!budget.exhausted(&1));
+        assert_eq!(budget.charge(&1, &items), items.len());
+    }
+
+    #[tokio::test]
+    async fn budget_refills_over_time() {
+        let items = response_items(10);
+        let cost = total_cost(&items);
+        let mut budget = RequestBudget::new(BudgetConfig {
+            capacity: cost,
+            refill_per_second: 100 * cost,
+        });
+        assert_eq!(budget.charge(&0, &items), items.len());
+        sleep(Duration::from_millis(50)).await;
+        assert!(!budget.exhausted(&0));
+        assert_eq!(budget.charge(&0, &items), items.len());
+    }
+
+    #[test]
+    fn prunes_only_peers_with_full_budget() {
+        let items = response_items(10);
+        let mut budget = RequestBudget::new(BudgetConfig {
+            capacity: total_cost(&items),
+            refill_per_second: 0,
+        });
+        budget.charge(&0, &items);
+        assert!(!budget.exhausted(&1));
+        budget.prune();
+        assert_eq!(budget.peers.len(), 1);
+        assert!(budget.peers.contains_key(&0));
+    }
+}
@@ -98,6 +98,8 @@ pub enum Metrics {
     Prometheus {
         event_calls: HashMap<Event, Counter<U64>>,
         event_errors: HashMap<Event, Counter<U64>>,
+        budget_violations: Counter<U64>,
+        throttled_cost: Counter<U64>,
     },
     Noop,
 }
@@ -134,9 +136,25 @@ impl Metrics {
                 )?,
             );
         }
+        let budget_violations = register(
+            Counter::new(
+                "aleph_sync_request_budget_violations",
+                "number of requests not served in full because the peer exceeded its budget",
+            )?,
+            &registry,
+        )?;
+        let throttled_cost = register(
+            Counter::new(
+                "aleph_sync_request_budget_throttled_cost",
+                "total cost of response items not sent because of exceeded budgets",
+            )?,
+            &registry,
+        )?;
         Ok(Metrics::Prometheus {
             event_calls,
             event_errors,
+            budget_violations,
+            throttled_cost,
         })
     }
 
@@ -159,4 +177,16 @@ impl Metrics {
             }
         }
     }
+
+    pub fn report_budget_violation(&self, cost_throttled: u64) {
+        if let Metrics::Prometheus {
+            budget_violations,
+            throttled_cost,
+            ..
+        } = self
+        {
+            budget_violations.inc();
+            throttled_cost.inc_by(cost_throttled);
+        }
+    }
 }
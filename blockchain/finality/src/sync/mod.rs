@@ -29,6 +29,7 @@ use crate::{
     BlockId,
 };
 
+mod budget;
 mod data;
 mod forest;
 mod handler;
@@ -39,6 +40,7 @@ mod task_queue;
 mod tasks;
 mod ticker;
 
+pub use budget::BudgetConfig;
 pub use data::MAX_MESSAGE_SIZE;
 pub use handler::DatabaseIO;
 pub use service::{Service, IO};
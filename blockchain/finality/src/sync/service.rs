@@ -34,6 +34,7 @@ use crate::{
     network::GossipNetwork,
     session::SessionBoundaryInfo,
     sync::{
+        budget::{response_item_cost, BudgetConfig, RequestBudget},
         data::{
             NetworkData, PreRequest, Request, ResponseItem, ResponseItems, State, VersionWrapper,
             VersionedNetworkData,
@@ -161,6 +162,7 @@ where
     block_requests_from_user: mpsc::UnboundedReceiver<B::UnverifiedHeader>,
     legacy_block_requests_from_user: mpsc::UnboundedReceiver<BlockId>,
     blocks_from_creator: mpsc::UnboundedReceiver<B>,
+    request_budget: RequestBudget<N::PeerId>,
     metrics: Metrics,
 }
 
@@ -213,6 +215,7 @@ where
         session_info: SessionBoundaryInfo,
         io: IO<B, J, N, CE, CS, F, BI>,
         metrics_registry: Option<Registry>,
+        budget_config: BudgetConfig,
     ) -> Result<
         (
             Self,
@@ -255,6 +258,7 @@ where
                 blocks_from_creator,
                 block_requests_from_user,
                 legacy_block_requests_from_user,
+                request_budget: RequestBudget::new(budget_config),
                 metrics,
             },
             CompatibilityRequestBlocks {
@@ -553,13 +557,43 @@ where
         response_items: &[ResponseItem<B, J>],
         peer: N::PeerId,
     ) -> Result<(), MsgLimiterError> {
-        let mut limiter = MsgLimiter::new(response_items);
+        let allowed = self.request_budget.charge(&peer, response_items);
+        if allowed < response_items.len() {
+            debug!(
+                target: LOG_TARGET,
+                "Peer {:?} exceeded its request budget, sending only {} out of {} response items.",
+                peer,
+                allowed,
+                response_items.len()
+            );
+            self.metrics.report_budget_violation(
+                response_items[allowed..]
+                    .iter()
+                    .map(response_item_cost)
+                    .sum(),
+            );
+        }
+        let mut limiter = MsgLimiter::new(&response_items[..allowed]);
         while let Some(chunk) = limiter.next_largest_msg()? {
             self.send_to(NetworkData::RequestResponse(chunk.to_vec()), peer.clone())
         }
         Ok(())
     }
 
+    // Every request is charged before it is handled, so peers which used up their whole budget
+    // get no responses, and cause no database reads, until it refills.
+    fn throttled(&mut self, peer: &N::PeerId) -> bool {
+        if self.request_budget.charge_request(peer) {
+            return false;
+        }
+        debug!(
+            target: LOG_TARGET,
+            "Ignoring a request from {:?}, which exceeded its request budget.", peer
+        );
+        self.metrics.report_budget_violation(0);
+        true
+    }
+
     fn handle_request(&mut self, request: Request<J>, peer: N::PeerId) {
         trace!(
             target: LOG_TARGET,
@@ -568,6 +602,9 @@ where
             peer
         );
         self.metrics.report_event(Event::HandleRequest);
+        if self.throttled(&peer) {
+            return;
+        }
 
         match self.handler.handle_request(request) {
             Ok((action, maybe_equivocation_proof)) => {
@@ -717,6 +754,9 @@ where
 
     fn handle_chain_extension_request(&mut self, state: State<J>, peer: N::PeerId) {
         self.metrics.report_event(Event::HandleExtensionRequest);
+        if self.throttled(&peer) {
+            return;
+        }
         match self.handler.handle_chain_extension_request(state) {
             Ok(Action::Response(response_items)) => {
                 if let Err(e) = self.send_big_response(&response_items, peer) {
@@ -831,6 +871,7 @@ where
                 },
                 _ = status_ticker.tick() => {
                     info!(target: LOG_TARGET, "{}", self.handler.status());
+                    self.request_budget.prune();
                 },
             }
         }
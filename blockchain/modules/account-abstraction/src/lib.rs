@@ -0,0 +1,392 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Account Abstraction Module
+//!
+//! ## Overview
+//!
+//! An EIP-4337 style entrypoint for smart contract wallets. Bundlers submit
+//! `UserOperation`s with `handle_ops`. Every operation is validated by its
+//! wallet, and by its paymaster if the fee is sponsored, then the fee is
+//! prefunded in native token and the operation is executed by the EVM on
+//! behalf of the wallet. The bundler is compensated with the actual fee of the
+//! operations it submitted.
+//!
+//! Fees paid in a non-native currency are swapped through the charge fee pool
+//! or dex of `transaction-payment`, the same way as `with_fee_currency`.
+//!
+//! The validation and execution calls are made with the `EntryPoint` address
+//! as `msg.sender`. Wallets implement
+//! `validateUserOp(bytes32 userOpHash, bytes signature) returns (uint256)` and
+//! paymasters implement
+//! `validatePaymasterUserOp(bytes32 userOpHash, address sender, uint256 maxFee, bytes paymasterData) returns (uint256)`,
+//! both returning zero to accept the operation.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	dispatch::PostDispatchInfo,
+	pallet_prelude::*,
+	traits::{ExistenceRequirement, NamedReservableCurrency},
+	weights::WeightToFee,
+};
+use frame_system::pallet_prelude::*;
+use module_support::{AddressMapping, ExecutionMode, FeeCurrencySwap, InvokeContext, EVM};
+use num_enum::IntoPrimitive;
+use parity_scale_codec::Encode;
+use primitives::{evm::EvmAddress, to_bytes, Balance, CurrencyId, ReserveIdentifier};
+use scale_info::TypeInfo;
+use sp_core::{H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::{
+	traits::{Convert, UniqueSaturatedInto},
+	DispatchError, RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub const RESERVE_ID: ReserveIdentifier = ReserveIdentifier::AccountAbstraction;
+
+#[module_evm_utility_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	ValidateUserOp = "validateUserOp(bytes32,bytes)",
+	ValidatePaymasterUserOp = "validatePaymasterUserOp(bytes32,address,uint256,bytes)",
+}
+
+/// An operation submitted on behalf of a smart contract wallet.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct UserOperation {
+	/// The wallet contract sending the operation.
+	pub sender: EvmAddress,
+
+	/// Anti-replay nonce of the wallet in the entrypoint.
+	pub nonce: u64,
+
+	/// The data the wallet is called with to execute the operation.
+	pub call_data: Vec<u8>,
+
+	/// Gas limit of the execution call.
+	pub call_gas_limit: u64,
+
+	/// Gas limit of each validation call.
+	pub verification_gas_limit: u64,
+
+	/// Storage limit of the validation and execution calls.
+	pub storage_limit: u32,
+
+	/// Currency the fee is paid in.
+	pub fee_currency_id: CurrencyId,
+
+	/// Maximum fee in native token the operation is willing to pay.
+	pub max_fee: Balance,
+
+	/// Contract paying the fee on behalf of the wallet.
+	pub paymaster: Option<EvmAddress>,
+
+	/// Data passed to the paymaster for validation.
+	pub paymaster_data: Vec<u8>,
+
+	/// Signature verified by the wallet.
+	pub signature: Vec<u8>,
+}
+
+impl UserOperation {
+	/// Total gas limit of the validation and execution calls.
+	pub fn gas_limit(&self) -> u64 {
+		let validations: u64 = if self.paymaster.is_some() { 2 } else { 1 };
+		self.verification_gas_limit
+			.saturating_mul(validations)
+			.saturating_add(self.call_gas_limit)
+	}
+
+	/// Length of the data hashed and ABI encoded for the validation and execution calls.
+	pub fn data_len(&self) -> u32 {
+		let len = self
+			.call_data
+			.len()
+			.saturating_add(self.paymaster_data.len())
+			.saturating_add(self.signature.len());
+		u32::try_from(len).unwrap_or(u32::MAX)
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Native currency to prefund operations and compensate bundlers.
+		type Currency: NamedReservableCurrency<Self::AccountId, ReserveIdentifier = ReserveIdentifier, Balance = Balance>;
+
+		/// Swap the fee currency of operations to native token.
+		type FeeCurrencySwap: FeeCurrencySwap<Self::AccountId, Balance>;
+
+		/// Native currency id
+		#[pallet::constant]
+		type NativeCurrencyId: Get<CurrencyId>;
+
+		/// EVM to validate and execute operations.
+		type EVM: EVM<Self::AccountId>;
+
+		/// Mapping from EVM address to Substrate account id.
+		type AddressMapping: AddressMapping<Self::AccountId>;
+
+		/// The EVM address of the entrypoint, the `msg.sender` of validation and
+		/// execution calls.
+		#[pallet::constant]
+		type EntryPoint: Get<EvmAddress>;
+
+		/// Chain ID of EVM.
+		#[pallet::constant]
+		type ChainId: Get<u64>;
+
+		/// Convert gas to weight.
+		type GasToWeight: Convert<u64, Weight>;
+
+		/// Convert the weight of used gas to fee.
+		type WeightToFee: WeightToFee<Balance = Balance>;
+
+		/// The maximum number of operations in a bundle.
+		#[pallet::constant]
+		type MaxUserOperations: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The bundle is empty
+		InvalidBundleSize,
+		/// The nonce of the operation is invalid
+		InvalidNonce,
+		/// The wallet rejected the operation
+		WalletValidationFailed,
+		/// The paymaster rejected the operation
+		PaymasterValidationFailed,
+		/// The fee payer cannot prefund the maximum fee
+		InsufficientPrefund,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A user operation has been handled.
+		UserOperationHandled {
+			user_op_hash: H256,
+			sender: EvmAddress,
+			paymaster: Option<EvmAddress>,
+			nonce: u64,
+			success: bool,
+			actual_fee: Balance,
+			actual_gas_used: u64,
+		},
+	}
+
+	/// The next nonce of wallets.
+	///
+	/// Nonces: map EvmAddress => u64
+	#[pallet::storage]
+	#[pallet::getter(fn nonces)]
+	pub type Nonces<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, u64, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Validate and execute a bundle of user operations. The caller is
+		/// compensated with the actual fee of every operation.
+		///
+		/// - `ops`: the user operations
+		#[pallet::call_index(0)]
+		#[pallet::weight({
+			let gas_limit = ops.iter().fold(0u64, |acc, op| acc.saturating_add(op.gas_limit()));
+			let data_len = ops.iter().fold(0u32, |acc, op| acc.saturating_add(op.data_len()));
+			T::WeightInfo::handle_ops(ops.len() as u32, data_len).saturating_add(T::GasToWeight::convert(gas_limit))
+		})]
+		pub fn handle_ops(
+			origin: OriginFor<T>,
+			ops: BoundedVec<UserOperation, T::MaxUserOperations>,
+		) -> DispatchResultWithPostInfo {
+			let bundler = ensure_signed(origin)?;
+			ensure!(!ops.is_empty(), Error::<T>::InvalidBundleSize);
+
+			let ops_count = ops.len() as u32;
+			let data_len = ops.iter().fold(0u32, |acc, op| acc.saturating_add(op.data_len()));
+			let mut used_gas: u64 = 0;
+			for op in ops {
+				used_gas = used_gas.saturating_add(Self::handle_op(&bundler, op)?);
+			}
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(
+					T::WeightInfo::handle_ops(ops_count, data_len).saturating_add(T::GasToWeight::convert(used_gas)),
+				),
+				pays_fee: Pays::Yes,
+			})
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The hash of the operation signed by the wallet, which covers everything
+	/// except the signature, bound to the entrypoint and the chain id.
+	pub fn user_op_hash(op: &UserOperation) -> H256 {
+		let payload = (
+			(op.sender, op.nonce, keccak_256(&op.call_data), op.call_gas_limit, op.verification_gas_limit),
+			(op.storage_limit, op.fee_currency_id, op.max_fee, op.paymaster, keccak_256(&op.paymaster_data)),
+			(T::EntryPoint::get(), T::ChainId::get()),
+		);
+		H256::from(payload.using_encoded(keccak_256))
+	}
+
+	/// Validate, prefund and execute one operation, and pay its actual fee to
+	/// the bundler. Returns the gas used.
+	fn handle_op(bundler: &T::AccountId, op: UserOperation) -> Result<u64, DispatchError> {
+		ensure!(Self::nonces(op.sender) == op.nonce, Error::<T>::InvalidNonce);
+		let user_op_hash = Self::user_op_hash(&op);
+
+		// validate the operation by the wallet
+		let mut input = Into::<u32>::into(Action::ValidateUserOp).to_be_bytes().to_vec();
+		input.extend_from_slice(user_op_hash.as_bytes());
+		// offset of the dynamic bytes parameter, which follows the 2 head words
+		input.extend_from_slice(&to_bytes(2u64 * 32));
+		append_bytes(&mut input, &op.signature);
+		let mut used_gas = Self::call_validation(op.sender, op.sender, input, &op)
+			.ok_or(Error::<T>::WalletValidationFailed)?;
+
+		// validate the sponsorship by the paymaster
+		let payer = if let Some(paymaster) = op.paymaster {
+			let mut input = Into::<u32>::into(Action::ValidatePaymasterUserOp).to_be_bytes().to_vec();
+			input.extend_from_slice(user_op_hash.as_bytes());
+			input.extend_from_slice(H256::from(op.sender).as_bytes());
+			input.extend_from_slice(&to_bytes(op.max_fee));
+			// offset of the dynamic bytes parameter, which follows the 4 head words
+			input.extend_from_slice(&to_bytes(4u64 * 32));
+			append_bytes(&mut input, &op.paymaster_data);
+			let paymaster_used_gas = Self::call_validation(paymaster, op.sender, input, &op)
+				.ok_or(Error::<T>::PaymasterValidationFailed)?;
+			used_gas = used_gas.saturating_add(paymaster_used_gas);
+
+			T::AddressMapping::get_account_id(&paymaster)
+		} else {
+			T::AddressMapping::get_account_id(&op.sender)
+		};
+
+		Nonces::<T>::insert(op.sender, op.nonce.saturating_add(1));
+
+		// prefund the maximum fee, swapped from the fee currency if it's not native
+		let surplus = if op.fee_currency_id == T::NativeCurrencyId::get() {
+			0
+		} else {
+			T::FeeCurrencySwap::swap_to_native_fee(&payer, op.max_fee, op.fee_currency_id)?
+		};
+		let prefund = op.max_fee.saturating_add(surplus);
+		T::Currency::reserve_named(&RESERVE_ID, &payer, prefund).map_err(|_| Error::<T>::InsufficientPrefund)?;
+
+		let context = InvokeContext {
+			contract: op.sender,
+			sender: T::EntryPoint::get(),
+			origin: op.sender,
+		};
+		let (success, call_used_gas) = match T::EVM::execute(
+			context,
+			op.call_data,
+			Default::default(),
+			op.call_gas_limit,
+			op.storage_limit,
+			ExecutionMode::Execute,
+		) {
+			Ok(info) => (info.exit_reason.is_succeed(), info.used_gas.unique_saturated_into()),
+			Err(_) => (false, op.call_gas_limit),
+		};
+		used_gas = used_gas.saturating_add(call_used_gas);
+
+		// settle the actual fee to the bundler, the unused prefund is left to the payer
+		let actual_fee = T::WeightToFee::weight_to_fee(&T::GasToWeight::convert(used_gas))
+			.min(op.max_fee)
+			.saturating_add(surplus);
+		T::Currency::unreserve_named(&RESERVE_ID, &payer, prefund);
+		T::Currency::transfer(&payer, bundler, actual_fee, ExistenceRequirement::AllowDeath)?;
+
+		Self::deposit_event(Event::UserOperationHandled {
+			user_op_hash,
+			sender: op.sender,
+			paymaster: op.paymaster,
+			nonce: op.nonce,
+			success,
+			actual_fee,
+			actual_gas_used: used_gas,
+		});
+
+		Ok(used_gas)
+	}
+
+	/// Call a validation method of `contract`, which accepts the operation by
+	/// returning zero. Returns the gas used if accepted.
+	fn call_validation(contract: EvmAddress, origin: EvmAddress, input: Vec<u8>, op: &UserOperation) -> Option<u64> {
+		let context = InvokeContext {
+			contract,
+			sender: T::EntryPoint::get(),
+			origin,
+		};
+		let info = T::EVM::execute(
+			context,
+			input,
+			Default::default(),
+			op.verification_gas_limit,
+			op.storage_limit,
+			ExecutionMode::Execute,
+		)
+		.ok()?;
+
+		// an empty return value means the contract doesn't exist or doesn't implement the method
+		if info.exit_reason.is_succeed() && info.value == [0u8; 32] {
+			Some(info.used_gas.unique_saturated_into())
+		} else {
+			None
+		}
+	}
+}
+
+/// Append an ABI encoded dynamic `bytes`: the length, then the data right
+/// padded to 32-byte alignment.
+fn append_bytes(input: &mut Vec<u8>, data: &[u8]) {
+	input.extend_from_slice(&to_bytes(U256::from(data.len())));
+	let padded_len = data.len().saturating_add(31) / 32 * 32;
+	input.extend_from_slice(data);
+	input.resize(input.len().saturating_add(padded_len.saturating_sub(data.len())), 0);
+}
@@ -0,0 +1,273 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the account abstraction module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types,
+	traits::{ConstU128, ConstU32, ConstU64, Currency},
+	weights::IdentityFee,
+};
+use module_evm_utility::evm::{ExitRevert, ExitSucceed};
+use module_support::mocks::MockAddressMapping;
+use primitives::{
+	evm::{CallInfo, ExitReason},
+	TokenSymbol,
+};
+use sp_core::H160;
+use sp_runtime::{traits::IdentityLookup, AccountId32, BuildStorage};
+use sp_std::cell::RefCell;
+use std::{collections::HashMap, str::FromStr};
+
+pub type AccountId = AccountId32;
+
+pub const ALICE: AccountId = AccountId32::new([1u8; 32]);
+pub const SEE: CurrencyId = CurrencyId::Token(TokenSymbol::SEE);
+pub const USSD: CurrencyId = CurrencyId::Token(TokenSymbol::USSD);
+
+/// Gas used by every validation call of the mock EVM.
+pub const VALIDATION_GAS: u64 = 10_000;
+/// Gas used by every execution call of the mock EVM.
+pub const EXECUTION_GAS: u64 = 30_000;
+
+pub fn wallet() -> EvmAddress {
+	EvmAddress::from_str("0x1000000000000000000000000000000000000001").unwrap()
+}
+
+pub fn paymaster() -> EvmAddress {
+	EvmAddress::from_str("0x2000000000000000000000000000000000000002").unwrap()
+}
+
+pub fn entry_point() -> EvmAddress {
+	EvmAddress::from_str("0x0000000000000000000000000000000000000800").unwrap()
+}
+
+pub fn account_of(address: EvmAddress) -> AccountId {
+	MockAddressMapping::get_account_id(&address)
+}
+
+mod account_abstraction {
+	pub use super::super::*;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+	type MaxLocks = ();
+	type MaxReserves = ConstU32<50>;
+	type ReserveIdentifier = ReserveIdentifier;
+	type WeightInfo = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type FreezeIdentifier = ();
+	type MaxHolds = ();
+	type MaxFreezes = ();
+}
+
+thread_local! {
+	/// Return values of contract methods, keyed by contract and selector.
+	static RETURNS: RefCell<HashMap<(EvmAddress, [u8; 4]), (ExitReason, Vec<u8>)>> = RefCell::new(HashMap::new());
+	/// Calls made to the mock EVM: (context, input, gas limit).
+	static CALLS: RefCell<Vec<(InvokeContext, Vec<u8>, u64)>> = RefCell::new(Vec::new());
+}
+
+/// Make `contract` return `value` with `reason` when called with `selector`.
+pub fn set_return(contract: EvmAddress, selector: [u8; 4], reason: ExitReason, value: Vec<u8>) {
+	RETURNS.with(|v| v.borrow_mut().insert((contract, selector), (reason, value)));
+}
+
+/// Make `contract` accept the validation call with `action`.
+pub fn accept(contract: EvmAddress, action: Action) {
+	set_return(
+		contract,
+		Into::<u32>::into(action).to_be_bytes(),
+		ExitReason::Succeed(ExitSucceed::Returned),
+		[0u8; 32].to_vec(),
+	);
+}
+
+/// Make `contract` revert when called with `selector`.
+pub fn revert(contract: EvmAddress, selector: [u8; 4]) {
+	set_return(contract, selector, ExitReason::Revert(ExitRevert::Reverted), vec![]);
+}
+
+pub fn evm_calls() -> Vec<(InvokeContext, Vec<u8>, u64)> {
+	CALLS.with(|v| v.borrow().clone())
+}
+
+/// Mock EVM with configured return values, which doesn't exist for unknown
+/// contract methods and returns nothing.
+pub struct MockEVM;
+impl EVM<AccountId> for MockEVM {
+	type Balance = Balance;
+
+	fn execute(
+		context: InvokeContext,
+		input: Vec<u8>,
+		_value: Balance,
+		gas_limit: u64,
+		_storage_limit: u32,
+		_mode: ExecutionMode,
+	) -> Result<CallInfo, DispatchError> {
+		CALLS.with(|v| v.borrow_mut().push((context, input.clone(), gas_limit)));
+
+		let mut selector = [0u8; 4];
+		if input.len() >= 4 {
+			selector.copy_from_slice(&input[0..4]);
+		}
+		let is_validation = selector == Into::<u32>::into(Action::ValidateUserOp).to_be_bytes()
+			|| selector == Into::<u32>::into(Action::ValidatePaymasterUserOp).to_be_bytes();
+		let used_gas = if is_validation { VALIDATION_GAS } else { EXECUTION_GAS };
+
+		let (exit_reason, value) = RETURNS
+			.with(|v| v.borrow().get(&(context.contract, selector)).cloned())
+			.unwrap_or((ExitReason::Succeed(ExitSucceed::Stopped), vec![]));
+
+		Ok(CallInfo {
+			exit_reason,
+			value,
+			used_gas: used_gas.min(gas_limit).into(),
+			used_storage: 0,
+			logs: vec![],
+		})
+	}
+
+	fn get_origin() -> Option<AccountId> {
+		None
+	}
+	fn set_origin(_origin: AccountId) {}
+	fn kill_origin() {}
+	fn push_xcm_origin(_origin: AccountId) {}
+	fn pop_xcm_origin() {}
+	fn kill_xcm_origin() {}
+	fn get_real_or_xcm_origin() -> Option<AccountId> {
+		None
+	}
+}
+
+/// Mock fee currency swap at a fixed rate of 2 USSD per native token, with a
+/// surplus of 10% of the fee.
+pub struct MockFeeCurrencySwap;
+impl FeeCurrencySwap<AccountId, Balance> for MockFeeCurrencySwap {
+	fn swap_to_native_fee(who: &AccountId, fee: Balance, currency_id: CurrencyId) -> Result<Balance, DispatchError> {
+		ensure!(currency_id == USSD, DispatchError::Other("unsupported fee currency"));
+		let surplus = fee / 10;
+		let native = fee + surplus;
+		SWAPPED.with(|v| *v.borrow_mut() += native * 2);
+		let _ = Balances::deposit_creating(who, native);
+		Ok(surplus)
+	}
+}
+
+thread_local! {
+	/// Total USSD swapped by the mock fee currency swap.
+	pub static SWAPPED: RefCell<Balance> = RefCell::new(0);
+}
+
+pub struct GasToWeight;
+impl Convert<u64, Weight> for GasToWeight {
+	fn convert(gas: u64) -> Weight {
+		Weight::from_parts(gas, 0)
+	}
+}
+
+parameter_types! {
+	pub const NativeCurrencyId: CurrencyId = SEE;
+	pub EntryPoint: EvmAddress = entry_point();
+	pub const MaxUserOperations: u32 = 4;
+}
+
+impl Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type FeeCurrencySwap = MockFeeCurrencySwap;
+	type NativeCurrencyId = NativeCurrencyId;
+	type EVM = MockEVM;
+	type AddressMapping = MockAddressMapping;
+	type EntryPoint = EntryPoint;
+	type ChainId = ConstU64<258>;
+	type GasToWeight = GasToWeight;
+	type WeightToFee = IdentityFee<Balance>;
+	type MaxUserOperations = MaxUserOperations;
+	type WeightInfo = ();
+}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Balances: pallet_balances,
+		AccountAbstraction: account_abstraction,
+	}
+);
+
+pub struct ExtBuilder {
+	balances: Vec<(AccountId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			balances: vec![
+				(ALICE, 1_000_000),
+				(account_of(wallet()), 1_000_000),
+				(account_of(paymaster()), 1_000_000),
+			],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn balances(mut self, balances: Vec<(AccountId, Balance)>) -> Self {
+		self.balances = balances;
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::<Runtime>::default()
+			.build_storage()
+			.unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: self.balances,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
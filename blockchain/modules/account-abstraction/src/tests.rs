@@ -0,0 +1,304 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the account abstraction module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{RuntimeEvent, *};
+use module_evm_utility::evm::ExitSucceed;
+use primitives::evm::ExitReason;
+
+const CALL_DATA: [u8; 4] = [0xaa, 0xbb, 0xcc, 0xdd];
+
+fn bundle(ops: Vec<UserOperation>) -> BoundedVec<UserOperation, MaxUserOperations> {
+	ops.try_into().unwrap()
+}
+
+fn user_op(nonce: u64) -> UserOperation {
+	UserOperation {
+		sender: wallet(),
+		nonce,
+		call_data: CALL_DATA.to_vec(),
+		call_gas_limit: 100_000,
+		verification_gas_limit: 50_000,
+		storage_limit: 1_000,
+		fee_currency_id: SEE,
+		max_fee: 100_000,
+		paymaster: None,
+		paymaster_data: vec![],
+		signature: vec![0x11; 65],
+	}
+}
+
+#[test]
+fn handle_ops_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		accept(wallet(), Action::ValidateUserOp);
+		let op = user_op(0);
+		let user_op_hash = AccountAbstraction::user_op_hash(&op);
+
+		assert_ok!(AccountAbstraction::handle_ops(RuntimeOrigin::signed(ALICE), bundle(vec![op])));
+		System::assert_last_event(RuntimeEvent::AccountAbstraction(crate::Event::UserOperationHandled {
+			user_op_hash,
+			sender: wallet(),
+			paymaster: None,
+			nonce: 0,
+			success: true,
+			actual_fee: 40_000,
+			actual_gas_used: 40_000,
+		}));
+		assert_eq!(AccountAbstraction::nonces(wallet()), 1);
+		assert_eq!(Balances::free_balance(ALICE), 1_040_000);
+		assert_eq!(Balances::free_balance(account_of(wallet())), 960_000);
+		assert_eq!(Balances::reserved_balance(account_of(wallet())), 0);
+
+		let calls = evm_calls();
+		assert_eq!(calls.len(), 2);
+
+		// validateUserOp(bytes32,bytes) with the signature padded to 3 words
+		let (context, input, gas_limit) = &calls[0];
+		assert_eq!(
+			*context,
+			InvokeContext {
+				contract: wallet(),
+				sender: entry_point(),
+				origin: wallet(),
+			}
+		);
+		assert_eq!(*gas_limit, 50_000);
+		assert_eq!(input.len(), 4 + 32 * 3 + 32 * 3);
+		assert_eq!(input[0..4], Into::<u32>::into(Action::ValidateUserOp).to_be_bytes());
+		assert_eq!(input[4..36], *user_op_hash.as_bytes());
+		assert_eq!(input[36..68], to_bytes(64u64));
+		assert_eq!(input[68..100], to_bytes(65u64));
+		assert_eq!(input[100..165], [0x11; 65]);
+		assert_eq!(input[165..], [0u8; 31]);
+
+		let (context, input, gas_limit) = &calls[1];
+		assert_eq!(
+			*context,
+			InvokeContext {
+				contract: wallet(),
+				sender: entry_point(),
+				origin: wallet(),
+			}
+		);
+		assert_eq!(*gas_limit, 100_000);
+		assert_eq!(*input, CALL_DATA.to_vec());
+
+		// replay is rejected
+		assert_noop!(
+			AccountAbstraction::handle_ops(RuntimeOrigin::signed(ALICE), bundle(vec![user_op(0)])),
+			Error::<Runtime>::InvalidNonce
+		);
+		assert_ok!(AccountAbstraction::handle_ops(
+			RuntimeOrigin::signed(ALICE),
+			bundle(vec![user_op(1), user_op(2)])
+		));
+		assert_eq!(AccountAbstraction::nonces(wallet()), 3);
+		assert_eq!(Balances::free_balance(ALICE), 1_120_000);
+	});
+}
+
+#[test]
+fn handle_ops_checks_bundle() {
+	ExtBuilder::default().build().execute_with(|| {
+		accept(wallet(), Action::ValidateUserOp);
+
+		assert_noop!(
+			AccountAbstraction::handle_ops(RuntimeOrigin::signed(ALICE), bundle(vec![])),
+			Error::<Runtime>::InvalidBundleSize
+		);
+		assert!(BoundedVec::<UserOperation, MaxUserOperations>::try_from(vec![
+			user_op(0),
+			user_op(1),
+			user_op(2),
+			user_op(3),
+			user_op(4)
+		])
+		.is_err());
+
+		// a failed operation fails the whole bundle
+		assert_noop!(
+			AccountAbstraction::handle_ops(RuntimeOrigin::signed(ALICE), bundle(vec![user_op(0), user_op(2)])),
+			Error::<Runtime>::InvalidNonce
+		);
+	});
+}
+
+#[test]
+fn wallet_validation_failed() {
+	ExtBuilder::default().build().execute_with(|| {
+		// the wallet doesn't implement validateUserOp
+		assert_noop!(
+			AccountAbstraction::handle_ops(RuntimeOrigin::signed(ALICE), bundle(vec![user_op(0)])),
+			Error::<Runtime>::WalletValidationFailed
+		);
+
+		revert(wallet(), Into::<u32>::into(Action::ValidateUserOp).to_be_bytes());
+		assert_noop!(
+			AccountAbstraction::handle_ops(RuntimeOrigin::signed(ALICE), bundle(vec![user_op(0)])),
+			Error::<Runtime>::WalletValidationFailed
+		);
+
+		// non-zero validation data rejects the operation
+		set_return(
+			wallet(),
+			Into::<u32>::into(Action::ValidateUserOp).to_be_bytes(),
+			ExitReason::Succeed(ExitSucceed::Returned),
+			to_bytes(1u64).to_vec(),
+		);
+		assert_noop!(
+			AccountAbstraction::handle_ops(RuntimeOrigin::signed(ALICE), bundle(vec![user_op(0)])),
+			Error::<Runtime>::WalletValidationFailed
+		);
+	});
+}
+
+#[test]
+fn paymaster_pays_fee() {
+	ExtBuilder::default().build().execute_with(|| {
+		accept(wallet(), Action::ValidateUserOp);
+		let op = UserOperation {
+			paymaster: Some(paymaster()),
+			paymaster_data: vec![0x22; 32],
+			..user_op(0)
+		};
+		let user_op_hash = AccountAbstraction::user_op_hash(&op);
+
+		assert_noop!(
+			AccountAbstraction::handle_ops(RuntimeOrigin::signed(ALICE), bundle(vec![op.clone()])),
+			Error::<Runtime>::PaymasterValidationFailed
+		);
+
+		accept(paymaster(), Action::ValidatePaymasterUserOp);
+		assert_ok!(AccountAbstraction::handle_ops(RuntimeOrigin::signed(ALICE), bundle(vec![op])));
+		System::assert_last_event(RuntimeEvent::AccountAbstraction(crate::Event::UserOperationHandled {
+			user_op_hash,
+			sender: wallet(),
+			paymaster: Some(paymaster()),
+			nonce: 0,
+			success: true,
+			actual_fee: 50_000,
+			actual_gas_used: 50_000,
+		}));
+		assert_eq!(Balances::free_balance(ALICE), 1_050_000);
+		assert_eq!(Balances::free_balance(account_of(wallet())), 1_000_000);
+		assert_eq!(Balances::free_balance(account_of(paymaster())), 950_000);
+
+		// validatePaymasterUserOp(bytes32,address,uint256,bytes), after the rejected attempt
+		let (context, input, _) = &evm_calls()[3];
+		assert_eq!(
+			*context,
+			InvokeContext {
+				contract: paymaster(),
+				sender: entry_point(),
+				origin: wallet(),
+			}
+		);
+		assert_eq!(input.len(), 4 + 32 * 4 + 32 * 2);
+		assert_eq!(input[0..4], Into::<u32>::into(Action::ValidatePaymasterUserOp).to_be_bytes());
+		assert_eq!(input[4..36], *user_op_hash.as_bytes());
+		assert_eq!(input[36..68], *H256::from(wallet()).as_bytes());
+		assert_eq!(input[68..100], to_bytes(100_000u128));
+		assert_eq!(input[100..132], to_bytes(128u64));
+		assert_eq!(input[132..164], to_bytes(32u64));
+		assert_eq!(input[164..], [0x22; 32]);
+	});
+}
+
+#[test]
+fn failed_execution_still_charges_fee() {
+	ExtBuilder::default().build().execute_with(|| {
+		accept(wallet(), Action::ValidateUserOp);
+		revert(wallet(), CALL_DATA);
+		let op = user_op(0);
+		let user_op_hash = AccountAbstraction::user_op_hash(&op);
+
+		assert_ok!(AccountAbstraction::handle_ops(RuntimeOrigin::signed(ALICE), bundle(vec![op])));
+		System::assert_last_event(RuntimeEvent::AccountAbstraction(crate::Event::UserOperationHandled {
+			user_op_hash,
+			sender: wallet(),
+			paymaster: None,
+			nonce: 0,
+			success: false,
+			actual_fee: 40_000,
+			actual_gas_used: 40_000,
+		}));
+		assert_eq!(AccountAbstraction::nonces(wallet()), 1);
+		assert_eq!(Balances::free_balance(ALICE), 1_040_000);
+		assert_eq!(Balances::free_balance(account_of(wallet())), 960_000);
+	});
+}
+
+#[test]
+fn fee_is_capped_by_max_fee() {
+	ExtBuilder::default().build().execute_with(|| {
+		accept(wallet(), Action::ValidateUserOp);
+		let op = UserOperation {
+			max_fee: 30_000,
+			..user_op(0)
+		};
+
+		assert_ok!(AccountAbstraction::handle_ops(RuntimeOrigin::signed(ALICE), bundle(vec![op])));
+		assert_eq!(Balances::free_balance(ALICE), 1_030_000);
+		assert_eq!(Balances::free_balance(account_of(wallet())), 970_000);
+
+		// the payer must be able to prefund the maximum fee
+		let op = UserOperation {
+			max_fee: 2_000_000,
+			..user_op(1)
+		};
+		assert_noop!(
+			AccountAbstraction::handle_ops(RuntimeOrigin::signed(ALICE), bundle(vec![op])),
+			Error::<Runtime>::InsufficientPrefund
+		);
+	});
+}
+
+#[test]
+fn pay_fee_with_non_native_currency() {
+	ExtBuilder::default().build().execute_with(|| {
+		accept(wallet(), Action::ValidateUserOp);
+		let op = UserOperation {
+			fee_currency_id: USSD,
+			..user_op(0)
+		};
+
+		assert_ok!(AccountAbstraction::handle_ops(RuntimeOrigin::signed(ALICE), bundle(vec![op])));
+		// max fee and 10% surplus are swapped, the surplus is charged on top of the actual fee
+		assert_eq!(SWAPPED.with(|v| *v.borrow()), 220_000);
+		assert_eq!(Balances::free_balance(ALICE), 1_050_000);
+		assert_eq!(Balances::free_balance(account_of(wallet())), 1_060_000);
+		assert_eq!(Balances::reserved_balance(account_of(wallet())), 0);
+
+		let op = UserOperation {
+			fee_currency_id: CurrencyId::ForeignAsset(0),
+			..user_op(1)
+		};
+		assert_noop!(
+			AccountAbstraction::handle_ops(RuntimeOrigin::signed(ALICE), bundle(vec![op])),
+			DispatchError::Other("unsupported fee currency")
+		);
+	});
+}
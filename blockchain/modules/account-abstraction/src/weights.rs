@@ -0,0 +1,67 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weights for module_account_abstraction
+//!
+//! Not benchmarked: the EVM calls are charged by `GasToWeight` on top of these weights, which
+//! cover the storage accessed by every operation and the hashing and ABI encoding of its data.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for module_account_abstraction.
+pub trait WeightInfo {
+	fn handle_ops(n: u32, b: u32, ) -> Weight;
+}
+
+/// Weights for module_account_abstraction using the Setheum node and recommended hardware.
+pub struct SetheumWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
+	// Storage: AccountAbstraction Nonces (r:1 w:1)
+	// Storage: EVM Accounts (r:3 w:0)
+	// Storage: Dex TradingPairStatuses (r:2 w:0)
+	// Storage: Dex LiquidityPool (r:2 w:2)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	fn handle_ops(n: u32, b: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(Weight::from_parts(150_000_000, 0).saturating_mul(n as u64))
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(b as u64))
+			.saturating_add(T::DbWeight::get().reads((14 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes((9 as u64).saturating_mul(n as u64)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn handle_ops(n: u32, b: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(Weight::from_parts(150_000_000, 0).saturating_mul(n as u64))
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(b as u64))
+			.saturating_add(RocksDbWeight::get().reads((14 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes((9 as u64).saturating_mul(n as u64)))
+	}
+}
@@ -26,6 +26,13 @@
 //! business. Auction types include:
 //!   - `collateral auction`: sell collateral assets for stablecoin to eliminate the
 //!     system's bad debit by auction
+//!   - `dutch auction`: sell collateral assets for stablecoin at a price that starts
+//!     above the oracle price and decays over time, keepers can buy partial lots
+//!     instantly at the current price
+//!   - `surplus auction`: sell the system's excessive stablecoin surplus for native
+//!     token, which is burned
+//!   - `debit auction`: mint native token to buy stablecoin to cover the system's bad
+//!     debit, the amount of native token decreases as bids increase
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -38,7 +45,8 @@ use frame_system::{
 	pallet_prelude::*,
 };
 use module_support::{
-	EcdpAuctionsManager, EcdpUssdTreasury, EcdpUssdTreasuryExtended, EcdpEmergencyShutdown, PriceProvider, Rate, SwapLimit,
+	ensure_none_or_keeper, EcdpAuctionsManager, EcdpUssdTreasury, EcdpUssdTreasuryExtended, EcdpEmergencyShutdown, Price, PriceProvider, Rate,
+	SwapLimit,
 };
 use orml_traits::{Auction, AuctionHandler, Change, MultiCurrency, OnNewBidResult};
 use orml_utilities::OffchainErr;
@@ -51,11 +59,11 @@ use sp_runtime::{
 		storage_lock::{StorageLock, Time},
 		Duration,
 	},
-	traits::{CheckedDiv, Saturating, Zero},
+	traits::{AtLeast32BitUnsigned, CheckedDiv, One, Saturating, UniqueSaturatedInto, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
-	DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
+	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
 };
 use sp_std::prelude::*;
 
@@ -130,6 +138,178 @@ impl<AccountId, BlockNumber> CollateralAuctionItem<AccountId, BlockNumber> {
 	}
 }
 
+/// Information of a surplus auction
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SurplusAuctionItem<BlockNumber> {
+	/// Fixed amount of stablecoin surplus for sale
+	#[codec(compact)]
+	amount: Balance,
+	/// Auction start time
+	start_time: BlockNumber,
+}
+
+/// Information of a debit auction
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DebitAuctionItem<BlockNumber> {
+	/// Initial amount of native token for sale
+	#[codec(compact)]
+	initial_amount: Balance,
+	/// Current amount of native token for sale
+	#[codec(compact)]
+	amount: Balance,
+	/// Fixed amount of stablecoin the winner pays to cover debit
+	#[codec(compact)]
+	fix: Balance,
+	/// Auction start time
+	start_time: BlockNumber,
+}
+
+impl<BlockNumber> DebitAuctionItem<BlockNumber> {
+	/// Return the amount of native token for sale at specific last bid
+	/// price and new bid price
+	fn amount_for_sale(&self, last_bid_price: Balance, new_bid_price: Balance) -> Balance {
+		if new_bid_price > last_bid_price && new_bid_price > self.fix {
+			Rate::checked_from_rational(sp_std::cmp::max(last_bid_price, self.fix), new_bid_price)
+				.and_then(|n| n.checked_mul_int(self.amount))
+				.unwrap_or(self.amount)
+		} else {
+			self.amount
+		}
+	}
+}
+
+/// The curve along which the price of a dutch auction decays
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum PriceDecayCurve<BlockNumber> {
+	/// The price decreases linearly from the start price to zero in `duration` blocks
+	Linear { duration: BlockNumber },
+	/// The price is cut by `cut` every `step` blocks
+	Stepwise { step: BlockNumber, cut: Rate },
+	/// The price is cut by `cut` every block
+	Exponential { cut: Rate },
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> PriceDecayCurve<BlockNumber> {
+	/// Return the price `elapsed` blocks after the auction started at
+	/// `start_price`
+	pub fn price(&self, start_price: Price, elapsed: BlockNumber) -> Price {
+		match *self {
+			PriceDecayCurve::Linear { duration } => {
+				if elapsed >= duration {
+					return Zero::zero();
+				}
+				let remaining: u128 = duration.saturating_sub(elapsed).unique_saturated_into();
+				let duration: u128 = duration.unique_saturated_into();
+				Price::checked_from_rational(remaining, duration)
+					.map(|ratio| start_price.saturating_mul(ratio))
+					.unwrap_or_else(Zero::zero)
+			}
+			PriceDecayCurve::Stepwise { step, cut } => {
+				let steps = elapsed.checked_div(&step).unwrap_or_else(Zero::zero);
+				Self::cut_price(start_price, cut, steps)
+			}
+			PriceDecayCurve::Exponential { cut } => Self::cut_price(start_price, cut, elapsed),
+		}
+	}
+
+	fn cut_price(start_price: Price, cut: Rate, times: BlockNumber) -> Price {
+		let times: u32 = times.unique_saturated_into();
+		let factor = Rate::one().saturating_sub(cut).saturating_pow(times as usize);
+		start_price.saturating_mul(factor)
+	}
+}
+
+/// Parameters of dutch auctions for a collateral type
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DutchAuctionParams<BlockNumber> {
+	/// The premium over the oracle price at which the auction starts
+	pub start_premium: Rate,
+	/// The curve along which the price decays
+	pub curve: PriceDecayCurve<BlockNumber>,
+	/// The number of blocks after which the auction can no longer be taken
+	/// and must be reset to a fresh start price
+	pub max_duration: BlockNumber,
+	/// The price below which the auction does not decay, as a share of the
+	/// start price
+	pub floor: Rate,
+}
+
+impl<BlockNumber: Zero> DutchAuctionParams<BlockNumber> {
+	/// Return whether the auction ends in finite time, its price actually
+	/// decays and the floor is not above the start price
+	pub fn is_valid(&self) -> bool {
+		let curve_valid = match &self.curve {
+			PriceDecayCurve::Linear { duration } => !duration.is_zero(),
+			PriceDecayCurve::Stepwise { step, cut } => !step.is_zero() && !cut.is_zero() && *cut <= Rate::one(),
+			PriceDecayCurve::Exponential { cut } => !cut.is_zero() && *cut <= Rate::one(),
+		};
+		curve_valid && !self.max_duration.is_zero() && self.floor <= Rate::one()
+	}
+}
+
+/// Information of a dutch collateral auction
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DutchAuctionItem<AccountId, BlockNumber> {
+	/// Refund recipient of the collateral left after the target is raised
+	refund_recipient: AccountId,
+	/// Collateral type for sale
+	currency_id: CurrencyId,
+	/// Initial collateral amount for sale
+	#[codec(compact)]
+	initial_amount: Balance,
+	/// Current collateral amount for sale
+	#[codec(compact)]
+	amount: Balance,
+	/// Stablecoin amount still to be raised by this auction
+	#[codec(compact)]
+	target: Balance,
+	/// Price of the collateral in stablecoin at `start_time`
+	start_price: Price,
+	/// Auction (re)start time
+	start_time: BlockNumber,
+	/// Parameters the auction was created with
+	params: DutchAuctionParams<BlockNumber>,
+}
+
+impl<AccountId, BlockNumber: AtLeast32BitUnsigned + Copy> DutchAuctionItem<AccountId, BlockNumber> {
+	/// Return whether the auction has run for too long or its price has
+	/// decayed to zero, and must be reset
+	fn needs_reset(&self, now: BlockNumber) -> bool {
+		now.saturating_sub(self.start_time) >= self.params.max_duration || self.current_price(now).is_zero()
+	}
+
+	/// Return the price of the collateral at `now`
+	fn current_price(&self, now: BlockNumber) -> Price {
+		let floor_price = self.start_price.saturating_mul(self.params.floor);
+		self.params
+			.curve
+			.price(self.start_price, now.saturating_sub(self.start_time))
+			.max(floor_price)
+	}
+
+	/// Return the collateral amount a keeper gets and the stablecoin amount
+	/// it pays for buying at most `max_collateral` at `price`
+	fn lot(&self, max_collateral: Balance, price: Price) -> (Balance, Balance) {
+		let collateral = sp_std::cmp::min(max_collateral, self.amount);
+		let payment = price.saturating_mul_int(collateral);
+		if payment > self.target {
+			// do not raise more than the target, keeper only gets the collateral
+			// that is worth the remaining target
+			let collateral = price
+				.reciprocal()
+				.map(|r| r.saturating_mul_int(self.target))
+				.unwrap_or(collateral)
+				.min(collateral);
+			(collateral, self.target)
+		} else {
+			(collateral, payment)
+		}
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -155,6 +335,11 @@ pub mod module {
 		#[pallet::constant]
 		type GetUSSDCurrencyId: Get<CurrencyId>;
 
+		/// The currency id surplus auctions are paid with and debit auctions
+		/// mint, the EDF governance token
+		#[pallet::constant]
+		type GetAuctionCurrencyId: Get<CurrencyId>;
+
 		/// Currency to transfer assets
 		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
 
@@ -177,6 +362,10 @@ pub mod module {
 		/// Emergency shutdown.
 		type EcdpEmergencyShutdown: EcdpEmergencyShutdown;
 
+		/// The origin which may update dutch auction parameters. Root can
+		/// always do this.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -195,6 +384,19 @@ pub mod module {
 		InvalidBidPrice,
 		/// Invalid input amount
 		InvalidAmount,
+		/// Dutch auctions are not enabled for the collateral type
+		DutchAuctionDisabled,
+		/// The dutch auction has run for too long and must be reset
+		DutchAuctionNeedsReset,
+		/// The dutch auction can still be taken and must not be reset
+		DutchAuctionNotExpired,
+		/// The current price of the dutch auction is above the limit
+		PriceExceedsLimit,
+		/// Must before system shutdown
+		MustBeforeShutdown,
+		/// The dutch auction parameters never end the auction, never decay the
+		/// price or have a floor above the start price
+		InvalidDutchAuctionParams,
 	}
 
 	#[pallet::event]
@@ -233,6 +435,64 @@ pub mod module {
 			target_stable_amount: Balance,
 			refund_recipient: T::AccountId,
 		},
+		/// Dutch auction created.
+		NewDutchAuction {
+			auction_id: AuctionId,
+			collateral_type: CurrencyId,
+			collateral_amount: Balance,
+			target_stable_amount: Balance,
+			start_price: Price,
+		},
+		/// Part of a dutch auction was bought by a keeper.
+		DutchAuctionTaken {
+			auction_id: AuctionId,
+			collateral_type: CurrencyId,
+			keeper: T::AccountId,
+			collateral_amount: Balance,
+			payment_amount: Balance,
+			price: Price,
+		},
+		/// Dutch auction restarted at a new start price.
+		DutchAuctionReset { auction_id: AuctionId, start_price: Price },
+		/// Dutch auction finished, the remaining collateral was refunded.
+		DutchAuctionFinished {
+			auction_id: AuctionId,
+			collateral_type: CurrencyId,
+			refund_collateral_amount: Balance,
+			unrecovered_target: Balance,
+		},
+		/// Active dutch auction cancelled.
+		CancelDutchAuction { auction_id: AuctionId },
+		/// Dutch auction parameters of the collateral type updated.
+		DutchAuctionParamsUpdated {
+			collateral_type: CurrencyId,
+			params: Option<DutchAuctionParams<BlockNumberFor<T>>>,
+		},
+		/// Surplus auction created.
+		NewSurplusAuction {
+			auction_id: AuctionId,
+			fix_surplus_amount: Balance,
+		},
+		/// Debit auction created.
+		NewDebitAuction {
+			auction_id: AuctionId,
+			initial_supply_amount: Balance,
+			fix_payment_amount: Balance,
+		},
+		/// Surplus auction dealt, the native token paid by the winner has been burned.
+		SurplusAuctionDealt {
+			auction_id: AuctionId,
+			surplus_amount: Balance,
+			winner: T::AccountId,
+			payment_amount: Balance,
+		},
+		/// Debit auction dealt, the native token has been minted to the winner.
+		DebitAuctionDealt {
+			auction_id: AuctionId,
+			debit_currency_amount: Balance,
+			winner: T::AccountId,
+			payment_amount: Balance,
+		},
 	}
 
 	/// Mapping from auction id to collateral auction info
@@ -258,6 +518,61 @@ pub mod module {
 	#[pallet::getter(fn total_target_in_auction)]
 	pub type TotalTargetInAuction<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+	/// Mapping from auction id to surplus auction info
+	///
+	/// SurplusAuctions: map AuctionId => Option<SurplusAuctionItem>
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_auctions)]
+	pub type SurplusAuctions<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, SurplusAuctionItem<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Mapping from auction id to debit auction info
+	///
+	/// DebitAuctions: map AuctionId => Option<DebitAuctionItem>
+	#[pallet::storage]
+	#[pallet::getter(fn debit_auctions)]
+	pub type DebitAuctions<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, DebitAuctionItem<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Record of the total surplus amount of all active surplus auctions
+	///
+	/// TotalSurplusInAuction: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_surplus_in_auction)]
+	pub type TotalSurplusInAuction<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// Record of the total fixed stablecoin payment of all active debit
+	/// auctions
+	///
+	/// TotalDebitInAuction: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_debit_in_auction)]
+	pub type TotalDebitInAuction<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// Mapping from collateral type to its dutch auction parameters, dutch
+	/// auctions are disabled for collateral types without parameters
+	///
+	/// DutchAuctionParameters: map CurrencyId => Option<DutchAuctionParams>
+	#[pallet::storage]
+	#[pallet::getter(fn dutch_auction_params)]
+	pub type DutchAuctionParameters<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, DutchAuctionParams<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Mapping from dutch auction id to dutch auction info
+	///
+	/// DutchAuctions: map AuctionId => Option<DutchAuctionItem>
+	#[pallet::storage]
+	#[pallet::getter(fn dutch_auctions)]
+	pub type DutchAuctions<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, DutchAuctionItem<T::AccountId, BlockNumberFor<T>>, OptionQuery>;
+
+	/// The id of the next dutch auction
+	///
+	/// NextDutchAuctionId: AuctionId
+	#[pallet::storage]
+	#[pallet::getter(fn next_dutch_auction_id)]
+	pub type NextDutchAuctionId<T: Config> = StorageValue<_, AuctionId, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -288,23 +603,130 @@ pub mod module {
 	impl<T: Config> Pallet<T> {
 		/// Cancel active auction after system shutdown
 		///
-		/// The dispatch origin of this call must be _None_.
+		/// The dispatch origin of this call must be _None_ or _Signed_ by a keeper. A keeper
+		/// is refunded the fee if the cancellation succeeds, otherwise the fee is charged.
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::cancel_collateral_auction())]
-		pub fn cancel(origin: OriginFor<T>, id: AuctionId) -> DispatchResult {
-			ensure_none(origin)?;
+		pub fn cancel(origin: OriginFor<T>, id: AuctionId) -> DispatchResultWithPostInfo {
+			let maybe_keeper = ensure_none_or_keeper::<T>(origin)?;
 			ensure!(T::EcdpEmergencyShutdown::is_shutdown(), Error::<T>::MustAfterShutdown);
 			<Self as EcdpAuctionsManager<T::AccountId>>::cancel_auction(id)?;
 			Self::deposit_event(Event::CancelAuction { auction_id: id });
+			if maybe_keeper.is_some() {
+				return Ok(Pays::No.into());
+			}
+			Ok(().into())
+		}
+
+		/// Update dutch auction parameters of a collateral type, `None`
+		/// disables dutch auctions for it. Running auctions keep the
+		/// parameters they were created with.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::call_index(1)]
+		#[pallet::weight((T::WeightInfo::set_dutch_auction_params(), DispatchClass::Operational))]
+		pub fn set_dutch_auction_params(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			params: Option<DutchAuctionParams<BlockNumberFor<T>>>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(params) = &params {
+				ensure!(params.is_valid(), Error::<T>::InvalidDutchAuctionParams);
+			}
+			DutchAuctionParameters::<T>::set(currency_id, params);
+			Self::deposit_event(Event::DutchAuctionParamsUpdated {
+				collateral_type: currency_id,
+				params,
+			});
 			Ok(())
 		}
+
+		/// Buy collateral of a dutch auction at its current price
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `id`: dutch auction id.
+		/// - `max_collateral_amount`: the maximum collateral amount to buy.
+		/// - `max_price`: the maximum price to pay per collateral unit.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::take_dutch_auction())]
+		pub fn take_dutch_auction(
+			origin: OriginFor<T>,
+			id: AuctionId,
+			#[pallet::compact] max_collateral_amount: Balance,
+			max_price: Price,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!T::EcdpEmergencyShutdown::is_shutdown(), Error::<T>::MustBeforeShutdown);
+			Self::do_take_dutch_auction(&who, id, max_collateral_amount, max_price)
+		}
+
+		/// Restart a dutch auction that ran for too long at a fresh start
+		/// price
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::reset_dutch_auction())]
+		pub fn reset_dutch_auction(origin: OriginFor<T>, id: AuctionId) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(!T::EcdpEmergencyShutdown::is_shutdown(), Error::<T>::MustBeforeShutdown);
+			let now = <frame_system::Pallet<T>>::block_number();
+			DutchAuctions::<T>::try_mutate(id, |maybe_auction| -> DispatchResult {
+				let dutch_auction = maybe_auction.as_mut().ok_or(Error::<T>::AuctionNotExists)?;
+				ensure!(dutch_auction.needs_reset(now), Error::<T>::DutchAuctionNotExpired);
+
+				let start_price = Self::dutch_auction_start_price(dutch_auction.currency_id, &dutch_auction.params)?;
+				dutch_auction.start_price = start_price;
+				dutch_auction.start_time = now;
+
+				Self::deposit_event(Event::DutchAuctionReset {
+					auction_id: id,
+					start_price,
+				});
+				Ok(())
+			})
+		}
+
+		/// Cancel active dutch auction after system shutdown
+		///
+		/// The dispatch origin of this call must be _None_ or _Signed_ by a keeper. A keeper
+		/// is refunded the fee if the cancellation succeeds, otherwise the fee is charged.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::cancel_dutch_auction())]
+		pub fn cancel_dutch_auction(origin: OriginFor<T>, id: AuctionId) -> DispatchResultWithPostInfo {
+			let maybe_keeper = ensure_none_or_keeper::<T>(origin)?;
+			ensure!(T::EcdpEmergencyShutdown::is_shutdown(), Error::<T>::MustAfterShutdown);
+			let dutch_auction = DutchAuctions::<T>::take(id).ok_or(Error::<T>::AuctionNotExists)?;
+			Self::cancel_dutch_auction_item(dutch_auction)?;
+			Self::deposit_event(Event::CancelDutchAuction { auction_id: id });
+			if maybe_keeper.is_some() {
+				return Ok(Pays::No.into());
+			}
+			Ok(().into())
+		}
 	}
 
 	#[pallet::validate_unsigned]
 	impl<T: Config> ValidateUnsigned for Pallet<T> {
 		type Call = Call<T>;
 		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-			if let Call::cancel { id: auction_id } = call {
+			if let Call::cancel_dutch_auction { id: auction_id } = call {
+				if !T::EcdpEmergencyShutdown::is_shutdown() {
+					return InvalidTransaction::Call.into();
+				}
+
+				if !DutchAuctions::<T>::contains_key(auction_id) {
+					return InvalidTransaction::Stale.into();
+				}
+
+				ValidTransaction::with_tag_prefix("EcdpAuctionsManagerOffchainWorker")
+					.priority(T::UnsignedPriority::get())
+					.and_provides(("dutch", auction_id))
+					.longevity(64_u64)
+					.propagate(true)
+					.build()
+			} else if let Call::cancel { id: auction_id } = call {
 				if !T::EcdpEmergencyShutdown::is_shutdown() {
 					return InvalidTransaction::Call.into();
 				}
@@ -316,7 +738,9 @@ pub mod module {
 							return InvalidTransaction::Stale.into();
 						}
 					}
-				} else {
+				} else if !SurplusAuctions::<T>::contains_key(auction_id)
+					&& !DebitAuctions::<T>::contains_key(auction_id)
+				{
 					return InvalidTransaction::Stale.into();
 				}
 
@@ -349,6 +773,17 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	fn submit_cancel_dutch_auction_tx(auction_id: AuctionId) {
+		let call = Call::<T>::cancel_dutch_auction { id: auction_id };
+		if let Err(err) = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()) {
+			log::info!(
+				target: "auction-manager",
+				"offchain worker: submit unsigned dutch auction cancel tx for AuctionId {:?} failed: {:?}",
+				auction_id, err,
+			);
+		}
+	}
+
 	fn _offchain_worker() -> Result<(), OffchainErr> {
 		// acquire offchain worker lock.
 		let lock_expiration = Duration::from_millis(LOCK_DURATION);
@@ -411,6 +846,27 @@ impl<T: Config> Pallet<T> {
 
 		if finished {
 			to_be_continue.clear();
+
+			// dutch auctions are removed once cancelled, so iterate them from the start
+			// with the remaining iterations.
+			for dutch_auction_id in <DutchAuctions<T>>::iter_keys() {
+				if iteration_count >= max_iterations {
+					break;
+				}
+				iteration_count += 1;
+				Self::submit_cancel_dutch_auction_tx(dutch_auction_id);
+				guard.extend_lock().map_err(|_| OffchainErr::OffchainLock)?;
+			}
+
+			// so are surplus and debit auctions.
+			for auction_id in <SurplusAuctions<T>>::iter_keys().chain(<DebitAuctions<T>>::iter_keys()) {
+				if iteration_count >= max_iterations {
+					break;
+				}
+				iteration_count += 1;
+				Self::submit_cancel_auction_tx(auction_id);
+				guard.extend_lock().map_err(|_| OffchainErr::OffchainLock)?;
+			}
 		} else {
 			to_be_continue.set(&iterator.last_raw_key());
 		}
@@ -477,6 +933,152 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	fn cancel_surplus_auction(
+		id: AuctionId,
+		surplus_auction: SurplusAuctionItem<BlockNumberFor<T>>,
+	) -> DispatchResult {
+		// if there's bid, mint the burned native token back to the bidder
+		if let Some((bidder, bid_price)) = Self::get_last_bid(id) {
+			T::Currency::deposit(T::GetAuctionCurrencyId::get(), &bidder, bid_price)?;
+
+			// decrease account ref of bidder
+			frame_system::Pallet::<T>::dec_consumers(&bidder);
+		}
+
+		TotalSurplusInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(surplus_auction.amount));
+
+		Ok(())
+	}
+
+	fn cancel_debit_auction(id: AuctionId, debit_auction: DebitAuctionItem<BlockNumberFor<T>>) -> DispatchResult {
+		// if there's bid, refund stablecoin to the bidder
+		if let Some((bidder, _)) = Self::get_last_bid(id) {
+			T::EcdpUssdTreasury::issue_debit(&bidder, debit_auction.fix, false)?;
+
+			// decrease account ref of bidder
+			frame_system::Pallet::<T>::dec_consumers(&bidder);
+		}
+
+		TotalDebitInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(debit_auction.fix));
+
+		Ok(())
+	}
+
+	/// Return the start price of a dutch auction of `currency_id`, the oracle
+	/// price plus the start premium.
+	fn dutch_auction_start_price(
+		currency_id: CurrencyId,
+		params: &DutchAuctionParams<BlockNumberFor<T>>,
+	) -> sp_std::result::Result<Price, DispatchError> {
+		let oracle_price = T::PriceSource::get_relative_price(currency_id, T::GetUSSDCurrencyId::get())
+			.ok_or(Error::<T>::InvalidFeedPrice)?;
+		Ok(oracle_price.saturating_mul(Rate::one().saturating_add(params.start_premium)))
+	}
+
+	/// Sell part of a dutch auction to `who` at the current price. Finishes
+	/// the auction once the target is raised or the collateral is sold out.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	pub fn do_take_dutch_auction(
+		who: &T::AccountId,
+		id: AuctionId,
+		max_collateral_amount: Balance,
+		max_price: Price,
+	) -> DispatchResult {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let mut dutch_auction = Self::dutch_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+		ensure!(!dutch_auction.needs_reset(now), Error::<T>::DutchAuctionNeedsReset);
+
+		let price = dutch_auction.current_price(now);
+		ensure!(price <= max_price, Error::<T>::PriceExceedsLimit);
+
+		let (collateral_amount, payment_amount) = dutch_auction.lot(max_collateral_amount, price);
+		ensure!(!collateral_amount.is_zero() && !payment_amount.is_zero(), Error::<T>::InvalidAmount);
+
+		// keeper pays stablecoin to CDP treasury and gets the collateral
+		T::EcdpUssdTreasury::deposit_surplus(who, payment_amount)?;
+		T::EcdpUssdTreasury::withdraw_collateral(who, dutch_auction.currency_id, collateral_amount)?;
+
+		dutch_auction.amount = dutch_auction.amount.saturating_sub(collateral_amount);
+		dutch_auction.target = dutch_auction.target.saturating_sub(payment_amount);
+		TotalCollateralInAuction::<T>::mutate(dutch_auction.currency_id, |balance| {
+			*balance = balance.saturating_sub(collateral_amount)
+		});
+		TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(payment_amount));
+
+		Self::deposit_event(Event::DutchAuctionTaken {
+			auction_id: id,
+			collateral_type: dutch_auction.currency_id,
+			keeper: who.clone(),
+			collateral_amount,
+			payment_amount,
+			price,
+		});
+
+		if dutch_auction.target.is_zero() || dutch_auction.amount.is_zero() {
+			DutchAuctions::<T>::remove(id);
+			Self::dutch_auction_end_handler(id, dutch_auction);
+		} else {
+			DutchAuctions::<T>::insert(id, dutch_auction);
+		}
+
+		Ok(())
+	}
+
+	fn dutch_auction_end_handler(
+		auction_id: AuctionId,
+		dutch_auction: DutchAuctionItem<T::AccountId, BlockNumberFor<T>>,
+	) {
+		// the target has been raised, the remaining collateral belongs to the refund recipient.
+		// Otherwise the collateral is sold out and the unrecovered target stays as system debit.
+		Self::try_refund_collateral(dutch_auction.currency_id, &dutch_auction.refund_recipient, dutch_auction.amount);
+
+		// decrement recipient account reference
+		frame_system::Pallet::<T>::dec_consumers(&dutch_auction.refund_recipient);
+
+		TotalCollateralInAuction::<T>::mutate(dutch_auction.currency_id, |balance| {
+			*balance = balance.saturating_sub(dutch_auction.amount)
+		});
+		TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(dutch_auction.target));
+
+		Self::deposit_event(Event::DutchAuctionFinished {
+			auction_id,
+			collateral_type: dutch_auction.currency_id,
+			refund_collateral_amount: dutch_auction.amount,
+			unrecovered_target: dutch_auction.target,
+		});
+	}
+
+	fn cancel_dutch_auction_item(dutch_auction: DutchAuctionItem<T::AccountId, BlockNumberFor<T>>) -> DispatchResult {
+		// calculate how much collateral to offset the remaining target in settle price
+		let settle_price = T::PriceSource::get_relative_price(T::GetUSSDCurrencyId::get(), dutch_auction.currency_id)
+			.ok_or(Error::<T>::InvalidFeedPrice)?;
+		let confiscate_collateral_amount = sp_std::cmp::min(
+			settle_price.saturating_mul_int(dutch_auction.target),
+			dutch_auction.amount,
+		);
+		let refund_collateral_amount = dutch_auction.amount.saturating_sub(confiscate_collateral_amount);
+
+		// refund remain collateral to refund recipient from CDP treasury
+		T::EcdpUssdTreasury::withdraw_collateral(
+			&dutch_auction.refund_recipient,
+			dutch_auction.currency_id,
+			refund_collateral_amount,
+		)?;
+
+		// decrease account ref of refund recipient
+		frame_system::Pallet::<T>::dec_consumers(&dutch_auction.refund_recipient);
+
+		// decrease total collateral and target in auction
+		TotalCollateralInAuction::<T>::mutate(dutch_auction.currency_id, |balance| {
+			*balance = balance.saturating_sub(dutch_auction.amount)
+		});
+		TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(dutch_auction.target));
+
+		Ok(())
+	}
+
 	/// Return `true` if price increment rate is greater than or equal to
 	/// minimum.
 	///
@@ -596,6 +1198,165 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
+	/// Handles surplus auction new bid. Returns `Ok(new_auction_end_time)`
+	/// if bid accepted.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	pub fn surplus_auction_bid_handler(
+		now: BlockNumberFor<T>,
+		id: AuctionId,
+		new_bid: (T::AccountId, Balance),
+		last_bid: Option<(T::AccountId, Balance)>,
+	) -> sp_std::result::Result<BlockNumberFor<T>, DispatchError> {
+		let (new_bidder, new_bid_price) = new_bid;
+		ensure!(!new_bid_price.is_zero(), Error::<T>::InvalidBidPrice);
+
+		let surplus_auction = Self::surplus_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+		let last_bid_price = last_bid.clone().map_or(Zero::zero(), |(_, price)| price); // get last bid price
+
+		// ensure new bid price is valid
+		ensure!(
+			Self::check_minimum_increment(
+				new_bid_price,
+				last_bid_price,
+				Zero::zero(),
+				Self::get_minimum_increment_size(now, surplus_auction.start_time),
+			),
+			Error::<T>::InvalidBidPrice
+		);
+
+		let native_currency_id = T::GetAuctionCurrencyId::get();
+		let last_bidder = last_bid.as_ref().map(|(who, _)| who);
+		let mut payment = new_bid_price;
+
+		// if there's bid before, return native token from new bidder to last bidder
+		if let Some(last_bidder) = last_bidder {
+			T::Currency::transfer(native_currency_id, &new_bidder, last_bidder, last_bid_price)?;
+			payment = payment
+				.checked_sub(last_bid_price)
+				// This should never fail because new bid price is always greater than last bid price.
+				.ok_or(Error::<T>::InvalidBidPrice)?;
+		}
+
+		// burn remain payment of new bidder
+		T::Currency::withdraw(native_currency_id, &new_bidder, payment)?;
+
+		Self::swap_bidders(&new_bidder, last_bidder);
+
+		Ok(now + Self::get_auction_time_to_close(now, surplus_auction.start_time))
+	}
+
+	/// Handles debit auction new bid. Returns `Ok(new_auction_end_time)` if
+	/// bid accepted.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	pub fn debit_auction_bid_handler(
+		now: BlockNumberFor<T>,
+		id: AuctionId,
+		new_bid: (T::AccountId, Balance),
+		last_bid: Option<(T::AccountId, Balance)>,
+	) -> sp_std::result::Result<BlockNumberFor<T>, DispatchError> {
+		let (new_bidder, new_bid_price) = new_bid;
+		ensure!(!new_bid_price.is_zero(), Error::<T>::InvalidBidPrice);
+
+		<DebitAuctions<T>>::try_mutate_exists(
+			id,
+			|debit_auction| -> sp_std::result::Result<BlockNumberFor<T>, DispatchError> {
+				let debit_auction = debit_auction.as_mut().ok_or(Error::<T>::AuctionNotExists)?;
+				let last_bid_price = last_bid.clone().map_or(Zero::zero(), |(_, price)| price); // get last bid price
+
+				// ensure new bid price is valid
+				ensure!(
+					new_bid_price >= debit_auction.fix
+						&& Self::check_minimum_increment(
+							new_bid_price,
+							last_bid_price,
+							debit_auction.fix,
+							Self::get_minimum_increment_size(now, debit_auction.start_time),
+						),
+					Error::<T>::InvalidBidPrice
+				);
+
+				let last_bidder = last_bid.as_ref().map(|(who, _)| who);
+
+				if let Some(last_bidder) = last_bidder {
+					// there's bid before, transfer the fixed payment from new bidder to last bidder
+					T::Currency::transfer(T::GetUSSDCurrencyId::get(), &new_bidder, last_bidder, debit_auction.fix)?;
+				} else {
+					// there's no bid before, transfer the fixed payment to CDP treasury
+					T::EcdpUssdTreasury::deposit_surplus(&new_bidder, debit_auction.fix)?;
+				}
+
+				Self::swap_bidders(&new_bidder, last_bidder);
+				debit_auction.amount = debit_auction.amount_for_sale(last_bid_price, new_bid_price);
+
+				Ok(now + Self::get_auction_time_to_close(now, debit_auction.start_time))
+			},
+		)
+	}
+
+	fn surplus_auction_end_handler(
+		auction_id: AuctionId,
+		surplus_auction: SurplusAuctionItem<BlockNumberFor<T>>,
+		last_bid: Option<(T::AccountId, Balance)>,
+	) {
+		// if there's no bid, the surplus is kept by CDP treasury
+		if let Some((bidder, bid_price)) = last_bid {
+			// the native token paid by the winner has already been burned
+			let res = T::EcdpUssdTreasury::withdraw_surplus(&bidder, surplus_auction.amount);
+			if let Err(e) = res {
+				log::warn!(
+					target: "auction-manager",
+					"withdraw_surplus: failed to withdraw {:?} surplus from CDP treasury to {:?}: {:?}. \
+					This is unexpected but should be safe",
+					surplus_auction.amount, bidder, e
+				);
+				debug_assert!(false);
+			}
+
+			Self::deposit_event(Event::SurplusAuctionDealt {
+				auction_id,
+				surplus_amount: surplus_auction.amount,
+				winner: bidder,
+				payment_amount: bid_price,
+			});
+		}
+
+		TotalSurplusInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(surplus_auction.amount));
+	}
+
+	fn debit_auction_end_handler(
+		auction_id: AuctionId,
+		debit_auction: DebitAuctionItem<BlockNumberFor<T>>,
+		last_bid: Option<(T::AccountId, Balance)>,
+	) {
+		// if there's no bid, the debit is left to be covered by a new debit auction
+		if let Some((bidder, _)) = last_bid {
+			// the fixed payment of the winner has already been deposited to CDP treasury
+			let res = T::Currency::deposit(T::GetAuctionCurrencyId::get(), &bidder, debit_auction.amount);
+			if let Err(e) = res {
+				log::warn!(
+					target: "auction-manager",
+					"deposit: failed to mint {:?} native token to {:?}: {:?}. \
+					This is unexpected but should be safe",
+					debit_auction.amount, bidder, e
+				);
+				debug_assert!(false);
+			}
+
+			Self::deposit_event(Event::DebitAuctionDealt {
+				auction_id,
+				debit_currency_amount: debit_auction.amount,
+				winner: bidder,
+				payment_amount: debit_auction.fix,
+			});
+		}
+
+		TotalDebitInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(debit_auction.fix));
+	}
+
 	fn collateral_auction_end_handler(
 		auction_id: AuctionId,
 		collateral_auction: CollateralAuctionItem<T::AccountId, BlockNumberFor<T>>,
@@ -745,7 +1506,13 @@ impl<T: Config> AuctionHandler<T::AccountId, Balance, BlockNumberFor<T>, Auction
 		new_bid: (T::AccountId, Balance),
 		last_bid: Option<(T::AccountId, Balance)>,
 	) -> OnNewBidResult<BlockNumberFor<T>> {
-		let bid_result = Self::collateral_auction_bid_handler(now, id, new_bid, last_bid);
+		let bid_result = if <CollateralAuctions<T>>::contains_key(id) {
+			Self::collateral_auction_bid_handler(now, id, new_bid, last_bid)
+		} else if <SurplusAuctions<T>>::contains_key(id) {
+			Self::surplus_auction_bid_handler(now, id, new_bid, last_bid)
+		} else {
+			Self::debit_auction_bid_handler(now, id, new_bid, last_bid)
+		};
 
 		match bid_result {
 			Ok(new_auction_end_time) => OnNewBidResult {
@@ -762,6 +1529,10 @@ impl<T: Config> AuctionHandler<T::AccountId, Balance, BlockNumberFor<T>, Auction
 	fn on_auction_ended(id: AuctionId, winner: Option<(T::AccountId, Balance)>) {
 		if let Some(collateral_auction) = <CollateralAuctions<T>>::take(id) {
 			Self::collateral_auction_end_handler(id, collateral_auction, winner.clone());
+		} else if let Some(surplus_auction) = <SurplusAuctions<T>>::take(id) {
+			Self::surplus_auction_end_handler(id, surplus_auction, winner.clone());
+		} else if let Some(debit_auction) = <DebitAuctions<T>>::take(id) {
+			Self::debit_auction_end_handler(id, debit_auction, winner.clone());
 		}
 
 		if let Some((bidder, _)) = &winner {
@@ -835,9 +1606,75 @@ impl<T: Config> EcdpAuctionsManager<T::AccountId> for Pallet<T> {
 		Ok(())
 	}
 
+	fn new_dutch_auction(
+		refund_recipient: &T::AccountId,
+		currency_id: Self::CurrencyId,
+		amount: Self::Balance,
+		target: Self::Balance,
+	) -> DispatchResult {
+		ensure!(!amount.is_zero() && !target.is_zero(), Error::<T>::InvalidAmount);
+		let params = Self::dutch_auction_params(currency_id).ok_or(Error::<T>::DutchAuctionDisabled)?;
+		let start_price = Self::dutch_auction_start_price(currency_id, &params)?;
+
+		TotalCollateralInAuction::<T>::try_mutate(currency_id, |total| -> DispatchResult {
+			*total = total.checked_add(amount).ok_or(Error::<T>::InvalidAmount)?;
+			Ok(())
+		})?;
+		TotalTargetInAuction::<T>::try_mutate(|total| -> DispatchResult {
+			*total = total.checked_add(target).ok_or(Error::<T>::InvalidAmount)?;
+			Ok(())
+		})?;
+
+		let auction_id = NextDutchAuctionId::<T>::try_mutate(|id| -> sp_std::result::Result<_, DispatchError> {
+			let current_id = *id;
+			*id = id.checked_add(One::one()).ok_or(ArithmeticError::Overflow)?;
+			Ok(current_id)
+		})?;
+
+		<DutchAuctions<T>>::insert(
+			auction_id,
+			DutchAuctionItem {
+				refund_recipient: refund_recipient.clone(),
+				currency_id,
+				initial_amount: amount,
+				amount,
+				target,
+				start_price,
+				start_time: <frame_system::Pallet<T>>::block_number(),
+				params,
+			},
+		);
+
+		// increment recipient account reference
+		if frame_system::Pallet::<T>::inc_consumers(refund_recipient).is_err() {
+			log::warn!(
+				target: "auction-manager",
+				"Attempt to `inc_consumers` for {:?} failed. \
+				This is unexpected but should be safe.",
+				refund_recipient.clone()
+			);
+		}
+
+		Self::deposit_event(Event::NewDutchAuction {
+			auction_id,
+			collateral_type: currency_id,
+			collateral_amount: amount,
+			target_stable_amount: target,
+			start_price,
+		});
+		Ok(())
+	}
+
 	fn cancel_auction(id: Self::AuctionId) -> DispatchResult {
-		let collateral_auction = <CollateralAuctions<T>>::take(id).ok_or(Error::<T>::AuctionNotExists)?;
-		Self::cancel_collateral_auction(id, collateral_auction)?;
+		if let Some(collateral_auction) = <CollateralAuctions<T>>::take(id) {
+			Self::cancel_collateral_auction(id, collateral_auction)?;
+		} else if let Some(surplus_auction) = <SurplusAuctions<T>>::take(id) {
+			Self::cancel_surplus_auction(id, surplus_auction)?;
+		} else if let Some(debit_auction) = <DebitAuctions<T>>::take(id) {
+			Self::cancel_debit_auction(id, debit_auction)?;
+		} else {
+			return Err(Error::<T>::AuctionNotExists.into());
+		}
 		T::Auction::remove_auction(id);
 		Ok(())
 	}
@@ -849,4 +1686,65 @@ impl<T: Config> EcdpAuctionsManager<T::AccountId> for Pallet<T> {
 	fn get_total_target_in_auction() -> Self::Balance {
 		Self::total_target_in_auction()
 	}
+
+	fn new_surplus_auction(amount: Self::Balance) -> DispatchResult {
+		ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+		ensure!(!T::EcdpEmergencyShutdown::is_shutdown(), Error::<T>::MustBeforeShutdown);
+		TotalSurplusInAuction::<T>::try_mutate(|total| -> DispatchResult {
+			*total = total.checked_add(amount).ok_or(Error::<T>::InvalidAmount)?;
+			Ok(())
+		})?;
+
+		let start_time = <frame_system::Pallet<T>>::block_number();
+		// use start_time + AuctionDurationSoftCap as the initial end-time of surplus auction.
+		let end_time = start_time.saturating_add(T::AuctionDurationSoftCap::get());
+		let auction_id = T::Auction::new_auction(start_time, Some(end_time))?;
+
+		<SurplusAuctions<T>>::insert(auction_id, SurplusAuctionItem { amount, start_time });
+
+		Self::deposit_event(Event::NewSurplusAuction {
+			auction_id,
+			fix_surplus_amount: amount,
+		});
+		Ok(())
+	}
+
+	fn new_debit_auction(initial_amount: Self::Balance, fix_debit: Self::Balance) -> DispatchResult {
+		ensure!(!initial_amount.is_zero() && !fix_debit.is_zero(), Error::<T>::InvalidAmount);
+		ensure!(!T::EcdpEmergencyShutdown::is_shutdown(), Error::<T>::MustBeforeShutdown);
+		TotalDebitInAuction::<T>::try_mutate(|total| -> DispatchResult {
+			*total = total.checked_add(fix_debit).ok_or(Error::<T>::InvalidAmount)?;
+			Ok(())
+		})?;
+
+		let start_time = <frame_system::Pallet<T>>::block_number();
+		// use start_time + AuctionDurationSoftCap as the initial end-time of debit auction.
+		let end_time = start_time.saturating_add(T::AuctionDurationSoftCap::get());
+		let auction_id = T::Auction::new_auction(start_time, Some(end_time))?;
+
+		<DebitAuctions<T>>::insert(
+			auction_id,
+			DebitAuctionItem {
+				initial_amount,
+				amount: initial_amount,
+				fix: fix_debit,
+				start_time,
+			},
+		);
+
+		Self::deposit_event(Event::NewDebitAuction {
+			auction_id,
+			initial_supply_amount: initial_amount,
+			fix_payment_amount: fix_debit,
+		});
+		Ok(())
+	}
+
+	fn get_total_surplus_in_auction() -> Self::Balance {
+		Self::total_surplus_in_auction()
+	}
+
+	fn get_total_debit_in_auction() -> Self::Balance {
+		Self::total_debit_in_auction()
+	}
 }
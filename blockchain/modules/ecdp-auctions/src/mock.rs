@@ -98,6 +98,7 @@ ord_parameter_types! {
 
 parameter_types! {
 	pub const GetUSSDCurrencyId: CurrencyId = USSD;
+	pub const GetAuctionCurrencyId: CurrencyId = EDF;
 	pub const MaxAuctionsCount: u32 = 10_000;
 	pub const EcdpUssdTreasuryPalletId: PalletId = PalletId(*b"set/ussdtrsymod");
 	pub TreasuryAccount: AccountId = PalletId(*b"set/ussdtrsyacc").into_account_truncating();
@@ -191,10 +192,12 @@ impl Config for Runtime {
 	type AuctionTimeToClose = ConstU64<100>;
 	type AuctionDurationSoftCap = ConstU64<2000>;
 	type GetUSSDCurrencyId = GetUSSDCurrencyId;
+	type GetAuctionCurrencyId = GetAuctionCurrencyId;
 	type EcdpUssdTreasury = EcdpUssdTreasuryModule;
 	type PriceSource = MockPriceSource;
 	type UnsignedPriority = ConstU64<1048576>; // 1 << 20
 	type EcdpEmergencyShutdown = MockEcdpEmergencyShutdown;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type WeightInfo = ();
 }
 
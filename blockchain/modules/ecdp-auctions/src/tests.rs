@@ -28,7 +28,7 @@ use mock::{RuntimeCall as MockCall, RuntimeEvent, *};
 use module_support::SwapManager;
 use sp_core::offchain::{testing, DbExternalities, OffchainDbExt, OffchainWorkerExt, StorageKind, TransactionPoolExt};
 use sp_io::offchain;
-use sp_runtime::traits::One;
+use sp_runtime::traits::{BadOrigin, One};
 
 fn run_to_block_offchain(n: u64) {
 	while System::block_number() < n {
@@ -625,6 +625,298 @@ fn cancel_collateral_auction_work() {
 	});
 }
 
+#[test]
+fn new_surplus_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EcdpAuctionsManagerModule::new_surplus_auction(0),
+			Error::<Runtime>::InvalidAmount,
+		);
+
+		assert_ok!(EcdpAuctionsManagerModule::new_surplus_auction(100));
+		System::assert_last_event(RuntimeEvent::EcdpAuctionsManagerModule(crate::Event::NewSurplusAuction {
+			auction_id: 0,
+			fix_surplus_amount: 100,
+		}));
+		assert_eq!(EcdpAuctionsManagerModule::total_surplus_in_auction(), 100);
+		assert_eq!(AuctionModule::auctions_index(), 1);
+		assert_eq!(
+			AuctionModule::auctions(0),
+			Some(orml_traits::AuctionInfo {
+				bid: None,
+				start: 1,
+				end: Some(2001)
+			})
+		);
+
+		mock_shutdown();
+		assert_noop!(
+			EcdpAuctionsManagerModule::new_surplus_auction(100),
+			Error::<Runtime>::MustBeforeShutdown,
+		);
+	});
+}
+
+#[test]
+fn surplus_auction_bid_handler_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			EcdpAuctionsManagerModule::surplus_auction_bid_handler(1, 0, (BOB, 50), None),
+			Error::<Runtime>::AuctionNotExists,
+		);
+
+		assert_ok!(EcdpAuctionsManagerModule::new_surplus_auction(100));
+		assert_eq!(Tokens::total_issuance(EDF), 3000);
+		let bob_ref_count_0 = System::consumers(&BOB);
+
+		assert_ok!(EcdpAuctionsManagerModule::surplus_auction_bid_handler(
+			1,
+			0,
+			(BOB, 50),
+			None
+		));
+		assert_eq!(Tokens::free_balance(EDF, &BOB), 950);
+		assert_eq!(Tokens::total_issuance(EDF), 2950);
+
+		let bob_ref_count_1 = System::consumers(&BOB);
+		assert_eq!(bob_ref_count_1, bob_ref_count_0 + 1);
+		let carol_ref_count_0 = System::consumers(&CAROL);
+
+		assert_noop!(
+			EcdpAuctionsManagerModule::surplus_auction_bid_handler(2, 0, (CAROL, 52), Some((BOB, 50))),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+		assert_ok!(EcdpAuctionsManagerModule::surplus_auction_bid_handler(
+			2,
+			0,
+			(CAROL, 53),
+			Some((BOB, 50))
+		));
+		assert_eq!(Tokens::free_balance(EDF, &BOB), 1000);
+		assert_eq!(Tokens::free_balance(EDF, &CAROL), 947);
+		assert_eq!(Tokens::total_issuance(EDF), 2947);
+
+		assert_eq!(System::consumers(&BOB), bob_ref_count_1 - 1);
+		assert_eq!(System::consumers(&CAROL), carol_ref_count_0 + 1);
+	});
+}
+
+#[test]
+fn surplus_auction_end_handler_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdTreasuryModule::on_system_surplus(200));
+		assert_ok!(EcdpAuctionsManagerModule::new_surplus_auction(100));
+		assert_ok!(EcdpAuctionsManagerModule::new_surplus_auction(100));
+		assert_ok!(EcdpAuctionsManagerModule::surplus_auction_bid_handler(
+			1,
+			0,
+			(BOB, 50),
+			None
+		));
+		assert_eq!(EcdpAuctionsManagerModule::total_surplus_in_auction(), 200);
+		let bob_ref_count_0 = System::consumers(&BOB);
+
+		EcdpAuctionsManagerModule::on_auction_ended(0, Some((BOB, 50)));
+		System::assert_last_event(RuntimeEvent::EcdpAuctionsManagerModule(crate::Event::SurplusAuctionDealt {
+			auction_id: 0,
+			surplus_amount: 100,
+			winner: BOB,
+			payment_amount: 50,
+		}));
+		assert_eq!(Tokens::free_balance(USSD, &BOB), 1100);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 100);
+		assert_eq!(EcdpAuctionsManagerModule::total_surplus_in_auction(), 100);
+		assert!(EcdpAuctionsManagerModule::surplus_auctions(0).is_none());
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0 - 1);
+
+		// the surplus is kept by treasury if there's no bid
+		EcdpAuctionsManagerModule::on_auction_ended(1, None);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 100);
+		assert_eq!(EcdpAuctionsManagerModule::total_surplus_in_auction(), 0);
+		assert!(EcdpAuctionsManagerModule::surplus_auctions(1).is_none());
+	});
+}
+
+#[test]
+fn debit_auction_methods() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EcdpAuctionsManagerModule::new_debit_auction(200, 100));
+		let debit_auction = EcdpAuctionsManagerModule::debit_auctions(0).unwrap();
+		assert_eq!(debit_auction.amount_for_sale(0, 100), 200);
+		assert_eq!(debit_auction.amount_for_sale(100, 150), 133);
+		assert_eq!(debit_auction.amount_for_sale(150, 200), 150);
+		assert_eq!(debit_auction.amount_for_sale(200, 200), 200);
+	});
+}
+
+#[test]
+fn new_debit_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EcdpAuctionsManagerModule::new_debit_auction(0, 100),
+			Error::<Runtime>::InvalidAmount,
+		);
+		assert_noop!(
+			EcdpAuctionsManagerModule::new_debit_auction(200, 0),
+			Error::<Runtime>::InvalidAmount,
+		);
+
+		assert_ok!(EcdpAuctionsManagerModule::new_debit_auction(200, 100));
+		System::assert_last_event(RuntimeEvent::EcdpAuctionsManagerModule(crate::Event::NewDebitAuction {
+			auction_id: 0,
+			initial_supply_amount: 200,
+			fix_payment_amount: 100,
+		}));
+		assert_eq!(EcdpAuctionsManagerModule::total_debit_in_auction(), 100);
+		assert_eq!(AuctionModule::auctions_index(), 1);
+
+		mock_shutdown();
+		assert_noop!(
+			EcdpAuctionsManagerModule::new_debit_auction(200, 100),
+			Error::<Runtime>::MustBeforeShutdown,
+		);
+	});
+}
+
+#[test]
+fn debit_auction_bid_handler_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			EcdpAuctionsManagerModule::debit_auction_bid_handler(1, 0, (BOB, 100), None),
+			Error::<Runtime>::AuctionNotExists,
+		);
+
+		assert_ok!(EcdpAuctionsManagerModule::new_debit_auction(200, 100));
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 0);
+		let bob_ref_count_0 = System::consumers(&BOB);
+
+		assert_noop!(
+			EcdpAuctionsManagerModule::debit_auction_bid_handler(1, 0, (BOB, 99), None),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+		assert_ok!(EcdpAuctionsManagerModule::debit_auction_bid_handler(
+			1,
+			0,
+			(BOB, 100),
+			None
+		));
+		assert_eq!(Tokens::free_balance(USSD, &BOB), 900);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 100);
+		assert_eq!(EcdpAuctionsManagerModule::debit_auctions(0).unwrap().amount, 200);
+
+		let bob_ref_count_1 = System::consumers(&BOB);
+		assert_eq!(bob_ref_count_1, bob_ref_count_0 + 1);
+		let carol_ref_count_0 = System::consumers(&CAROL);
+
+		assert_noop!(
+			EcdpAuctionsManagerModule::debit_auction_bid_handler(2, 0, (CAROL, 104), Some((BOB, 100))),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+		assert_ok!(EcdpAuctionsManagerModule::debit_auction_bid_handler(
+			2,
+			0,
+			(CAROL, 200),
+			Some((BOB, 100))
+		));
+		assert_eq!(Tokens::free_balance(USSD, &BOB), 1000);
+		assert_eq!(Tokens::free_balance(USSD, &CAROL), 900);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 100);
+		assert_eq!(EcdpAuctionsManagerModule::debit_auctions(0).unwrap().amount, 100);
+
+		assert_eq!(System::consumers(&BOB), bob_ref_count_1 - 1);
+		assert_eq!(System::consumers(&CAROL), carol_ref_count_0 + 1);
+	});
+}
+
+#[test]
+fn debit_auction_end_handler_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpAuctionsManagerModule::new_debit_auction(200, 100));
+		assert_ok!(EcdpAuctionsManagerModule::new_debit_auction(200, 100));
+		assert_ok!(EcdpAuctionsManagerModule::debit_auction_bid_handler(
+			1,
+			0,
+			(BOB, 100),
+			None
+		));
+		assert_eq!(EcdpAuctionsManagerModule::total_debit_in_auction(), 200);
+		assert_eq!(Tokens::total_issuance(EDF), 3000);
+		let bob_ref_count_0 = System::consumers(&BOB);
+
+		EcdpAuctionsManagerModule::on_auction_ended(0, Some((BOB, 100)));
+		System::assert_last_event(RuntimeEvent::EcdpAuctionsManagerModule(crate::Event::DebitAuctionDealt {
+			auction_id: 0,
+			debit_currency_amount: 200,
+			winner: BOB,
+			payment_amount: 100,
+		}));
+		assert_eq!(Tokens::free_balance(EDF, &BOB), 1200);
+		assert_eq!(Tokens::total_issuance(EDF), 3200);
+		assert_eq!(EcdpAuctionsManagerModule::total_debit_in_auction(), 100);
+		assert!(EcdpAuctionsManagerModule::debit_auctions(0).is_none());
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0 - 1);
+
+		// no native token is minted if there's no bid
+		EcdpAuctionsManagerModule::on_auction_ended(1, None);
+		assert_eq!(Tokens::total_issuance(EDF), 3200);
+		assert_eq!(EcdpAuctionsManagerModule::total_debit_in_auction(), 0);
+		assert!(EcdpAuctionsManagerModule::debit_auctions(1).is_none());
+	});
+}
+
+#[test]
+fn cancel_surplus_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdTreasuryModule::on_system_surplus(100));
+		assert_ok!(EcdpAuctionsManagerModule::new_surplus_auction(100));
+		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(BOB), 0, 50));
+		assert_eq!(Tokens::free_balance(EDF, &BOB), 950);
+		let bob_ref_count_0 = System::consumers(&BOB);
+
+		mock_shutdown();
+		assert_ok!(EcdpAuctionsManagerModule::cancel(RuntimeOrigin::none(), 0));
+		System::assert_last_event(RuntimeEvent::EcdpAuctionsManagerModule(crate::Event::CancelAuction {
+			auction_id: 0,
+		}));
+
+		assert_eq!(Tokens::free_balance(EDF, &BOB), 1000);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 100);
+		assert_eq!(EcdpAuctionsManagerModule::total_surplus_in_auction(), 0);
+		assert!(EcdpAuctionsManagerModule::surplus_auctions(0).is_none());
+		assert!(AuctionModule::auction_info(0).is_none());
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0 - 1);
+	});
+}
+
+#[test]
+fn cancel_debit_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpAuctionsManagerModule::new_debit_auction(200, 100));
+		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(BOB), 0, 100));
+		assert_eq!(Tokens::free_balance(USSD, &BOB), 900);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 100);
+		assert_eq!(EcdpUssdTreasuryModule::debit_pool(), 0);
+		let bob_ref_count_0 = System::consumers(&BOB);
+
+		mock_shutdown();
+		assert_ok!(EcdpAuctionsManagerModule::cancel(RuntimeOrigin::none(), 0));
+
+		assert_eq!(Tokens::free_balance(USSD, &BOB), 1000);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 100);
+		assert_eq!(EcdpUssdTreasuryModule::debit_pool(), 100);
+		assert_eq!(EcdpAuctionsManagerModule::total_debit_in_auction(), 0);
+		assert!(EcdpAuctionsManagerModule::debit_auctions(0).is_none());
+		assert!(AuctionModule::auction_info(0).is_none());
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0 - 1);
+	});
+}
+
 #[test]
 fn offchain_worker_cancels_auction_in_shutdown() {
 	let (offchain, _offchain_state) = testing::TestOffchainExt::new();
@@ -736,3 +1028,480 @@ fn offchain_default_max_iterator_works() {
 		assert_eq!(pool_state.write().transactions.len(), 1001);
 	});
 }
+
+fn dutch_auction_params(curve: PriceDecayCurve<BlockNumber>, max_duration: BlockNumber) -> DutchAuctionParams<BlockNumber> {
+	DutchAuctionParams {
+		start_premium: Rate::saturating_from_rational(20, 100),
+		curve,
+		max_duration,
+		floor: Rate::zero(),
+	}
+}
+
+#[test]
+fn price_decay_curve_work() {
+	let start_price = Price::saturating_from_integer(2);
+
+	let linear = PriceDecayCurve::<BlockNumber>::Linear { duration: 100 };
+	assert_eq!(linear.price(start_price, 0), start_price);
+	assert_eq!(linear.price(start_price, 50), Price::one());
+	assert_eq!(linear.price(start_price, 100), Price::zero());
+	assert_eq!(linear.price(start_price, 150), Price::zero());
+	assert_eq!(
+		PriceDecayCurve::<BlockNumber>::Linear { duration: 0 }.price(start_price, 0),
+		Price::zero()
+	);
+
+	let stepwise = PriceDecayCurve::<BlockNumber>::Stepwise {
+		step: 10,
+		cut: Rate::saturating_from_rational(1, 2),
+	};
+	assert_eq!(stepwise.price(start_price, 9), start_price);
+	assert_eq!(stepwise.price(start_price, 10), Price::one());
+	assert_eq!(stepwise.price(start_price, 25), Price::saturating_from_rational(1, 2));
+
+	let exponential = PriceDecayCurve::<BlockNumber>::Exponential {
+		cut: Rate::saturating_from_rational(1, 2),
+	};
+	assert_eq!(exponential.price(start_price, 0), start_price);
+	assert_eq!(exponential.price(start_price, 1), Price::one());
+	assert_eq!(exponential.price(start_price, 2), Price::saturating_from_rational(1, 2));
+}
+
+#[test]
+fn set_dutch_auction_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let params = dutch_auction_params(PriceDecayCurve::Linear { duration: 100 }, 200);
+		assert_noop!(
+			EcdpAuctionsManagerModule::set_dutch_auction_params(RuntimeOrigin::signed(BOB), BTC, Some(params)),
+			BadOrigin,
+		);
+
+		assert_ok!(EcdpAuctionsManagerModule::set_dutch_auction_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(params)
+		));
+		System::assert_last_event(RuntimeEvent::EcdpAuctionsManagerModule(
+			crate::Event::DutchAuctionParamsUpdated {
+				collateral_type: BTC,
+				params: Some(params),
+			},
+		));
+		assert_eq!(EcdpAuctionsManagerModule::dutch_auction_params(BTC), Some(params));
+
+		assert_ok!(EcdpAuctionsManagerModule::set_dutch_auction_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			None
+		));
+		assert_eq!(EcdpAuctionsManagerModule::dutch_auction_params(BTC), None);
+	});
+}
+
+#[test]
+fn set_dutch_auction_params_rejects_invalid_params() {
+	ExtBuilder::default().build().execute_with(|| {
+		let half = Rate::saturating_from_rational(1, 2);
+		let invalid = [
+			dutch_auction_params(PriceDecayCurve::Linear { duration: 0 }, 200),
+			dutch_auction_params(PriceDecayCurve::Stepwise { step: 0, cut: half }, 200),
+			dutch_auction_params(
+				PriceDecayCurve::Stepwise {
+					step: 10,
+					cut: Rate::zero(),
+				},
+				200,
+			),
+			dutch_auction_params(
+				PriceDecayCurve::Exponential {
+					cut: Rate::saturating_from_integer(2),
+				},
+				200,
+			),
+			dutch_auction_params(PriceDecayCurve::Linear { duration: 100 }, 0),
+			DutchAuctionParams {
+				floor: Rate::saturating_from_rational(3, 2),
+				..dutch_auction_params(PriceDecayCurve::Linear { duration: 100 }, 200)
+			},
+		];
+		for params in invalid {
+			assert_noop!(
+				EcdpAuctionsManagerModule::set_dutch_auction_params(RuntimeOrigin::signed(ALICE), BTC, Some(params)),
+				Error::<Runtime>::InvalidDutchAuctionParams,
+			);
+		}
+	});
+}
+
+#[test]
+fn dutch_auction_price_stops_at_floor() {
+	let params = DutchAuctionParams {
+		floor: Rate::saturating_from_rational(1, 4),
+		..dutch_auction_params(PriceDecayCurve::Linear { duration: 100 }, 200)
+	};
+	assert!(params.is_valid());
+	let item = DutchAuctionItem {
+		refund_recipient: ALICE,
+		currency_id: BTC,
+		initial_amount: 10,
+		amount: 10,
+		target: 100,
+		start_price: Price::saturating_from_integer(2),
+		start_time: 0,
+		params,
+	};
+	assert_eq!(item.current_price(50), Price::one());
+	assert_eq!(item.current_price(100), Price::saturating_from_rational(1, 2));
+	assert!(!item.needs_reset(100));
+	assert!(item.needs_reset(200));
+}
+
+#[test]
+fn new_dutch_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EcdpAuctionsManagerModule::new_dutch_auction(&ALICE, BTC, 10, 100),
+			Error::<Runtime>::DutchAuctionDisabled,
+		);
+
+		assert_ok!(EcdpAuctionsManagerModule::set_dutch_auction_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(dutch_auction_params(PriceDecayCurve::Linear { duration: 100 }, 200))
+		));
+		assert_noop!(
+			EcdpAuctionsManagerModule::new_dutch_auction(&ALICE, BTC, 10, 0),
+			Error::<Runtime>::InvalidAmount,
+		);
+		MockPriceSource::set_relative_price(None);
+		assert_noop!(
+			EcdpAuctionsManagerModule::new_dutch_auction(&ALICE, BTC, 10, 100),
+			Error::<Runtime>::InvalidFeedPrice,
+		);
+		MockPriceSource::set_relative_price(Some(Price::one()));
+
+		let ref_count_0 = System::consumers(&ALICE);
+		assert_ok!(EcdpAuctionsManagerModule::new_dutch_auction(&ALICE, BTC, 10, 100));
+		System::assert_last_event(RuntimeEvent::EcdpAuctionsManagerModule(crate::Event::NewDutchAuction {
+			auction_id: 0,
+			collateral_type: BTC,
+			collateral_amount: 10,
+			target_stable_amount: 100,
+			start_price: Price::saturating_from_rational(12, 10),
+		}));
+
+		assert_eq!(EcdpAuctionsManagerModule::total_collateral_in_auction(BTC), 10);
+		assert_eq!(EcdpAuctionsManagerModule::total_target_in_auction(), 100);
+		assert_eq!(EcdpAuctionsManagerModule::next_dutch_auction_id(), 1);
+		assert_eq!(AuctionModule::auctions_index(), 0);
+		assert_eq!(System::consumers(&ALICE), ref_count_0 + 1);
+	});
+}
+
+#[test]
+fn take_dutch_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(EcdpAuctionsManagerModule::set_dutch_auction_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(dutch_auction_params(PriceDecayCurve::Linear { duration: 100 }, 200))
+		));
+		assert_ok!(EcdpAuctionsManagerModule::new_dutch_auction(&ALICE, BTC, 100, 40));
+		let alice_ref_count_0 = System::consumers(&ALICE);
+		assert_noop!(
+			EcdpAuctionsManagerModule::take_dutch_auction(RuntimeOrigin::signed(BOB), 1, 50, Price::one()),
+			Error::<Runtime>::AuctionNotExists,
+		);
+
+		// price decays to 1.2 * 50 / 100 = 0.6
+		System::set_block_number(51);
+		assert_noop!(
+			EcdpAuctionsManagerModule::take_dutch_auction(
+				RuntimeOrigin::signed(BOB),
+				0,
+				50,
+				Price::saturating_from_rational(1, 2)
+			),
+			Error::<Runtime>::PriceExceedsLimit,
+		);
+
+		assert_ok!(EcdpAuctionsManagerModule::take_dutch_auction(
+			RuntimeOrigin::signed(BOB),
+			0,
+			50,
+			Price::one()
+		));
+		System::assert_last_event(RuntimeEvent::EcdpAuctionsManagerModule(crate::Event::DutchAuctionTaken {
+			auction_id: 0,
+			collateral_type: BTC,
+			keeper: BOB,
+			collateral_amount: 50,
+			payment_amount: 30,
+			price: Price::saturating_from_rational(6, 10),
+		}));
+		assert_eq!(Tokens::free_balance(USSD, &BOB), 970);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1050);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 30);
+		assert_eq!(EcdpUssdTreasuryModule::total_collaterals(BTC), 50);
+		assert_eq!(EcdpAuctionsManagerModule::total_collateral_in_auction(BTC), 50);
+		assert_eq!(EcdpAuctionsManagerModule::total_target_in_auction(), 10);
+		let dutch_auction = EcdpAuctionsManagerModule::dutch_auctions(0).unwrap();
+		assert_eq!(dutch_auction.amount, 50);
+		assert_eq!(dutch_auction.target, 10);
+
+		// price decays to 1.2 * 40 / 100 = 0.48, the remaining target 10 is worth 20 collateral
+		System::set_block_number(61);
+		assert_ok!(EcdpAuctionsManagerModule::take_dutch_auction(
+			RuntimeOrigin::signed(CAROL),
+			0,
+			1000,
+			Price::one()
+		));
+		System::assert_has_event(RuntimeEvent::EcdpAuctionsManagerModule(crate::Event::DutchAuctionTaken {
+			auction_id: 0,
+			collateral_type: BTC,
+			keeper: CAROL,
+			collateral_amount: 20,
+			payment_amount: 10,
+			price: Price::saturating_from_rational(48, 100),
+		}));
+		System::assert_last_event(RuntimeEvent::EcdpAuctionsManagerModule(
+			crate::Event::DutchAuctionFinished {
+				auction_id: 0,
+				collateral_type: BTC,
+				refund_collateral_amount: 30,
+				unrecovered_target: 0,
+			},
+		));
+		assert_eq!(Tokens::free_balance(USSD, &CAROL), 990);
+		assert_eq!(Tokens::free_balance(BTC, &CAROL), 920);
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1030);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 40);
+		assert_eq!(EcdpUssdTreasuryModule::total_collaterals(BTC), 0);
+		assert_eq!(EcdpAuctionsManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(EcdpAuctionsManagerModule::total_target_in_auction(), 0);
+		assert!(EcdpAuctionsManagerModule::dutch_auctions(0).is_none());
+		assert_eq!(System::consumers(&ALICE), alice_ref_count_0 - 1);
+	});
+}
+
+#[test]
+fn take_dutch_auction_sold_out_leaves_unrecovered_target() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(EcdpAuctionsManagerModule::set_dutch_auction_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(dutch_auction_params(PriceDecayCurve::Linear { duration: 100 }, 200))
+		));
+		assert_ok!(EcdpAuctionsManagerModule::new_dutch_auction(&ALICE, BTC, 100, 100));
+
+		// price decays to 1.2 * 50 / 100 = 0.6, all collateral only raises 60
+		System::set_block_number(51);
+		assert_ok!(EcdpAuctionsManagerModule::take_dutch_auction(
+			RuntimeOrigin::signed(BOB),
+			0,
+			1000,
+			Price::one()
+		));
+		System::assert_last_event(RuntimeEvent::EcdpAuctionsManagerModule(
+			crate::Event::DutchAuctionFinished {
+				auction_id: 0,
+				collateral_type: BTC,
+				refund_collateral_amount: 0,
+				unrecovered_target: 40,
+			},
+		));
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1100);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 60);
+		assert_eq!(EcdpAuctionsManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(EcdpAuctionsManagerModule::total_target_in_auction(), 0);
+		assert!(EcdpAuctionsManagerModule::dutch_auctions(0).is_none());
+	});
+}
+
+#[test]
+fn reset_dutch_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(EcdpAuctionsManagerModule::set_dutch_auction_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(dutch_auction_params(PriceDecayCurve::Linear { duration: 1000 }, 100))
+		));
+		assert_ok!(EcdpAuctionsManagerModule::new_dutch_auction(&ALICE, BTC, 100, 40));
+
+		System::set_block_number(50);
+		assert_noop!(
+			EcdpAuctionsManagerModule::reset_dutch_auction(RuntimeOrigin::signed(BOB), 0),
+			Error::<Runtime>::DutchAuctionNotExpired,
+		);
+
+		System::set_block_number(101);
+		assert_noop!(
+			EcdpAuctionsManagerModule::take_dutch_auction(RuntimeOrigin::signed(BOB), 0, 10, Price::one()),
+			Error::<Runtime>::DutchAuctionNeedsReset,
+		);
+
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_integer(2)));
+		assert_ok!(EcdpAuctionsManagerModule::reset_dutch_auction(
+			RuntimeOrigin::signed(BOB),
+			0
+		));
+		System::assert_last_event(RuntimeEvent::EcdpAuctionsManagerModule(crate::Event::DutchAuctionReset {
+			auction_id: 0,
+			start_price: Price::saturating_from_rational(24, 10),
+		}));
+		let dutch_auction = EcdpAuctionsManagerModule::dutch_auctions(0).unwrap();
+		assert_eq!(dutch_auction.start_time, 101);
+		assert_eq!(dutch_auction.current_price(101), Price::saturating_from_rational(24, 10));
+
+		assert_ok!(EcdpAuctionsManagerModule::take_dutch_auction(
+			RuntimeOrigin::signed(BOB),
+			0,
+			10,
+			Price::saturating_from_integer(3)
+		));
+		assert_eq!(Tokens::free_balance(USSD, &BOB), 976);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1010);
+	});
+}
+
+#[test]
+fn cancel_dutch_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(EcdpAuctionsManagerModule::set_dutch_auction_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(dutch_auction_params(PriceDecayCurve::Linear { duration: 100 }, 200))
+		));
+		assert_ok!(EcdpAuctionsManagerModule::new_dutch_auction(&ALICE, BTC, 100, 50));
+		assert_noop!(
+			EcdpAuctionsManagerModule::cancel_dutch_auction(RuntimeOrigin::none(), 0),
+			Error::<Runtime>::MustAfterShutdown,
+		);
+		let alice_ref_count_0 = System::consumers(&ALICE);
+
+		mock_shutdown();
+		assert_noop!(
+			EcdpAuctionsManagerModule::take_dutch_auction(RuntimeOrigin::signed(BOB), 0, 10, Price::one()),
+			Error::<Runtime>::MustBeforeShutdown,
+		);
+		assert_ok!(EcdpAuctionsManagerModule::cancel_dutch_auction(RuntimeOrigin::none(), 0));
+		System::assert_last_event(RuntimeEvent::EcdpAuctionsManagerModule(crate::Event::CancelDutchAuction {
+			auction_id: 0,
+		}));
+
+		// collateral worth the target stays in CDP treasury, the rest is refunded
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1050);
+		assert_eq!(EcdpUssdTreasuryModule::total_collaterals(BTC), 50);
+		assert_eq!(EcdpAuctionsManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(EcdpAuctionsManagerModule::total_target_in_auction(), 0);
+		assert!(EcdpAuctionsManagerModule::dutch_auctions(0).is_none());
+		assert_eq!(System::consumers(&ALICE), alice_ref_count_0 - 1);
+	});
+}
+
+#[test]
+fn offchain_worker_cancels_dutch_auction_in_shutdown() {
+	let (offchain, _offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut ext = ExtBuilder::default().build();
+	ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(OffchainDbExt::new(offchain));
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpAuctionsManagerModule::set_dutch_auction_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(dutch_auction_params(PriceDecayCurve::Linear { duration: 100 }, 200))
+		));
+		assert_ok!(EcdpAuctionsManagerModule::new_dutch_auction(&ALICE, BTC, 10, 100));
+		mock_shutdown();
+
+		run_to_block_offchain(2);
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		if let MockCall::EcdpAuctionsManagerModule(crate::Call::cancel_dutch_auction { id: auction_id }) = tx.call {
+			assert_ok!(EcdpAuctionsManagerModule::cancel_dutch_auction(
+				RuntimeOrigin::none(),
+				auction_id
+			));
+		}
+
+		assert!(EcdpAuctionsManagerModule::dutch_auctions(0).is_none());
+		assert!(pool_state.write().transactions.pop().is_none());
+	});
+}
+
+#[test]
+fn keeper_cancel_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdTreasuryModule::deposit_collateral(&CAROL, BTC, 10));
+		assert_ok!(EcdpAuctionsManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(BOB), 0, 80));
+		assert_noop!(
+			EcdpAuctionsManagerModule::cancel(RuntimeOrigin::signed(CAROL), 0),
+			Error::<Runtime>::MustAfterShutdown,
+		);
+
+		mock_shutdown();
+		let post_info = EcdpAuctionsManagerModule::cancel(RuntimeOrigin::signed(CAROL), 0).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		System::assert_last_event(RuntimeEvent::EcdpAuctionsManagerModule(crate::Event::CancelAuction {
+			auction_id: 0,
+		}));
+		assert_eq!(Tokens::free_balance(USSD, &BOB), 1000);
+		assert!(EcdpAuctionsManagerModule::collateral_auctions(0).is_none());
+
+		// the fee is charged if the auction is already cancelled
+		assert_noop!(
+			EcdpAuctionsManagerModule::cancel(RuntimeOrigin::signed(CAROL), 0),
+			Error::<Runtime>::AuctionNotExists,
+		);
+	});
+}
+
+#[test]
+fn keeper_cancel_dutch_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(EcdpAuctionsManagerModule::set_dutch_auction_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(dutch_auction_params(PriceDecayCurve::Linear { duration: 100 }, 200))
+		));
+		assert_ok!(EcdpAuctionsManagerModule::new_dutch_auction(&ALICE, BTC, 100, 50));
+		assert_noop!(
+			EcdpAuctionsManagerModule::cancel_dutch_auction(RuntimeOrigin::signed(BOB), 0),
+			Error::<Runtime>::MustAfterShutdown,
+		);
+
+		mock_shutdown();
+		let post_info = EcdpAuctionsManagerModule::cancel_dutch_auction(RuntimeOrigin::signed(BOB), 0).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		System::assert_last_event(RuntimeEvent::EcdpAuctionsManagerModule(crate::Event::CancelDutchAuction {
+			auction_id: 0,
+		}));
+		assert!(EcdpAuctionsManagerModule::dutch_auctions(0).is_none());
+
+		// the fee is charged if the dutch auction is already cancelled
+		assert_noop!(
+			EcdpAuctionsManagerModule::cancel_dutch_auction(RuntimeOrigin::signed(BOB), 0),
+			Error::<Runtime>::AuctionNotExists,
+		);
+	});
+}
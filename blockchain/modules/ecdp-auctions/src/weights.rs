@@ -50,6 +50,10 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for module_auction_manager.
 pub trait WeightInfo {
 	fn cancel_collateral_auction() -> Weight;
+	fn set_dutch_auction_params() -> Weight;
+	fn take_dutch_auction() -> Weight;
+	fn reset_dutch_auction() -> Weight;
+	fn cancel_dutch_auction() -> Weight;
 }
 
 /// Weights for module_auction_manager using the Setheum node and recommended hardware.
@@ -60,6 +64,25 @@ impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(9 as u64))
 			.saturating_add(T::DbWeight::get().writes(7 as u64))
 	}
+	fn set_dutch_auction_params() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn take_dutch_auction() -> Weight {
+		Weight::from_parts(71_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+	}
+	fn reset_dutch_auction() -> Weight {
+		Weight::from_parts(32_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn cancel_dutch_auction() -> Weight {
+		Weight::from_parts(64_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(7 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -69,4 +92,23 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(9 as u64))
 			.saturating_add(RocksDbWeight::get().writes(7 as u64))
 	}
+	fn set_dutch_auction_params() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn take_dutch_auction() -> Weight {
+		Weight::from_parts(71_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+	}
+	fn reset_dutch_auction() -> Weight {
+		Weight::from_parts(32_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn cancel_dutch_auction() -> Weight {
+		Weight::from_parts(64_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(7 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
 }
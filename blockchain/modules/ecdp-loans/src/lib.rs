@@ -216,7 +216,8 @@ impl<T: Config> Pallet<T> {
 
 		// ensure pass risk check
 		let EcdpPosition { collateral, debit } = Self::positions(currency_id, who);
-		T::EcdpUssdRiskManager::check_position_valid(
+		T::EcdpUssdRiskManager::check_account_position_valid(
+			who,
 			currency_id,
 			collateral,
 			debit,
@@ -227,6 +228,9 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// transfer whole loan of `from` to `to`
+	///
+	/// Ensured atomic.
+	#[transactional]
 	pub fn transfer_loan(from: &T::AccountId, to: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
 		// get `from` position data
 		let EcdpPosition { collateral, debit } = Self::positions(currency_id, from);
@@ -243,7 +247,13 @@ impl<T: Config> Pallet<T> {
 			.expect("existing debit balance cannot overflow; qed");
 
 		// check new position
-		T::EcdpUssdRiskManager::check_position_valid(currency_id, new_to_collateral_balance, new_to_debit_balance, true)?;
+		T::EcdpUssdRiskManager::check_account_position_valid(
+			to,
+			currency_id,
+			new_to_collateral_balance,
+			new_to_debit_balance,
+			true,
+		)?;
 
 		// balance -> amount
 		let collateral_adjustment = Self::amount_try_from_balance(collateral)?;
@@ -257,6 +267,9 @@ impl<T: Config> Pallet<T> {
 		)?;
 		Self::update_loan(to, currency_id, collateral_adjustment, debit_adjustment)?;
 
+		// the remaining positions of `from` may rely on the transferred one as a basket
+		T::EcdpUssdRiskManager::check_account_position_valid(from, currency_id, Zero::zero(), Zero::zero(), true)?;
+
 		Self::deposit_event(Event::TransferLoan {
 			from: from.clone(),
 			to: to.clone(),
@@ -30,15 +30,20 @@
 #![allow(clippy::unused_unit)]
 #![allow(clippy::upper_case_acronyms)]
 
-use frame_support::{pallet_prelude::*, traits::UnixTime, transactional, BoundedVec, PalletId};
+use frame_support::{
+	dispatch::{DispatchInfo, GetDispatchInfo, PostDispatchInfo},
+	pallet_prelude::*,
+	traits::UnixTime,
+	transactional, BoundedVec, PalletId,
+};
 use frame_system::{
 	offchain::{SendTransactionTypes, SubmitTransaction},
 	pallet_prelude::*,
 };
 use module_support::{
-	AddressMapping, EcdpUssdTreasury, EcdpUssdTreasuryExtended, SwapManager, EVMBridge, EcdpEmergencyShutdown, ExchangeRate,
-	FractionalRate, InvokeContext, LiquidateCollateral, LiquidationEvmBridge, Price, PriceProvider, Rate, Ratio,
-	EcdpUssdRiskManager, Swap, SwapLimit,
+	ensure_none_or_keeper, AddressMapping, EcdpUssdTreasury, EcdpUssdTreasuryExtended, SwapManager, EVMBridge, EcdpEmergencyShutdown, ExchangeRate,
+	FlashMint, FlashMintEvmBridge, FractionalRate, InvokeContext, LiquidateCollateral, LiquidationEvmBridge, Price,
+	PriceProvider, Rate, Ratio, EcdpUssdRiskManager, Swap, SwapLimit,
 };
 use orml_traits::{Change, GetByKey, MultiCurrency};
 use orml_utilities::OffchainErr;
@@ -56,12 +61,13 @@ use sp_runtime::{
 		Duration,
 	},
 	traits::{
-		AccountIdConversion, Bounded, One, Saturating, StaticLookup, UniqueSaturatedInto, Zero,
+		AccountIdConversion, Bounded, Dispatchable, One, Saturating, StaticLookup, UniqueSaturatedInto,
+		Zero,
 	},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
-	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
+	ArithmeticError, DispatchError, DispatchResult, Either, FixedPointNumber, RuntimeDebug,
 };
 use sp_std::{marker::PhantomData, prelude::*};
 
@@ -78,6 +84,8 @@ pub const OFFCHAIN_WORKER_MAX_ITERATIONS: &[u8] = b"setheum/ecdp-ussd-engine/max
 pub const LOCK_DURATION: u64 = 100;
 pub const DEFAULT_MAX_ITERATIONS: u32 = 1000;
 
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 pub type LoansOf<T> = module_ecdp_loans::Pallet<T>;
 pub type CurrencyOf<T> = <T as Config>::Currency;
 
@@ -103,6 +111,19 @@ pub struct RiskManagementParams {
 	/// of ECDP so that the current collateral ratio is lower than the
 	/// required collateral ratio. `None` value means not set
 	pub required_collateral_ratio: Option<Ratio>,
+
+	/// Partial liquidation target ratio, if it's set, liquidating an unsafe
+	/// ECDP only repays enough debit to restore its collateral ratio to this
+	/// ratio, and the liquidation penalty is only charged on the repaid
+	/// debit. Must be above the liquidation ratio. `None` value means the
+	/// whole ECDP is liquidated
+	pub partial_liquidation_target_ratio: Option<Ratio>,
+
+	/// Stability fee rate per second, the debit exchange rate of this
+	/// collateral type compounds by it over time, and the accrued fee is
+	/// issued to the surplus pool of ECDP USSD treasury. `None` value means
+	/// no stability fee
+	pub stability_fee_per_sec: Option<FractionalRate>,
 }
 
 // typedef to help polkadot.js disambiguate Change with different generic
@@ -110,6 +131,9 @@ pub struct RiskManagementParams {
 type ChangeOptionRate = Change<Option<Rate>>;
 type ChangeOptionRatio = Change<Option<Ratio>>;
 type ChangeBalance = Change<Balance>;
+type ChangeRate = Change<Rate>;
+
+type CallOf<T> = <T as Config>::RuntimeCall;
 
 /// Status of SCDP
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
@@ -119,6 +143,31 @@ pub enum CDPStatus {
 	ChecksFailed(DispatchError),
 }
 
+/// How a liquidation protection rule restores the collateral ratio of an ECDP
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum ProtectionAction {
+	/// Sell collateral on DEX to repay debit.
+	Deleverage,
+	/// Deposit collateral from the free balance of the ECDP owner, `max_amount` is the
+	/// remaining amount of collateral that may still be deposited by this rule.
+	TopUp { max_amount: Balance },
+}
+
+/// Liquidation protection rule registered by the owner of an ECDP
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct ProtectionRule {
+	/// The rule is triggered when the collateral ratio of the ECDP is below
+	/// the trigger ratio. Must be above the liquidation ratio.
+	pub trigger_ratio: Ratio,
+
+	/// The collateral ratio which the ECDP is restored to when the rule is
+	/// triggered. Must be above the trigger ratio.
+	pub target_ratio: Ratio,
+
+	/// The action to restore the collateral ratio.
+	pub action: ProtectionAction,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -213,6 +262,23 @@ pub mod module {
 		/// Evm Origin account when settle ERC20 type ECDP
 		type SettleErc20EvmOrigin: Get<Self::AccountId>;
 
+		/// The runtime call which is dispatched with the flash minted USSD.
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo, Info = DispatchInfo>
+			+ GetDispatchInfo
+			+ IsType<<Self as frame_system::Config>::RuntimeCall>;
+
+		/// Evm Bridge for notifying the receiver contract of a flash mint.
+		type FlashMintEvmBridge: FlashMintEvmBridge;
+
+		/// The maximum number of calls executed within a single flash mint.
+		#[pallet::constant]
+		type MaxFlashMintCalls: Get<u32>;
+
+		/// The maximum number of collateral types in a multi-collateral basket.
+		#[pallet::constant]
+		type MaxBasketCurrencies: Get<u32>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -259,6 +325,32 @@ pub mod module {
 		CollateralContractNotFound,
 		/// Invalid rate
 		InvalidRate,
+		/// The partial liquidation target ratio is not above the liquidation ratio
+		InvalidPartialLiquidationTargetRatio,
+		/// The trigger ratio of the protection rule is not above the liquidation ratio,
+		/// or the target ratio is not above the trigger ratio
+		InvalidProtectionRule,
+		/// The ECDP has no liquidation protection rule
+		NoProtectionRule,
+		/// The liquidation protection rule of the ECDP is not triggered
+		ProtectionNotTriggered,
+		/// The outstanding flash minted USSD would exceed the flash mint ceiling
+		ExceedFlashMintCeiling,
+		/// The flash minted USSD and the fee are not repaid at the end of the flash mint
+		FlashMintNotRepaid,
+		/// Exceeds `T::MaxFlashMintCalls`.
+		TooManyFlashMintCalls,
+		/// Liquidation of the collateral is paused because its feed price is stale
+		StaleFeedPrice,
+		/// The basket has duplicated collateral types, or collateral types without
+		/// a basket haircut or with a liquidation protection rule
+		InvalidBasket,
+		/// Exceeds `T::MaxBasketCurrencies`.
+		TooManyBasketCurrencies,
+		/// The operation is not supported for ECDPs in a multi-collateral basket
+		CDPInBasket,
+		/// The basket haircut cannot be removed while baskets hold the collateral type
+		BasketHaircutInUse,
 	}
 
 	#[pallet::event]
@@ -305,10 +397,78 @@ pub mod module {
 			collateral_type: CurrencyId,
 			new_total_debit_value: Balance,
 		},
+		/// The partial liquidation target ratio for specific collateral type updated.
+		PartialLiquidationTargetRatioUpdated {
+			collateral_type: CurrencyId,
+			new_partial_liquidation_target_ratio: Option<Ratio>,
+		},
+		/// The stability fee rate per second for specific collateral type updated.
+		StabilityFeePerSecUpdated {
+			collateral_type: CurrencyId,
+			new_stability_fee_per_sec: Option<Rate>,
+		},
+		/// The liquidation protection rule of an ECDP updated.
+		LiquidationProtectionUpdated {
+			collateral_type: CurrencyId,
+			owner: T::AccountId,
+			rule: Option<ProtectionRule>,
+		},
+		/// Sold collateral of the protected ECDP on DEX to repay debit.
+		ProtectedCDPDeleveraged {
+			collateral_type: CurrencyId,
+			owner: T::AccountId,
+			sold_collateral_amount: Balance,
+			repaid_debit_value: Balance,
+		},
+		/// Deposited collateral from the free balance of the owner into the protected ECDP.
+		ProtectedCDPToppedUp {
+			collateral_type: CurrencyId,
+			owner: T::AccountId,
+			collateral_amount: Balance,
+		},
 		/// A new liquidation contract is registered.
 		LiquidationContractRegistered { address: EvmAddress },
 		/// A new liquidation contract is deregistered.
 		LiquidationContractDeregistered { address: EvmAddress },
+		/// The flash mint ceiling updated.
+		FlashMintCeilingUpdated { new_ceiling: Balance },
+		/// The flash mint fee rate updated.
+		FlashMintFeeRateUpdated { new_fee_rate: Rate },
+		/// The keeper reward rate of the liquidation penalty updated.
+		KeeperRewardRateUpdated { new_reward_rate: Rate },
+		/// The maximum keeper reward of a liquidation updated.
+		MaxKeeperRewardUpdated { new_max_reward: Balance },
+		/// A keeper was rewarded for liquidating the unsafe ECDP.
+		KeeperRewarded {
+			keeper: T::AccountId,
+			collateral_type: CurrencyId,
+			owner: T::AccountId,
+			reward: Balance,
+		},
+		/// USSD was flash minted and repaid with the fee.
+		FlashMinted {
+			receiver: T::AccountId,
+			amount: Balance,
+			fee: Balance,
+		},
+		/// The basket haircut for specific collateral type updated.
+		BasketHaircutUpdated {
+			collateral_type: CurrencyId,
+			new_haircut: Option<Ratio>,
+		},
+		/// The multi-collateral basket of an ECDP owner updated.
+		BasketUpdated {
+			owner: T::AccountId,
+			collateral_types: Vec<CurrencyId>,
+		},
+		/// Liquidate the unsafe multi-collateral basket.
+		LiquidateUnsafeBasket {
+			owner: T::AccountId,
+			bad_debt_value: Balance,
+			target_amount: Balance,
+		},
+		/// Settle the multi-collateral basket that has debit.
+		SettleBasketInDebit { owner: T::AccountId },
 	}
 
 	/// Mapping from collateral type to its exchange rate of debit units and
@@ -326,6 +486,87 @@ pub mod module {
 	#[pallet::getter(fn collateral_params)]
 	pub type CollateralParams<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, RiskManagementParams, OptionQuery>;
 
+	/// Timestamp in seconds of the last stability fee accumulation
+	///
+	/// LastAccumulationSecs: u64
+	#[pallet::storage]
+	#[pallet::getter(fn last_accumulation_secs)]
+	pub type LastAccumulationSecs<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// The liquidation protection rules registered by ECDP owners
+	///
+	/// LiquidationProtections: double_map CurrencyId, AccountId => Option<ProtectionRule>
+	#[pallet::storage]
+	#[pallet::getter(fn liquidation_protections)]
+	pub type LiquidationProtections<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyId, Twox64Concat, T::AccountId, ProtectionRule, OptionQuery>;
+
+	/// The maximum amount of USSD that can be outstanding from flash mints at
+	/// the same time. Zero disables flash minting.
+	///
+	/// FlashMintCeiling: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn flash_mint_ceiling)]
+	pub type FlashMintCeiling<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The fee rate charged on the flash minted amount, the fee is deposited
+	/// to the surplus pool of ECDP USSD treasury.
+	///
+	/// FlashMintFeeRate: Rate
+	#[pallet::storage]
+	#[pallet::getter(fn flash_mint_fee_rate)]
+	pub type FlashMintFeeRate<T: Config> = StorageValue<_, Rate, ValueQuery>;
+
+	/// The amount of USSD currently flash minted and not yet repaid. Only
+	/// non-zero within a flash mint.
+	///
+	/// FlashMintOutstanding: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn flash_mint_outstanding)]
+	pub type FlashMintOutstanding<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The rate of the liquidation penalty rewarded to the keeper who
+	/// liquidates an unsafe ECDP with a signed call.
+	///
+	/// KeeperRewardRate: Rate
+	#[pallet::storage]
+	#[pallet::getter(fn keeper_reward_rate)]
+	pub type KeeperRewardRate<T: Config> = StorageValue<_, Rate, ValueQuery>;
+
+	/// The maximum reward of a keeper for a single liquidation.
+	///
+	/// MaxKeeperReward: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn max_keeper_reward)]
+	pub type MaxKeeperReward<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The haircut applied to the collateral value of a collateral type in
+	/// multi-collateral baskets, only collateral types with a haircut can
+	/// join a basket. A basket is unsafe when its haircut collateral value is
+	/// below its debit value.
+	///
+	/// BasketHaircuts: CurrencyId => Option<Ratio>
+	#[pallet::storage]
+	#[pallet::getter(fn basket_haircuts)]
+	pub type BasketHaircuts<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Ratio, OptionQuery>;
+
+	/// The multi-collateral baskets of ECDP owners, the ECDPs of the owner
+	/// under these collateral types are margined together and their
+	/// collateral is sold in this order on liquidation.
+	///
+	/// Baskets: AccountId => BoundedVec<CurrencyId, MaxBasketCurrencies>
+	#[pallet::storage]
+	#[pallet::getter(fn baskets)]
+	pub type Baskets<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BoundedVec<CurrencyId, T::MaxBasketCurrencies>, ValueQuery>;
+
+	/// The number of multi-collateral baskets that hold a collateral type.
+	///
+	/// BasketMembers: CurrencyId => u32
+	#[pallet::storage]
+	#[pallet::getter(fn basket_members)]
+	pub type BasketMembers<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, u32, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn liquidation_contracts)]
 	pub type LiquidationContracts<T: Config> =
@@ -352,6 +593,7 @@ pub mod module {
 			self.collaterals_params.iter().for_each(
 				|(
 					currency_id,
+					stability_fee_per_sec,
 					liquidation_ratio,
 					liquidation_penalty,
 					required_collateral_ratio,
@@ -365,6 +607,9 @@ pub mod module {
 							liquidation_penalty: liquidation_penalty
 								.map(|v| FractionalRate::try_from(v).expect("liquidation_penalty out of bound")),
 							required_collateral_ratio: *required_collateral_ratio,
+							partial_liquidation_target_ratio: None,
+							stability_fee_per_sec: stability_fee_per_sec
+								.map(|v| FractionalRate::try_from(v).expect("stability_fee_per_sec out of bound")),
 						},
 					);
 				},
@@ -373,10 +618,37 @@ pub mod module {
 	}
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Accumulate stability fee of all collateral types by updating their
+		/// debit exchange rates.
+		fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
+			let now_as_secs: u64 = T::UnixTime::now().as_secs();
+			let last_accumulation_secs = Self::last_accumulation_secs();
+
+			let mut count: u32 = 0;
+			// the first accumulation only records the time
+			if !T::EcdpEmergencyShutdown::is_shutdown() && !last_accumulation_secs.is_zero() {
+				for currency_id in Self::get_collateral_currency_ids() {
+					count += 1;
+					if let Err(e) = Self::accumulate_stability_fee(currency_id, now_as_secs, last_accumulation_secs) {
+						log::warn!(
+							target: "ecdp-ussd-engine",
+							"accumulate_stability_fee: failed to accumulate stability fee for {:?}: {:?}",
+							currency_id,
+							e,
+						);
+					}
+				}
+			}
+			LastAccumulationSecs::<T>::put(now_as_secs);
+
+			<T as Config>::WeightInfo::on_initialize(count)
+		}
+
 		/// Runs after every block. Start offchain worker to check ECDP and
 		/// submit unsigned tx to trigger liquidation or settlement.
 		fn offchain_worker(now: BlockNumberFor<T>) {
@@ -401,27 +673,47 @@ pub mod module {
 	impl<T: Config> Pallet<T> {
 		/// Liquidate unsafe ECDP
 		///
-		/// The dispatch origin of this call must be _None_.
+		/// The dispatch origin of this call must be _None_ or _Signed_ by a keeper. A keeper
+		/// is rewarded out of the liquidation penalty collected by the liquidation, nothing if
+		/// the collateral went to auction, and refunded the fee if the liquidation succeeds,
+		/// otherwise the fee is charged.
 		///
 		/// - `currency_id`: ECDP's collateral type.
 		/// - `who`: ECDP's owner.
+		///
+		/// An ECDP in a multi-collateral basket liquidates the whole basket, so the weight
+		/// covers `T::MaxBasketCurrencies` liquidations.
 		#[pallet::call_index(0)]
-		#[pallet::weight(<T as Config>::WeightInfo::liquidate_by_auction(<T as Config>::EcdpUssdTreasury::max_auction()))]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::liquidate_by_auction(<T as Config>::EcdpUssdTreasury::max_auction())
+				.saturating_mul(T::MaxBasketCurrencies::get().max(1).into())
+		)]
 		pub fn liquidate(
 			origin: OriginFor<T>,
 			currency_id: CurrencyId,
 			who: <T::Lookup as StaticLookup>::Source,
 		) -> DispatchResultWithPostInfo {
-			ensure_none(origin)?;
+			let maybe_keeper = ensure_none_or_keeper::<T>(origin)?;
 			let who = T::Lookup::lookup(who)?;
 			ensure!(!T::EcdpEmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
-			let consumed_weight: Weight = Self::liquidate_unsafe_cdp(who, currency_id)?;
+			let surplus_before = <T as Config>::EcdpUssdTreasury::get_surplus_pool();
+			let debit_before = <T as Config>::EcdpUssdTreasury::get_debit_pool();
+			let (consumed_weight, penalty) = Self::do_liquidate_unsafe_cdp(who.clone(), currency_id)?;
+			if let Some(keeper) = maybe_keeper {
+				// collateral sold to auctions has not paid the penalty yet
+				let collected_penalty = <T as Config>::EcdpUssdTreasury::get_surplus_pool()
+					.saturating_sub(surplus_before)
+					.saturating_sub(<T as Config>::EcdpUssdTreasury::get_debit_pool().saturating_sub(debit_before));
+				Self::reward_keeper(&keeper, currency_id, &who, penalty.min(collected_penalty));
+				return Ok((Some(consumed_weight), Pays::No).into());
+			}
 			Ok(Some(consumed_weight).into())
 		}
 
 		/// Settle ECDP that has debit after system shutdown
 		///
-		/// The dispatch origin of this call must be _None_.
+		/// The dispatch origin of this call must be _None_ or _Signed_ by a keeper. A keeper
+		/// is refunded the fee if the settlement succeeds, otherwise the fee is charged.
 		///
 		/// - `currency_id`: ECDP's collateral type.
 		/// - `who`: ECDP's owner.
@@ -431,12 +723,15 @@ pub mod module {
 			origin: OriginFor<T>,
 			currency_id: CurrencyId,
 			who: <T::Lookup as StaticLookup>::Source,
-		) -> DispatchResult {
-			ensure_none(origin)?;
+		) -> DispatchResultWithPostInfo {
+			let maybe_keeper = ensure_none_or_keeper::<T>(origin)?;
 			let who = T::Lookup::lookup(who)?;
 			ensure!(T::EcdpEmergencyShutdown::is_shutdown(), Error::<T>::MustAfterShutdown);
 			Self::settle_cdp_has_debit(who, currency_id)?;
-			Ok(())
+			if maybe_keeper.is_some() {
+				return Ok(Pays::No.into());
+			}
+			Ok(().into())
 		}
 
 		/// Update parameters related to risk management of ECDP under specific
@@ -452,6 +747,10 @@ pub mod module {
 		/// - `required_collateral_ratio`: required collateral ratio, `None` means do not update,
 		///   `Some(None)` means update it to `None`.
 		/// - `maximum_total_debit_value`: maximum total debit value.
+		/// - `partial_liquidation_target_ratio`: partial liquidation target ratio, `None` means do
+		///   not update, `Some(None)` means update it to `None`.
+		/// - `stability_fee_per_sec`: stability fee rate per second, `None` means do not update,
+		///   `Some(None)` means update it to `None`.
 		#[pallet::call_index(2)]
 		#[pallet::weight((<T as Config>::WeightInfo::set_collateral_params(), DispatchClass::Operational))]
 		pub fn set_collateral_params(
@@ -461,10 +760,26 @@ pub mod module {
 			liquidation_penalty: ChangeOptionRate,
 			required_collateral_ratio: ChangeOptionRatio,
 			maximum_total_debit_value: ChangeBalance,
+			partial_liquidation_target_ratio: ChangeOptionRatio,
+			stability_fee_per_sec: ChangeOptionRate,
 		) -> DispatchResult {
 			T::UpdateOrigin::ensure_origin(origin)?;
 
 			let mut collateral_params = Self::collateral_params(currency_id).unwrap_or_default();
+			if let Change::NewValue(maybe_rate) = stability_fee_per_sec {
+				match (collateral_params.stability_fee_per_sec.as_mut(), maybe_rate) {
+					(Some(existing), Some(rate)) => existing.try_set(rate).map_err(|_| Error::<T>::InvalidRate)?,
+					(None, Some(rate)) => {
+						let fractional_rate = FractionalRate::try_from(rate).map_err(|_| Error::<T>::InvalidRate)?;
+						collateral_params.stability_fee_per_sec = Some(fractional_rate);
+					}
+					_ => collateral_params.stability_fee_per_sec = None,
+				}
+				Self::deposit_event(Event::StabilityFeePerSecUpdated {
+					collateral_type: currency_id,
+					new_stability_fee_per_sec: maybe_rate,
+				});
+			}
 			if let Change::NewValue(update) = liquidation_ratio {
 				collateral_params.liquidation_ratio = update;
 				Self::deposit_event(Event::LiquidationRatioUpdated {
@@ -500,6 +815,18 @@ pub mod module {
 					new_total_debit_value: val,
 				});
 			}
+			if let Change::NewValue(update) = partial_liquidation_target_ratio {
+				collateral_params.partial_liquidation_target_ratio = update;
+				Self::deposit_event(Event::PartialLiquidationTargetRatioUpdated {
+					collateral_type: currency_id,
+					new_partial_liquidation_target_ratio: update,
+				});
+			}
+			if let Some(target_ratio) = collateral_params.partial_liquidation_target_ratio {
+				let liquidation_ratio =
+					collateral_params.liquidation_ratio.unwrap_or_else(T::DefaultLiquidationRatio::get);
+				ensure!(target_ratio > liquidation_ratio, Error::<T>::InvalidPartialLiquidationTargetRatio);
+			}
 			CollateralParams::<T>::insert(currency_id, collateral_params);
 			Ok(())
 		}
@@ -523,6 +850,173 @@ pub mod module {
 			Self::deposit_event(Event::LiquidationContractDeregistered { address });
 			Ok(())
 		}
+
+		/// Execute the liquidation protection rule of the ECDP whose collateral
+		/// ratio is below the trigger ratio of the rule
+		///
+		/// The dispatch origin of this call must be _None_.
+		///
+		/// - `currency_id`: ECDP's collateral type.
+		/// - `who`: ECDP's owner.
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T as Config>::WeightInfo::protect())]
+		pub fn protect(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(!T::EcdpEmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			Self::protect_cdp(who, currency_id)?;
+			Ok(())
+		}
+
+		/// Update parameters of USSD flash minting
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `ceiling`: the maximum outstanding flash minted amount.
+		/// - `fee_rate`: the fee rate charged on the flash minted amount, must not exceed one.
+		#[pallet::call_index(6)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_flash_mint_params(), DispatchClass::Operational))]
+		pub fn set_flash_mint_params(
+			origin: OriginFor<T>,
+			ceiling: ChangeBalance,
+			fee_rate: ChangeRate,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			if let Change::NewValue(new_ceiling) = ceiling {
+				FlashMintCeiling::<T>::put(new_ceiling);
+				Self::deposit_event(Event::FlashMintCeilingUpdated { new_ceiling });
+			}
+			if let Change::NewValue(new_fee_rate) = fee_rate {
+				ensure!(new_fee_rate <= Rate::one(), Error::<T>::InvalidRate);
+				FlashMintFeeRate::<T>::put(new_fee_rate);
+				Self::deposit_event(Event::FlashMintFeeRateUpdated { new_fee_rate });
+			}
+			Ok(())
+		}
+
+		/// Flash mint USSD to the caller and dispatch `calls` with it, the minted
+		/// amount plus the fee must be held by the caller after the calls, or the
+		/// whole extrinsic is reverted.
+		///
+		/// The dispatch origin of this call must be `Signed` by the transactor.
+		///
+		/// - `amount`: the amount of USSD to flash mint.
+		/// - `calls`: the calls dispatched with the caller as origin.
+		#[pallet::call_index(7)]
+		#[pallet::weight({
+			let dispatch_weight = calls.iter()
+				.map(|call| call.get_dispatch_info().weight)
+				.fold(Weight::zero(), |total, weight| total.saturating_add(weight));
+			<T as Config>::WeightInfo::flash_mint(calls.len() as u32).saturating_add(dispatch_weight)
+		})]
+		pub fn flash_mint(origin: OriginFor<T>, amount: Balance, calls: Vec<CallOf<T>>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(calls.len() <= T::MaxFlashMintCalls::get() as usize, Error::<T>::TooManyFlashMintCalls);
+
+			Self::do_flash_mint(&who, amount, |_| {
+				for call in calls {
+					call.dispatch(frame_system::RawOrigin::Signed(who.clone()).into()).map_err(|e| e.error)?;
+				}
+				Ok(())
+			})?;
+			Ok(())
+		}
+
+		/// Flash mint USSD to the `receiver` contract and call its `onFlashMint`
+		/// with `data`, the contract must hold the minted amount plus the fee when
+		/// it returns, or the whole extrinsic is reverted.
+		///
+		/// The dispatch origin of this call must be `Signed` by the transactor.
+		///
+		/// - `amount`: the amount of USSD to flash mint.
+		/// - `receiver`: the receiver contract address.
+		/// - `data`: arbitrary data passed to the receiver contract.
+		#[pallet::call_index(8)]
+		#[pallet::weight(<T as Config>::WeightInfo::flash_mint_to_contract())]
+		pub fn flash_mint_to_contract(
+			origin: OriginFor<T>,
+			amount: Balance,
+			receiver: EvmAddress,
+			data: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			<Self as FlashMint<T::AccountId, Balance>>::flash_mint_to_contract(&who, receiver, amount, data)?;
+			Ok(())
+		}
+
+		/// Update the reward of keepers who liquidate unsafe ECDPs
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `reward_rate`: the rate of the liquidation penalty rewarded to the keeper, must not
+		///   exceed one.
+		/// - `max_reward`: the maximum reward of a keeper for a single liquidation.
+		#[pallet::call_index(9)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_keeper_reward_params(), DispatchClass::Operational))]
+		pub fn set_keeper_reward_params(
+			origin: OriginFor<T>,
+			reward_rate: ChangeRate,
+			max_reward: ChangeBalance,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			if let Change::NewValue(new_reward_rate) = reward_rate {
+				ensure!(new_reward_rate <= Rate::one(), Error::<T>::InvalidRate);
+				KeeperRewardRate::<T>::put(new_reward_rate);
+				Self::deposit_event(Event::KeeperRewardRateUpdated { new_reward_rate });
+			}
+			if let Change::NewValue(new_max_reward) = max_reward {
+				MaxKeeperReward::<T>::put(new_max_reward);
+				Self::deposit_event(Event::MaxKeeperRewardUpdated { new_max_reward });
+			}
+			Ok(())
+		}
+
+		/// Update the haircut applied to the collateral value of specific
+		/// collateral type in multi-collateral baskets
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `haircut`: the haircut, must be above zero and below one. `None` means the
+		///   collateral type cannot join baskets, only allowed when no basket holds it.
+		#[pallet::call_index(10)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_basket_haircut(), DispatchClass::Operational))]
+		pub fn set_basket_haircut(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			haircut: Option<Ratio>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			if let Some(new_haircut) = haircut {
+				ensure!(
+					CollateralParams::<T>::contains_key(currency_id),
+					Error::<T>::InvalidCollateralType,
+				);
+				ensure!(
+					!new_haircut.is_zero() && new_haircut < Ratio::one(),
+					Error::<T>::InvalidRate
+				);
+				BasketHaircuts::<T>::insert(currency_id, new_haircut);
+			} else {
+				ensure!(
+					Self::basket_members(currency_id).is_zero(),
+					Error::<T>::BasketHaircutInUse
+				);
+				BasketHaircuts::<T>::remove(currency_id);
+			}
+			Self::deposit_event(Event::BasketHaircutUpdated {
+				collateral_type: currency_id,
+				new_haircut: haircut,
+			});
+			Ok(())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -535,9 +1029,10 @@ pub mod module {
 					let account = T::Lookup::lookup(who.clone())?;
 					let EcdpPosition { collateral, debit } = <LoansOf<T>>::positions(currency_id, &account);
 					if !matches!(
-						Self::check_cdp_status(*currency_id, collateral, debit),
+						Self::check_account_cdp_status(&account, *currency_id, collateral, debit),
 						CDPStatus::Unsafe
 					) || T::EcdpEmergencyShutdown::is_shutdown()
+						|| Self::is_liquidation_paused(&account, *currency_id)
 					{
 						return InvalidTransaction::Stale.into();
 					}
@@ -563,6 +1058,23 @@ pub mod module {
 						.propagate(true)
 						.build()
 				}
+				Call::protect { currency_id, who } => {
+					let account = T::Lookup::lookup(who.clone())?;
+					let EcdpPosition { collateral, debit } = <LoansOf<T>>::positions(currency_id, &account);
+					if Self::get_protection_amount(*currency_id, &account, collateral, debit).is_none()
+						|| T::EcdpEmergencyShutdown::is_shutdown()
+					{
+						return InvalidTransaction::Stale.into();
+					}
+
+					// protection takes precedence over liquidation of the same ECDP
+					ValidTransaction::with_tag_prefix("EcdpUssdEngineOffchainWorker")
+						.priority(T::UnsignedPriority::get().saturating_add(1))
+						.and_provides((<frame_system::Pallet<T>>::block_number(), b"protect", currency_id, who))
+						.longevity(64_u64)
+						.propagate(true)
+						.build()
+				}
 				_ => InvalidTransaction::Call.into(),
 			}
 		}
@@ -585,6 +1097,21 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	fn submit_unsigned_protection_tx(currency_id: CurrencyId, who: T::AccountId) {
+		let who = T::Lookup::unlookup(who);
+		let call = Call::<T>::protect {
+			currency_id,
+			who: who.clone(),
+		};
+		if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+			log::info!(
+				target: "ecdp-ussd-engine offchain worker",
+				"submit unsigned protection tx for \nCDP - AccountId {:?} CurrencyId {:?} \nfailed!",
+				who, currency_id,
+			);
+		}
+	}
+
 	fn submit_unsigned_settlement_tx(currency_id: CurrencyId, who: T::AccountId) {
 		let who = T::Lookup::unlookup(who);
 		let call = Call::<T>::settle {
@@ -663,9 +1190,15 @@ impl<T: Config> Pallet<T> {
 
 		#[allow(clippy::while_let_on_iterator)]
 		while let Some((who, EcdpPosition { collateral, debit })) = map_iterator.next() {
+			if !is_shutdown && Self::get_protection_amount(currency_id, &who, collateral, debit).is_some() {
+				// execute the liquidation protection rule before liquidation, the liquidation tx
+				// is still submitted in case the protection fails.
+				Self::submit_unsigned_protection_tx(currency_id, who.clone());
+			}
 			if !is_shutdown
+				&& !Self::is_liquidation_paused(&who, currency_id)
 				&& matches!(
-					Self::check_cdp_status(currency_id, collateral, debit),
+					Self::check_account_cdp_status(&who, currency_id, collateral, debit),
 					CDPStatus::Unsafe
 				) {
 				// liquidate unsafe CDPs before emergency shutdown occurs
@@ -736,6 +1269,165 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Check the status of `who`'s ECDP under `currency_id`, ECDPs in the
+	/// multi-collateral basket of `who` are checked across the whole basket.
+	pub fn check_account_cdp_status(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral_amount: Balance,
+		debit_amount: Balance,
+	) -> CDPStatus {
+		if Self::is_in_basket(who, currency_id) {
+			Self::check_basket_status(who)
+		} else {
+			Self::check_cdp_status(currency_id, collateral_amount, debit_amount)
+		}
+	}
+
+	/// Check the status of the multi-collateral basket of `who`, it is unsafe
+	/// when its haircut collateral value is below its debit value.
+	pub fn check_basket_status(who: &T::AccountId) -> CDPStatus {
+		match Self::get_basket_values(who, &Self::baskets(who), None) {
+			Ok((collateral_value, debit_value, _)) => {
+				if collateral_value < debit_value {
+					CDPStatus::Unsafe
+				} else {
+					CDPStatus::Safe
+				}
+			}
+			Err(e) => CDPStatus::ChecksFailed(e),
+		}
+	}
+
+	pub fn is_in_basket(who: &T::AccountId, currency_id: CurrencyId) -> bool {
+		Self::baskets(who).contains(&currency_id)
+	}
+
+	/// Liquidation of `who`'s ECDP under `currency_id` is paused while the
+	/// feed price of its collateral, or of any collateral in its basket, is
+	/// stale.
+	fn is_liquidation_paused(who: &T::AccountId, currency_id: CurrencyId) -> bool {
+		let basket = Self::baskets(who);
+		if basket.contains(&currency_id) {
+			basket.iter().any(|currency_id| T::PriceSource::is_price_stale(*currency_id))
+		} else {
+			T::PriceSource::is_price_stale(currency_id)
+		}
+	}
+
+	/// Return the haircut collateral value, the debit value and the debit
+	/// value scaled by the required collateral ratios of the multi-collateral
+	/// basket of `who` made of `currencies`. The ECDP under the collateral
+	/// type of `maybe_position` is replaced by its collateral and debit
+	/// amounts.
+	fn get_basket_values(
+		who: &T::AccountId,
+		currencies: &[CurrencyId],
+		maybe_position: Option<(CurrencyId, Balance, Balance)>,
+	) -> Result<(Balance, Balance, Balance), DispatchError> {
+		let stable_currency_id = T::GetUSSDCurrencyId::get();
+		let mut collateral_value: Balance = Zero::zero();
+		let mut debit_value: Balance = Zero::zero();
+		let mut required_value: Balance = Zero::zero();
+
+		for currency_id in currencies {
+			let (collateral, debit) = match maybe_position {
+				Some((position_currency_id, collateral, debit)) if position_currency_id == *currency_id => {
+					(collateral, debit)
+				}
+				_ => {
+					let EcdpPosition { collateral, debit } = <LoansOf<T>>::positions(currency_id, who);
+					(collateral, debit)
+				}
+			};
+			let haircut = Self::basket_haircuts(currency_id).ok_or(Error::<T>::InvalidBasket)?;
+
+			if !collateral.is_zero() {
+				let price = T::PriceSource::get_relative_price(*currency_id, stable_currency_id)
+					.ok_or(Error::<T>::InvalidFeedPrice)?;
+				collateral_value = collateral_value.saturating_add(
+					Ratio::one()
+						.saturating_sub(haircut)
+						.saturating_mul_int(price.saturating_mul_int(collateral)),
+				);
+			}
+			let position_debit_value = Self::get_debit_value(*currency_id, debit);
+			let required_ratio = Self::required_collateral_ratio(*currency_id)?.unwrap_or_else(Ratio::one);
+			debit_value = debit_value.saturating_add(position_debit_value);
+			required_value = required_value.saturating_add(required_ratio.saturating_mul_int(position_debit_value));
+		}
+
+		Ok((collateral_value, debit_value, required_value))
+	}
+
+	/// Check the multi-collateral basket of `who` made of `currencies`, its
+	/// haircut collateral value must cover its debit value, and also the
+	/// debit value at the required collateral ratios if `check_required_ratio`.
+	fn check_basket_valid(
+		who: &T::AccountId,
+		currencies: &[CurrencyId],
+		maybe_position: Option<(CurrencyId, Balance, Balance)>,
+		check_required_ratio: bool,
+	) -> DispatchResult {
+		let (collateral_value, debit_value, required_value) =
+			Self::get_basket_values(who, currencies, maybe_position)?;
+		if !debit_value.is_zero() {
+			if check_required_ratio {
+				ensure!(
+					collateral_value >= required_value,
+					Error::<T>::BelowRequiredCollateralRatio
+				);
+			}
+			ensure!(collateral_value >= debit_value, Error::<T>::BelowLiquidationRatio);
+			ensure!(
+				debit_value >= T::MinimumDebitValue::get(),
+				Error::<T>::RemainDebitValueTooSmall,
+			);
+		}
+		Ok(())
+	}
+
+	/// Set the multi-collateral basket of `who` to the ECDPs under
+	/// `currencies`, whose collateral is sold in the given order on
+	/// liquidation, or dissolve it if `currencies` is empty. The ECDPs
+	/// leaving the basket must be valid on their own.
+	pub fn set_basket(who: &T::AccountId, currencies: Vec<CurrencyId>) -> DispatchResult {
+		let basket: BoundedVec<CurrencyId, T::MaxBasketCurrencies> =
+			currencies.try_into().map_err(|_| Error::<T>::TooManyBasketCurrencies)?;
+		for (i, currency_id) in basket.iter().enumerate() {
+			ensure!(
+				!basket[..i].contains(currency_id)
+					&& BasketHaircuts::<T>::contains_key(currency_id)
+					&& !LiquidationProtections::<T>::contains_key(currency_id, who),
+				Error::<T>::InvalidBasket
+			);
+		}
+
+		for currency_id in Self::baskets(who).iter().filter(|c| !basket.contains(c)) {
+			let EcdpPosition { collateral, debit } = <LoansOf<T>>::positions(currency_id, who);
+			Self::check_position_valid(*currency_id, collateral, debit, true)?;
+		}
+		Self::check_basket_valid(who, &basket, None, true)?;
+
+		let old_basket = Self::baskets(who);
+		for currency_id in old_basket.iter().filter(|c| !basket.contains(c)) {
+			BasketMembers::<T>::mutate(currency_id, |members| *members = members.saturating_sub(1));
+		}
+		for currency_id in basket.iter().filter(|c| !old_basket.contains(c)) {
+			BasketMembers::<T>::mutate(currency_id, |members| *members = members.saturating_add(1));
+		}
+		if basket.is_empty() {
+			Baskets::<T>::remove(who);
+		} else {
+			Baskets::<T>::insert(who, &basket);
+		}
+		Self::deposit_event(Event::BasketUpdated {
+			owner: who.clone(),
+			collateral_types: basket.into_inner(),
+		});
+		Ok(())
+	}
+
 	pub fn maximum_total_debit_value(currency_id: CurrencyId) -> Result<Balance, DispatchError> {
 		let params = Self::collateral_params(currency_id).ok_or(Error::<T>::InvalidCollateralType)?;
 		Ok(params.maximum_total_debit_value)
@@ -759,6 +1451,46 @@ impl<T: Config> Pallet<T> {
 			.unwrap_or_else(|| T::DefaultLiquidationPenalty::get().into_inner()))
 	}
 
+	pub fn get_stability_fee_per_sec(currency_id: CurrencyId) -> Result<Rate, DispatchError> {
+		let params = Self::collateral_params(currency_id).ok_or(Error::<T>::InvalidCollateralType)?;
+		Ok(params.stability_fee_per_sec.map(|v| v.into_inner()).unwrap_or_default())
+	}
+
+	/// The rate by which the debit exchange rate grows in `secs` seconds when
+	/// compounding `rate_per_sec` every second.
+	pub fn compound_stability_fee_rate(rate_per_sec: Rate, secs: u64) -> Rate {
+		rate_per_sec
+			.saturating_add(Rate::one())
+			.saturating_pow(secs.unique_saturated_into())
+			.saturating_sub(Rate::one())
+	}
+
+	/// Grow the debit exchange rate of `currency_id` by the stability fee
+	/// accrued since `last_accumulation_secs`, and issue the stablecoin of
+	/// the accrued fee to the surplus pool of ECDP USSD treasury.
+	fn accumulate_stability_fee(currency_id: CurrencyId, now_secs: u64, last_accumulation_secs: u64) -> DispatchResult {
+		let interval_secs = now_secs.saturating_sub(last_accumulation_secs);
+		let rate_to_accumulate =
+			Self::compound_stability_fee_rate(Self::get_stability_fee_per_sec(currency_id)?, interval_secs);
+		let total_debits = <LoansOf<T>>::total_positions(currency_id).debit;
+
+		if !rate_to_accumulate.is_zero() && !total_debits.is_zero() {
+			let debit_exchange_rate = Self::get_debit_exchange_rate(currency_id);
+			let debit_exchange_rate_increment = debit_exchange_rate.saturating_mul(rate_to_accumulate);
+			let issued_stable_coin_balance = debit_exchange_rate_increment.saturating_mul_int(total_debits);
+
+			// issue stablecoin to surplus pool
+			<T as Config>::EcdpUssdTreasury::on_system_surplus(issued_stable_coin_balance)?;
+
+			// update exchange rate when issue success
+			DebitExchangeRate::<T>::insert(
+				currency_id,
+				debit_exchange_rate.saturating_add(debit_exchange_rate_increment),
+			);
+		}
+		Ok(())
+	}
+
 	pub fn get_debit_exchange_rate(currency_id: CurrencyId) -> ExchangeRate {
 		Self::debit_exchange_rate(currency_id).unwrap_or_else(T::DefaultDebitExchangeRate::get)
 	}
@@ -932,7 +1664,7 @@ impl<T: Config> Pallet<T> {
 
 		let EcdpPosition { collateral, debit } = <LoansOf<T>>::positions(currency_id, who);
 		// check the ECDP if is still at valid risk
-		Self::check_position_valid(currency_id, collateral, debit, false)?;
+		Self::check_account_position_valid(who, currency_id, collateral, debit, false)?;
 		// debit cap check due to new issued stable coin
 		Self::check_debit_cap(currency_id, <LoansOf<T>>::total_positions(currency_id).debit)?;
 		Ok(())
@@ -1028,7 +1760,8 @@ impl<T: Config> Pallet<T> {
 		<T as Config>::EcdpUssdTreasury::burn_debit(&loans_module_account, decrease_debit_value)?;
 
 		// check the ECDP if it is still at valid risk.
-		Self::check_position_valid(
+		Self::check_account_position_valid(
+			who,
 			currency_id,
 			collateral.saturating_sub(decrease_collateral),
 			debit.saturating_sub(decrease_debit_balance),
@@ -1039,6 +1772,10 @@ impl<T: Config> Pallet<T> {
 
 	// settle cdp has debit when emergency shutdown
 	pub fn settle_cdp_has_debit(who: T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+		if Self::is_in_basket(&who, currency_id) {
+			return Self::settle_basket_has_debit(who);
+		}
+
 		let EcdpPosition { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
 		ensure!(!debit.is_zero(), Error::<T>::NoDebitValue);
 
@@ -1068,6 +1805,52 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Settle the multi-collateral basket of `who` when emergency shutdown:
+	/// confiscate all debit of the basket, and the collateral worth the debit
+	/// value at the settle price in the liquidation order of the basket.
+	fn settle_basket_has_debit(who: T::AccountId) -> DispatchResult {
+		let basket = Self::baskets(&who);
+		let mut remain_debit_value: Balance = Zero::zero();
+		for currency_id in basket.iter() {
+			let debit = <LoansOf<T>>::positions(currency_id, &who).debit;
+			remain_debit_value = remain_debit_value.saturating_add(Self::get_debit_value(*currency_id, debit));
+		}
+		ensure!(!remain_debit_value.is_zero(), Error::<T>::NoDebitValue);
+
+		let stable_currency_id = T::GetUSSDCurrencyId::get();
+		for currency_id in basket.iter() {
+			let EcdpPosition { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
+			let confiscate_collateral_amount = if remain_debit_value.is_zero() {
+				Zero::zero()
+			} else {
+				let settle_price: Price = T::PriceSource::get_relative_price(stable_currency_id, *currency_id)
+					.ok_or(Error::<T>::InvalidFeedPrice)?;
+				let amount = sp_std::cmp::min(settle_price.saturating_mul_int(remain_debit_value), collateral);
+				let covered_value = settle_price
+					.reciprocal()
+					.map_or(remain_debit_value, |price| price.saturating_mul_int(amount));
+				remain_debit_value = remain_debit_value.saturating_sub(covered_value);
+				amount
+			};
+			if confiscate_collateral_amount.is_zero() && debit.is_zero() {
+				continue;
+			}
+
+			if let CurrencyId::Erc20(_) = currency_id {
+				T::EVMBridge::set_origin(T::SettleErc20EvmOrigin::get());
+			}
+
+			<LoansOf<T>>::confiscate_collateral_and_debit(&who, *currency_id, confiscate_collateral_amount, debit)?;
+
+			if let CurrencyId::Erc20(_) = currency_id {
+				T::EVMBridge::kill_origin();
+			}
+		}
+
+		Self::deposit_event(Event::SettleBasketInDebit { owner: who });
+		Ok(())
+	}
+
 	// close cdp has debit by swap collateral to exact debit
 	#[transactional]
 	pub fn close_cdp_has_debit_by_dex(
@@ -1075,6 +1858,7 @@ impl<T: Config> Pallet<T> {
 		currency_id: CurrencyId,
 		max_collateral_amount: Balance,
 	) -> DispatchResult {
+		ensure!(!Self::is_in_basket(&who, currency_id), Error::<T>::CDPInBasket);
 		let EcdpPosition { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
 		ensure!(!debit.is_zero(), Error::<T>::NoDebitValue);
 		ensure!(
@@ -1113,6 +1897,22 @@ impl<T: Config> Pallet<T> {
 
 	// liquidate unsafe cdp
 	pub fn liquidate_unsafe_cdp(who: T::AccountId, currency_id: CurrencyId) -> Result<Weight, DispatchError> {
+		Self::do_liquidate_unsafe_cdp(who, currency_id).map(|(weight, _)| weight)
+	}
+
+	/// Liquidate the unsafe ECDP, return the consumed weight and the liquidation
+	/// penalty value.
+	fn do_liquidate_unsafe_cdp(who: T::AccountId, currency_id: CurrencyId) -> Result<(Weight, Balance), DispatchError> {
+		// liquidation is paused while the feed price of the collateral is stale
+		ensure!(
+			!Self::is_liquidation_paused(&who, currency_id),
+			Error::<T>::StaleFeedPrice
+		);
+
+		if Self::is_in_basket(&who, currency_id) {
+			return Self::do_liquidate_unsafe_basket(who);
+		}
+
 		let EcdpPosition { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
 
 		// ensure the cdp is unsafe
@@ -1124,13 +1924,35 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::MustBeUnsafe
 		);
 
-		// confiscate all collateral and debit of unsafe cdp to cdp treasury
+		// confiscate the part of unsafe cdp needed to restore the partial liquidation target ratio,
+		// or all collateral and debit of it, to cdp treasury
+		let (collateral, debit) =
+			Self::get_partial_liquidation_amounts(currency_id, collateral, debit).unwrap_or((collateral, debit));
 		<LoansOf<T>>::confiscate_collateral_and_debit(&who, currency_id, collateral, debit)?;
 
 		let bad_debt_value = Self::get_debit_value(currency_id, debit);
 		let liquidation_penalty = Self::get_liquidation_penalty(currency_id)?;
 		let target_stable_amount = liquidation_penalty.saturating_mul_acc_int(bad_debt_value);
+		Self::liquidate_confiscated_collateral(&who, currency_id, collateral, target_stable_amount)?;
 
+		Self::deposit_event(Event::LiquidateUnsafeCDP {
+			collateral_type: currency_id,
+			owner: who,
+			collateral_amount: collateral,
+			bad_debt_value,
+			target_amount: target_stable_amount,
+		});
+		Ok((T::WeightInfo::liquidate_by_dex(), target_stable_amount.saturating_sub(bad_debt_value)))
+	}
+
+	/// Liquidate the collateral confiscated from `who`'s ECDP to get
+	/// `target_stable_amount` of stable coin for ECDP USSD treasury.
+	fn liquidate_confiscated_collateral(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral: Balance,
+		target_stable_amount: Balance,
+	) -> DispatchResult {
 		match currency_id {
 			CurrencyId::DexShare(dex_share_0, dex_share_1) => {
 				let token_0: CurrencyId = dex_share_0.into();
@@ -1152,7 +1974,7 @@ impl<T: Config> Pallet<T> {
 					// these's stable refund
 					if existing_stable > target_stable_amount {
 						<T as Config>::EcdpUssdTreasury::withdraw_collateral(
-							&who,
+							who,
 							stable_currency_id,
 							existing_stable
 								.checked_sub(target_stable_amount)
@@ -1161,28 +1983,290 @@ impl<T: Config> Pallet<T> {
 					}
 
 					let remain_target = target_stable_amount.saturating_sub(existing_stable);
-					Self::handle_liquidated_collateral(&who, need_handle_currency, handle_amount, remain_target)?;
+					Self::handle_liquidated_collateral(who, need_handle_currency, handle_amount, remain_target)?;
 				} else {
 					// token_0 and token_1 each take half target_stable
 					let target_0 = target_stable_amount / 2;
 					let target_1 = target_stable_amount.saturating_sub(target_0);
-					Self::handle_liquidated_collateral(&who, token_0, amount_0, target_0)?;
-					Self::handle_liquidated_collateral(&who, token_1, amount_1, target_1)?;
+					Self::handle_liquidated_collateral(who, token_0, amount_0, target_0)?;
+					Self::handle_liquidated_collateral(who, token_1, amount_1, target_1)?;
 				}
 			}
 			_ => {
-				Self::handle_liquidated_collateral(&who, currency_id, collateral, target_stable_amount)?;
+				Self::handle_liquidated_collateral(who, currency_id, collateral, target_stable_amount)?;
 			}
 		}
+		Ok(())
+	}
 
-		Self::deposit_event(Event::LiquidateUnsafeCDP {
-			collateral_type: currency_id,
+	/// Liquidate the unsafe multi-collateral basket of `who`: confiscate all
+	/// debit of the basket, and sell the collateral in the liquidation order
+	/// of the basket until the debit value and the liquidation penalty are
+	/// covered. Return the consumed weight and the liquidation penalty value.
+	fn do_liquidate_unsafe_basket(who: T::AccountId) -> Result<(Weight, Balance), DispatchError> {
+		ensure!(
+			matches!(Self::check_basket_status(&who), CDPStatus::Unsafe),
+			Error::<T>::MustBeUnsafe
+		);
+
+		let basket = Self::baskets(&who);
+		let mut bad_debt_value: Balance = Zero::zero();
+		let mut target_stable_amount: Balance = Zero::zero();
+		for currency_id in basket.iter() {
+			let debit = <LoansOf<T>>::positions(currency_id, &who).debit;
+			if debit.is_zero() {
+				continue;
+			}
+			let debit_value = Self::get_debit_value(*currency_id, debit);
+			let liquidation_penalty = Self::get_liquidation_penalty(*currency_id)?;
+			<LoansOf<T>>::confiscate_collateral_and_debit(&who, *currency_id, Zero::zero(), debit)?;
+			bad_debt_value = bad_debt_value.saturating_add(debit_value);
+			target_stable_amount =
+				target_stable_amount.saturating_add(liquidation_penalty.saturating_mul_acc_int(debit_value));
+		}
+
+		let stable_currency_id = T::GetUSSDCurrencyId::get();
+		let mut weight = Weight::zero();
+		let mut remain_target = target_stable_amount;
+		for currency_id in basket.iter() {
+			if remain_target.is_zero() {
+				break;
+			}
+			let collateral = <LoansOf<T>>::positions(currency_id, &who).collateral;
+			if collateral.is_zero() {
+				continue;
+			}
+			let price = T::PriceSource::get_relative_price(*currency_id, stable_currency_id)
+				.ok_or(Error::<T>::InvalidFeedPrice)?;
+			let target = remain_target.min(price.saturating_mul_int(collateral));
+
+			// the collateral not needed for the target is refunded to the owner
+			<LoansOf<T>>::confiscate_collateral_and_debit(&who, *currency_id, collateral, Zero::zero())?;
+			Self::liquidate_confiscated_collateral(&who, *currency_id, collateral, target)?;
+			remain_target = remain_target.saturating_sub(target);
+			weight = weight.saturating_add(T::WeightInfo::liquidate_by_auction(
+				<T as Config>::EcdpUssdTreasury::max_auction(),
+			));
+		}
+
+		let target_amount = target_stable_amount.saturating_sub(remain_target);
+		Self::deposit_event(Event::LiquidateUnsafeBasket {
 			owner: who,
-			collateral_amount: collateral,
 			bad_debt_value,
-			target_amount: target_stable_amount,
+			target_amount,
 		});
-		Ok(T::WeightInfo::liquidate_by_dex())
+		Ok((weight, target_amount.saturating_sub(bad_debt_value)))
+	}
+
+	/// Reward the keeper out of the liquidation `penalty` already collected by
+	/// ECDP USSD treasury, limited by the maximum keeper reward and the surplus
+	/// pool of ECDP USSD treasury.
+	fn reward_keeper(keeper: &T::AccountId, currency_id: CurrencyId, owner: &T::AccountId, penalty: Balance) {
+		let reward = Self::keeper_reward_rate()
+			.saturating_mul_int(penalty)
+			.min(Self::max_keeper_reward())
+			.min(<T as Config>::EcdpUssdTreasury::get_surplus_pool());
+		if reward.is_zero() {
+			return;
+		}
+
+		match <T as Config>::EcdpUssdTreasury::withdraw_surplus(keeper, reward) {
+			Ok(()) => Self::deposit_event(Event::KeeperRewarded {
+				keeper: keeper.clone(),
+				collateral_type: currency_id,
+				owner: owner.clone(),
+				reward,
+			}),
+			Err(e) => log::warn!(
+				target: "ecdp-ussd-engine",
+				"reward_keeper: failed to reward keeper {:?}: {:?}",
+				keeper,
+				e,
+			),
+		}
+	}
+
+	/// Return the collateral and debit amounts to confiscate so that the
+	/// collateral ratio of the remaining ECDP is restored to the partial
+	/// liquidation target ratio. The confiscated collateral is worth the
+	/// repaid debit value plus the liquidation penalty at the oracle price.
+	///
+	/// Return `None` if partial liquidation is not enabled for the
+	/// collateral type, or the ECDP cannot be saved by it, or the
+	/// remaining ECDP would be dust, in which case the whole ECDP should
+	/// be liquidated.
+	pub fn get_partial_liquidation_amounts(
+		currency_id: CurrencyId,
+		collateral: Balance,
+		debit: Balance,
+	) -> Option<(Balance, Balance)> {
+		let target_ratio = Self::collateral_params(currency_id)?.partial_liquidation_target_ratio?;
+		let price = T::PriceSource::get_relative_price(currency_id, T::GetUSSDCurrencyId::get())?;
+		let confiscate_rate = Rate::one().saturating_add(Self::get_liquidation_penalty(currency_id).ok()?);
+
+		// only confiscating less collateral value than the target ratio per unit of repaid debit
+		// value raises the collateral ratio
+		if target_ratio <= confiscate_rate {
+			return None;
+		}
+
+		// solve (collateral_value - repay_value * confiscate_rate) / (debit_value - repay_value)
+		// = target_ratio, round up so that the target ratio is reached
+		let collateral_value = price.saturating_mul_int(collateral);
+		let debit_value = Self::get_debit_value(currency_id, debit);
+		let shortfall = target_ratio.saturating_mul_int(debit_value).checked_sub(collateral_value)?;
+		let repay_value = target_ratio
+			.saturating_sub(confiscate_rate)
+			.reciprocal()?
+			.saturating_mul_int(shortfall)
+			.saturating_add(One::one());
+
+		let repay_debit = Self::try_convert_to_debit_balance(currency_id, repay_value)?;
+		let confiscate_collateral =
+			price.reciprocal()?.saturating_mul_int(confiscate_rate.saturating_mul_int(repay_value));
+		if repay_debit.is_zero() || repay_debit >= debit || confiscate_collateral >= collateral {
+			return None;
+		}
+
+		let remain_debit_value = Self::get_debit_value(currency_id, debit.saturating_sub(repay_debit));
+		let remain_collateral = collateral.saturating_sub(confiscate_collateral);
+		if remain_debit_value < T::MinimumDebitValue::get()
+			|| remain_collateral < T::MinimumCollateralAmount::get(&currency_id)
+		{
+			return None;
+		}
+
+		Some((confiscate_collateral, repay_debit))
+	}
+
+	/// Register, update or remove (if `maybe_rule` is `None`) the liquidation
+	/// protection rule of `who`'s ECDP under `currency_id`.
+	pub fn set_liquidation_protection(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		maybe_rule: Option<ProtectionRule>,
+	) -> DispatchResult {
+		if let Some(rule) = &maybe_rule {
+			ensure!(
+				CollateralParams::<T>::contains_key(currency_id),
+				Error::<T>::InvalidCollateralType,
+			);
+			ensure!(!Self::is_in_basket(who, currency_id), Error::<T>::CDPInBasket);
+			let liquidation_ratio = Self::get_liquidation_ratio(currency_id)?;
+			ensure!(
+				rule.trigger_ratio > liquidation_ratio && rule.target_ratio > rule.trigger_ratio,
+				Error::<T>::InvalidProtectionRule
+			);
+			LiquidationProtections::<T>::insert(currency_id, who, rule);
+		} else {
+			LiquidationProtections::<T>::remove(currency_id, who);
+		}
+
+		Self::deposit_event(Event::LiquidationProtectionUpdated {
+			collateral_type: currency_id,
+			owner: who.clone(),
+			rule: maybe_rule,
+		});
+		Ok(())
+	}
+
+	/// Return the liquidation protection rule of the ECDP and the collateral
+	/// amount to sell or to top up, which restores the collateral ratio of
+	/// the ECDP to the target ratio of the rule at the oracle price.
+	///
+	/// Return `None` if the ECDP has no protection rule, or the rule is not
+	/// triggered, or it cannot be executed.
+	pub fn get_protection_amount(
+		currency_id: CurrencyId,
+		who: &T::AccountId,
+		collateral: Balance,
+		debit: Balance,
+	) -> Option<(ProtectionRule, Balance)> {
+		if debit.is_zero() || Self::is_in_basket(who, currency_id) {
+			return None;
+		}
+		let rule = Self::liquidation_protections(currency_id, who)?;
+		let price = T::PriceSource::get_relative_price(currency_id, T::GetUSSDCurrencyId::get())?;
+		if Self::calculate_collateral_ratio(currency_id, collateral, debit, price) >= rule.trigger_ratio {
+			return None;
+		}
+
+		let collateral_value = price.saturating_mul_int(collateral);
+		let target_collateral_value = rule.target_ratio.saturating_mul_int(Self::get_debit_value(currency_id, debit));
+		let shortfall = target_collateral_value.checked_sub(collateral_value)?;
+		let amount = match rule.action {
+			ProtectionAction::Deleverage => {
+				// solve (collateral_value - sell_value) / (debit_value - sell_value) = target_ratio,
+				// round up so that the target ratio is reached
+				let sell_value =
+					rule.target_ratio.saturating_sub(Ratio::one()).reciprocal()?.saturating_mul_int(shortfall);
+				let sell_collateral = price.reciprocal()?.saturating_mul_int(sell_value).saturating_add(One::one());
+				if sell_collateral >= collateral {
+					return None;
+				}
+				sell_collateral
+			}
+			ProtectionAction::TopUp { max_amount } => price
+				.reciprocal()?
+				.saturating_mul_int(shortfall)
+				.saturating_add(One::one())
+				.min(max_amount)
+				.min(<T as Config>::Currency::free_balance(currency_id, who)),
+		};
+
+		if amount.is_zero() {
+			None
+		} else {
+			Some((rule, amount))
+		}
+	}
+
+	// execute the liquidation protection rule of cdp
+	pub fn protect_cdp(who: T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+		let EcdpPosition { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
+		ensure!(
+			LiquidationProtections::<T>::contains_key(currency_id, &who),
+			Error::<T>::NoProtectionRule
+		);
+		let (mut rule, amount) = Self::get_protection_amount(currency_id, &who, collateral, debit)
+			.ok_or(Error::<T>::ProtectionNotTriggered)?;
+
+		match rule.action {
+			ProtectionAction::Deleverage => {
+				// the stable coin received for the sold collateral is bounded by the slippage limit
+				// for the price of oracle
+				let price = T::PriceSource::get_relative_price(currency_id, T::GetUSSDCurrencyId::get())
+					.ok_or(Error::<T>::InvalidFeedPrice)?;
+				let min_decrease_debit_value = Ratio::one()
+					.saturating_sub(T::MaxSwapSlippageCompareToOracle::get())
+					.saturating_mul_int(price.saturating_mul_int(amount));
+				Self::shrink_position_debit(&who, currency_id, amount, min_decrease_debit_value)?;
+
+				let EcdpPosition { debit: remain_debit, .. } = <LoansOf<T>>::positions(currency_id, &who);
+				Self::deposit_event(Event::ProtectedCDPDeleveraged {
+					collateral_type: currency_id,
+					owner: who,
+					sold_collateral_amount: amount,
+					repaid_debit_value: Self::get_debit_value(currency_id, debit.saturating_sub(remain_debit)),
+				});
+			}
+			ProtectionAction::TopUp { max_amount } => {
+				let collateral_adjustment = <LoansOf<T>>::amount_try_from_balance(amount)?;
+				Self::adjust_position(&who, currency_id, collateral_adjustment, Zero::zero())?;
+
+				// consume the top up allowance of the rule
+				rule.action = ProtectionAction::TopUp {
+					max_amount: max_amount.saturating_sub(amount),
+				};
+				LiquidationProtections::<T>::insert(currency_id, &who, rule);
+				Self::deposit_event(Event::ProtectedCDPToppedUp {
+					collateral_type: currency_id,
+					owner: who,
+					collateral_amount: amount,
+				});
+			}
+		}
+		Ok(())
 	}
 
 	pub fn handle_liquidated_collateral(
@@ -1201,6 +2285,42 @@ impl<T: Config> Pallet<T> {
 		LiquidateByPriority::<T>::liquidate(who, currency_id, amount, target_stable_amount)
 	}
 
+	/// Flash mint `amount` of USSD to `receiver` and execute `callback` with the fee, then burn
+	/// the minted amount from `receiver` and collect the fee into the surplus pool.
+	/// Returns the fee charged.
+	#[transactional]
+	fn do_flash_mint(
+		receiver: &T::AccountId,
+		amount: Balance,
+		callback: impl FnOnce(Balance) -> DispatchResult,
+	) -> Result<Balance, DispatchError> {
+		ensure!(!T::EcdpEmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+
+		let fee = Self::flash_fee(amount);
+		FlashMintOutstanding::<T>::try_mutate(|outstanding| -> DispatchResult {
+			let new_outstanding = outstanding.checked_add(amount).ok_or(ArithmeticError::Overflow)?;
+			ensure!(new_outstanding <= Self::flash_mint_ceiling(), Error::<T>::ExceedFlashMintCeiling);
+			*outstanding = new_outstanding;
+			Ok(())
+		})?;
+
+		<T as Config>::EcdpUssdTreasury::issue_debit(receiver, amount, true)?;
+		callback(fee)?;
+		<T as Config>::EcdpUssdTreasury::burn_debit(receiver, amount).map_err(|_| Error::<T>::FlashMintNotRepaid)?;
+		if !fee.is_zero() {
+			<T as Config>::EcdpUssdTreasury::deposit_surplus(receiver, fee)
+				.map_err(|_| Error::<T>::FlashMintNotRepaid)?;
+		}
+
+		FlashMintOutstanding::<T>::mutate(|outstanding| *outstanding = outstanding.saturating_sub(amount));
+		Self::deposit_event(Event::FlashMinted {
+			receiver: receiver.clone(),
+			amount,
+			fee,
+		});
+		Ok(fee)
+	}
+
 	pub fn get_collateral_currency_ids() -> Vec<CurrencyId> {
 		CollateralParams::<T>::iter_keys().collect()
 	}
@@ -1215,7 +2335,12 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
-type LiquidateByPriority<T> = (LiquidateViaDex<T>, LiquidateViaContracts<T>, LiquidateViaAuction<T>);
+type LiquidateByPriority<T> = (
+	LiquidateViaDex<T>,
+	LiquidateViaContracts<T>,
+	LiquidateViaDutchAuction<T>,
+	LiquidateViaAuction<T>,
+);
 
 pub struct LiquidateViaDex<T>(PhantomData<T>);
 impl<T: Config> LiquidateCollateral<T::AccountId> for LiquidateViaDex<T> {
@@ -1410,6 +2535,20 @@ impl<T: Config> LiquidateCollateral<T::AccountId> for LiquidateViaAuction<T> {
 	}
 }
 
+/// Sells the collateral in a dutch auction, fails if dutch auctions are not
+/// enabled for the collateral type.
+pub struct LiquidateViaDutchAuction<T>(PhantomData<T>);
+impl<T: Config> LiquidateCollateral<T::AccountId> for LiquidateViaDutchAuction<T> {
+	fn liquidate(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		target_stable_amount: Balance,
+	) -> DispatchResult {
+		<T as Config>::EcdpUssdTreasury::create_dutch_auction(currency_id, amount, target_stable_amount, who.clone())
+	}
+}
+
 impl<T: Config> EcdpUssdRiskManager<T::AccountId, CurrencyId, Balance, Balance> for Pallet<T> {
 	fn get_debit_value(currency_id: CurrencyId, debit_balance: Balance) -> Balance {
 		Self::convert_to_debit_value(currency_id, debit_balance)
@@ -1458,6 +2597,26 @@ impl<T: Config> EcdpUssdRiskManager<T::AccountId, CurrencyId, Balance, Balance>
 		Ok(())
 	}
 
+	fn check_account_position_valid(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral_balance: Balance,
+		debit_balance: Balance,
+		check_required_ratio: bool,
+	) -> DispatchResult {
+		let basket = Self::baskets(who);
+		if basket.contains(&currency_id) {
+			Self::check_basket_valid(
+				who,
+				&basket,
+				Some((currency_id, collateral_balance, debit_balance)),
+				check_required_ratio,
+			)
+		} else {
+			Self::check_position_valid(currency_id, collateral_balance, debit_balance, check_required_ratio)
+		}
+	}
+
 	fn check_debit_cap(currency_id: CurrencyId, total_debit_balance: Balance) -> DispatchResult {
 		let hard_cap = Self::maximum_total_debit_value(currency_id)?;
 		let total_debit_value = Self::get_debit_value(currency_id, total_debit_balance);
@@ -1476,6 +2635,125 @@ impl<T: Config> Get<Vec<CurrencyId>> for CollateralCurrencyIds<T> {
 	}
 }
 
+impl<T: Config> FlashMint<T::AccountId, Balance> for Pallet<T> {
+	fn max_flash_mint() -> Balance {
+		if T::EcdpEmergencyShutdown::is_shutdown() {
+			return Zero::zero();
+		}
+		Self::flash_mint_ceiling().saturating_sub(Self::flash_mint_outstanding())
+	}
+
+	fn flash_fee(amount: Balance) -> Balance {
+		Self::flash_mint_fee_rate().saturating_mul_int(amount)
+	}
+
+	fn flash_mint_to_contract(
+		initiator: &T::AccountId,
+		receiver: EvmAddress,
+		amount: Balance,
+		data: Vec<u8>,
+	) -> Result<Balance, DispatchError> {
+		let receiver_account_id = T::EvmAddressMapping::get_account_id(&receiver);
+		Self::do_flash_mint(&receiver_account_id, amount, |fee| {
+			let initiator_evm_address = T::EvmAddressMapping::get_or_create_evm_address(initiator);
+			T::FlashMintEvmBridge::on_flash_mint(
+				InvokeContext {
+					contract: receiver,
+					sender: Self::evm_address(),
+					origin: initiator_evm_address,
+				},
+				initiator_evm_address,
+				amount,
+				fee,
+				data,
+			)
+		})
+	}
+}
+
+/// Risk management params before the partial liquidation target ratio was
+/// added, storage version 0
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default, TypeInfo)]
+pub struct RiskManagementParamsV0 {
+	pub maximum_total_debit_value: Balance,
+	pub liquidation_ratio: Option<Ratio>,
+	pub liquidation_penalty: Option<FractionalRate>,
+	pub required_collateral_ratio: Option<Ratio>,
+}
+
+/// Risk management params with the partial liquidation target ratio, storage
+/// version 1
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default, TypeInfo)]
+pub struct RiskManagementParamsV1 {
+	pub maximum_total_debit_value: Balance,
+	pub liquidation_ratio: Option<Ratio>,
+	pub liquidation_penalty: Option<FractionalRate>,
+	pub required_collateral_ratio: Option<Ratio>,
+	pub partial_liquidation_target_ratio: Option<Ratio>,
+}
+
+/// Migrate `CollateralParams` stored in the version 0 layout to the version 1
+/// layout, the partial liquidation target ratio of every collateral type is
+/// left unset.
+pub struct MigrateRiskManagementParamsToV1<T>(PhantomData<T>);
+impl<T: Config> frame_support::traits::OnRuntimeUpgrade for MigrateRiskManagementParamsToV1<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 1 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		// the values are written in the version 1 layout, later versions are
+		// migrated from it by their own migrations
+		let currency_ids: Vec<CurrencyId> = CollateralParams::<T>::iter_keys().collect();
+		for currency_id in currency_ids.iter() {
+			let key = CollateralParams::<T>::hashed_key_for(currency_id);
+			if let Some(old) = frame_support::storage::unhashed::get::<RiskManagementParamsV0>(&key) {
+				frame_support::storage::unhashed::put(
+					&key,
+					&RiskManagementParamsV1 {
+						maximum_total_debit_value: old.maximum_total_debit_value,
+						liquidation_ratio: old.liquidation_ratio,
+						liquidation_penalty: old.liquidation_penalty,
+						required_collateral_ratio: old.required_collateral_ratio,
+						partial_liquidation_target_ratio: None,
+					},
+				);
+			}
+		}
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		let count = currency_ids.len() as u64;
+		T::DbWeight::get().reads_writes(count + 1, count + 1)
+	}
+}
+
+/// Migrate `CollateralParams` stored in the version 1 layout to the version 2
+/// layout, the stability fee of every collateral type is left unset.
+pub struct MigrateRiskManagementParamsToV2<T>(PhantomData<T>);
+impl<T: Config> frame_support::traits::OnRuntimeUpgrade for MigrateRiskManagementParamsToV2<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() != 1 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut count: u64 = 0;
+		CollateralParams::<T>::translate::<RiskManagementParamsV1, _>(|_, old| {
+			count += 1;
+			Some(RiskManagementParams {
+				maximum_total_debit_value: old.maximum_total_debit_value,
+				liquidation_ratio: old.liquidation_ratio,
+				liquidation_penalty: old.liquidation_penalty,
+				required_collateral_ratio: old.required_collateral_ratio,
+				partial_liquidation_target_ratio: old.partial_liquidation_target_ratio,
+				stability_fee_per_sec: None,
+			})
+		});
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(count + 1, count + 1)
+	}
+}
+
 /// Pick a new PRN, in the range [0, `max`) (exclusive).
 fn pick_u32<R: RngCore>(rng: &mut R, max: u32) -> u32 {
 	rng.next_u32() % max
@@ -134,6 +134,7 @@ thread_local! {
 	static EDF_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::one()));
 	static LP_USSD_EDF_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::one()));
 	static LP_EDF_BTC_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::one()));
+	static STALE_PRICES: RefCell<Vec<CurrencyId>> = RefCell::new(vec![]);
 }
 
 pub struct MockPriceSource;
@@ -147,6 +148,16 @@ impl MockPriceSource {
 			_ => {}
 		}
 	}
+
+	pub fn set_stale(currency_id: CurrencyId, stale: bool) {
+		STALE_PRICES.with(|v| {
+			let mut stale_prices = v.borrow_mut();
+			stale_prices.retain(|id| *id != currency_id);
+			if stale {
+				stale_prices.push(currency_id);
+			}
+		})
+	}
 }
 impl PriceProvider<CurrencyId> for MockPriceSource {
 	fn get_price(currency_id: CurrencyId) -> Option<Price> {
@@ -159,6 +170,10 @@ impl PriceProvider<CurrencyId> for MockPriceSource {
 			_ => None,
 		}
 	}
+
+	fn is_price_stale(currency_id: CurrencyId) -> bool {
+		STALE_PRICES.with(|v| v.borrow().contains(&currency_id))
+	}
 }
 
 thread_local! {
@@ -189,6 +204,15 @@ impl EcdpAuctionsManager<AccountId> for MockEcdpAuctionsManager {
 		Ok(())
 	}
 
+	fn new_dutch_auction(
+		_refund_recipient: &AccountId,
+		_currency_id: Self::CurrencyId,
+		_amount: Self::Balance,
+		_target: Self::Balance,
+	) -> DispatchResult {
+		Err(sp_runtime::DispatchError::Other("dutch auctions are disabled"))
+	}
+
 	fn cancel_auction(_id: Self::AuctionId) -> DispatchResult {
 		AUCTION.with(|v| *v.borrow_mut() = None);
 		Ok(())
@@ -201,6 +225,22 @@ impl EcdpAuctionsManager<AccountId> for MockEcdpAuctionsManager {
 	fn get_total_collateral_in_auction(_id: Self::CurrencyId) -> Self::Balance {
 		Self::auction().map(|auction| auction.2).unwrap_or_default()
 	}
+
+	fn new_surplus_auction(_amount: Self::Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn new_debit_auction(_initial_amount: Self::Balance, _fix_debit: Self::Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn get_total_surplus_in_auction() -> Self::Balance {
+		Default::default()
+	}
+
+	fn get_total_debit_in_auction() -> Self::Balance {
+		Default::default()
+	}
 }
 
 parameter_types! {
@@ -384,6 +424,45 @@ impl LiquidationEvmBridge for MockLiquidationEvmBridge {
 	}
 }
 
+pub fn flash_mint_receiver_addr() -> EvmAddress {
+	EvmAddress::from_str(&"0x2000000000000000000000000000000000000000").unwrap()
+}
+
+thread_local! {
+	static FLASH_MINTED: RefCell<(EvmAddress, EvmAddress, Balance, Balance, Vec<u8>)> = RefCell::new((EvmAddress::default(), EvmAddress::default(), 0, 0, vec![]));
+	static FLASH_MINT_REPAY: RefCell<bool> = RefCell::new(true);
+}
+
+pub struct MockFlashMintEvmBridge;
+impl MockFlashMintEvmBridge {
+	pub fn flash_minted() -> (EvmAddress, EvmAddress, Balance, Balance, Vec<u8>) {
+		FLASH_MINTED.with(|v| v.borrow().clone())
+	}
+	pub fn reset() {
+		FLASH_MINT_REPAY.with(|v| *v.borrow_mut() = true);
+	}
+	pub fn set_repay(repay: bool) {
+		FLASH_MINT_REPAY.with(|v| *v.borrow_mut() = repay);
+	}
+}
+impl FlashMintEvmBridge for MockFlashMintEvmBridge {
+	fn on_flash_mint(
+		context: InvokeContext,
+		initiator: EvmAddress,
+		amount: Balance,
+		fee: Balance,
+		data: Vec<u8>,
+	) -> DispatchResult {
+		// the receiver contract earns the fee with the minted USSD
+		if FLASH_MINT_REPAY.with(|v| *v.borrow()) {
+			let receiver = <Runtime as Config>::EvmAddressMapping::get_account_id(&context.contract);
+			Currencies::deposit(GetUSSDCurrencyId::get(), &receiver, fee)?;
+		}
+		FLASH_MINTED.with(|v| *v.borrow_mut() = (context.contract, initiator, amount, fee, data));
+		Ok(())
+	}
+}
+
 ord_parameter_types! {
 	pub const One: AccountId = ALICE;
 }
@@ -430,6 +509,10 @@ impl Config for Runtime {
 	type Swap = SpecificJointsSwap<EdfisSwapModule, AlternativeSwapPathJointList>;
 	type EVMBridge = module_evm_bridge::EVMBridge<Runtime>;
 	type SettleErc20EvmOrigin = SettleErc20EvmOrigin;
+	type RuntimeCall = RuntimeCall;
+	type FlashMintEvmBridge = MockFlashMintEvmBridge;
+	type MaxFlashMintCalls = ConstU32<4>;
+	type MaxBasketCurrencies = ConstU32<4>;
 	type WeightInfo = ();
 }
 
@@ -510,6 +593,7 @@ impl ExtBuilder {
 		.unwrap();
 
 		MockLiquidationEvmBridge::reset();
+		MockFlashMintEvmBridge::reset();
 
 		t.into()
 	}
@@ -52,11 +52,12 @@ fn setup_default_collateral(currency_id: CurrencyId) {
 	assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 		RuntimeOrigin::signed(ALICE),
 		currency_id,
-		Change::NewValue(Some(Default::default())),
 		Change::NoChange,
 		Change::NoChange,
 		Change::NoChange,
 		Change::NewValue(10000),
+		Change::NoChange,
+		Change::NewValue(Some(Default::default())),
 	));
 }
 
@@ -66,22 +67,24 @@ fn check_cdp_status_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_eq!(EcdpUssdEngineModule::check_cdp_status(BTC, 100, 500), CDPStatus::Safe);
 
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NoChange,
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
 			Change::NoChange,
 			Change::NoChange,
 			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
 		));
 		assert_eq!(EcdpUssdEngineModule::check_cdp_status(BTC, 100, 500), CDPStatus::Unsafe);
 
@@ -118,11 +121,12 @@ fn get_liquidation_penalty_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(5, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_eq!(
 			EcdpUssdEngineModule::get_liquidation_penalty(BTC),
@@ -141,11 +145,12 @@ fn get_liquidation_ratio_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(5, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_eq!(
 			EcdpUssdEngineModule::get_liquidation_ratio(BTC),
@@ -162,23 +167,29 @@ fn set_collateral_params_work() {
 			EcdpUssdEngineModule::set_collateral_params(
 				RuntimeOrigin::signed(AccountId::new([5u8; 32])),
 				BTC,
-				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 				Change::NewValue(10000),
+				Change::NoChange,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			),
 			BadOrigin
 		);
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
+		System::assert_has_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::StabilityFeePerSecUpdated {
+			collateral_type: BTC,
+			new_stability_fee_per_sec: Some(Rate::saturating_from_rational(1, 100000)),
+		}));
 		System::assert_has_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::LiquidationRatioUpdated {
 			collateral_type: BTC,
 			new_liquidation_ratio: Some(Ratio::saturating_from_rational(3, 2)),
@@ -203,11 +214,12 @@ fn set_collateral_params_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 
 		let new_collateral_params = EcdpUssdEngineModule::collateral_params(BTC).unwrap();
@@ -225,6 +237,136 @@ fn set_collateral_params_work() {
 			Some(Ratio::saturating_from_rational(9, 5))
 		);
 		assert_eq!(new_collateral_params.maximum_total_debit_value, 10000);
+		assert_eq!(new_collateral_params.partial_liquidation_target_ratio, None);
+		assert_eq!(
+			new_collateral_params.stability_fee_per_sec.map(|v| v.into_inner()),
+			Some(Rate::saturating_from_rational(1, 100000))
+		);
+
+		assert_noop!(
+			EcdpUssdEngineModule::set_collateral_params(
+				RuntimeOrigin::signed(ALICE),
+				BTC,
+				Change::NoChange,
+				Change::NoChange,
+				Change::NoChange,
+				Change::NoChange,
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NoChange,
+			),
+			Error::<Runtime>::InvalidPartialLiquidationTargetRatio
+		);
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+			Change::NoChange,
+		));
+		System::assert_has_event(RuntimeEvent::EcdpUssdEngineModule(
+			crate::Event::PartialLiquidationTargetRatioUpdated {
+				collateral_type: BTC,
+				new_partial_liquidation_target_ratio: Some(Ratio::saturating_from_rational(2, 1)),
+			},
+		));
+		assert_eq!(
+			EcdpUssdEngineModule::collateral_params(BTC)
+				.unwrap()
+				.partial_liquidation_target_ratio,
+			Some(Ratio::saturating_from_rational(2, 1))
+		);
+	});
+}
+
+#[test]
+fn compound_stability_fee_rate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			EcdpUssdEngineModule::compound_stability_fee_rate(Rate::saturating_from_rational(1, 100), 0),
+			Rate::zero()
+		);
+		assert_eq!(
+			EcdpUssdEngineModule::compound_stability_fee_rate(Rate::saturating_from_rational(1, 100), 2),
+			Rate::saturating_from_rational(201, 10000)
+		);
+		assert_eq!(EcdpUssdEngineModule::compound_stability_fee_rate(Rate::zero(), 100), Rate::zero());
+	});
+}
+
+#[test]
+fn accumulate_stability_fee_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100))),
+		));
+		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+		assert_eq!(EcdpUssdEngineModule::get_debit_value(BTC, 500), 50);
+
+		// the first accumulation only records the time
+		Timestamp::set_timestamp(1_000_000);
+		EcdpUssdEngineModule::on_initialize(1);
+		assert_eq!(EcdpUssdEngineModule::last_accumulation_secs(), 1000);
+		assert_eq!(EcdpUssdEngineModule::debit_exchange_rate(BTC), None);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 0);
+
+		Timestamp::set_timestamp(1_002_000);
+		EcdpUssdEngineModule::on_initialize(2);
+		assert_eq!(EcdpUssdEngineModule::last_accumulation_secs(), 1002);
+		assert_eq!(
+			EcdpUssdEngineModule::debit_exchange_rate(BTC),
+			Some(ExchangeRate::saturating_from_rational(10201, 100000))
+		);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 1);
+
+		// zero stability fee keeps the debit exchange rate
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(Some(Rate::zero())),
+		));
+		Timestamp::set_timestamp(1_010_000);
+		EcdpUssdEngineModule::on_initialize(3);
+		assert_eq!(EcdpUssdEngineModule::last_accumulation_secs(), 1010);
+		assert_eq!(
+			EcdpUssdEngineModule::debit_exchange_rate(BTC),
+			Some(ExchangeRate::saturating_from_rational(10201, 100000))
+		);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 1);
+
+		// no accumulation after emergency shutdown
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100))),
+		));
+		mock_shutdown();
+		Timestamp::set_timestamp(1_020_000);
+		EcdpUssdEngineModule::on_initialize(4);
+		assert_eq!(
+			EcdpUssdEngineModule::debit_exchange_rate(BTC),
+			Some(ExchangeRate::saturating_from_rational(10201, 100000))
+		);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 1);
 	});
 }
 
@@ -234,11 +376,12 @@ fn calculate_collateral_ratio_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_eq!(
 			EcdpUssdEngineModule::calculate_collateral_ratio(BTC, 100, 500, Price::saturating_from_rational(1, 1)),
@@ -253,11 +396,12 @@ fn check_debit_cap_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_ok!(EcdpUssdEngineModule::check_debit_cap(BTC, 100000));
 		assert_noop!(
@@ -273,11 +417,12 @@ fn check_position_valid_failed_when_invalid_feed_price() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(1, 1))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 
 		MockPriceSource::set_price(BTC, None);
@@ -297,11 +442,12 @@ fn check_position_valid_failed_when_remain_debit_value_too_small() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(1, 1))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_noop!(
 			EcdpUssdEngineModule::check_position_valid(BTC, 2, 10, true),
@@ -316,11 +462,12 @@ fn check_position_valid_ratio_below_liquidate_ratio() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(10, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_noop!(
 			EcdpUssdEngineModule::check_position_valid(BTC, 91, 500, true),
@@ -335,11 +482,12 @@ fn check_position_valid_ratio_below_required_ratio() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_ok!(EcdpUssdEngineModule::check_position_valid(BTC, 89, 500, false));
 		assert_noop!(
@@ -432,11 +580,12 @@ fn expand_position_collateral_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			EDF,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, EDF, 100, 2500));
 		assert_eq!(
@@ -522,8 +671,9 @@ fn expand_position_collateral_work() {
 			Change::NoChange,
 			Change::NoChange,
 			Change::NoChange,
-			Change::NoChange,
 			Change::NewValue(900),
+			Change::NoChange,
+			Change::NoChange,
 		));
 		assert_noop!(
 			EcdpUssdEngineModule::expand_position_collateral(&ALICE, EDF, 101, 0),
@@ -556,11 +706,12 @@ fn expand_position_collateral_for_lp_ussd_edf_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			LP_USSD_EDF,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		setup_default_collateral(EDF);
 		setup_default_collateral(USSD);
@@ -620,11 +771,12 @@ fn shrink_position_debit_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			EDF,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		setup_default_collateral(USSD);
 		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, EDF, 100, 5000));
@@ -722,11 +874,12 @@ fn shrink_position_debit_for_lp_ussd_edf_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			LP_USSD_EDF,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		setup_default_collateral(EDF);
 		setup_default_collateral(USSD);
@@ -797,11 +950,12 @@ fn remain_debit_value_too_small_check() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 100, 500));
 		assert_noop!(
@@ -819,11 +973,12 @@ fn liquidate_unsafe_cdp_by_collateral_auction() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		setup_default_collateral(USSD);
 		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 100, 500));
@@ -838,11 +993,12 @@ fn liquidate_unsafe_cdp_by_collateral_auction() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NoChange,
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
 			Change::NoChange,
 			Change::NoChange,
 			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
 		));
 		assert_ok!(EcdpUssdEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
 
@@ -868,17 +1024,68 @@ fn liquidate_unsafe_cdp_by_collateral_auction() {
 }
 
 #[test]
-fn liquidate_unsafe_cdp_by_collateral_auction_when_limited_by_slippage() {
+fn liquidation_is_paused_when_feed_price_is_stale() {
 	ExtBuilder::default().build().execute_with(|| {
 		System::set_block_number(1);
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+			Change::NoChange,
 			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+		));
+		setup_default_collateral(USSD);
+		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		MockPriceSource::set_stale(BTC, true);
+		assert_noop!(
+			EcdpUssdEngineModule::liquidate_unsafe_cdp(ALICE, BTC),
+			Error::<Runtime>::StaleFeedPrice,
+		);
+		assert_eq!(
+			EcdpUssdEngineModule::validate_unsigned(
+				TransactionSource::Local,
+				&crate::Call::liquidate {
+					currency_id: BTC,
+					who: ALICE,
+				},
+			),
+			InvalidTransaction::Stale.into(),
+		);
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).collateral, 100);
+
+		MockPriceSource::set_stale(BTC, false);
+		assert_ok!(EcdpUssdEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).collateral, 0);
+	});
+}
+
+#[test]
+fn liquidate_unsafe_cdp_by_collateral_auction_when_limited_by_slippage() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		setup_default_collateral(USSD);
 		assert_ok!(EdfisSwapModule::add_liquidity(
@@ -901,11 +1108,12 @@ fn liquidate_unsafe_cdp_by_collateral_auction_when_limited_by_slippage() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NoChange,
 			Change::NewValue(Some(Ratio::max_value())),
 			Change::NoChange,
 			Change::NoChange,
 			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
 		));
 
 		// pool is enough, but slippage limit the swap
@@ -943,11 +1151,12 @@ fn liquidate_unsafe_cdp_by_swap() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		setup_default_collateral(EDF);
 		setup_default_collateral(USSD);
@@ -971,11 +1180,12 @@ fn liquidate_unsafe_cdp_by_swap() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NoChange,
 			Change::NewValue(Some(Ratio::max_value())),
 			Change::NoChange,
 			Change::NoChange,
 			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
 		));
 
 		assert_ok!(EcdpUssdEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
@@ -997,72 +1207,602 @@ fn liquidate_unsafe_cdp_by_swap() {
 }
 
 #[test]
-fn liquidate_unsafe_cdp_of_lp_ussd_edf_and_swap_edf() {
+fn partially_liquidate_unsafe_cdp_to_target_ratio() {
 	ExtBuilder::default().build().execute_with(|| {
 		System::set_block_number(1);
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
-			LP_USSD_EDF,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			BTC,
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
-			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
-		setup_default_collateral(EDF);
 		setup_default_collateral(USSD);
-
-		assert_ok!(EdfisSwapModule::add_liquidity(
-			RuntimeOrigin::signed(CAROL),
-			USSD,
-			EDF,
-			10000,
-			500,
-			0,
-			false
-		));
-		assert_eq!(EdfisSwapModule::get_liquidity_pool(USSD, EDF), (10000, 500));
-		assert_eq!(Currencies::total_issuance(LP_USSD_EDF), 20000);
-		assert_ok!(Currencies::transfer(
-			RuntimeOrigin::signed(CAROL),
-			ALICE,
-			LP_USSD_EDF,
-			1000
-		));
-		assert_eq!(Currencies::free_balance(LP_USSD_EDF, &ALICE), 1000);
-		assert_eq!(Currencies::free_balance(EDF, &ALICE), 1000);
-		assert_eq!(Currencies::free_balance(USSD, &ALICE), 0);
-
-		MockPriceSource::set_price(EDF, Price::checked_from_rational(20, 1));
-		MockPriceSource::set_price(LP_USSD_EDF, Price::checked_from_rational(1, 1));
-
-		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, LP_USSD_EDF, 1000, 5000));
-		assert_eq!(Currencies::free_balance(LP_USSD_EDF, &ALICE), 0);
-		assert_eq!(Currencies::free_balance(EDF, &ALICE), 1000);
-		assert_eq!(Currencies::free_balance(USSD, &ALICE), 500);
-		assert_eq!(EcdpLoansModule::positions(LP_USSD_EDF, ALICE).debit, 5000);
-		assert_eq!(EcdpLoansModule::positions(LP_USSD_EDF, ALICE).collateral, 1000);
-		assert_eq!(Currencies::free_balance(LP_USSD_EDF, &EcdpLoansModule::account_id()), 1000);
-		assert_eq!(EcdpUssdTreasuryModule::debit_pool(), 0);
-		assert_eq!(Currencies::free_balance(USSD, &EcdpUssdTreasuryModule::account_id()), 0);
-		assert_eq!(Currencies::free_balance(EDF, &EcdpUssdTreasuryModule::account_id()), 0);
-		assert_eq!(
-			Currencies::free_balance(LP_USSD_EDF, &EcdpUssdTreasuryModule::account_id()),
-			0
-		);
-		assert_eq!(MockEcdpAuctionsManager::auction(), None);
+		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 100, 500));
 
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
-			LP_USSD_EDF,
+			BTC,
+			Change::NewValue(Some(Ratio::saturating_from_rational(5, 2))),
 			Change::NoChange,
-			Change::NewValue(Some(Ratio::max_value())),
 			Change::NoChange,
 			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
 			Change::NoChange,
 		));
+		assert_eq!(EcdpUssdEngineModule::check_cdp_status(BTC, 100, 500), CDPStatus::Unsafe);
 
-		assert_ok!(EcdpUssdEngineModule::liquidate_unsafe_cdp(ALICE, LP_USSD_EDF));
+		// repay (3 * 50 - 100) / (3 - 1.2) ~= 28 debit value, confiscate 28 * 1.2 ~= 33 collateral
+		assert_eq!(EcdpUssdEngineModule::get_partial_liquidation_amounts(BTC, 100, 500), Some((33, 280)));
+		assert_ok!(EcdpUssdEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: BTC,
+			owner: ALICE,
+			collateral_amount: 33,
+			bad_debt_value: 28,
+			target_amount: 33,
+		}));
+		assert_eq!(MockEcdpAuctionsManager::auction(), Some((ALICE, BTC, 33, 33)));
+		assert_eq!(EcdpUssdTreasuryModule::debit_pool(), 28);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).debit, 220);
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).collateral, 67);
+		assert_eq!(EcdpUssdEngineModule::check_cdp_status(BTC, 67, 220), CDPStatus::Safe);
+	});
+}
+
+#[test]
+fn partial_liquidation_falls_back_to_whole_cdp() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+		));
+		setup_default_collateral(USSD);
+		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+
+		// collateral is worth less than the debit plus penalty, partial liquidation cannot save it
+		MockPriceSource::set_price(BTC, Some(Price::saturating_from_rational(1, 2)));
+		assert_eq!(EcdpUssdEngineModule::get_partial_liquidation_amounts(BTC, 100, 500), None);
+		assert_ok!(EcdpUssdEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: BTC,
+			owner: ALICE,
+			collateral_amount: 100,
+			bad_debt_value: 50,
+			target_amount: 60,
+		}));
+		assert_eq!(EcdpUssdTreasuryModule::debit_pool(), 50);
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).collateral, 0);
+	});
+}
+
+fn setup_basket_collateral() {
+	for currency_id in [BTC, EDF] {
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			currency_id,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+		));
+	}
+	setup_default_collateral(USSD);
+	assert_ok!(EcdpUssdEngineModule::set_basket_haircut(
+		RuntimeOrigin::signed(ALICE),
+		BTC,
+		Some(Ratio::saturating_from_rational(1, 2))
+	));
+	assert_ok!(EcdpUssdEngineModule::set_basket_haircut(
+		RuntimeOrigin::signed(ALICE),
+		EDF,
+		Some(Ratio::saturating_from_rational(1, 5))
+	));
+}
+
+#[test]
+fn set_basket_haircut_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EcdpUssdEngineModule::set_basket_haircut(RuntimeOrigin::signed(BOB), BTC, None),
+			BadOrigin
+		);
+		assert_noop!(
+			EcdpUssdEngineModule::set_basket_haircut(
+				RuntimeOrigin::signed(ALICE),
+				BTC,
+				Some(Ratio::saturating_from_rational(1, 2))
+			),
+			Error::<Runtime>::InvalidCollateralType
+		);
+
+		setup_default_collateral(BTC);
+		assert_noop!(
+			EcdpUssdEngineModule::set_basket_haircut(RuntimeOrigin::signed(ALICE), BTC, Some(Ratio::one())),
+			Error::<Runtime>::InvalidRate
+		);
+		assert_noop!(
+			EcdpUssdEngineModule::set_basket_haircut(RuntimeOrigin::signed(ALICE), BTC, Some(Ratio::zero())),
+			Error::<Runtime>::InvalidRate
+		);
+		assert_ok!(EcdpUssdEngineModule::set_basket_haircut(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(Ratio::saturating_from_rational(1, 2))
+		));
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::BasketHaircutUpdated {
+			collateral_type: BTC,
+			new_haircut: Some(Ratio::saturating_from_rational(1, 2)),
+		}));
+		assert_eq!(
+			EcdpUssdEngineModule::basket_haircuts(BTC),
+			Some(Ratio::saturating_from_rational(1, 2))
+		);
+
+		// the haircut cannot be removed while a basket holds the collateral type
+		assert_ok!(EcdpUssdEngineModule::set_basket(&ALICE, vec![BTC]));
+		assert_eq!(EcdpUssdEngineModule::basket_members(BTC), 1);
+		assert_noop!(
+			EcdpUssdEngineModule::set_basket_haircut(RuntimeOrigin::signed(ALICE), BTC, None),
+			Error::<Runtime>::BasketHaircutInUse
+		);
+		assert_ok!(EcdpUssdEngineModule::set_basket(&ALICE, vec![]));
+		assert_eq!(EcdpUssdEngineModule::basket_members(BTC), 0);
+
+		assert_ok!(EcdpUssdEngineModule::set_basket_haircut(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			None
+		));
+		assert_eq!(EcdpUssdEngineModule::basket_haircuts(BTC), None);
+	});
+}
+
+#[test]
+fn set_basket_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		setup_basket_collateral();
+
+		assert_noop!(
+			EcdpUssdEngineModule::set_basket(&ALICE, vec![BTC, EDF, BTC, EDF, BTC]),
+			Error::<Runtime>::TooManyBasketCurrencies
+		);
+		assert_noop!(
+			EcdpUssdEngineModule::set_basket(&ALICE, vec![BTC, BTC]),
+			Error::<Runtime>::InvalidBasket
+		);
+		assert_noop!(
+			EcdpUssdEngineModule::set_basket(&ALICE, vec![BTC, SEE]),
+			Error::<Runtime>::InvalidBasket
+		);
+
+		// ECDPs with liquidation protection cannot join a basket
+		let rule = ProtectionRule {
+			trigger_ratio: Ratio::saturating_from_rational(9, 5),
+			target_ratio: Ratio::saturating_from_rational(2, 1),
+			action: ProtectionAction::Deleverage,
+		};
+		assert_ok!(EcdpUssdEngineModule::set_liquidation_protection(
+			&ALICE,
+			EDF,
+			Some(rule.clone())
+		));
+		assert_noop!(
+			EcdpUssdEngineModule::set_basket(&ALICE, vec![EDF, BTC]),
+			Error::<Runtime>::InvalidBasket
+		);
+		assert_ok!(EcdpUssdEngineModule::set_liquidation_protection(&ALICE, EDF, None));
+
+		assert_ok!(EcdpUssdEngineModule::set_basket(&ALICE, vec![EDF, BTC]));
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::BasketUpdated {
+			owner: ALICE,
+			collateral_types: vec![EDF, BTC],
+		}));
+		assert_eq!(EcdpUssdEngineModule::baskets(&ALICE).into_inner(), vec![EDF, BTC]);
+		assert!(EcdpUssdEngineModule::is_in_basket(&ALICE, BTC));
+		assert!(!EcdpUssdEngineModule::is_in_basket(&BOB, BTC));
+
+		// the debit under BTC is backed by the collateral under EDF
+		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, EDF, 100, 0));
+		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 0, 400));
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).collateral, 0);
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).debit, 400);
+		assert_eq!(Currencies::free_balance(USSD, &ALICE), 40);
+
+		// the haircut collateral value 80 is below the debit value 50 at the required ratio
+		assert_noop!(
+			EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 0, 100),
+			Error::<Runtime>::BelowRequiredCollateralRatio
+		);
+		// the haircut collateral value 56 is below the debit value 40 at the required ratio
+		assert_noop!(
+			EcdpUssdEngineModule::adjust_position(&ALICE, EDF, -30, 0),
+			Error::<Runtime>::BelowRequiredCollateralRatio
+		);
+
+		// operations on a single ECDP are not supported in a basket
+		assert_noop!(
+			EcdpUssdEngineModule::set_liquidation_protection(&ALICE, BTC, Some(rule)),
+			Error::<Runtime>::CDPInBasket
+		);
+		assert_noop!(
+			EcdpUssdEngineModule::close_cdp_has_debit_by_dex(ALICE, BTC, 100),
+			Error::<Runtime>::CDPInBasket
+		);
+
+		// the ECDP under BTC is not valid on its own
+		assert_noop!(
+			EcdpUssdEngineModule::set_basket(&ALICE, vec![EDF]),
+			Error::<Runtime>::BelowRequiredCollateralRatio
+		);
+
+		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 0, -400));
+		assert_ok!(EcdpUssdEngineModule::set_basket(&ALICE, vec![]));
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::BasketUpdated {
+			owner: ALICE,
+			collateral_types: vec![],
+		}));
+		assert!(!Baskets::<Runtime>::contains_key(&ALICE));
+	});
+}
+
+#[test]
+fn liquidate_unsafe_basket_in_liquidation_order() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		setup_basket_collateral();
+		assert_ok!(EcdpUssdEngineModule::set_basket(&ALICE, vec![EDF, BTC]));
+		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, EDF, 100, 0));
+		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 60, 600));
+
+		// the ECDP under BTC is unsafe on its own but the basket is safe
+		assert_eq!(EcdpUssdEngineModule::check_cdp_status(BTC, 60, 600), CDPStatus::Unsafe);
+		assert_eq!(
+			EcdpUssdEngineModule::check_account_cdp_status(&ALICE, BTC, 60, 600),
+			CDPStatus::Safe
+		);
+		assert_noop!(
+			EcdpUssdEngineModule::liquidate_unsafe_cdp(ALICE, BTC),
+			Error::<Runtime>::MustBeUnsafe
+		);
+
+		// the haircut collateral value 20 + 30 is below the debit value 60
+		MockPriceSource::set_price(EDF, Some(Price::saturating_from_rational(1, 4)));
+		assert_eq!(EcdpUssdEngineModule::check_basket_status(&ALICE), CDPStatus::Unsafe);
+
+		// liquidation is paused while the feed price of any collateral in the basket is stale
+		MockPriceSource::set_stale(EDF, true);
+		assert_noop!(
+			EcdpUssdEngineModule::liquidate_unsafe_cdp(ALICE, BTC),
+			Error::<Runtime>::StaleFeedPrice
+		);
+		MockPriceSource::set_stale(EDF, false);
+
+		assert_ok!(EcdpUssdEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::LiquidateUnsafeBasket {
+			owner: ALICE,
+			bad_debt_value: 60,
+			target_amount: 72,
+		}));
+		assert_eq!(EcdpUssdTreasuryModule::debit_pool(), 60);
+		assert_eq!(EcdpLoansModule::positions(EDF, ALICE), EcdpPosition::default());
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE), EcdpPosition::default());
+
+		// EDF covers 25 of the target first, then BTC covers the remaining 47
+		assert_eq!(MockEcdpAuctionsManager::auction(), Some((ALICE, BTC, 60, 47)));
+	});
+}
+
+#[test]
+fn settle_basket_has_debit_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		setup_basket_collateral();
+		assert_ok!(EcdpUssdEngineModule::set_basket(&ALICE, vec![EDF, BTC]));
+		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, EDF, 100, 0));
+		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 0, 400));
+
+		mock_shutdown();
+		assert_ok!(EcdpUssdEngineModule::settle(RuntimeOrigin::none(), BTC, ALICE));
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::SettleBasketInDebit {
+			owner: ALICE,
+		}));
+		assert_eq!(EcdpLoansModule::positions(EDF, ALICE).collateral, 60);
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(EcdpUssdTreasuryModule::debit_pool(), 40);
+		assert_noop!(
+			EcdpUssdEngineModule::settle(RuntimeOrigin::none(), EDF, ALICE),
+			Error::<Runtime>::NoDebitValue
+		);
+	});
+}
+
+fn setup_protected_collateral() {
+	MockPriceSource::set_price(EDF, Some(Price::saturating_from_rational(10, 1)));
+	assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+		RuntimeOrigin::signed(ALICE),
+		EDF,
+		Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+		Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+		Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+		Change::NewValue(10000),
+		Change::NoChange,
+		Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+	));
+	setup_default_collateral(USSD);
+	assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, EDF, 100, 5000));
+}
+
+#[test]
+fn set_liquidation_protection_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let rule = ProtectionRule {
+			trigger_ratio: Ratio::saturating_from_rational(9, 5),
+			target_ratio: Ratio::saturating_from_rational(2, 1),
+			action: ProtectionAction::Deleverage,
+		};
+		assert_noop!(
+			EcdpUssdEngineModule::set_liquidation_protection(&ALICE, EDF, Some(rule.clone())),
+			Error::<Runtime>::InvalidCollateralType
+		);
+
+		setup_protected_collateral();
+		assert_noop!(
+			EcdpUssdEngineModule::set_liquidation_protection(
+				&ALICE,
+				EDF,
+				Some(ProtectionRule {
+					trigger_ratio: Ratio::saturating_from_rational(3, 2),
+					..rule.clone()
+				})
+			),
+			Error::<Runtime>::InvalidProtectionRule
+		);
+		assert_noop!(
+			EcdpUssdEngineModule::set_liquidation_protection(
+				&ALICE,
+				EDF,
+				Some(ProtectionRule {
+					target_ratio: Ratio::saturating_from_rational(9, 5),
+					..rule.clone()
+				})
+			),
+			Error::<Runtime>::InvalidProtectionRule
+		);
+
+		assert_ok!(EcdpUssdEngineModule::set_liquidation_protection(
+			&ALICE,
+			EDF,
+			Some(rule.clone())
+		));
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(
+			crate::Event::LiquidationProtectionUpdated {
+				collateral_type: EDF,
+				owner: ALICE,
+				rule: Some(rule.clone()),
+			},
+		));
+		assert_eq!(EcdpUssdEngineModule::liquidation_protections(EDF, ALICE), Some(rule));
+
+		// the rule is not triggered when the collateral ratio is above the trigger ratio
+		assert_eq!(EcdpUssdEngineModule::get_protection_amount(EDF, &ALICE, 100, 5000), None);
+		assert_noop!(
+			EcdpUssdEngineModule::protect(RuntimeOrigin::none(), EDF, ALICE),
+			Error::<Runtime>::ProtectionNotTriggered
+		);
+
+		assert_ok!(EcdpUssdEngineModule::set_liquidation_protection(&ALICE, EDF, None));
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(
+			crate::Event::LiquidationProtectionUpdated {
+				collateral_type: EDF,
+				owner: ALICE,
+				rule: None,
+			},
+		));
+		assert_eq!(EcdpUssdEngineModule::liquidation_protections(EDF, ALICE), None);
+		assert_noop!(
+			EcdpUssdEngineModule::protect(RuntimeOrigin::none(), EDF, ALICE),
+			Error::<Runtime>::NoProtectionRule
+		);
+	});
+}
+
+#[test]
+fn protect_cdp_by_deleverage_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		setup_protected_collateral();
+		assert_ok!(EcdpUssdEngineModule::set_liquidation_protection(
+			&ALICE,
+			EDF,
+			Some(ProtectionRule {
+				trigger_ratio: Ratio::saturating_from_rational(9, 5),
+				target_ratio: Ratio::saturating_from_rational(2, 1),
+				action: ProtectionAction::Deleverage,
+			})
+		));
+
+		// collateral ratio drops to 1.6, sell 26 collateral to restore it to 2
+		MockPriceSource::set_price(EDF, Some(Price::saturating_from_rational(8, 1)));
+		assert_eq!(
+			EcdpUssdEngineModule::get_protection_amount(EDF, &ALICE, 100, 5000).map(|(_, amount)| amount),
+			Some(26)
+		);
+
+		// the swap is bounded by the slippage limit for the price of oracle
+		assert_ok!(EdfisSwapModule::add_liquidity(
+			RuntimeOrigin::signed(CAROL),
+			USSD,
+			EDF,
+			80,
+			10,
+			0,
+			false
+		));
+		assert_noop!(
+			EcdpUssdEngineModule::protect(RuntimeOrigin::none(), EDF, ALICE),
+			SwapError::CannotSwap
+		);
+		assert_ok!(EdfisSwapModule::add_liquidity(
+			RuntimeOrigin::signed(CAROL),
+			USSD,
+			EDF,
+			7920,
+			990,
+			0,
+			false
+		));
+		assert_eq!(EdfisSwapModule::get_liquidity_pool(EDF, USSD), (1000, 8000));
+
+		assert_ok!(EcdpUssdEngineModule::protect(RuntimeOrigin::none(), EDF, ALICE));
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::ProtectedCDPDeleveraged {
+			collateral_type: EDF,
+			owner: ALICE,
+			sold_collateral_amount: 26,
+			repaid_debit_value: 202,
+		}));
+		assert_eq!(
+			EcdpLoansModule::positions(EDF, ALICE),
+			EcdpPosition {
+				collateral: 74,
+				debit: 2980
+			}
+		);
+		assert_eq!(Currencies::free_balance(USSD, &ALICE), 500);
+		assert_eq!(EdfisSwapModule::get_liquidity_pool(EDF, USSD), (1026, 7798));
+		assert_eq!(EcdpUssdEngineModule::get_protection_amount(EDF, &ALICE, 74, 2980), None);
+	});
+}
+
+#[test]
+fn protect_cdp_by_top_up_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		setup_protected_collateral();
+		assert_ok!(EcdpUssdEngineModule::set_liquidation_protection(
+			&ALICE,
+			EDF,
+			Some(ProtectionRule {
+				trigger_ratio: Ratio::saturating_from_rational(9, 5),
+				target_ratio: Ratio::saturating_from_rational(2, 1),
+				action: ProtectionAction::TopUp { max_amount: 30 },
+			})
+		));
+		assert_eq!(Currencies::free_balance(EDF, &ALICE), 900);
+
+		// collateral ratio drops to 1.6, deposit 26 collateral to restore it to 2
+		MockPriceSource::set_price(EDF, Some(Price::saturating_from_rational(8, 1)));
+		assert_ok!(EcdpUssdEngineModule::protect(RuntimeOrigin::none(), EDF, ALICE));
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::ProtectedCDPToppedUp {
+			collateral_type: EDF,
+			owner: ALICE,
+			collateral_amount: 26,
+		}));
+		assert_eq!(EcdpLoansModule::positions(EDF, ALICE).collateral, 126);
+		assert_eq!(Currencies::free_balance(EDF, &ALICE), 874);
+		assert_eq!(
+			EcdpUssdEngineModule::liquidation_protections(EDF, ALICE).map(|rule| rule.action),
+			Some(ProtectionAction::TopUp { max_amount: 4 })
+		);
+
+		// the top up is limited by the remaining allowance of the rule
+		MockPriceSource::set_price(EDF, Some(Price::saturating_from_rational(6, 1)));
+		assert_ok!(EcdpUssdEngineModule::protect(RuntimeOrigin::none(), EDF, ALICE));
+		assert_eq!(EcdpLoansModule::positions(EDF, ALICE).collateral, 130);
+		assert_eq!(Currencies::free_balance(EDF, &ALICE), 870);
+		assert_noop!(
+			EcdpUssdEngineModule::protect(RuntimeOrigin::none(), EDF, ALICE),
+			Error::<Runtime>::ProtectionNotTriggered
+		);
+	});
+}
+
+#[test]
+fn liquidate_unsafe_cdp_of_lp_ussd_edf_and_swap_edf() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			LP_USSD_EDF,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
+			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+		));
+		setup_default_collateral(EDF);
+		setup_default_collateral(USSD);
+
+		assert_ok!(EdfisSwapModule::add_liquidity(
+			RuntimeOrigin::signed(CAROL),
+			USSD,
+			EDF,
+			10000,
+			500,
+			0,
+			false
+		));
+		assert_eq!(EdfisSwapModule::get_liquidity_pool(USSD, EDF), (10000, 500));
+		assert_eq!(Currencies::total_issuance(LP_USSD_EDF), 20000);
+		assert_ok!(Currencies::transfer(
+			RuntimeOrigin::signed(CAROL),
+			ALICE,
+			LP_USSD_EDF,
+			1000
+		));
+		assert_eq!(Currencies::free_balance(LP_USSD_EDF, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(EDF, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(USSD, &ALICE), 0);
+
+		MockPriceSource::set_price(EDF, Price::checked_from_rational(20, 1));
+		MockPriceSource::set_price(LP_USSD_EDF, Price::checked_from_rational(1, 1));
+
+		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, LP_USSD_EDF, 1000, 5000));
+		assert_eq!(Currencies::free_balance(LP_USSD_EDF, &ALICE), 0);
+		assert_eq!(Currencies::free_balance(EDF, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(USSD, &ALICE), 500);
+		assert_eq!(EcdpLoansModule::positions(LP_USSD_EDF, ALICE).debit, 5000);
+		assert_eq!(EcdpLoansModule::positions(LP_USSD_EDF, ALICE).collateral, 1000);
+		assert_eq!(Currencies::free_balance(LP_USSD_EDF, &EcdpLoansModule::account_id()), 1000);
+		assert_eq!(EcdpUssdTreasuryModule::debit_pool(), 0);
+		assert_eq!(Currencies::free_balance(USSD, &EcdpUssdTreasuryModule::account_id()), 0);
+		assert_eq!(Currencies::free_balance(EDF, &EcdpUssdTreasuryModule::account_id()), 0);
+		assert_eq!(
+			Currencies::free_balance(LP_USSD_EDF, &EcdpUssdTreasuryModule::account_id()),
+			0
+		);
+		assert_eq!(MockEcdpAuctionsManager::auction(), None);
+
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			LP_USSD_EDF,
+			Change::NewValue(Some(Ratio::max_value())),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		assert_ok!(EcdpUssdEngineModule::liquidate_unsafe_cdp(ALICE, LP_USSD_EDF));
 		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::LiquidateUnsafeCDP {
 			collateral_type: LP_USSD_EDF,
 			owner: ALICE,
@@ -1101,11 +1841,12 @@ fn liquidate_unsafe_cdp_of_lp_ussd_edf_and_ussd_take_whole_target() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			LP_USSD_EDF,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		setup_default_collateral(EDF);
 		setup_default_collateral(USSD);
@@ -1153,11 +1894,12 @@ fn liquidate_unsafe_cdp_of_lp_ussd_edf_and_ussd_take_whole_target() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			LP_USSD_EDF,
-			Change::NoChange,
 			Change::NewValue(Some(Ratio::max_value())),
 			Change::NoChange,
 			Change::NoChange,
 			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
 		));
 
 		assert_ok!(EcdpUssdEngineModule::liquidate_unsafe_cdp(ALICE, LP_USSD_EDF));
@@ -1199,11 +1941,12 @@ fn liquidate_unsafe_cdp_of_lp_ussd_edf_and_create_edf_auction() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			LP_USSD_EDF,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(2, 1))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		setup_default_collateral(EDF);
 		setup_default_collateral(USSD);
@@ -1251,11 +1994,12 @@ fn liquidate_unsafe_cdp_of_lp_ussd_edf_and_create_edf_auction() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			LP_USSD_EDF,
-			Change::NoChange,
 			Change::NewValue(Some(Ratio::max_value())),
 			Change::NoChange,
 			Change::NoChange,
 			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
 		));
 
 		assert_ok!(EcdpUssdEngineModule::liquidate_unsafe_cdp(ALICE, LP_USSD_EDF));
@@ -1297,11 +2041,12 @@ fn settle_cdp_has_debit_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 100, 0));
 		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
@@ -1347,11 +2092,12 @@ fn close_cdp_has_debit_by_dex_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 
 		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 100, 0));
@@ -1376,11 +2122,12 @@ fn close_cdp_has_debit_by_dex_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NoChange,
 			Change::NewValue(Some(Ratio::saturating_from_rational(5, 2))),
 			Change::NoChange,
 			Change::NoChange,
 			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
 		));
 		assert_noop!(
 			EcdpUssdEngineModule::close_cdp_has_debit_by_dex(ALICE, BTC, 100),
@@ -1390,11 +2137,12 @@ fn close_cdp_has_debit_by_dex_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NoChange,
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NoChange,
 			Change::NoChange,
 			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
 		));
 
 		// max collateral amount limit swap
@@ -1448,11 +2196,12 @@ fn close_cdp_has_debit_by_swap_on_alternative_path() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 
 		assert_eq!(EdfisSwapModule::get_liquidity_pool(BTC, SEE), (100, 1000));
@@ -1468,11 +2217,12 @@ fn close_cdp_has_debit_by_swap_on_alternative_path() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NoChange,
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NoChange,
 			Change::NoChange,
 			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
 		));
 		assert_ok!(EcdpUssdEngineModule::close_cdp_has_debit_by_dex(ALICE, BTC, 100));
 		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::CloseCDPInDebitByDEX {
@@ -1532,11 +2282,12 @@ fn offchain_worker_works_cdp() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NoChange,
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
 			Change::NoChange,
 			Change::NoChange,
 			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
 		));
 		run_to_block_offchain(System::block_number() + collateral_currencies_num);
 
@@ -1556,29 +2307,85 @@ fn offchain_worker_works_cdp() {
 		}
 		// empty offchain tx pool (Bob was not liquidated)
 		assert!(pool_state.write().transactions.pop().is_none());
-		// alice is liquidated but bob is not
-		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).debit, 0);
-		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).collateral, 0);
-		assert_eq!(EcdpLoansModule::positions(BTC, BOB).debit, 100);
-		assert_eq!(EcdpLoansModule::positions(BTC, BOB).collateral, 100);
+		// alice is liquidated but bob is not
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).collateral, 0);
+		assert_eq!(EcdpLoansModule::positions(BTC, BOB).debit, 100);
+		assert_eq!(EcdpLoansModule::positions(BTC, BOB).collateral, 100);
+
+		// emergency shutdown will settle Bobs debit position
+		mock_shutdown();
+		assert!(MockEcdpEmergencyShutdown::is_shutdown());
+		run_to_block_offchain(System::block_number() + collateral_currencies_num);
+		// offchain worker will settle bob's position
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		if let MockCall::EcdpUssdEngineModule(crate::Call::settle {
+			currency_id: currency_call,
+			who: who_call,
+		}) = tx.call
+		{
+			assert_ok!(EcdpUssdEngineModule::settle(RuntimeOrigin::none(), currency_call, who_call));
+		}
+		// emergency shutdown settles bob's debit position
+		assert_eq!(EcdpLoansModule::positions(BTC, BOB).debit, 0);
+		assert_eq!(EcdpLoansModule::positions(BTC, BOB).collateral, 90);
+	});
+}
+
+#[test]
+fn offchain_worker_protects_cdp_before_liquidation() {
+	let (offchain, _offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut ext = ExtBuilder::default().build();
+	ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(OffchainDbExt::new(offchain));
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		setup_protected_collateral();
+		let collateral_currencies_num = CollateralCurrencyIds::<Runtime>::get().len() as u64;
+		assert_ok!(EcdpUssdEngineModule::set_liquidation_protection(
+			&ALICE,
+			EDF,
+			Some(ProtectionRule {
+				trigger_ratio: Ratio::saturating_from_rational(9, 5),
+				target_ratio: Ratio::saturating_from_rational(2, 1),
+				action: ProtectionAction::TopUp { max_amount: 100 },
+			})
+		));
+
+		// the rule is not triggered
+		run_to_block_offchain(System::block_number() + collateral_currencies_num);
+		assert!(pool_state.write().transactions.pop().is_none());
 
-		// emergency shutdown will settle Bobs debit position
-		mock_shutdown();
-		assert!(MockEcdpEmergencyShutdown::is_shutdown());
+		// the ECDP is unsafe, both protection and liquidation are submitted
+		MockPriceSource::set_price(EDF, Some(Price::saturating_from_rational(7, 1)));
 		run_to_block_offchain(System::block_number() + collateral_currencies_num);
-		// offchain worker will settle bob's position
-		let tx = pool_state.write().transactions.pop().unwrap();
-		let tx = Extrinsic::decode(&mut &*tx).unwrap();
-		if let MockCall::EcdpUssdEngineModule(crate::Call::settle {
+		let liquidation_tx = pool_state.write().transactions.pop().unwrap();
+		let liquidation_tx = Extrinsic::decode(&mut &*liquidation_tx).unwrap();
+		assert!(matches!(
+			liquidation_tx.call,
+			MockCall::EcdpUssdEngineModule(crate::Call::liquidate { .. })
+		));
+		let protection_tx = pool_state.write().transactions.pop().unwrap();
+		let protection_tx = Extrinsic::decode(&mut &*protection_tx).unwrap();
+		if let MockCall::EcdpUssdEngineModule(crate::Call::protect {
 			currency_id: currency_call,
 			who: who_call,
-		}) = tx.call
+		}) = protection_tx.call
 		{
-			assert_ok!(EcdpUssdEngineModule::settle(RuntimeOrigin::none(), currency_call, who_call));
+			assert_ok!(EcdpUssdEngineModule::protect(RuntimeOrigin::none(), currency_call, who_call));
 		}
-		// emergency shutdown settles bob's debit position
-		assert_eq!(EcdpLoansModule::positions(BTC, BOB).debit, 0);
-		assert_eq!(EcdpLoansModule::positions(BTC, BOB).collateral, 90);
+		assert!(pool_state.write().transactions.pop().is_none());
+
+		// the ECDP is restored to the target ratio before liquidation
+		assert_eq!(EcdpLoansModule::positions(EDF, ALICE).collateral, 143);
+		assert_noop!(
+			EcdpUssdEngineModule::liquidate(RuntimeOrigin::none(), EDF, ALICE),
+			Error::<Runtime>::MustBeUnsafe
+		);
 	});
 }
 
@@ -1598,11 +2405,12 @@ fn offchain_worker_iteration_limit_works() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 
 		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 100, 500));
@@ -1611,11 +2419,12 @@ fn offchain_worker_iteration_limit_works() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NoChange,
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
 			Change::NoChange,
 			Change::NoChange,
 			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
 		));
 		run_to_block_offchain(2);
 		let tx = pool_state.write().transactions.pop().unwrap();
@@ -1671,11 +2480,12 @@ fn offchain_default_max_iterator_works() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 
 		System::set_block_number(1);
@@ -1694,11 +2504,12 @@ fn offchain_default_max_iterator_works() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NoChange,
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
 			Change::NoChange,
 			Change::NoChange,
 			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
 		));
 		run_to_block_offchain(2);
 		// should only run 1000 iterations stopping due to DEFAULT_MAX_ITERATIONS
@@ -1715,11 +2526,12 @@ fn minimal_collateral_works() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		// Check position fails if collateral is too small
 		assert_noop!(
@@ -1876,3 +2688,440 @@ fn liquidation_fails_if_insufficient_repayment() {
 		);
 	});
 }
+
+#[test]
+fn set_flash_mint_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EcdpUssdEngineModule::set_flash_mint_params(
+				RuntimeOrigin::signed(BOB),
+				Change::NewValue(10000),
+				Change::NewValue(Rate::saturating_from_rational(1, 100)),
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			EcdpUssdEngineModule::set_flash_mint_params(
+				RuntimeOrigin::signed(ALICE),
+				Change::NoChange,
+				Change::NewValue(Rate::saturating_from_rational(101, 100)),
+			),
+			Error::<Runtime>::InvalidRate
+		);
+
+		assert_ok!(EcdpUssdEngineModule::set_flash_mint_params(
+			RuntimeOrigin::signed(ALICE),
+			Change::NewValue(10000),
+			Change::NewValue(Rate::saturating_from_rational(1, 100)),
+		));
+		System::assert_has_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::FlashMintCeilingUpdated {
+			new_ceiling: 10000,
+		}));
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::FlashMintFeeRateUpdated {
+			new_fee_rate: Rate::saturating_from_rational(1, 100),
+		}));
+		assert_eq!(EcdpUssdEngineModule::flash_mint_ceiling(), 10000);
+		assert_eq!(EcdpUssdEngineModule::flash_mint_fee_rate(), Rate::saturating_from_rational(1, 100));
+		assert_eq!(EcdpUssdEngineModule::max_flash_mint(), 10000);
+		assert_eq!(EcdpUssdEngineModule::flash_fee(5000), 50);
+	});
+}
+
+#[test]
+fn flash_mint_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdEngineModule::set_flash_mint_params(
+			RuntimeOrigin::signed(ALICE),
+			Change::NewValue(10000),
+			Change::NewValue(Rate::saturating_from_rational(1, 100)),
+		));
+
+		// exceed the ceiling
+		assert_noop!(
+			EcdpUssdEngineModule::flash_mint(RuntimeOrigin::signed(CAROL), 10001, vec![]),
+			Error::<Runtime>::ExceedFlashMintCeiling
+		);
+		// too many calls
+		assert_noop!(
+			EcdpUssdEngineModule::flash_mint(
+				RuntimeOrigin::signed(CAROL),
+				1000,
+				vec![MockCall::System(frame_system::Call::remark { remark: vec![] }); 5],
+			),
+			Error::<Runtime>::TooManyFlashMintCalls
+		);
+		// the fee can not be paid
+		assert_noop!(
+			EcdpUssdEngineModule::flash_mint(RuntimeOrigin::signed(ALICE), 1000, vec![]),
+			Error::<Runtime>::FlashMintNotRepaid
+		);
+		// the minted USSD is transferred away
+		assert_noop!(
+			EcdpUssdEngineModule::flash_mint(
+				RuntimeOrigin::signed(CAROL),
+				5000,
+				vec![MockCall::Currencies(orml_currencies::Call::transfer {
+					dest: BOB,
+					currency_id: USSD,
+					amount: 5001,
+				})],
+			),
+			Error::<Runtime>::FlashMintNotRepaid
+		);
+		// error of the calls is returned
+		assert_noop!(
+			EcdpUssdEngineModule::flash_mint(
+				RuntimeOrigin::signed(CAROL),
+				5000,
+				vec![MockCall::Currencies(orml_currencies::Call::transfer {
+					dest: BOB,
+					currency_id: USSD,
+					amount: 20000,
+				})],
+			),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+		// nested flash mint is limited by the outstanding amount
+		assert_noop!(
+			EcdpUssdEngineModule::flash_mint(
+				RuntimeOrigin::signed(CAROL),
+				6000,
+				vec![MockCall::EcdpUssdEngineModule(crate::Call::flash_mint {
+					amount: 5000,
+					calls: vec![],
+				})],
+			),
+			Error::<Runtime>::ExceedFlashMintCeiling
+		);
+
+		assert_ok!(EcdpUssdEngineModule::flash_mint(
+			RuntimeOrigin::signed(CAROL),
+			5000,
+			vec![MockCall::Currencies(orml_currencies::Call::transfer {
+				dest: BOB,
+				currency_id: USSD,
+				amount: 5000,
+			})],
+		));
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::FlashMinted {
+			receiver: CAROL,
+			amount: 5000,
+			fee: 50,
+		}));
+		assert_eq!(Currencies::free_balance(USSD, &CAROL), 4950);
+		assert_eq!(Currencies::free_balance(USSD, &BOB), 5000);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 50);
+		assert_eq!(Currencies::total_issuance(USSD), 10000);
+		assert_eq!(EcdpUssdEngineModule::flash_mint_outstanding(), 0);
+
+		mock_shutdown();
+		assert_eq!(EcdpUssdEngineModule::max_flash_mint(), 0);
+		assert_noop!(
+			EcdpUssdEngineModule::flash_mint(RuntimeOrigin::signed(CAROL), 1000, vec![]),
+			Error::<Runtime>::AlreadyShutdown
+		);
+	});
+}
+
+#[test]
+fn flash_mint_to_contract_work() {
+	let receiver = flash_mint_receiver_addr();
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdEngineModule::set_flash_mint_params(
+			RuntimeOrigin::signed(ALICE),
+			Change::NewValue(10000),
+			Change::NewValue(Rate::saturating_from_rational(1, 100)),
+		));
+
+		MockFlashMintEvmBridge::set_repay(false);
+		assert_noop!(
+			EcdpUssdEngineModule::flash_mint_to_contract(RuntimeOrigin::signed(ALICE), 1000, receiver, vec![1]),
+			Error::<Runtime>::FlashMintNotRepaid
+		);
+
+		MockFlashMintEvmBridge::set_repay(true);
+		assert_ok!(EcdpUssdEngineModule::flash_mint_to_contract(
+			RuntimeOrigin::signed(ALICE),
+			1000,
+			receiver,
+			vec![1, 2, 3]
+		));
+		let receiver_account_id = <Runtime as Config>::EvmAddressMapping::get_account_id(&receiver);
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::FlashMinted {
+			receiver: receiver_account_id.clone(),
+			amount: 1000,
+			fee: 10,
+		}));
+		assert_eq!(
+			MockFlashMintEvmBridge::flash_minted(),
+			(
+				receiver,
+				<Runtime as Config>::EvmAddressMapping::get_or_create_evm_address(&ALICE),
+				1000,
+				10,
+				vec![1, 2, 3]
+			)
+		);
+		assert_eq!(Currencies::free_balance(USSD, &receiver_account_id), 0);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 10);
+	});
+}
+
+#[test]
+fn set_keeper_reward_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EcdpUssdEngineModule::set_keeper_reward_params(
+				RuntimeOrigin::signed(BOB),
+				Change::NewValue(Rate::saturating_from_rational(1, 2)),
+				Change::NewValue(100),
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			EcdpUssdEngineModule::set_keeper_reward_params(
+				RuntimeOrigin::signed(ALICE),
+				Change::NewValue(Rate::saturating_from_rational(3, 2)),
+				Change::NoChange,
+			),
+			Error::<Runtime>::InvalidRate
+		);
+
+		assert_ok!(EcdpUssdEngineModule::set_keeper_reward_params(
+			RuntimeOrigin::signed(ALICE),
+			Change::NewValue(Rate::saturating_from_rational(1, 2)),
+			Change::NewValue(100),
+		));
+		System::assert_has_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::KeeperRewardRateUpdated {
+			new_reward_rate: Rate::saturating_from_rational(1, 2),
+		}));
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::MaxKeeperRewardUpdated {
+			new_max_reward: 100,
+		}));
+		assert_eq!(EcdpUssdEngineModule::keeper_reward_rate(), Rate::saturating_from_rational(1, 2));
+		assert_eq!(EcdpUssdEngineModule::max_keeper_reward(), 100);
+	});
+}
+
+#[test]
+fn keeper_liquidate_unsafe_cdp_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+		));
+		assert_ok!(EcdpUssdEngineModule::set_keeper_reward_params(
+			RuntimeOrigin::signed(ALICE),
+			Change::NewValue(Rate::saturating_from_rational(1, 2)),
+			Change::NewValue(100),
+		));
+		setup_default_collateral(EDF);
+		setup_default_collateral(USSD);
+		assert_ok!(EdfisSwapModule::add_liquidity(
+			RuntimeOrigin::signed(CAROL),
+			BTC,
+			USSD,
+			100,
+			121,
+			0,
+			false
+		));
+		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+
+		// the fee is charged for liquidating a safe ECDP
+		assert_noop!(
+			EcdpUssdEngineModule::liquidate(RuntimeOrigin::signed(BOB), BTC, ALICE),
+			Error::<Runtime>::MustBeUnsafe
+		);
+
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Ratio::max_value())),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		let post_info = EcdpUssdEngineModule::liquidate(RuntimeOrigin::signed(BOB), BTC, ALICE).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		System::assert_has_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: BTC,
+			owner: ALICE,
+			collateral_amount: 100,
+			bad_debt_value: 50,
+			target_amount: 60,
+		}));
+		// half of the penalty is rewarded to the keeper
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::KeeperRewarded {
+			keeper: BOB,
+			collateral_type: BTC,
+			owner: ALICE,
+			reward: 5,
+		}));
+		assert_eq!(Currencies::free_balance(USSD, &BOB), 5);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 55);
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).debit, 0);
+
+		// the fee is charged if the ECDP is already liquidated
+		assert_noop!(
+			EcdpUssdEngineModule::liquidate(RuntimeOrigin::signed(BOB), BTC, ALICE),
+			Error::<Runtime>::MustBeUnsafe
+		);
+	});
+}
+
+#[test]
+fn keeper_is_not_rewarded_before_penalty_is_collected() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+		));
+		assert_ok!(EcdpUssdEngineModule::set_keeper_reward_params(
+			RuntimeOrigin::signed(ALICE),
+			Change::NewValue(Rate::saturating_from_rational(1, 2)),
+			Change::NewValue(100),
+		));
+		setup_default_collateral(USSD);
+		assert_ok!(EcdpUssdTreasuryModule::on_system_surplus(100));
+		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		// without DEX liquidity the collateral goes to auction, the penalty is not collected yet
+		let post_info = EcdpUssdEngineModule::liquidate(RuntimeOrigin::signed(BOB), BTC, ALICE).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		assert_eq!(EcdpUssdTreasuryModule::debit_pool(), 50);
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).debit, 0);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::EcdpUssdEngineModule(crate::Event::KeeperRewarded { .. })
+		)));
+		assert_eq!(Currencies::free_balance(USSD, &BOB), 0);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 100);
+	});
+}
+
+#[test]
+fn keeper_settle_cdp_has_debit_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+		));
+		assert_ok!(EcdpUssdEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+
+		mock_shutdown();
+		let post_info = EcdpUssdEngineModule::settle(RuntimeOrigin::signed(BOB), BTC, ALICE).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		System::assert_last_event(RuntimeEvent::EcdpUssdEngineModule(crate::Event::SettleCDPInDebit {
+			collateral_type: BTC,
+			owner: ALICE,
+		}));
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).debit, 0);
+
+		// the fee is charged if the ECDP has no debit
+		assert_noop!(
+			EcdpUssdEngineModule::settle(RuntimeOrigin::signed(BOB), BTC, ALICE),
+			Error::<Runtime>::NoDebitValue
+		);
+	});
+}
+
+#[test]
+fn migrate_risk_management_params_to_v1_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		StorageVersion::new(0).put::<EcdpUssdEngineModule>();
+		let key = CollateralParams::<Runtime>::hashed_key_for(BTC);
+		let old = RiskManagementParamsV0 {
+			maximum_total_debit_value: 10000,
+			liquidation_ratio: Some(Ratio::saturating_from_rational(3, 2)),
+			liquidation_penalty: None,
+			required_collateral_ratio: Some(Ratio::saturating_from_rational(9, 5)),
+		};
+		frame_support::storage::unhashed::put(&key, &old);
+
+		<MigrateRiskManagementParamsToV1<Runtime> as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(
+			frame_support::storage::unhashed::get::<RiskManagementParamsV1>(&key),
+			Some(RiskManagementParamsV1 {
+				maximum_total_debit_value: 10000,
+				liquidation_ratio: Some(Ratio::saturating_from_rational(3, 2)),
+				liquidation_penalty: None,
+				required_collateral_ratio: Some(Ratio::saturating_from_rational(9, 5)),
+				partial_liquidation_target_ratio: None,
+			})
+		);
+		assert_eq!(StorageVersion::get::<EcdpUssdEngineModule>(), 1);
+
+		// runs only once
+		frame_support::storage::unhashed::put(&key, &old);
+		<MigrateRiskManagementParamsToV1<Runtime> as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(frame_support::storage::unhashed::get::<RiskManagementParamsV0>(&key), Some(old));
+	});
+}
+
+#[test]
+fn migrate_risk_management_params_to_v2_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		StorageVersion::new(0).put::<EcdpUssdEngineModule>();
+		let old = RiskManagementParamsV0 {
+			maximum_total_debit_value: 10000,
+			liquidation_ratio: Some(Ratio::saturating_from_rational(3, 2)),
+			liquidation_penalty: None,
+			required_collateral_ratio: Some(Ratio::saturating_from_rational(9, 5)),
+		};
+		frame_support::storage::unhashed::put(&CollateralParams::<Runtime>::hashed_key_for(BTC), &old);
+
+		// a chain at version 0 goes through both layouts
+		<(MigrateRiskManagementParamsToV1<Runtime>, MigrateRiskManagementParamsToV2<Runtime>) as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(
+			EcdpUssdEngineModule::collateral_params(BTC),
+			Some(RiskManagementParams {
+				maximum_total_debit_value: 10000,
+				liquidation_ratio: Some(Ratio::saturating_from_rational(3, 2)),
+				liquidation_penalty: None,
+				required_collateral_ratio: Some(Ratio::saturating_from_rational(9, 5)),
+				partial_liquidation_target_ratio: None,
+				stability_fee_per_sec: None,
+			})
+		);
+		assert_eq!(StorageVersion::get::<EcdpUssdEngineModule>(), 2);
+	});
+}
@@ -49,17 +49,32 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for module_cdp_engine.
 pub trait WeightInfo {
+	fn on_initialize(c: u32, ) -> Weight;
 	fn set_collateral_params() -> Weight;
 	fn liquidate_by_auction(b: u32) -> Weight;
 	fn liquidate_by_dex() -> Weight;
 	fn settle() -> Weight;
 	fn register_liquidation_contract() -> Weight;
 	fn deregister_liquidation_contract() -> Weight;
+	fn protect() -> Weight;
+	fn set_flash_mint_params() -> Weight;
+	fn flash_mint(c: u32, ) -> Weight;
+	fn flash_mint_to_contract() -> Weight;
+	fn set_keeper_reward_params() -> Weight;
+	fn set_basket_haircut() -> Weight;
 }
 
 /// Weights for module_cdp_engine using the Setheum node and recommended hardware.
 pub struct SetheumWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
+	fn on_initialize(c: u32, ) -> Weight {
+		Weight::from_parts(33_000_000, 0)
+			.saturating_add(Weight::from_parts(12_000_000, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().reads((4 as u64).saturating_mul(c as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(c as u64)))
+	}
 	fn set_collateral_params() -> Weight {
 		Weight::from_parts(37_000_000, 0)
 			.saturating_add(T::DbWeight::get().reads(1 as u64))
@@ -90,10 +105,47 @@ impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(11 as u64))
 			.saturating_add(T::DbWeight::get().writes(8 as u64))
 	}
+	fn protect() -> Weight {
+		Weight::from_parts(252_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(22 as u64))
+			.saturating_add(T::DbWeight::get().writes(13 as u64))
+	}
+	fn set_flash_mint_params() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn flash_mint(c: u32, ) -> Weight {
+		Weight::from_parts(78_000_000, 0)
+			.saturating_add(Weight::from_parts(4_000_000, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	fn flash_mint_to_contract() -> Weight {
+		Weight::from_parts(96_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	fn set_keeper_reward_params() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn set_basket_haircut() -> Weight {
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	fn on_initialize(c: u32, ) -> Weight {
+		Weight::from_parts(33_000_000, 0)
+			.saturating_add(Weight::from_parts(12_000_000, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().reads((4 as u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(c as u64)))
+	}
 	fn set_collateral_params() -> Weight {
 		Weight::from_parts(37_000_000, 0)
 			.saturating_add(RocksDbWeight::get().reads(1 as u64))
@@ -125,4 +177,33 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(11 as u64))
 			.saturating_add(RocksDbWeight::get().writes(8 as u64))
 	}
+	fn protect() -> Weight {
+		Weight::from_parts(252_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(22 as u64))
+			.saturating_add(RocksDbWeight::get().writes(13 as u64))
+	}
+	fn set_flash_mint_params() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn flash_mint(c: u32, ) -> Weight {
+		Weight::from_parts(78_000_000, 0)
+			.saturating_add(Weight::from_parts(4_000_000, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+	}
+	fn flash_mint_to_contract() -> Weight {
+		Weight::from_parts(96_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
+	fn set_keeper_reward_params() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn set_basket_haircut() -> Weight {
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
 }
@@ -0,0 +1,349 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # ECDP USSD PSM Module
+//!
+//! ## Overview
+//!
+//! The Peg Stability Module of Slick USD ECDP Protocol. It allows anyone to swap
+//! the stable assets approved by governance 1:1 to newly minted USSD, and swap
+//! USSD back to the stable assets held by this module, which keeps the price of
+//! USSD close to its peg.
+//!
+//! The USSD minted against every stable asset is capped by its debt ceiling. The
+//! fees charged on both directions are paid to the surplus pool of ECDP USSD
+//! Treasury. Swaps can be paused by `transaction-pause` in an emergency.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional, PalletId};
+use frame_system::pallet_prelude::*;
+use module_support::{EcdpUssdTreasury, Erc20InfoMapping, Rate};
+use orml_traits::{Change, MultiCurrency};
+use parity_scale_codec::MaxEncodedLen;
+use primitives::{Balance, CurrencyId};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AccountIdConversion, One, Zero},
+	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
+};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// Parameters of a stable asset approved for the PSM
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default, TypeInfo, MaxEncodedLen)]
+pub struct StableAssetParams {
+	/// Fee rate charged on the USSD minted when swapping the stable asset to
+	/// USSD.
+	pub fee_in: Rate,
+
+	/// Fee rate charged on the USSD burned when swapping USSD to the stable
+	/// asset.
+	pub fee_out: Rate,
+
+	/// Maximum amount of USSD that can be minted against the stable asset.
+	pub debt_ceiling: Balance,
+}
+
+// typedef to help polkadot.js disambiguate Change with different generic
+// parameters
+type ChangeRate = Change<Rate>;
+type ChangeBalance = Change<Balance>;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin which may approve stable assets and update their
+		/// parameters.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Currency for transfer assets
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// Stablecoin currency id
+		#[pallet::constant]
+		type GetUSSDCurrencyId: Get<CurrencyId>;
+
+		/// The ECDP USSD treasury to issue and burn USSD, and to receive the fees
+		type EcdpUssdTreasury: EcdpUssdTreasury<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
+
+		/// Mapping to get the decimals of ERC20 tokens and foreign assets
+		type Erc20InfoMapping: Erc20InfoMapping;
+
+		/// The PSM's module id, keeps the stable assets swapped in.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The currency is not an approved stable asset
+		NotStableAsset,
+		/// The currency cannot be approved as stable asset
+		InvalidStableAsset,
+		/// Invalid fee rate
+		InvalidRate,
+		/// The USSD minted against the stable asset would exceed its debt ceiling
+		ExceedDebtCeiling,
+		/// The swap amount is too small
+		AmountTooSmall,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The parameters of a stable asset updated.
+		StableAssetParamsUpdated {
+			currency_id: CurrencyId,
+			params: StableAssetParams,
+		},
+		/// Swapped the stable asset to USSD.
+		SwappedToUSSD {
+			who: T::AccountId,
+			currency_id: CurrencyId,
+			supply_amount: Balance,
+			target_amount: Balance,
+			fee: Balance,
+		},
+		/// Swapped USSD to the stable asset.
+		SwappedFromUSSD {
+			who: T::AccountId,
+			currency_id: CurrencyId,
+			supply_amount: Balance,
+			target_amount: Balance,
+			fee: Balance,
+		},
+	}
+
+	/// Mapping from approved stable asset to its parameters
+	///
+	/// StableAssets: map CurrencyId => Option<StableAssetParams>
+	#[pallet::storage]
+	#[pallet::getter(fn stable_assets)]
+	pub type StableAssets<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, StableAssetParams, OptionQuery>;
+
+	/// The amount of USSD minted against the stable asset
+	///
+	/// StableAssetDebits: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn stable_asset_debits)]
+	pub type StableAssetDebits<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Swap the stable asset to USSD 1:1, `fee_in` of the USSD is paid to
+		/// the surplus pool of ECDP USSD Treasury.
+		///
+		/// - `currency_id`: the approved stable asset.
+		/// - `amount`: the amount of the stable asset to swap.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::swap_to_ussd())]
+		pub fn swap_to_ussd(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_swap_to_ussd(&who, currency_id, amount)?;
+			Ok(())
+		}
+
+		/// Swap USSD to the stable asset 1:1, `fee_out` of the USSD is paid to
+		/// the surplus pool of ECDP USSD Treasury in addition.
+		///
+		/// - `currency_id`: the approved stable asset.
+		/// - `amount`: the amount of the stable asset to receive.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::swap_from_ussd())]
+		pub fn swap_from_ussd(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_swap_from_ussd(&who, currency_id, amount)?;
+			Ok(())
+		}
+
+		/// Approve the stable asset or update its parameters
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: the stable asset.
+		/// - `fee_in`: fee rate of swapping the stable asset to USSD.
+		/// - `fee_out`: fee rate of swapping USSD to the stable asset.
+		/// - `debt_ceiling`: maximum amount of USSD minted against the stable asset, set it to zero
+		///   to stop swapping the stable asset to USSD.
+		#[pallet::call_index(2)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_stable_asset_params(), DispatchClass::Operational))]
+		pub fn set_stable_asset_params(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			fee_in: ChangeRate,
+			fee_out: ChangeRate,
+			debt_ceiling: ChangeBalance,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				currency_id != T::GetUSSDCurrencyId::get() && T::Erc20InfoMapping::decimals(currency_id).is_some(),
+				Error::<T>::InvalidStableAsset
+			);
+
+			let mut params = Self::stable_assets(currency_id).unwrap_or_default();
+			if let Change::NewValue(rate) = fee_in {
+				ensure!(rate <= Rate::one(), Error::<T>::InvalidRate);
+				params.fee_in = rate;
+			}
+			if let Change::NewValue(rate) = fee_out {
+				ensure!(rate <= Rate::one(), Error::<T>::InvalidRate);
+				params.fee_out = rate;
+			}
+			if let Change::NewValue(val) = debt_ceiling {
+				params.debt_ceiling = val;
+			}
+			StableAssets::<T>::insert(currency_id, &params);
+			Self::deposit_event(Event::StableAssetParamsUpdated { currency_id, params });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The PSM's account, keeps the stable assets swapped in.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// Convert `amount` of the stable asset to the amount of USSD of the same
+	/// value, by the decimals of them.
+	pub fn convert_to_ussd(currency_id: CurrencyId, amount: Balance, round_up: bool) -> Result<Balance, DispatchError> {
+		let asset_decimals = T::Erc20InfoMapping::decimals(currency_id).ok_or(Error::<T>::InvalidStableAsset)?;
+		let ussd_decimals =
+			T::Erc20InfoMapping::decimals(T::GetUSSDCurrencyId::get()).ok_or(Error::<T>::InvalidStableAsset)?;
+
+		if asset_decimals <= ussd_decimals {
+			let factor = 10u128
+				.checked_pow((ussd_decimals - asset_decimals).into())
+				.ok_or(ArithmeticError::Overflow)?;
+			Ok(amount.checked_mul(factor).ok_or(ArithmeticError::Overflow)?)
+		} else {
+			let factor = 10u128
+				.checked_pow((asset_decimals - ussd_decimals).into())
+				.ok_or(ArithmeticError::Overflow)?;
+			let ussd_amount = amount / factor;
+			if round_up && !(amount % factor).is_zero() {
+				Ok(ussd_amount.saturating_add(One::one()))
+			} else {
+				Ok(ussd_amount)
+			}
+		}
+	}
+
+	/// Swap `amount` of the stable asset to USSD, return the USSD amount
+	/// received by `who`.
+	#[transactional]
+	pub fn do_swap_to_ussd(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		let params = Self::stable_assets(currency_id).ok_or(Error::<T>::NotStableAsset)?;
+		// round down so that the minted USSD is always backed
+		let ussd_amount = Self::convert_to_ussd(currency_id, amount, false)?;
+		ensure!(!ussd_amount.is_zero(), Error::<T>::AmountTooSmall);
+
+		StableAssetDebits::<T>::try_mutate(currency_id, |debit| -> DispatchResult {
+			let new_debit = debit.checked_add(ussd_amount).ok_or(ArithmeticError::Overflow)?;
+			ensure!(new_debit <= params.debt_ceiling, Error::<T>::ExceedDebtCeiling);
+			*debit = new_debit;
+			Ok(())
+		})?;
+		T::Currency::transfer(currency_id, who, &Self::account_id(), amount)?;
+
+		let fee = params.fee_in.saturating_mul_int(ussd_amount);
+		let target_amount = ussd_amount.saturating_sub(fee);
+		T::EcdpUssdTreasury::issue_debit(who, target_amount, true)?;
+		if !fee.is_zero() {
+			T::EcdpUssdTreasury::on_system_surplus(fee)?;
+		}
+
+		Self::deposit_event(Event::SwappedToUSSD {
+			who: who.clone(),
+			currency_id,
+			supply_amount: amount,
+			target_amount,
+			fee,
+		});
+		Ok(target_amount)
+	}
+
+	/// Swap USSD to `amount` of the stable asset, return the USSD amount paid
+	/// by `who`.
+	#[transactional]
+	pub fn do_swap_from_ussd(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		let params = Self::stable_assets(currency_id).ok_or(Error::<T>::NotStableAsset)?;
+		ensure!(!amount.is_zero(), Error::<T>::AmountTooSmall);
+		// round up so that the burned USSD always covers the withdrawn stable asset
+		let ussd_amount = Self::convert_to_ussd(currency_id, amount, true)?;
+
+		StableAssetDebits::<T>::mutate(currency_id, |debit| *debit = debit.saturating_sub(ussd_amount));
+		T::EcdpUssdTreasury::burn_debit(who, ussd_amount)?;
+
+		let fee = params.fee_out.saturating_mul_int(ussd_amount);
+		if !fee.is_zero() {
+			T::EcdpUssdTreasury::deposit_surplus(who, fee)?;
+		}
+		T::Currency::transfer(currency_id, &Self::account_id(), who, amount)?;
+
+		let supply_amount = ussd_amount.saturating_add(fee);
+		Self::deposit_event(Event::SwappedFromUSSD {
+			who: who.clone(),
+			currency_id,
+			supply_amount,
+			target_amount: amount,
+			fee,
+		});
+		Ok(supply_amount)
+	}
+}
@@ -0,0 +1,249 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the ECDP USSD PSM module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	construct_runtime, ord_parameter_types, parameter_types,
+	traits::{ConstU32, ConstU64, EverythingBut, Nothing},
+};
+use frame_system::EnsureSignedBy;
+use module_support::Ratio;
+use orml_traits::parameter_type_with_key;
+use primitives::{currency::TokenInfo, evm::EvmAddress, Amount, TokenSymbol};
+use sp_core::H256;
+use sp_runtime::{traits::IdentityLookup, BuildStorage};
+use sp_std::str::FromStr;
+
+pub type AccountId = u128;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const USSD: CurrencyId = CurrencyId::Token(TokenSymbol::USSD);
+pub const EDF: CurrencyId = CurrencyId::Token(TokenSymbol::EDF);
+pub const USDC: CurrencyId = CurrencyId::ForeignAsset(0);
+
+pub fn dai() -> CurrencyId {
+	CurrencyId::Erc20(EvmAddress::from_str("0x0000000000000000000000000000000000000d41").unwrap())
+}
+
+mod ecdp_ussd_psm {
+	pub use super::super::*;
+}
+
+impl frame_system::Config for Runtime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Nonce = u64;
+	type RuntimeCall = RuntimeCall;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = EverythingBut<module_transaction_pause::PausedTransactionFilter<Runtime>>;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type CurrencyHooks = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Nothing;
+}
+
+ord_parameter_types! {
+	pub const One: AccountId = 1;
+}
+
+impl module_transaction_pause::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const GetUSSDCurrencyId: CurrencyId = USSD;
+	pub const TreasuryAccount: AccountId = 10;
+	pub const EcdpUssdPsmPalletId: PalletId = PalletId(*b"set/ussdpsm");
+}
+
+pub struct MockEcdpUssdTreasury;
+impl EcdpUssdTreasury<AccountId> for MockEcdpUssdTreasury {
+	type Balance = Balance;
+	type CurrencyId = CurrencyId;
+
+	fn get_surplus_pool() -> Balance {
+		Tokens::free_balance(USSD, &TreasuryAccount::get())
+	}
+
+	fn get_debit_pool() -> Balance {
+		unimplemented!()
+	}
+
+	fn get_total_collaterals(_: CurrencyId) -> Balance {
+		unimplemented!()
+	}
+
+	fn get_debit_proportion(_: Balance) -> Ratio {
+		unimplemented!()
+	}
+
+	fn on_system_debit(_: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn on_system_surplus(amount: Balance) -> DispatchResult {
+		Tokens::deposit(USSD, &TreasuryAccount::get(), amount)
+	}
+
+	fn issue_debit(who: &AccountId, debit: Balance, _: bool) -> DispatchResult {
+		Tokens::deposit(USSD, who, debit)
+	}
+
+	fn burn_debit(who: &AccountId, debit: Balance) -> DispatchResult {
+		Tokens::withdraw(USSD, who, debit)
+	}
+
+	fn deposit_surplus(from: &AccountId, surplus: Balance) -> DispatchResult {
+		Tokens::transfer(USSD, from, &TreasuryAccount::get(), surplus)
+	}
+
+	fn withdraw_surplus(_: &AccountId, _: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn deposit_collateral(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn withdraw_collateral(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+}
+
+pub struct MockErc20InfoMapping;
+impl Erc20InfoMapping for MockErc20InfoMapping {
+	fn name(_: CurrencyId) -> Option<Vec<u8>> {
+		None
+	}
+
+	fn symbol(_: CurrencyId) -> Option<Vec<u8>> {
+		None
+	}
+
+	fn decimals(currency_id: CurrencyId) -> Option<u8> {
+		match currency_id {
+			USDC => Some(6),
+			CurrencyId::Erc20(_) if currency_id == dai() => Some(18),
+			_ => currency_id.decimals(),
+		}
+	}
+
+	fn encode_evm_address(_: CurrencyId) -> Option<EvmAddress> {
+		None
+	}
+
+	fn decode_evm_address(_: EvmAddress) -> Option<CurrencyId> {
+		None
+	}
+}
+
+impl Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type Currency = Tokens;
+	type GetUSSDCurrencyId = GetUSSDCurrencyId;
+	type EcdpUssdTreasury = MockEcdpUssdTreasury;
+	type Erc20InfoMapping = MockErc20InfoMapping;
+	type PalletId = EcdpUssdPsmPalletId;
+	type WeightInfo = ();
+}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		EcdpUssdPsm: ecdp_ussd_psm,
+		Tokens: orml_tokens,
+		TransactionPause: module_transaction_pause,
+	}
+);
+
+pub struct ExtBuilder {
+	balances: Vec<(AccountId, CurrencyId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			balances: vec![
+				(ALICE, USDC, 1_000_000_000),
+				(ALICE, dai(), 1_000_000_000_000_000_000_000),
+				(BOB, USSD, 1_000_000_000_000_000),
+			],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::<Runtime>::default()
+			.build_storage()
+			.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			balances: self.balances,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		t.into()
+	}
+}
@@ -0,0 +1,233 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the ECDP USSD PSM module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{RuntimeCall, RuntimeEvent, *};
+use sp_runtime::traits::{BadOrigin, Dispatchable};
+
+fn setup_usdc() {
+	assert_ok!(EcdpUssdPsm::set_stable_asset_params(
+		RuntimeOrigin::signed(ALICE),
+		USDC,
+		Change::NewValue(Rate::saturating_from_rational(1, 100)),
+		Change::NewValue(Rate::saturating_from_rational(2, 100)),
+		Change::NewValue(150_000_000_000_000),
+	));
+}
+
+#[test]
+fn set_stable_asset_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EcdpUssdPsm::set_stable_asset_params(
+				RuntimeOrigin::signed(BOB),
+				USDC,
+				Change::NoChange,
+				Change::NoChange,
+				Change::NewValue(1000),
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			EcdpUssdPsm::set_stable_asset_params(
+				RuntimeOrigin::signed(ALICE),
+				USSD,
+				Change::NoChange,
+				Change::NoChange,
+				Change::NewValue(1000),
+			),
+			Error::<Runtime>::InvalidStableAsset
+		);
+		assert_noop!(
+			EcdpUssdPsm::set_stable_asset_params(
+				RuntimeOrigin::signed(ALICE),
+				CurrencyId::ForeignAsset(1),
+				Change::NoChange,
+				Change::NoChange,
+				Change::NewValue(1000),
+			),
+			Error::<Runtime>::InvalidStableAsset
+		);
+		assert_noop!(
+			EcdpUssdPsm::set_stable_asset_params(
+				RuntimeOrigin::signed(ALICE),
+				USDC,
+				Change::NewValue(Rate::saturating_from_rational(11, 10)),
+				Change::NoChange,
+				Change::NewValue(1000),
+			),
+			Error::<Runtime>::InvalidRate
+		);
+
+		setup_usdc();
+		let params = StableAssetParams {
+			fee_in: Rate::saturating_from_rational(1, 100),
+			fee_out: Rate::saturating_from_rational(2, 100),
+			debt_ceiling: 150_000_000_000_000,
+		};
+		System::assert_last_event(RuntimeEvent::EcdpUssdPsm(crate::Event::StableAssetParamsUpdated {
+			currency_id: USDC,
+			params: params.clone(),
+		}));
+		assert_eq!(EcdpUssdPsm::stable_assets(USDC), Some(params));
+
+		assert_ok!(EcdpUssdPsm::set_stable_asset_params(
+			RuntimeOrigin::signed(ALICE),
+			USDC,
+			Change::NoChange,
+			Change::NewValue(Rate::zero()),
+			Change::NoChange,
+		));
+		assert_eq!(
+			EcdpUssdPsm::stable_assets(USDC),
+			Some(StableAssetParams {
+				fee_in: Rate::saturating_from_rational(1, 100),
+				fee_out: Rate::zero(),
+				debt_ceiling: 150_000_000_000_000,
+			})
+		);
+	});
+}
+
+#[test]
+fn convert_to_ussd_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(EcdpUssdPsm::convert_to_ussd(USDC, 1, false), Ok(1_000_000));
+		assert_eq!(EcdpUssdPsm::convert_to_ussd(USDC, 1, true), Ok(1_000_000));
+		assert_eq!(EcdpUssdPsm::convert_to_ussd(dai(), 1_500_000, false), Ok(1));
+		assert_eq!(EcdpUssdPsm::convert_to_ussd(dai(), 1_500_000, true), Ok(2));
+		assert_eq!(EcdpUssdPsm::convert_to_ussd(dai(), 1_000_000, true), Ok(1));
+		assert_noop!(
+			EcdpUssdPsm::convert_to_ussd(CurrencyId::ForeignAsset(1), 1, false),
+			Error::<Runtime>::InvalidStableAsset
+		);
+	});
+}
+
+#[test]
+fn swap_to_ussd_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EcdpUssdPsm::swap_to_ussd(RuntimeOrigin::signed(ALICE), USDC, 100_000_000),
+			Error::<Runtime>::NotStableAsset
+		);
+
+		setup_usdc();
+		assert_ok!(EcdpUssdPsm::swap_to_ussd(RuntimeOrigin::signed(ALICE), USDC, 100_000_000));
+		System::assert_last_event(RuntimeEvent::EcdpUssdPsm(crate::Event::SwappedToUSSD {
+			who: ALICE,
+			currency_id: USDC,
+			supply_amount: 100_000_000,
+			target_amount: 99_000_000_000_000,
+			fee: 1_000_000_000_000,
+		}));
+		assert_eq!(Tokens::free_balance(USDC, &ALICE), 900_000_000);
+		assert_eq!(Tokens::free_balance(USSD, &ALICE), 99_000_000_000_000);
+		assert_eq!(Tokens::free_balance(USDC, &EcdpUssdPsm::account_id()), 100_000_000);
+		assert_eq!(MockEcdpUssdTreasury::get_surplus_pool(), 1_000_000_000_000);
+		assert_eq!(EcdpUssdPsm::stable_asset_debits(USDC), 100_000_000_000_000);
+
+		// the USSD minted against USDC is capped by the debt ceiling
+		assert_noop!(
+			EcdpUssdPsm::swap_to_ussd(RuntimeOrigin::signed(ALICE), USDC, 60_000_000),
+			Error::<Runtime>::ExceedDebtCeiling
+		);
+		assert_ok!(EcdpUssdPsm::swap_to_ussd(RuntimeOrigin::signed(ALICE), USDC, 50_000_000));
+		assert_eq!(EcdpUssdPsm::stable_asset_debits(USDC), 150_000_000_000_000);
+
+		// the stable asset amount worth less than 1 USSD unit cannot be swapped
+		assert_ok!(EcdpUssdPsm::set_stable_asset_params(
+			RuntimeOrigin::signed(ALICE),
+			dai(),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(1_000_000_000_000),
+		));
+		assert_noop!(
+			EcdpUssdPsm::swap_to_ussd(RuntimeOrigin::signed(ALICE), dai(), 999_999),
+			Error::<Runtime>::AmountTooSmall
+		);
+	});
+}
+
+#[test]
+fn swap_from_ussd_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		setup_usdc();
+		assert_ok!(EcdpUssdPsm::swap_to_ussd(RuntimeOrigin::signed(ALICE), USDC, 100_000_000));
+
+		assert_ok!(EcdpUssdPsm::swap_from_ussd(RuntimeOrigin::signed(BOB), USDC, 50_000_000));
+		System::assert_last_event(RuntimeEvent::EcdpUssdPsm(crate::Event::SwappedFromUSSD {
+			who: BOB,
+			currency_id: USDC,
+			supply_amount: 51_000_000_000_000,
+			target_amount: 50_000_000,
+			fee: 1_000_000_000_000,
+		}));
+		assert_eq!(Tokens::free_balance(USDC, &BOB), 50_000_000);
+		assert_eq!(Tokens::free_balance(USSD, &BOB), 949_000_000_000_000);
+		assert_eq!(Tokens::free_balance(USDC, &EcdpUssdPsm::account_id()), 50_000_000);
+		assert_eq!(MockEcdpUssdTreasury::get_surplus_pool(), 2_000_000_000_000);
+		assert_eq!(EcdpUssdPsm::stable_asset_debits(USDC), 50_000_000_000_000);
+
+		// cannot withdraw more than the stable asset held by PSM
+		assert_noop!(
+			EcdpUssdPsm::swap_from_ussd(RuntimeOrigin::signed(BOB), USDC, 60_000_000),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+	});
+}
+
+#[test]
+fn swap_can_be_paused_by_transaction_pause() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_usdc();
+		let swap_call = RuntimeCall::EcdpUssdPsm(crate::Call::swap_to_ussd {
+			currency_id: USDC,
+			amount: 100_000_000,
+		});
+
+		assert_ok!(TransactionPause::pause_transaction(
+			RuntimeOrigin::signed(ALICE),
+			b"EcdpUssdPsm".to_vec(),
+			b"swap_to_ussd".to_vec()
+		));
+		assert_noop!(
+			swap_call.clone().dispatch(RuntimeOrigin::signed(ALICE)),
+			frame_system::Error::<Runtime>::CallFiltered
+		);
+
+		assert_ok!(TransactionPause::unpause_transaction(
+			RuntimeOrigin::signed(ALICE),
+			b"EcdpUssdPsm".to_vec(),
+			b"swap_to_ussd".to_vec()
+		));
+		assert_ok!(swap_call.dispatch(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(Tokens::free_balance(USSD, &ALICE), 99_000_000_000_000);
+	});
+}
@@ -0,0 +1,86 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weights for module_ecdp_ussd_psm
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for module_ecdp_ussd_psm.
+pub trait WeightInfo {
+	fn swap_to_ussd() -> Weight;
+	fn swap_from_ussd() -> Weight;
+	fn set_stable_asset_params() -> Weight;
+}
+
+/// Weights for module_ecdp_ussd_psm using the Setheum node and recommended hardware.
+pub struct SetheumWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
+	// Storage: EcdpUssdPsm StableAssets (r:1 w:0)
+	// Storage: EcdpUssdPsm StableAssetDebits (r:1 w:1)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: Tokens TotalIssuance (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn swap_to_ussd() -> Weight {
+		Weight::from_parts(78_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+	}
+	// Storage: EcdpUssdPsm StableAssets (r:1 w:0)
+	// Storage: EcdpUssdPsm StableAssetDebits (r:1 w:1)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: Tokens TotalIssuance (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn swap_from_ussd() -> Weight {
+		Weight::from_parts(78_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+	}
+	// Storage: EcdpUssdPsm StableAssets (r:1 w:1)
+	fn set_stable_asset_params() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn swap_to_ussd() -> Weight {
+		Weight::from_parts(78_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+	}
+	fn swap_from_ussd() -> Weight {
+		Weight::from_parts(78_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+	}
+	fn set_stable_asset_params() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+}
@@ -25,6 +25,11 @@
 //! The ECDP USSD Treasury manages the bad debts generated by ECDPs,
 //! and handle excessive surplus or debits timely in order to keep the system healthy with low risk.
 //! It's the only entry for issuing/burning USSD.
+//!
+//! The surplus exceeding the surplus buffer is sold for EDF by surplus auctions,
+//! and the EDF paid is burned. The debit that cannot be offset by the surplus is
+//! covered by debit auctions, which mint EDF to buy USSD. Both are created when
+//! block start.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -33,7 +38,7 @@
 use frame_support::{pallet_prelude::*, transactional, PalletId};
 use frame_system::pallet_prelude::*;
 use module_support::{EcdpAuctionsManager, EcdpUssdTreasury, EcdpUssdTreasuryExtended, SwapManager, Ratio, Swap, SwapLimit};
-use orml_traits::{MultiCurrency, MultiCurrencyExtended};
+use orml_traits::{Change, MultiCurrency, MultiCurrencyExtended};
 use primitives::{Balance, CurrencyId};
 use sp_runtime::{
 	traits::{AccountIdConversion, One, Zero},
@@ -48,6 +53,8 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+type ChangeBalance = Change<Balance>;
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*; 
@@ -118,6 +125,14 @@ pub mod module {
 		},
 		/// The buffer amount of debit pool that will not be offset by suplus pool updated.
 		DebitOffsetBufferUpdated { amount: Balance },
+		/// The buffer amount of surplus pool that will not be auctioned updated.
+		SurplusBufferSizeUpdated { amount: Balance },
+		/// The fixed surplus amount per lot of surplus auction updated.
+		SurplusAuctionFixedSizeUpdated { amount: Balance },
+		/// The fixed debit amount per lot of debit auction updated.
+		DebitAuctionFixedSizeUpdated { amount: Balance },
+		/// The initial EDF amount per lot of debit auction updated.
+		InitialAmountPerDebitAuctionUpdated { amount: Balance },
 	}
 
 	/// The expected amount size for per lot collateral auction of specific
@@ -143,6 +158,38 @@ pub mod module {
 	#[pallet::getter(fn debit_offset_buffer)]
 	pub type DebitOffsetBuffer<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+	/// The buffer amount of surplus pool that will not be sold by surplus
+	/// auction.
+	///
+	/// SurplusBufferSize: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_buffer_size)]
+	pub type SurplusBufferSize<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The fixed surplus amount per lot of surplus auction. Surplus auctions
+	/// are disabled if zero.
+	///
+	/// SurplusAuctionFixedSize: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_auction_fixed_size)]
+	pub type SurplusAuctionFixedSize<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The fixed debit amount per lot of debit auction. Debit auctions are
+	/// disabled if zero.
+	///
+	/// DebitAuctionFixedSize: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn debit_auction_fixed_size)]
+	pub type DebitAuctionFixedSize<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The initial EDF amount for sale per lot of debit auction. Debit
+	/// auctions are disabled if zero.
+	///
+	/// InitialAmountPerDebitAuction: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn initial_amount_per_debit_auction)]
+	pub type InitialAmountPerDebitAuction<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T> {
@@ -166,6 +213,13 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Create surplus or debit auctions for excessive surplus or debits of
+		/// system when block start
+		fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
+			let created_auctions = Self::create_system_auctions();
+			T::WeightInfo::on_initialize(created_auctions)
+		}
+
 		/// Handle excessive surplus or debits of system when block end
 		fn on_finalize(_now: BlockNumberFor<T>) {
 			// offset the same amount between debit pool and surplus pool
@@ -281,6 +335,44 @@ pub mod module {
 			});
 			Ok(())
 		}
+
+		/// Update parameters of surplus auction and debit auction
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `surplus_buffer_size`: the surplus amount that will not be auctioned
+		/// - `surplus_auction_fixed_size`: the surplus amount per lot of surplus auction
+		/// - `debit_auction_fixed_size`: the debit amount per lot of debit auction
+		/// - `initial_amount_per_debit_auction`: the initial EDF amount for sale per lot of debit
+		///   auction
+		#[pallet::call_index(5)]
+		#[pallet::weight((T::WeightInfo::set_system_auction_params(), DispatchClass::Operational))]
+		pub fn set_system_auction_params(
+			origin: OriginFor<T>,
+			surplus_buffer_size: ChangeBalance,
+			surplus_auction_fixed_size: ChangeBalance,
+			debit_auction_fixed_size: ChangeBalance,
+			initial_amount_per_debit_auction: ChangeBalance,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Change::NewValue(amount) = surplus_buffer_size {
+				SurplusBufferSize::<T>::put(amount);
+				Self::deposit_event(Event::SurplusBufferSizeUpdated { amount });
+			}
+			if let Change::NewValue(amount) = surplus_auction_fixed_size {
+				SurplusAuctionFixedSize::<T>::put(amount);
+				Self::deposit_event(Event::SurplusAuctionFixedSizeUpdated { amount });
+			}
+			if let Change::NewValue(amount) = debit_auction_fixed_size {
+				DebitAuctionFixedSize::<T>::put(amount);
+				Self::deposit_event(Event::DebitAuctionFixedSizeUpdated { amount });
+			}
+			if let Change::NewValue(amount) = initial_amount_per_debit_auction {
+				InitialAmountPerDebitAuction::<T>::put(amount);
+				Self::deposit_event(Event::InitialAmountPerDebitAuctionUpdated { amount });
+			}
+			Ok(())
+		}
 	}
 }
 
@@ -306,11 +398,73 @@ impl<T: Config> Pallet<T> {
 			.saturating_sub(T::EcdpAuctionsManagerHandler::get_total_collateral_in_auction(currency_id))
 	}
 
+	/// Get current total surplus of system not in surplus auction.
+	pub fn surplus_pool_not_in_auction() -> Balance {
+		Self::surplus_pool().saturating_sub(T::EcdpAuctionsManagerHandler::get_total_surplus_in_auction())
+	}
+
+	/// Create surplus auctions for the surplus exceeding the debit pool and
+	/// the surplus buffer, and debit auctions for the debit exceeding the
+	/// debit offset buffer that cannot be offset by the surplus. Returns the
+	/// number of created auctions.
+	fn create_system_auctions() -> u32 {
+		let max_auctions_count = T::MaxAuctionsCount::get();
+		let mut created_auctions: u32 = 0;
+
+		let surplus_auction_fixed_size = Self::surplus_auction_fixed_size();
+		if !surplus_auction_fixed_size.is_zero() {
+			let mut remain_surplus = Self::surplus_pool_not_in_auction()
+				.saturating_sub(Self::debit_pool())
+				.saturating_sub(Self::surplus_buffer_size());
+
+			while remain_surplus >= surplus_auction_fixed_size && created_auctions < max_auctions_count {
+				if let Err(e) = T::EcdpAuctionsManagerHandler::new_surplus_auction(surplus_auction_fixed_size) {
+					log::warn!(
+						target: "ecdp-ussd-treasury",
+						"create_system_auctions: failed to create surplus auction of {:?}: {:?}",
+						surplus_auction_fixed_size, e
+					);
+					break;
+				}
+				remain_surplus = remain_surplus.saturating_sub(surplus_auction_fixed_size);
+				created_auctions = created_auctions.saturating_add(1);
+			}
+		}
+
+		let debit_auction_fixed_size = Self::debit_auction_fixed_size();
+		let initial_amount_per_debit_auction = Self::initial_amount_per_debit_auction();
+		if !debit_auction_fixed_size.is_zero() && !initial_amount_per_debit_auction.is_zero() {
+			let mut remain_debit = Self::debit_pool()
+				.saturating_sub(Self::debit_offset_buffer())
+				.saturating_sub(Self::surplus_pool_not_in_auction())
+				.saturating_sub(T::EcdpAuctionsManagerHandler::get_total_debit_in_auction());
+
+			while remain_debit >= debit_auction_fixed_size && created_auctions < max_auctions_count {
+				if let Err(e) = T::EcdpAuctionsManagerHandler::new_debit_auction(
+					initial_amount_per_debit_auction,
+					debit_auction_fixed_size,
+				) {
+					log::warn!(
+						target: "ecdp-ussd-treasury",
+						"create_system_auctions: failed to create debit auction of {:?}: {:?}",
+						debit_auction_fixed_size, e
+					);
+					break;
+				}
+				remain_debit = remain_debit.saturating_sub(debit_auction_fixed_size);
+				created_auctions = created_auctions.saturating_add(1);
+			}
+		}
+
+		created_auctions
+	}
+
 	fn offset_surplus_and_debit() {
-		// The part of the debit pool that exceeds the debit offset buffer can be offset by the surplus
+		// The part of the debit pool that exceeds the debit offset buffer can be offset by the surplus,
+		// except the surplus in auction.
 		let offset_amount = sp_std::cmp::min(
 			Self::debit_pool().saturating_sub(Self::debit_offset_buffer()),
-			Self::surplus_pool(),
+			Self::surplus_pool_not_in_auction(),
 		);
 
 		// Burn the amount that is equal to offset amount of USSD
@@ -495,6 +649,19 @@ impl<T: Config> EcdpUssdTreasuryExtended<T::AccountId> for Pallet<T> {
 		Ok(created_auctions)
 	}
 
+	fn create_dutch_auction(
+		currency_id: CurrencyId,
+		amount: Balance,
+		target: Balance,
+		refund_receiver: T::AccountId,
+	) -> DispatchResult {
+		ensure!(
+			Self::total_collaterals_not_in_auction(currency_id) >= amount,
+			Error::<T>::CollateralNotEnough,
+		);
+		T::EcdpAuctionsManagerHandler::new_dutch_auction(&refund_receiver, currency_id, amount, target)
+	}
+
 	fn remove_liquidity_for_lp_collateral(
 		lp_currency_id: CurrencyId,
 		amount: Balance,
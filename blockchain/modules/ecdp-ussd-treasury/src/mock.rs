@@ -138,6 +138,10 @@ impl module_edfis_swap_legacy::Config for Runtime {
 thread_local! {
 	pub static TOTAL_COLLATERAL_AUCTION: RefCell<u32> = RefCell::new(0);
 	pub static TOTAL_COLLATERAL_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
+	pub static TOTAL_SURPLUS_AUCTION: RefCell<u32> = RefCell::new(0);
+	pub static TOTAL_SURPLUS_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
+	pub static TOTAL_DEBIT_AUCTION: RefCell<u32> = RefCell::new(0);
+	pub static TOTAL_DEBIT_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
 }
 
 pub struct MockEcdpAuctionsManager;
@@ -157,6 +161,17 @@ impl EcdpAuctionsManager<AccountId> for MockEcdpAuctionsManager {
 		Ok(())
 	}
 
+	fn new_dutch_auction(
+		_refund_recipient: &AccountId,
+		_currency_id: Self::CurrencyId,
+		amount: Self::Balance,
+		_target: Self::Balance,
+	) -> DispatchResult {
+		TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut() += 1);
+		TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow_mut() += amount);
+		Ok(())
+	}
+
 	fn cancel_auction(_id: Self::AuctionId) -> DispatchResult {
 		unimplemented!()
 	}
@@ -168,6 +183,26 @@ impl EcdpAuctionsManager<AccountId> for MockEcdpAuctionsManager {
 	fn get_total_target_in_auction() -> Self::Balance {
 		unimplemented!()
 	}
+
+	fn new_surplus_auction(amount: Self::Balance) -> DispatchResult {
+		TOTAL_SURPLUS_AUCTION.with(|v| *v.borrow_mut() += 1);
+		TOTAL_SURPLUS_IN_AUCTION.with(|v| *v.borrow_mut() += amount);
+		Ok(())
+	}
+
+	fn new_debit_auction(_initial_amount: Self::Balance, fix_debit: Self::Balance) -> DispatchResult {
+		TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut() += 1);
+		TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut() += fix_debit);
+		Ok(())
+	}
+
+	fn get_total_surplus_in_auction() -> Self::Balance {
+		TOTAL_SURPLUS_IN_AUCTION.with(|v| *v.borrow_mut())
+	}
+
+	fn get_total_debit_in_auction() -> Self::Balance {
+		TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut())
+	}
 }
 
 ord_parameter_types! {
@@ -316,6 +316,28 @@ fn create_collateral_auctions_work() {
 	});
 }
 
+#[test]
+fn create_dutch_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Currencies::deposit(BTC, &EcdpUssdTreasuryModule::account_id(), 1000));
+		assert_noop!(
+			EcdpUssdTreasuryModule::create_dutch_auction(BTC, 1001, 1000, ALICE),
+			Error::<Runtime>::CollateralNotEnough,
+		);
+
+		// dutch auction is never split into lots
+		assert_ok!(EcdpUssdTreasuryModule::set_expected_collateral_auction_size(
+			RuntimeOrigin::signed(1),
+			BTC,
+			300
+		));
+		assert_ok!(EcdpUssdTreasuryModule::create_dutch_auction(BTC, 1000, 1000, ALICE));
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 1);
+		assert_eq!(TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow_mut()), 1000);
+		assert_eq!(EcdpUssdTreasuryModule::total_collaterals_not_in_auction(BTC), 0);
+	});
+}
+
 #[test]
 fn remove_liquidity_for_lp_collateral_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -569,3 +591,111 @@ fn offset_surplus_and_debit_limited_by_debit_offset_buffer() {
 		assert_eq!(EcdpUssdTreasuryModule::debit_offset_buffer(), 200);
 	});
 }
+
+#[test]
+fn set_system_auction_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EcdpUssdTreasuryModule::set_system_auction_params(
+				RuntimeOrigin::signed(5),
+				Change::NewValue(100),
+				Change::NoChange,
+				Change::NoChange,
+				Change::NoChange,
+			),
+			BadOrigin
+		);
+		assert_ok!(EcdpUssdTreasuryModule::set_system_auction_params(
+			RuntimeOrigin::signed(1),
+			Change::NewValue(100),
+			Change::NewValue(200),
+			Change::NoChange,
+			Change::NewValue(1000),
+		));
+		System::assert_has_event(RuntimeEvent::EcdpUssdTreasuryModule(
+			crate::Event::SurplusBufferSizeUpdated { amount: 100 },
+		));
+		System::assert_has_event(RuntimeEvent::EcdpUssdTreasuryModule(
+			crate::Event::SurplusAuctionFixedSizeUpdated { amount: 200 },
+		));
+		System::assert_last_event(RuntimeEvent::EcdpUssdTreasuryModule(
+			crate::Event::InitialAmountPerDebitAuctionUpdated { amount: 1000 },
+		));
+		assert_eq!(EcdpUssdTreasuryModule::surplus_buffer_size(), 100);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_auction_fixed_size(), 200);
+		assert_eq!(EcdpUssdTreasuryModule::debit_auction_fixed_size(), 0);
+		assert_eq!(EcdpUssdTreasuryModule::initial_amount_per_debit_auction(), 1000);
+	});
+}
+
+#[test]
+fn create_surplus_auctions_on_initialize_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EcdpUssdTreasuryModule::on_system_surplus(650));
+		EcdpUssdTreasuryModule::on_initialize(1);
+		assert_eq!(TOTAL_SURPLUS_AUCTION.with(|v| *v.borrow_mut()), 0);
+
+		assert_ok!(EcdpUssdTreasuryModule::set_system_auction_params(
+			RuntimeOrigin::signed(1),
+			Change::NewValue(100),
+			Change::NewValue(200),
+			Change::NoChange,
+			Change::NoChange,
+		));
+		EcdpUssdTreasuryModule::on_initialize(2);
+		assert_eq!(TOTAL_SURPLUS_AUCTION.with(|v| *v.borrow_mut()), 2);
+		assert_eq!(TOTAL_SURPLUS_IN_AUCTION.with(|v| *v.borrow_mut()), 400);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool_not_in_auction(), 250);
+
+		// the remaining surplus exceeding the buffer is less than the lot size
+		EcdpUssdTreasuryModule::on_initialize(3);
+		assert_eq!(TOTAL_SURPLUS_AUCTION.with(|v| *v.borrow_mut()), 2);
+
+		// the surplus to offset the debit pool is not auctioned
+		assert_ok!(EcdpUssdTreasuryModule::on_system_debit(50));
+		assert_ok!(EcdpUssdTreasuryModule::on_system_surplus(200));
+		EcdpUssdTreasuryModule::on_initialize(4);
+		assert_eq!(TOTAL_SURPLUS_AUCTION.with(|v| *v.borrow_mut()), 3);
+		assert_eq!(TOTAL_SURPLUS_IN_AUCTION.with(|v| *v.borrow_mut()), 600);
+
+		// the surplus in auction is not used to offset the debit pool
+		EcdpUssdTreasuryModule::on_finalize(4);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool(), 800);
+		assert_eq!(EcdpUssdTreasuryModule::surplus_pool_not_in_auction(), 200);
+		assert_eq!(EcdpUssdTreasuryModule::debit_pool(), 0);
+	});
+}
+
+#[test]
+fn create_debit_auctions_on_initialize_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EcdpUssdTreasuryModule::set_system_auction_params(
+			RuntimeOrigin::signed(1),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(100),
+			Change::NewValue(1000),
+		));
+		assert_ok!(EcdpUssdTreasuryModule::set_debit_offset_buffer(
+			RuntimeOrigin::signed(1),
+			50
+		));
+		assert_ok!(EcdpUssdTreasuryModule::on_system_debit(1000));
+		assert_ok!(EcdpUssdTreasuryModule::on_system_surplus(300));
+
+		// the debit that can be offset by the surplus is not auctioned, and the
+		// auctions created per block are capped by MaxAuctionsCount
+		EcdpUssdTreasuryModule::on_initialize(1);
+		assert_eq!(TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut()), 5);
+		assert_eq!(TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut()), 500);
+		assert_eq!(TOTAL_SURPLUS_AUCTION.with(|v| *v.borrow_mut()), 0);
+
+		EcdpUssdTreasuryModule::on_initialize(2);
+		assert_eq!(TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut()), 6);
+		assert_eq!(TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut()), 600);
+
+		EcdpUssdTreasuryModule::on_initialize(3);
+		assert_eq!(TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut()), 6);
+	});
+}
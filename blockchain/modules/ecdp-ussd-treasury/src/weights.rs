@@ -53,6 +53,8 @@ pub trait WeightInfo {
 	fn auction_collateral(b: u32) -> Weight;
 	fn exchange_collateral_to_ussd() -> Weight;
 	fn set_expected_collateral_auction_size() -> Weight;
+	fn set_system_auction_params() -> Weight;
+	fn on_initialize(c: u32) -> Weight;
 }
 
 /// Weights for module_ecdp_ussd_treasury using the Setheum node and recommended hardware.
@@ -80,6 +82,16 @@ impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4 as u64))
 			.saturating_add(T::DbWeight::get().writes(3 as u64))
 	}
+	fn set_system_auction_params() -> Weight {
+		Weight::from_parts(28_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	fn on_initialize(c: u32, ) -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(Weight::from_parts(24_312_000, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(9 as u64))
+			.saturating_add(T::DbWeight::get().writes((4 as u64).saturating_mul(c as u64)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -105,4 +117,14 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4 as u64))
 			.saturating_add(RocksDbWeight::get().writes(3 as u64))
 	}
+	fn set_system_auction_params() -> Weight {
+		Weight::from_parts(28_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn on_initialize(c: u32, ) -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(Weight::from_parts(24_312_000, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(9 as u64))
+			.saturating_add(RocksDbWeight::get().writes((4 as u64).saturating_mul(c as u64)))
+	}
 }
@@ -33,6 +33,7 @@
 
 use frame_support::{pallet_prelude::*, traits::NamedReservableCurrency};
 use frame_system::pallet_prelude::*;
+use module_cdp_engine::ProtectionRule;
 use module_support::{EcdpUssdTreasury, EcdpEmergencyShutdown, ExchangeRate, EcdpUssdManager, PriceProvider, Ratio};
 use primitives::{Amount, Balance, CurrencyId, EcdpPosition, ReserveIdentifier};
 use sp_core::U256;
@@ -373,6 +374,52 @@ pub mod module {
 			});
 			Ok(())
 		}
+
+		/// Register or update the liquidation protection rule of caller's ECDP
+		/// under `currency_id`, or remove it if `rule` is `None`. When the
+		/// collateral ratio of the ECDP falls below the trigger ratio of the rule,
+		/// the ECDP is automatically deleveraged or topped up to the target ratio
+		/// before liquidation.
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `rule`: the liquidation protection rule.
+		#[pallet::call_index(10)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_liquidation_protection())]
+		pub fn set_liquidation_protection(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			rule: Option<ProtectionRule>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			// not allowed to register protection rule after system shutdown
+			if rule.is_some() {
+				ensure!(!T::EcdpEmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			}
+			<module_cdp_engine::Pallet<T>>::set_liquidation_protection(&who, currency_id, rule)?;
+			Ok(())
+		}
+
+		/// Set caller's multi-collateral basket to the ECDPs under
+		/// `currency_ids`, or dissolve it if `currency_ids` is empty. The
+		/// collateral ratio of the basket is computed across its ECDPs with
+		/// the basket haircut of each collateral type, and on liquidation its
+		/// collateral is sold in the order of `currency_ids`. The ECDPs leaving
+		/// the basket must be valid on their own.
+		///
+		/// - `currency_ids`: collateral currency ids in liquidation order.
+		#[pallet::call_index(11)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_basket(currency_ids.len() as u32))]
+		pub fn set_basket(origin: OriginFor<T>, currency_ids: Vec<CurrencyId>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			// not allowed to form new baskets after system shutdown
+			if !currency_ids.is_empty() {
+				ensure!(!T::EcdpEmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			}
+			<module_cdp_engine::Pallet<T>>::set_basket(&who, currency_ids)?;
+			Ok(())
+		}
 	}
 }
 
@@ -440,6 +487,10 @@ impl<T: Config> EcdpUssdManager<T::AccountId, CurrencyId, Amount, Balance> for P
 		]
 	}
 
+	fn get_liquidation_ratio(currency_id: CurrencyId) -> Option<Ratio> {
+		<module_cdp_engine::Pallet<T>>::get_liquidation_ratio(currency_id).ok()
+	}
+
 	fn get_current_collateral_ratio(who: &T::AccountId, currency_id: CurrencyId) -> Option<Ratio> {
 		let EcdpPosition { collateral, debit } = <module_ecdp_loans::Pallet<T>>::positions(currency_id, who);
 		let stable_currency_id = T::GetUSSDCurrencyId::get();
@@ -158,6 +158,15 @@ impl EcdpAuctionsManager<AccountId> for MockEcdpAuctionsManager {
 		Ok(())
 	}
 
+	fn new_dutch_auction(
+		_refund_recipient: &AccountId,
+		_currency_id: Self::CurrencyId,
+		_amount: Self::Balance,
+		_target: Self::Balance,
+	) -> DispatchResult {
+		Err(sp_runtime::DispatchError::Other("dutch auctions are disabled"))
+	}
+
 	fn cancel_auction(_id: Self::AuctionId) -> DispatchResult {
 		Ok(())
 	}
@@ -169,6 +178,22 @@ impl EcdpAuctionsManager<AccountId> for MockEcdpAuctionsManager {
 	fn get_total_collateral_in_auction(_id: Self::CurrencyId) -> Self::Balance {
 		Default::default()
 	}
+
+	fn new_surplus_auction(_amount: Self::Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn new_debit_auction(_initial_amount: Self::Balance, _fix_debit: Self::Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn get_total_surplus_in_auction() -> Self::Balance {
+		Default::default()
+	}
+
+	fn get_total_debit_in_auction() -> Self::Balance {
+		Default::default()
+	}
 }
 
 thread_local! {
@@ -310,6 +335,10 @@ impl module_cdp_engine::Config for Runtime {
 	type Swap = SpecificJointsSwap<(), AlternativeSwapPathJointList>;
 	type EVMBridge = module_evm_bridge::EVMBridge<Runtime>;
 	type SettleErc20EvmOrigin = SettleErc20EvmOrigin;
+	type RuntimeCall = RuntimeCall;
+	type FlashMintEvmBridge = ();
+	type MaxFlashMintCalls = ConstU32<4>;
+	type MaxBasketCurrencies = ConstU32<4>;
 	type WeightInfo = ();
 }
 
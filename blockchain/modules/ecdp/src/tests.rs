@@ -111,11 +111,12 @@ fn transfer_loan_from_should_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_ok!(EcdpModule::adjust_loan(RuntimeOrigin::signed(ALICE), BTC, 100, 50));
 		assert_ok!(EcdpModule::authorize(RuntimeOrigin::signed(ALICE), BTC, BOB));
@@ -141,11 +142,12 @@ fn adjust_loan_should_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_ok!(EcdpModule::adjust_loan(RuntimeOrigin::signed(ALICE), BTC, 100, 50));
 		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).collateral, 100);
@@ -159,11 +161,12 @@ fn adjust_loan_by_debit_value_should_work() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 
 		assert_ok!(EcdpModule::adjust_loan_by_debit_value(
@@ -206,16 +209,91 @@ fn on_emergency_shutdown_should_work() {
 }
 
 #[test]
-fn close_loan_has_debit_by_dex_work() {
+fn set_liquidation_protection_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+			Change::NoChange,
 			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+		));
+		let rule = module_cdp_engine::ProtectionRule {
+			trigger_ratio: Ratio::saturating_from_rational(9, 5),
+			target_ratio: Ratio::saturating_from_rational(2, 1),
+			action: module_cdp_engine::ProtectionAction::Deleverage,
+		};
+		assert_ok!(EcdpModule::set_liquidation_protection(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
+			Some(rule.clone())
+		));
+		assert_eq!(EcdpUssdEngineModule::liquidation_protections(BTC, ALICE), Some(rule.clone()));
+
+		// rules can only be removed after emergency shutdown
+		mock_shutdown();
+		assert_noop!(
+			EcdpModule::set_liquidation_protection(RuntimeOrigin::signed(ALICE), BTC, Some(rule)),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+		assert_ok!(EcdpModule::set_liquidation_protection(RuntimeOrigin::signed(ALICE), BTC, None));
+		assert_eq!(EcdpUssdEngineModule::liquidation_protections(BTC, ALICE), None);
+	});
+}
+
+#[test]
+fn set_basket_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		for currency_id in [BTC, EDF] {
+			assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+				RuntimeOrigin::signed(ALICE),
+				currency_id,
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(10000),
+				Change::NoChange,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			));
+			assert_ok!(EcdpUssdEngineModule::set_basket_haircut(
+				RuntimeOrigin::signed(ALICE),
+				currency_id,
+				Some(Ratio::saturating_from_rational(1, 2))
+			));
+		}
+		assert_ok!(EcdpModule::set_basket(RuntimeOrigin::signed(ALICE), vec![EDF, BTC]));
+		assert_eq!(EcdpUssdEngineModule::baskets(&ALICE).into_inner(), vec![EDF, BTC]);
+
+		// the debit under BTC is backed by the collateral under EDF
+		assert_ok!(EcdpModule::adjust_loan(RuntimeOrigin::signed(ALICE), EDF, 100, 0));
+		assert_ok!(EcdpModule::adjust_loan(RuntimeOrigin::signed(ALICE), BTC, 0, 250));
+		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).debit, 250);
+		assert_ok!(EcdpModule::transfer_debit(RuntimeOrigin::signed(ALICE), BTC, EDF, 100));
+
+		// baskets can only be dissolved after emergency shutdown
+		mock_shutdown();
+		assert_noop!(
+			EcdpModule::set_basket(RuntimeOrigin::signed(ALICE), vec![EDF]),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+	});
+}
+
+#[test]
+fn close_loan_has_debit_by_dex_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
+			RuntimeOrigin::signed(ALICE),
+			BTC,
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_ok!(EcdpModule::adjust_loan(RuntimeOrigin::signed(ALICE), BTC, 100, 50));
 		assert_eq!(EcdpLoansModule::positions(BTC, ALICE).collateral, 100);
@@ -238,20 +316,22 @@ fn transfer_debit_works() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			EDF,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 
 		// set up two loans
@@ -311,20 +391,22 @@ fn transfer_debit_no_ussd() {
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			BTC,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 		assert_ok!(EcdpUssdEngineModule::set_collateral_params(
 			RuntimeOrigin::signed(ALICE),
 			EDF,
-			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
 			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
+			Change::NoChange,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
 		));
 
 		// set up two loans
@@ -59,6 +59,8 @@ pub trait WeightInfo {
 	fn shrink_position_debit() -> Weight;
 	fn transfer_debit() -> Weight;
 	fn precompile_get_current_collateral_ratio() -> Weight;
+	fn set_liquidation_protection() -> Weight;
+	fn set_basket(c: u32, ) -> Weight;
 }
 
 /// Weights for module_ecdp using the Setheum node and recommended hardware.
@@ -225,6 +227,22 @@ impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
 		Weight::from_parts(44_244_000, 0)
 			.saturating_add(T::DbWeight::get().reads(11 as u64))
 	}
+	// Storage: EcdpEmergencyShutdown IsShutdown (r:1 w:0)
+	// Storage: CdpEngine CollateralParams (r:1 w:0)
+	// Storage: CdpEngine LiquidationProtections (r:0 w:1)
+	fn set_liquidation_protection() -> Weight {
+		Weight::from_parts(32_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_basket(c: u32, ) -> Weight {
+		Weight::from_parts(29_000_000, 0)
+			// Standard Error: 14_000
+			.saturating_add(Weight::from_parts(16_000_000, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().reads((6 as u64).saturating_mul(c as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -281,4 +299,17 @@ impl WeightInfo for () {
 		Weight::from_parts(44_244_000, 0)
 			.saturating_add(RocksDbWeight::get().reads(11 as u64))
 	}
+	fn set_liquidation_protection() -> Weight {
+		Weight::from_parts(32_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_basket(c: u32, ) -> Weight {
+		Weight::from_parts(29_000_000, 0)
+			// Standard Error: 14_000
+			.saturating_add(Weight::from_parts(16_000_000, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().reads((6 as u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
 }
@@ -26,8 +26,9 @@ use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
 use frame_system::pallet_prelude::*;
 use module_evm::{ExitReason, ExitSucceed};
 use module_support::{
-	evm::limits::{erc20, liquidation},
-	EVMBridge as EVMBridgeTrait, ExecutionMode, InvokeContext, LiquidationEvmBridge as LiquidationEvmBridgeT, EVM,
+	evm::limits::{erc20, flash_mint, liquidation},
+	EVMBridge as EVMBridgeTrait, ExecutionMode, FlashMintEvmBridge as FlashMintEvmBridgeT, InvokeContext,
+	LiquidationEvmBridge as LiquidationEvmBridgeT, EVM,
 };
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use primitives::{evm::EvmAddress, Balance};
@@ -51,6 +52,7 @@ pub enum Action {
 	Liquidate = "liquidate(address,address,uint256,uint256)",
 	OnCollateralTransfer = "onCollateralTransfer(address,uint256)",
 	OnRepaymentRefund = "onRepaymentRefund(address,uint256)",
+	OnFlashMint = "onFlashMint(address,uint256,uint256,bytes)",
 }
 
 mod mock;
@@ -347,7 +349,65 @@ impl<T: Config> LiquidationEvmBridgeT for LiquidationEvmBridge<T> {
 	}
 }
 
+pub struct FlashMintEvmBridge<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> FlashMintEvmBridgeT for FlashMintEvmBridge<T> {
+	fn on_flash_mint(
+		context: InvokeContext,
+		initiator: EvmAddress,
+		amount: Balance,
+		fee: Balance,
+		data: Vec<u8>,
+	) -> DispatchResult {
+		let input = Pallet::<T>::encode_on_flash_mint(initiator, amount, fee, &data);
+
+		// the callback runs with a fixed gas limit, which the flash mint precompile charges its caller
+		let info = T::EVM::execute(
+			context,
+			input,
+			Default::default(),
+			flash_mint::ON_FLASH_MINT.gas,
+			flash_mint::ON_FLASH_MINT.storage,
+			ExecutionMode::Execute,
+		)?;
+
+		Pallet::<T>::handle_exit_reason(info.exit_reason)?;
+
+		// return value is true.
+		let mut bytes = [0u8; 32];
+		U256::from(1).to_big_endian(&mut bytes);
+
+		// Check return value to make sure not calling on empty contracts.
+		ensure!(
+			!info.value.is_empty() && info.value == bytes,
+			Error::<T>::InvalidReturnValue
+		);
+		Ok(())
+	}
+}
+
 impl<T: Config> Pallet<T> {
+	/// ABI encode the call of `onFlashMint(address,uint256,uint256,bytes)` on a flash mint receiver.
+	pub(crate) fn encode_on_flash_mint(initiator: EvmAddress, amount: Balance, fee: Balance, data: &[u8]) -> Vec<u8> {
+		// flash mint receiver contract method hash
+		let mut input = Into::<u32>::into(Action::OnFlashMint).to_be_bytes().to_vec();
+		// append initiator address
+		input.extend_from_slice(H256::from(initiator).as_bytes());
+		// append minted amount
+		input.extend_from_slice(H256::from_uint(&U256::from(amount)).as_bytes());
+		// append fee amount
+		input.extend_from_slice(H256::from_uint(&U256::from(fee)).as_bytes());
+		// append offset of the dynamic bytes parameter, which follows the 4 head words
+		input.extend_from_slice(H256::from_uint(&U256::from(4 * 32)).as_bytes());
+		// append bytes length
+		input.extend_from_slice(H256::from_uint(&U256::from(data.len())).as_bytes());
+		// append bytes data, right padded to 32-byte alignment
+		let padded_len = data.len().saturating_add(31) / 32 * 32;
+		input.extend_from_slice(data);
+		input.resize(input.len().saturating_add(padded_len.saturating_sub(data.len())), 0);
+		input
+	}
+
 	fn handle_exit_reason(exit_reason: ExitReason) -> Result<(), DispatchError> {
 		match exit_reason {
 			ExitReason::Succeed(ExitSucceed::Returned) => Ok(()),
@@ -355,3 +355,25 @@ fn liquidation_err_fails_as_expected() {
 			);
 		});
 }
+
+#[test]
+fn should_encode_on_flash_mint() {
+	let initiator = H160::from_low_u64_be(1);
+	let data = vec![0xaa; 33];
+	let input = Pallet::<Runtime>::encode_on_flash_mint(initiator, 2, 3, &data);
+
+	// selector, 4 head words, bytes length and the data padded to 2 words
+	assert_eq!(input.len(), 4 + 4 * 32 + 32 + 2 * 32);
+	assert_eq!(input[..4], Into::<u32>::into(Action::OnFlashMint).to_be_bytes());
+	assert_eq!(input[4..36], *H256::from(initiator).as_bytes());
+	assert_eq!(input[36..68], *H256::from_low_u64_be(2).as_bytes());
+	assert_eq!(input[68..100], *H256::from_low_u64_be(3).as_bytes());
+	assert_eq!(input[100..132], *H256::from_low_u64_be(128).as_bytes());
+	assert_eq!(input[132..164], *H256::from_low_u64_be(33).as_bytes());
+	assert_eq!(input[164..197], data[..]);
+	assert!(input[197..].iter().all(|byte| *byte == 0));
+
+	let input = Pallet::<Runtime>::encode_on_flash_mint(initiator, 2, 3, &[]);
+	assert_eq!(input.len(), 4 + 4 * 32 + 32);
+	assert_eq!(input[132..164], *H256::from_low_u64_be(0).as_bytes());
+}
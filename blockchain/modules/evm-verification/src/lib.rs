@@ -0,0 +1,244 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # EVM Verification Module
+//!
+//! ## Overview
+//!
+//! A registry of verified EVM contracts. Verifiers recompile the solc standard-JSON input
+//! of a contract offline with a solc release pinned by the node, and register the compiled
+//! runtime code with the hashes of the ABI and of the input. The compiled code must match
+//! the deployed code, ignoring the metadata and the values of immutable variables, which
+//! are only allowed as `PUSH32` operands.
+//!
+//! A verification applies to the code it was made for, and is not returned anymore once
+//! the code of the contract changes. The ABI itself is served by the nodes by its hash.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use module_support::EVMManager;
+use primitives::{
+	evm::{is_code_reproduced, EvmAddress, VerifiedContract},
+	Balance,
+};
+use sp_core::H256;
+use sp_io::hashing::keccak_256;
+use sp_std::vec::Vec;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// EVM manager to query the deployed code of contracts.
+		type EVMManager: EVMManager<Self::AccountId, Balance>;
+
+		/// The origin which may add and remove verifiers and verifications.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum size of the compiled code of a contract.
+		#[pallet::constant]
+		type MaxCodeSize: Get<u32>;
+
+		/// The maximum number of immutable variable references in the compiled code.
+		#[pallet::constant]
+		type MaxImmutableReferences: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The caller is not a verifier
+		NotVerifier,
+		/// The account is already a verifier
+		AlreadyVerifier,
+		/// The contract does not exist
+		ContractNotFound,
+		/// The compiled code does not match the deployed code
+		CodeMismatch,
+		/// The contract is not verified
+		VerificationNotFound,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A verifier is added.
+		VerifierAdded { who: T::AccountId },
+		/// A verifier is removed.
+		VerifierRemoved { who: T::AccountId },
+		/// A contract is verified.
+		ContractVerified {
+			contract: EvmAddress,
+			code_hash: H256,
+			abi_hash: H256,
+			source_hash: H256,
+			compiler_version: u32,
+			verifier: T::AccountId,
+		},
+		/// The verification of a contract is removed.
+		VerificationRemoved { contract: EvmAddress },
+	}
+
+	/// The accounts allowed to register verifications.
+	///
+	/// Verifiers: map AccountId => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn verifiers)]
+	pub type Verifiers<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, (), OptionQuery>;
+
+	/// The verifications of contracts, which may be outdated by a code change.
+	///
+	/// Verifications: map EvmAddress => Option<VerifiedContract>
+	#[pallet::storage]
+	#[pallet::getter(fn verifications)]
+	pub type Verifications<T: Config> =
+		StorageMap<_, Twox64Concat, EvmAddress, VerifiedContract<T::AccountId, BlockNumberFor<T>>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Allow an account to register verifications.
+		///
+		/// - `who`: the verifier
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::add_verifier())]
+		pub fn add_verifier(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(!Verifiers::<T>::contains_key(&who), Error::<T>::AlreadyVerifier);
+
+			Verifiers::<T>::insert(&who, ());
+			Self::deposit_event(Event::VerifierAdded { who });
+			Ok(())
+		}
+
+		/// Disallow an account to register verifications. The verifications it registered
+		/// are kept.
+		///
+		/// - `who`: the verifier
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::remove_verifier())]
+		pub fn remove_verifier(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Verifiers::<T>::take(&who).ok_or(Error::<T>::NotVerifier)?;
+
+			Self::deposit_event(Event::VerifierRemoved { who });
+			Ok(())
+		}
+
+		/// Register the verification of a contract, replacing any previous one.
+		///
+		/// - `contract`: the verified contract
+		/// - `compiled_code`: the runtime code output by solc for the contract
+		/// - `immutable_references`: the sorted `(start, length)` ranges of the immutable
+		///   variables in the compiled code, each the operand of a `PUSH32`
+		/// - `abi_hash`: keccak-256 hash of the ABI output by solc
+		/// - `source_hash`: keccak-256 hash of the standard-JSON input
+		/// - `compiler_version`: solc version, packed as `major << 16 | minor << 8 | patch`
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::verify_contract(
+			compiled_code.len() as u32,
+			immutable_references.len() as u32,
+		))]
+		pub fn verify_contract(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			compiled_code: BoundedVec<u8, T::MaxCodeSize>,
+			immutable_references: BoundedVec<(u32, u32), T::MaxImmutableReferences>,
+			abi_hash: H256,
+			source_hash: H256,
+			compiler_version: u32,
+		) -> DispatchResult {
+			let verifier = ensure_signed(origin)?;
+			ensure!(Verifiers::<T>::contains_key(&verifier), Error::<T>::NotVerifier);
+
+			let code = T::EVMManager::query_code(contract);
+			ensure!(!code.is_empty(), Error::<T>::ContractNotFound);
+			ensure!(
+				is_code_reproduced(&code, &compiled_code, &immutable_references),
+				Error::<T>::CodeMismatch
+			);
+
+			let code_hash = H256::from(keccak_256(&code));
+			Verifications::<T>::insert(
+				contract,
+				VerifiedContract {
+					code_hash,
+					abi_hash,
+					source_hash,
+					compiler_version,
+					verifier: verifier.clone(),
+					verified_at: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+
+			Self::deposit_event(Event::ContractVerified {
+				contract,
+				code_hash,
+				abi_hash,
+				source_hash,
+				compiler_version,
+				verifier,
+			});
+			Ok(())
+		}
+
+		/// Remove the verification of a contract.
+		///
+		/// - `contract`: the verified contract
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::remove_verification())]
+		pub fn remove_verification(origin: OriginFor<T>, contract: EvmAddress) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Verifications::<T>::take(contract).ok_or(Error::<T>::VerificationNotFound)?;
+
+			Self::deposit_event(Event::VerificationRemoved { contract });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The verification of the current code of `contract`.
+	pub fn verified_contract(contract: EvmAddress) -> Option<VerifiedContract<T::AccountId, BlockNumberFor<T>>> {
+		Self::verifications(contract)
+			.filter(|verification| H256::from(keccak_256(&T::EVMManager::query_code(contract))) == verification.code_hash)
+	}
+}
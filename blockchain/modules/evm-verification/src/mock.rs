@@ -0,0 +1,150 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the evm verification module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{construct_runtime, derive_impl, ord_parameter_types, traits::ConstU32};
+use frame_system::EnsureSignedBy;
+use sp_core::H160;
+use sp_runtime::{traits::IdentityLookup, BuildStorage, DispatchError, DispatchResult};
+use sp_std::cell::RefCell;
+use std::{collections::HashMap, str::FromStr};
+
+pub type AccountId = u128;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const ADMIN: AccountId = 10;
+
+pub fn contract() -> EvmAddress {
+	EvmAddress::from_str("0x1000000000000000000000000000000000000001").unwrap()
+}
+
+mod evm_verification {
+	pub use super::super::*;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+}
+
+thread_local! {
+	static CODES: RefCell<HashMap<EvmAddress, Vec<u8>>> = RefCell::new(HashMap::new());
+}
+
+/// Deploy `code` at `contract`.
+pub fn set_code(contract: EvmAddress, code: Vec<u8>) {
+	CODES.with(|v| v.borrow_mut().insert(contract, code));
+}
+
+pub struct MockEVMManager;
+impl EVMManager<AccountId, Balance> for MockEVMManager {
+	fn query_new_contract_extra_bytes() -> u32 {
+		unimplemented!()
+	}
+
+	fn query_storage_deposit_per_byte() -> Balance {
+		unimplemented!()
+	}
+
+	fn query_maintainer(_: H160) -> Result<H160, DispatchError> {
+		unimplemented!()
+	}
+
+	fn query_code(contract: H160) -> Vec<u8> {
+		CODES.with(|v| v.borrow().get(&contract).cloned().unwrap_or_default())
+	}
+
+	fn query_developer_deposit() -> Balance {
+		unimplemented!()
+	}
+
+	fn query_publication_fee() -> Balance {
+		unimplemented!()
+	}
+
+	fn transfer_maintainer(_: AccountId, _: H160, _: H160) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn publish_contract_precompile(_: AccountId, _: H160) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn query_developer_status(_: AccountId) -> bool {
+		unimplemented!()
+	}
+
+	fn enable_account_contract_development(_: AccountId) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn disable_account_contract_development(_: AccountId) -> DispatchResult {
+		unimplemented!()
+	}
+}
+
+ord_parameter_types! {
+	pub const Admin: AccountId = ADMIN;
+}
+
+impl Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type EVMManager = MockEVMManager;
+	type UpdateOrigin = EnsureSignedBy<Admin, AccountId>;
+	type MaxCodeSize = ConstU32<1024>;
+	type MaxImmutableReferences = ConstU32<4>;
+	type WeightInfo = ();
+}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		EvmVerification: evm_verification,
+	}
+);
+
+pub struct ExtBuilder;
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::<Runtime>::default()
+			.build_storage()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
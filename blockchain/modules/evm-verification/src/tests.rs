@@ -0,0 +1,190 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the evm verification module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{RuntimeEvent, *};
+use sp_runtime::traits::BadOrigin;
+
+// the immutable pushed by the code
+const IMMUTABLE: (u32, u32) = (3, 32);
+
+// runtime code pushing an immutable, then a CBOR metadata map and its length
+fn code(immutable: u8, metadata: &[u8]) -> Vec<u8> {
+	[&[0x60, 0x80, 0x7f][..], &[immutable; 32][..], metadata].concat()
+}
+
+fn deployed_code() -> Vec<u8> {
+	code(0x12, &[0xa1, 0x61, 0x61, 0x01, 0x00, 0x04])
+}
+
+fn compiled_code() -> Vec<u8> {
+	code(0x00, &[0xa1, 0x61, 0x62, 0x02, 0x00, 0x04])
+}
+
+fn abi_hash() -> H256 {
+	H256::repeat_byte(0xab)
+}
+
+fn source_hash() -> H256 {
+	H256::repeat_byte(0x5c)
+}
+
+fn verify(who: AccountId, compiled_code: Vec<u8>, immutable_references: Vec<(u32, u32)>) -> DispatchResult {
+	EvmVerification::verify_contract(
+		RuntimeOrigin::signed(who),
+		contract(),
+		compiled_code.try_into().unwrap(),
+		immutable_references.try_into().unwrap(),
+		abi_hash(),
+		source_hash(),
+		0x00_08_18,
+	)
+}
+
+#[test]
+fn manage_verifiers_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			EvmVerification::add_verifier(RuntimeOrigin::signed(ALICE), ALICE),
+			BadOrigin
+		);
+		assert_ok!(EvmVerification::add_verifier(RuntimeOrigin::signed(ADMIN), ALICE));
+		System::assert_last_event(RuntimeEvent::EvmVerification(crate::Event::VerifierAdded { who: ALICE }));
+		assert_eq!(EvmVerification::verifiers(ALICE), Some(()));
+		assert_noop!(
+			EvmVerification::add_verifier(RuntimeOrigin::signed(ADMIN), ALICE),
+			Error::<Runtime>::AlreadyVerifier
+		);
+
+		assert_noop!(
+			EvmVerification::remove_verifier(RuntimeOrigin::signed(ALICE), ALICE),
+			BadOrigin
+		);
+		assert_noop!(
+			EvmVerification::remove_verifier(RuntimeOrigin::signed(ADMIN), BOB),
+			Error::<Runtime>::NotVerifier
+		);
+		assert_ok!(EvmVerification::remove_verifier(RuntimeOrigin::signed(ADMIN), ALICE));
+		System::assert_last_event(RuntimeEvent::EvmVerification(crate::Event::VerifierRemoved { who: ALICE }));
+		assert_eq!(EvmVerification::verifiers(ALICE), None);
+	});
+}
+
+#[test]
+fn verify_contract_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EvmVerification::add_verifier(RuntimeOrigin::signed(ADMIN), ALICE));
+
+		assert_noop!(
+			verify(BOB, compiled_code(), vec![IMMUTABLE]),
+			Error::<Runtime>::NotVerifier
+		);
+		assert_noop!(
+			verify(ALICE, compiled_code(), vec![IMMUTABLE]),
+			Error::<Runtime>::ContractNotFound
+		);
+
+		set_code(contract(), deployed_code());
+		assert_noop!(
+			verify(ALICE, compiled_code(), vec![]),
+			Error::<Runtime>::CodeMismatch
+		);
+		assert_noop!(
+			verify(ALICE, code(0x00, &[0x00]), vec![IMMUTABLE]),
+			Error::<Runtime>::CodeMismatch
+		);
+		// immutable references only mask `PUSH32` operands
+		assert_noop!(
+			verify(ALICE, compiled_code(), vec![(3, 2)]),
+			Error::<Runtime>::CodeMismatch
+		);
+		assert_noop!(
+			verify(ALICE, code(0x00, &[0xa1, 0x61, 0x62, 0x02, 0x00, 0x04]), vec![(2, 32)]),
+			Error::<Runtime>::CodeMismatch
+		);
+
+		assert_ok!(verify(ALICE, compiled_code(), vec![IMMUTABLE]));
+		let code_hash = H256::from(keccak_256(&deployed_code()));
+		System::assert_last_event(RuntimeEvent::EvmVerification(crate::Event::ContractVerified {
+			contract: contract(),
+			code_hash,
+			abi_hash: abi_hash(),
+			source_hash: source_hash(),
+			compiler_version: 0x00_08_18,
+			verifier: ALICE,
+		}));
+		let verification = VerifiedContract {
+			code_hash,
+			abi_hash: abi_hash(),
+			source_hash: source_hash(),
+			compiler_version: 0x00_08_18,
+			verifier: ALICE,
+			verified_at: 1,
+		};
+		assert_eq!(EvmVerification::verifications(contract()), Some(verification.clone()));
+		assert_eq!(EvmVerification::verified_contract(contract()), Some(verification));
+
+		// the verification doesn't apply to new code
+		set_code(contract(), code(0x13, &[]));
+		assert!(EvmVerification::verifications(contract()).is_some());
+		assert_eq!(EvmVerification::verified_contract(contract()), None);
+
+		// and can be replaced
+		System::set_block_number(2);
+		assert_ok!(verify(ALICE, code(0x00, &[]), vec![IMMUTABLE]));
+		assert_eq!(
+			EvmVerification::verified_contract(contract()).map(|v| (v.code_hash, v.verified_at)),
+			Some((H256::from(keccak_256(&code(0x13, &[]))), 2))
+		);
+	});
+}
+
+#[test]
+fn remove_verification_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EvmVerification::add_verifier(RuntimeOrigin::signed(ADMIN), ALICE));
+		set_code(contract(), deployed_code());
+
+		assert_noop!(
+			EvmVerification::remove_verification(RuntimeOrigin::signed(ADMIN), contract()),
+			Error::<Runtime>::VerificationNotFound
+		);
+		assert_ok!(verify(ALICE, compiled_code(), vec![IMMUTABLE]));
+
+		assert_noop!(
+			EvmVerification::remove_verification(RuntimeOrigin::signed(ALICE), contract()),
+			BadOrigin
+		);
+		assert_ok!(EvmVerification::remove_verification(
+			RuntimeOrigin::signed(ADMIN),
+			contract()
+		));
+		System::assert_last_event(RuntimeEvent::EvmVerification(crate::Event::VerificationRemoved {
+			contract: contract(),
+		}));
+		assert_eq!(EvmVerification::verifications(contract()), None);
+		assert_eq!(EvmVerification::verified_contract(contract()), None);
+	});
+}
@@ -0,0 +1,101 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weights for module_evm_verification
+//!
+//! Not benchmarked: `verify_contract` is charged per byte of the compiled code for reading,
+//! comparing and hashing the deployed code, which must be of the same size to be verified.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for module_evm_verification.
+pub trait WeightInfo {
+	fn add_verifier() -> Weight;
+	fn remove_verifier() -> Weight;
+	fn verify_contract(c: u32, i: u32, ) -> Weight;
+	fn remove_verification() -> Weight;
+}
+
+/// Weights for module_evm_verification using the Setheum node and recommended hardware.
+pub struct SetheumWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
+	// Storage: EvmVerification Verifiers (r:1 w:1)
+	fn add_verifier() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EvmVerification Verifiers (r:1 w:1)
+	fn remove_verifier() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EvmVerification Verifiers (r:1 w:0)
+	// Storage: EVM Accounts (r:1 w:0)
+	// Storage: EVM Codes (r:1 w:0)
+	// Storage: System Number (r:1 w:0)
+	// Storage: EvmVerification Verifications (r:0 w:1)
+	fn verify_contract(c: u32, i: u32, ) -> Weight {
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(c as u64))
+			.saturating_add(Weight::from_parts(250_000, 0).saturating_mul(i as u64))
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: EvmVerification Verifications (r:1 w:1)
+	fn remove_verification() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn add_verifier() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn remove_verifier() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn verify_contract(c: u32, i: u32, ) -> Weight {
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(c as u64))
+			.saturating_add(Weight::from_parts(250_000, 0).saturating_mul(i as u64))
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn remove_verification() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+}
@@ -21,8 +21,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::all)]
 
-use primitives::evm::{AccessListItem, BlockLimits, CallInfo, CreateInfo, EstimateResourcesRequest};
-use sp_core::H160;
+use primitives::evm::{
+	tracing::{TraceOutcome, TracerConfig},
+	AccessListItem, BlockLimits, CallInfo, CreateInfo, EstimateResourcesRequest, EvmReceipt, VerifiedContract,
+};
+use sp_core::{H160, H256, U256};
 use sp_runtime::{
 	codec::Codec,
 	traits::{MaybeDisplay, MaybeFromStr},
@@ -81,4 +84,70 @@ sp_api::decl_runtime_apis! {
 			estimate: bool,
 		) -> Result<CreateInfo, sp_runtime::DispatchError>;
 	}
+
+	/// State and receipts served by the Ethereum JSON-RPC.
+	pub trait EthereumRuntimeRPCApi {
+		fn chain_id() -> u64;
+
+		/// Free balance, in EVM decimals.
+		fn balance(address: H160) -> U256;
+
+		/// Nonce of the account mapped to `address`, the one transactions are signed with.
+		fn transaction_count(address: H160) -> U256;
+
+		fn code_at(address: H160) -> Vec<u8>;
+
+		fn storage_at(address: H160, index: H256) -> H256;
+
+		/// Lowest gas price accepted by `eth_call_v2`.
+		fn tx_fee_per_gas() -> U256;
+
+		/// EIP-1559 base fee per gas of the transactions of the next block.
+		fn base_fee_per_gas() -> U256;
+
+		/// Timestamp of the block, in milliseconds.
+		fn timestamp() -> u64;
+
+		/// Receipts of the EVM executions of the block, in extrinsic order.
+		fn receipts() -> Vec<EvmReceipt>;
+
+		/// Convert a signed, RLP encoded Ethereum transaction into an `eth_call_v2` extrinsic.
+		fn ethereum_transaction_to_extrinsic(
+			transaction: Vec<u8>,
+		) -> Result<<Block as sp_runtime::traits::Block>::Extrinsic, sp_runtime::DispatchError>;
+	}
+
+	/// Verified contracts served by the EVM verification RPC.
+	pub trait EVMVerificationApi<AccountId, BlockNumber> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// The verification of the current code of `address`.
+		fn verified_contract(address: H160) -> Option<VerifiedContract<AccountId, BlockNumber>>;
+	}
+
+	/// Runtimes built without the `tracing` feature return an error.
+	pub trait EVMTraceApi<Balance> where
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+	{
+		/// Apply `extrinsic` on top of the current state and trace every EVM execution it performs.
+		///
+		/// The caller is expected to have initialized the block and applied the extrinsics
+		/// preceding `extrinsic` with the same runtime api instance.
+		fn trace_extrinsic(
+			extrinsic: <Block as sp_runtime::traits::Block>::Extrinsic,
+			tracer_config: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError>;
+
+		fn trace_call(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer_config: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError>;
+	}
 }
@@ -2046,6 +2046,10 @@ impl<T: Config> EVMManager<T::AccountId, BalanceOf<T>> for Pallet<T> {
 		})
 	}
 
+	fn query_code(contract: EvmAddress) -> Vec<u8> {
+		Pallet::<T>::code_at_address(&contract).into_inner()
+	}
+
 	fn query_developer_deposit() -> BalanceOf<T> {
 		convert_decimals_to_evm(T::DeveloperDeposit::get())
 	}
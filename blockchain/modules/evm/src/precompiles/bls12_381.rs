@@ -0,0 +1,650 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! BLS12-381 curve operations as specified by EIP-2537.
+//! https://eips.ethereum.org/EIPS/eip-2537
+
+use super::Precompile;
+use crate::{PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult};
+use ark_bls12_381::{g1, g2, Bls12_381, Fq, Fq2, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{
+	hashing::{curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurve},
+	pairing::Pairing,
+	AffineRepr, CurveGroup, VariableBaseMSM,
+};
+use ark_ff::{BigInt, BigInteger, One, PrimeField, Zero};
+use module_evm_utility::evm::{ExitError, ExitSucceed};
+use sp_std::vec::Vec;
+
+/// Encoded base field element: 16 zero bytes followed by the 48-byte big-endian value.
+const FP_LENGTH: usize = 64;
+const FP_PADDING_LENGTH: usize = 16;
+const G1_LENGTH: usize = 2 * FP_LENGTH;
+const G2_LENGTH: usize = 4 * FP_LENGTH;
+const SCALAR_LENGTH: usize = 32;
+const G1_MSM_PAIR_LENGTH: usize = G1_LENGTH + SCALAR_LENGTH;
+const G2_MSM_PAIR_LENGTH: usize = G2_LENGTH + SCALAR_LENGTH;
+const PAIRING_PAIR_LENGTH: usize = G1_LENGTH + G2_LENGTH;
+
+/// Multiplier used by the MSM discount tables.
+const MSM_MULTIPLIER: u64 = 1000;
+
+/// Discounts for G1 MSM, indexed by `k - 1`, capped at the last entry.
+const G1_MSM_DISCOUNT_TABLE: [u16; 128] = [
+	1000, 949, 848, 797, 764, 750, 738, 728, 719, 712, 705, 698, 692, 687, 682, 677, 673, 669, 665, 661, 658, 654, 651,
+	648, 645, 642, 640, 637, 635, 632, 630, 627, 625, 623, 621, 619, 617, 615, 613, 611, 609, 608, 606, 604, 603, 601,
+	599, 598, 596, 595, 593, 592, 591, 589, 588, 586, 585, 584, 582, 581, 580, 579, 577, 576, 575, 574, 573, 572, 570,
+	569, 568, 567, 566, 565, 564, 563, 562, 561, 560, 559, 558, 557, 556, 555, 554, 553, 552, 551, 550, 549, 548, 547,
+	547, 546, 545, 544, 543, 542, 541, 540, 540, 539, 538, 537, 536, 536, 535, 534, 533, 532, 532, 531, 530, 529, 528,
+	528, 527, 526, 525, 525, 524, 523, 522, 522, 521, 520, 520, 519,
+];
+
+/// Discounts for G2 MSM, indexed by `k - 1`, capped at the last entry.
+const G2_MSM_DISCOUNT_TABLE: [u16; 128] = [
+	1000, 1000, 923, 884, 855, 832, 812, 796, 782, 770, 759, 749, 740, 732, 724, 717, 711, 704, 699, 693, 688, 683, 679,
+	674, 670, 666, 663, 659, 655, 652, 649, 646, 643, 640, 637, 634, 632, 629, 627, 624, 622, 620, 618, 615, 613, 611,
+	609, 607, 606, 604, 602, 600, 598, 597, 595, 593, 592, 590, 589, 587, 586, 584, 583, 582, 580, 579, 578, 576, 575,
+	574, 573, 571, 570, 569, 568, 567, 566, 565, 563, 562, 561, 560, 559, 558, 557, 556, 555, 554, 553, 552, 552, 551,
+	550, 549, 548, 547, 546, 545, 545, 544, 543, 542, 541, 541, 540, 539, 538, 537, 537, 536, 535, 535, 534, 533, 532,
+	532, 531, 530, 530, 529, 528, 528, 527, 526, 526, 525, 524, 524,
+];
+
+fn failure(reason: &'static str) -> PrecompileFailure {
+	PrecompileFailure::Error {
+		exit_status: ExitError::Other(reason.into()),
+	}
+}
+
+fn ensure_length(input: &[u8], length: usize) -> Result<(), PrecompileFailure> {
+	if input.len() != length {
+		return Err(failure("Invalid input length"));
+	}
+	Ok(())
+}
+
+/// Returns the number of pairs in `input`, which must be a non-empty multiple of `pair_length`.
+fn pairs_count(input: &[u8], pair_length: usize) -> Result<usize, PrecompileFailure> {
+	if input.is_empty() || input.len() % pair_length != 0 {
+		return Err(failure("Invalid input length"));
+	}
+	Ok(input.len() / pair_length)
+}
+
+fn msm_gas_cost(k: usize, multiplication_cost: u64, discount_table: &[u16; 128]) -> u64 {
+	let discount = discount_table[k.min(discount_table.len()) - 1] as u64;
+	(k as u64)
+		.saturating_mul(multiplication_cost)
+		.saturating_mul(discount)
+		/ MSM_MULTIPLIER
+}
+
+fn read_fp(input: &[u8]) -> Result<Fq, PrecompileFailure> {
+	if input[..FP_PADDING_LENGTH] != [0u8; FP_PADDING_LENGTH] {
+		return Err(failure("Invalid field element top bytes"));
+	}
+
+	let mut limbs = [0u64; 6];
+	for (limb, chunk) in limbs.iter_mut().zip(input[FP_PADDING_LENGTH..FP_LENGTH].rchunks(8)) {
+		*limb = u64::from_be_bytes(chunk.try_into().expect("chunk is 8 bytes; qed"));
+	}
+
+	// rejects values not less than the field modulus
+	Fq::from_bigint(BigInt::new(limbs)).ok_or_else(|| failure("Invalid field element"))
+}
+
+fn read_fp2(input: &[u8]) -> Result<Fq2, PrecompileFailure> {
+	let c0 = read_fp(&input[..FP_LENGTH])?;
+	let c1 = read_fp(&input[FP_LENGTH..2 * FP_LENGTH])?;
+	Ok(Fq2::new(c0, c1))
+}
+
+fn read_scalar(input: &[u8]) -> BigInt<4> {
+	let mut limbs = [0u64; 4];
+	for (limb, chunk) in limbs.iter_mut().zip(input[..SCALAR_LENGTH].rchunks(8)) {
+		*limb = u64::from_be_bytes(chunk.try_into().expect("chunk is 8 bytes; qed"));
+	}
+	BigInt::new(limbs)
+}
+
+fn read_g1(input: &[u8], subgroup_check: bool) -> Result<G1Affine, PrecompileFailure> {
+	let x = read_fp(&input[..FP_LENGTH])?;
+	let y = read_fp(&input[FP_LENGTH..G1_LENGTH])?;
+
+	if x.is_zero() && y.is_zero() {
+		return Ok(G1Affine::identity());
+	}
+
+	let point = G1Affine::new_unchecked(x, y);
+	if !point.is_on_curve() {
+		return Err(failure("Invalid point, not on curve"));
+	}
+	if subgroup_check && !point.is_in_correct_subgroup_assuming_on_curve() {
+		return Err(failure("Invalid point, not in subgroup"));
+	}
+
+	Ok(point)
+}
+
+fn read_g2(input: &[u8], subgroup_check: bool) -> Result<G2Affine, PrecompileFailure> {
+	let x = read_fp2(&input[..2 * FP_LENGTH])?;
+	let y = read_fp2(&input[2 * FP_LENGTH..G2_LENGTH])?;
+
+	if x.is_zero() && y.is_zero() {
+		return Ok(G2Affine::identity());
+	}
+
+	let point = G2Affine::new_unchecked(x, y);
+	if !point.is_on_curve() {
+		return Err(failure("Invalid point, not on curve"));
+	}
+	if subgroup_check && !point.is_in_correct_subgroup_assuming_on_curve() {
+		return Err(failure("Invalid point, not in subgroup"));
+	}
+
+	Ok(point)
+}
+
+fn encode_fp(fp: &Fq, output: &mut [u8]) {
+	output[FP_PADDING_LENGTH..FP_LENGTH].copy_from_slice(&fp.into_bigint().to_bytes_be());
+}
+
+fn encode_g1(point: G1Affine) -> Vec<u8> {
+	let mut output = [0u8; G1_LENGTH];
+	if !point.infinity {
+		encode_fp(&point.x, &mut output[..FP_LENGTH]);
+		encode_fp(&point.y, &mut output[FP_LENGTH..]);
+	}
+	output.to_vec()
+}
+
+fn encode_g2(point: G2Affine) -> Vec<u8> {
+	let mut output = [0u8; G2_LENGTH];
+	if !point.infinity {
+		encode_fp(&point.x.c0, &mut output[..FP_LENGTH]);
+		encode_fp(&point.x.c1, &mut output[FP_LENGTH..2 * FP_LENGTH]);
+		encode_fp(&point.y.c0, &mut output[2 * FP_LENGTH..3 * FP_LENGTH]);
+		encode_fp(&point.y.c1, &mut output[3 * FP_LENGTH..]);
+	}
+	output.to_vec()
+}
+
+/// The Bls12381G1Add builtin
+pub struct Bls12381G1Add;
+
+impl Bls12381G1Add {
+	const GAS_COST: u64 = 375;
+}
+
+impl Precompile for Bls12381G1Add {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381G1Add::GAS_COST)?;
+
+		let input = handle.input();
+		ensure_length(input, 2 * G1_LENGTH)?;
+
+		// no subgroup check is required for addition
+		let p1 = read_g1(&input[..G1_LENGTH], false)?;
+		let p2 = read_g1(&input[G1_LENGTH..], false)?;
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output: encode_g1((p1.into_group() + p2).into_affine()),
+		})
+	}
+
+	#[cfg(feature = "evm-tests")]
+	fn execute_ext(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &crate::Context,
+		is_static: bool,
+	) -> Result<(PrecompileOutput, u64), PrecompileFailure> {
+		let mut handle = crate::precompiles::tests::MockPrecompileHandle::new(&input, target_gas, context, is_static);
+		let output = Self::execute(&mut handle)?;
+
+		Ok((output, handle.gas_used))
+	}
+}
+
+/// The Bls12381G1Msm builtin
+pub struct Bls12381G1Msm;
+
+impl Bls12381G1Msm {
+	const MUL_GAS_COST: u64 = 12_000;
+}
+
+impl Precompile for Bls12381G1Msm {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let k = pairs_count(handle.input(), G1_MSM_PAIR_LENGTH)?;
+		handle.record_cost(msm_gas_cost(k, Bls12381G1Msm::MUL_GAS_COST, &G1_MSM_DISCOUNT_TABLE))?;
+
+		let input = handle.input();
+		let mut bases = Vec::with_capacity(k);
+		let mut scalars = Vec::with_capacity(k);
+		for pair in input.chunks(G1_MSM_PAIR_LENGTH) {
+			bases.push(read_g1(&pair[..G1_LENGTH], true)?);
+			scalars.push(read_scalar(&pair[G1_LENGTH..]));
+		}
+
+		let result = G1Projective::msm_bigint(&bases, &scalars);
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output: encode_g1(result.into_affine()),
+		})
+	}
+
+	#[cfg(feature = "evm-tests")]
+	fn execute_ext(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &crate::Context,
+		is_static: bool,
+	) -> Result<(PrecompileOutput, u64), PrecompileFailure> {
+		let mut handle = crate::precompiles::tests::MockPrecompileHandle::new(&input, target_gas, context, is_static);
+		let output = Self::execute(&mut handle)?;
+
+		Ok((output, handle.gas_used))
+	}
+}
+
+/// The Bls12381G2Add builtin
+pub struct Bls12381G2Add;
+
+impl Bls12381G2Add {
+	const GAS_COST: u64 = 600;
+}
+
+impl Precompile for Bls12381G2Add {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381G2Add::GAS_COST)?;
+
+		let input = handle.input();
+		ensure_length(input, 2 * G2_LENGTH)?;
+
+		// no subgroup check is required for addition
+		let p1 = read_g2(&input[..G2_LENGTH], false)?;
+		let p2 = read_g2(&input[G2_LENGTH..], false)?;
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output: encode_g2((p1.into_group() + p2).into_affine()),
+		})
+	}
+
+	#[cfg(feature = "evm-tests")]
+	fn execute_ext(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &crate::Context,
+		is_static: bool,
+	) -> Result<(PrecompileOutput, u64), PrecompileFailure> {
+		let mut handle = crate::precompiles::tests::MockPrecompileHandle::new(&input, target_gas, context, is_static);
+		let output = Self::execute(&mut handle)?;
+
+		Ok((output, handle.gas_used))
+	}
+}
+
+/// The Bls12381G2Msm builtin
+pub struct Bls12381G2Msm;
+
+impl Bls12381G2Msm {
+	const MUL_GAS_COST: u64 = 22_500;
+}
+
+impl Precompile for Bls12381G2Msm {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let k = pairs_count(handle.input(), G2_MSM_PAIR_LENGTH)?;
+		handle.record_cost(msm_gas_cost(k, Bls12381G2Msm::MUL_GAS_COST, &G2_MSM_DISCOUNT_TABLE))?;
+
+		let input = handle.input();
+		let mut bases = Vec::with_capacity(k);
+		let mut scalars = Vec::with_capacity(k);
+		for pair in input.chunks(G2_MSM_PAIR_LENGTH) {
+			bases.push(read_g2(&pair[..G2_LENGTH], true)?);
+			scalars.push(read_scalar(&pair[G2_LENGTH..]));
+		}
+
+		let result = G2Projective::msm_bigint(&bases, &scalars);
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output: encode_g2(result.into_affine()),
+		})
+	}
+
+	#[cfg(feature = "evm-tests")]
+	fn execute_ext(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &crate::Context,
+		is_static: bool,
+	) -> Result<(PrecompileOutput, u64), PrecompileFailure> {
+		let mut handle = crate::precompiles::tests::MockPrecompileHandle::new(&input, target_gas, context, is_static);
+		let output = Self::execute(&mut handle)?;
+
+		Ok((output, handle.gas_used))
+	}
+}
+
+/// The Bls12381Pairing builtin
+pub struct Bls12381Pairing;
+
+impl Bls12381Pairing {
+	const BASE_GAS_COST: u64 = 37_700;
+	const GAS_COST_PER_PAIRING: u64 = 32_600;
+}
+
+impl Precompile for Bls12381Pairing {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let k = pairs_count(handle.input(), PAIRING_PAIR_LENGTH)?;
+		handle.record_cost(
+			Bls12381Pairing::BASE_GAS_COST.saturating_add((k as u64).saturating_mul(Bls12381Pairing::GAS_COST_PER_PAIRING)),
+		)?;
+
+		let input = handle.input();
+		let mut g1_points = Vec::with_capacity(k);
+		let mut g2_points = Vec::with_capacity(k);
+		for pair in input.chunks(PAIRING_PAIR_LENGTH) {
+			g1_points.push(read_g1(&pair[..G1_LENGTH], true)?);
+			g2_points.push(read_g2(&pair[G1_LENGTH..], true)?);
+		}
+
+		let mut output = [0u8; 32];
+		if Bls12_381::multi_pairing(g1_points, g2_points).0.is_one() {
+			output[31] = 1;
+		}
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output: output.to_vec(),
+		})
+	}
+
+	#[cfg(feature = "evm-tests")]
+	fn execute_ext(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &crate::Context,
+		is_static: bool,
+	) -> Result<(PrecompileOutput, u64), PrecompileFailure> {
+		let mut handle = crate::precompiles::tests::MockPrecompileHandle::new(&input, target_gas, context, is_static);
+		let output = Self::execute(&mut handle)?;
+
+		Ok((output, handle.gas_used))
+	}
+}
+
+/// The Bls12381MapFpToG1 builtin
+pub struct Bls12381MapFpToG1;
+
+impl Bls12381MapFpToG1 {
+	const GAS_COST: u64 = 5_500;
+}
+
+impl Precompile for Bls12381MapFpToG1 {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381MapFpToG1::GAS_COST)?;
+
+		let input = handle.input();
+		ensure_length(input, FP_LENGTH)?;
+
+		let fp = read_fp(input)?;
+		let point = WBMap::<g1::Config>::new()
+			.and_then(|mapper| mapper.map_to_curve(fp))
+			.map_err(|_| failure("Failed to map field element to G1"))?
+			.clear_cofactor();
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output: encode_g1(point),
+		})
+	}
+
+	#[cfg(feature = "evm-tests")]
+	fn execute_ext(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &crate::Context,
+		is_static: bool,
+	) -> Result<(PrecompileOutput, u64), PrecompileFailure> {
+		let mut handle = crate::precompiles::tests::MockPrecompileHandle::new(&input, target_gas, context, is_static);
+		let output = Self::execute(&mut handle)?;
+
+		Ok((output, handle.gas_used))
+	}
+}
+
+/// The Bls12381MapFp2ToG2 builtin
+pub struct Bls12381MapFp2ToG2;
+
+impl Bls12381MapFp2ToG2 {
+	const GAS_COST: u64 = 23_800;
+}
+
+impl Precompile for Bls12381MapFp2ToG2 {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381MapFp2ToG2::GAS_COST)?;
+
+		let input = handle.input();
+		ensure_length(input, 2 * FP_LENGTH)?;
+
+		let fp2 = read_fp2(input)?;
+		let point = WBMap::<g2::Config>::new()
+			.and_then(|mapper| mapper.map_to_curve(fp2))
+			.map_err(|_| failure("Failed to map field element to G2"))?
+			.clear_cofactor();
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output: encode_g2(point),
+		})
+	}
+
+	#[cfg(feature = "evm-tests")]
+	fn execute_ext(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &crate::Context,
+		is_static: bool,
+	) -> Result<(PrecompileOutput, u64), PrecompileFailure> {
+		let mut handle = crate::precompiles::tests::MockPrecompileHandle::new(&input, target_gas, context, is_static);
+		let output = Self::execute(&mut handle)?;
+
+		Ok((output, handle.gas_used))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::precompiles::tests::MockPrecompileHandle;
+	use ark_bls12_381::Fr;
+	use module_evm_utility::evm::Context;
+	use sp_core::U256;
+
+	fn get_context() -> Context {
+		Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: U256::zero(),
+		}
+	}
+
+	fn scalar(value: u64) -> [u8; 32] {
+		let mut output = [0u8; 32];
+		output[24..].copy_from_slice(&value.to_be_bytes());
+		output
+	}
+
+	#[test]
+	fn bls12_381_g1_add() {
+		let g = G1Affine::generator();
+		let mut input = encode_g1(g);
+		input.extend(encode_g1(g));
+
+		let mut handle = MockPrecompileHandle::new(&input, None, &get_context(), false);
+		let output = Bls12381G1Add::execute(&mut handle).unwrap().output;
+		assert_eq!(output, encode_g1((g * Fr::from(2u64)).into_affine()));
+		assert_eq!(handle.gas_used, 375);
+
+		// adding the point at infinity
+		let mut input = encode_g1(g);
+		input.extend([0u8; G1_LENGTH]);
+		assert_eq!(
+			Bls12381G1Add::execute(&mut MockPrecompileHandle::new(&input, None, &get_context(), false))
+				.unwrap()
+				.output,
+			encode_g1(g)
+		);
+
+		// should fail - point not on curve
+		let mut input = encode_g1(g);
+		input.extend(encode_g1(g));
+		input[G1_LENGTH - 1] ^= 1;
+		assert_eq!(
+			Bls12381G1Add::execute(&mut MockPrecompileHandle::new(&input, None, &get_context(), false)),
+			Err(failure("Invalid point, not on curve"))
+		);
+
+		// should fail - padding is not zero
+		let mut input = encode_g1(g);
+		input.extend(encode_g1(g));
+		input[0] = 1;
+		assert_eq!(
+			Bls12381G1Add::execute(&mut MockPrecompileHandle::new(&input, None, &get_context(), false)),
+			Err(failure("Invalid field element top bytes"))
+		);
+
+		// should fail - invalid length
+		assert_eq!(
+			Bls12381G1Add::execute(&mut MockPrecompileHandle::new(&input[1..], None, &get_context(), false)),
+			Err(failure("Invalid input length"))
+		);
+	}
+
+	#[test]
+	fn bls12_381_g1_msm() {
+		let g = G1Affine::generator();
+		let mut input = encode_g1(g);
+		input.extend(scalar(2));
+		input.extend(encode_g1(g));
+		input.extend(scalar(3));
+
+		let mut handle = MockPrecompileHandle::new(&input, None, &get_context(), false);
+		let output = Bls12381G1Msm::execute(&mut handle).unwrap().output;
+		assert_eq!(output, encode_g1((g * Fr::from(5u64)).into_affine()));
+		// 2 * 12000 * 949 / 1000
+		assert_eq!(handle.gas_used, 22_776);
+
+		// should fail - empty input
+		assert_eq!(
+			Bls12381G1Msm::execute(&mut MockPrecompileHandle::new(&[], None, &get_context(), false)),
+			Err(failure("Invalid input length"))
+		);
+	}
+
+	#[test]
+	fn bls12_381_g2_add_and_msm() {
+		let g = G2Affine::generator();
+		let mut input = encode_g2(g);
+		input.extend(encode_g2(g));
+
+		let mut handle = MockPrecompileHandle::new(&input, None, &get_context(), false);
+		let output = Bls12381G2Add::execute(&mut handle).unwrap().output;
+		assert_eq!(output, encode_g2((g * Fr::from(2u64)).into_affine()));
+		assert_eq!(handle.gas_used, 600);
+
+		let mut input = encode_g2(g);
+		input.extend(scalar(7));
+
+		let mut handle = MockPrecompileHandle::new(&input, None, &get_context(), false);
+		let output = Bls12381G2Msm::execute(&mut handle).unwrap().output;
+		assert_eq!(output, encode_g2((g * Fr::from(7u64)).into_affine()));
+		assert_eq!(handle.gas_used, 22_500);
+	}
+
+	#[test]
+	fn bls12_381_pairing() {
+		let g1 = G1Affine::generator();
+		let g2 = G2Affine::generator();
+
+		// e(g1, g2) * e(-g1, g2) == 1
+		let mut input = encode_g1(g1);
+		input.extend(encode_g2(g2));
+		input.extend(encode_g1(-g1));
+		input.extend(encode_g2(g2));
+
+		let mut handle = MockPrecompileHandle::new(&input, None, &get_context(), false);
+		let output = Bls12381Pairing::execute(&mut handle).unwrap().output;
+		let mut expected = [0u8; 32];
+		expected[31] = 1;
+		assert_eq!(output, expected.to_vec());
+		assert_eq!(handle.gas_used, 37_700 + 2 * 32_600);
+
+		// e(g1, g2) != 1
+		let mut input = encode_g1(g1);
+		input.extend(encode_g2(g2));
+		assert_eq!(
+			Bls12381Pairing::execute(&mut MockPrecompileHandle::new(&input, None, &get_context(), false))
+				.unwrap()
+				.output,
+			[0u8; 32].to_vec()
+		);
+	}
+
+	#[test]
+	fn bls12_381_map_to_curve() {
+		let mut input = [0u8; FP_LENGTH];
+		input[FP_LENGTH - 1] = 1;
+
+		let mut handle = MockPrecompileHandle::new(&input, None, &get_context(), false);
+		let output = Bls12381MapFpToG1::execute(&mut handle).unwrap().output;
+		let point = read_g1(&output, true).unwrap();
+		assert!(!point.is_zero());
+		assert_eq!(handle.gas_used, 5_500);
+
+		let mut input = [0u8; 2 * FP_LENGTH];
+		input[FP_LENGTH - 1] = 1;
+		input[2 * FP_LENGTH - 1] = 2;
+
+		let mut handle = MockPrecompileHandle::new(&input, None, &get_context(), false);
+		let output = Bls12381MapFp2ToG2::execute(&mut handle).unwrap().output;
+		let point = read_g2(&output, true).unwrap();
+		assert!(!point.is_zero());
+		assert_eq!(handle.gas_used, 23_800);
+
+		// should fail - field element is not less than the modulus
+		let mut input = [0xffu8; FP_LENGTH];
+		input[..FP_PADDING_LENGTH].copy_from_slice(&[0u8; FP_PADDING_LENGTH]);
+		assert_eq!(
+			Bls12381MapFpToG1::execute(&mut MockPrecompileHandle::new(&input, None, &get_context(), false)),
+			Err(failure("Invalid field element"))
+		);
+	}
+
+	#[test]
+	fn msm_discount_is_capped() {
+		assert_eq!(msm_gas_cost(1, 12_000, &G1_MSM_DISCOUNT_TABLE), 12_000);
+		assert_eq!(msm_gas_cost(128, 12_000, &G1_MSM_DISCOUNT_TABLE), 128 * 12_000 * 519 / 1000);
+		assert_eq!(msm_gas_cost(200, 22_500, &G2_MSM_DISCOUNT_TABLE), 200 * 22_500 * 524 / 1000);
+	}
+}
@@ -0,0 +1,291 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::Precompile;
+use crate::{PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult};
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G2Affine};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{BigInt, One, PrimeField};
+use ark_serialize::CanonicalDeserialize;
+use frame_support::traits::Get;
+use hex_literal::hex;
+use module_evm_utility::evm::{ExitError, ExitSucceed};
+use sp_std::marker::PhantomData;
+
+/// Version byte prefixed to the sha256 hash of a KZG commitment.
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+/// Number of field elements in a blob.
+pub const FIELD_ELEMENTS_PER_BLOB: u64 = 4096;
+/// Modulus of the BLS12-381 scalar field, big-endian.
+pub const BLS_MODULUS: [u8; 32] = hex!("73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001");
+/// Compressed `g2_monomial[1]` of the Ethereum KZG ceremony output, i.e. `[tau]G2`.
+pub const ETHEREUM_KZG_TAU_G2: [u8; 96] = hex!(
+	"b5bfd7dd8cdeb128843bc287230af38926187075cbfbefa81009a2ce615ac53d2914e5870cb452d2afaaab24f3499f72
+	185cbfee53492714734429b7b38608e23926c911cceceac9a36851477ba4c60b087041de621000edc98edada20c1def2"
+);
+
+/// The `[tau]G2` point of the Ethereum mainnet trusted setup.
+pub struct EthereumKzgTauG2;
+impl Get<[u8; 96]> for EthereumKzgTauG2 {
+	fn get() -> [u8; 96] {
+		ETHEREUM_KZG_TAU_G2
+	}
+}
+
+/// The KzgPointEvaluation builtin
+///
+/// Verifies a KZG proof that the polynomial committed to by `commitment` evaluates to `y` at
+/// `z`, as specified by EIP-4844. Input is
+/// `versioned_hash || z || y || commitment || proof` (192 bytes).
+///
+/// `TauG2` provides the compressed `[tau]G2` point of the trusted setup. On Ethereum
+/// compatible chains this must be `g2_monomial[1]` of the KZG ceremony output.
+pub struct KzgPointEvaluation<TauG2>(PhantomData<TauG2>);
+
+impl<TauG2> KzgPointEvaluation<TauG2> {
+	const GAS_COST: u64 = 50_000; // https://eips.ethereum.org/EIPS/eip-4844
+	const INPUT_LENGTH: usize = 192;
+}
+
+fn failure(reason: &'static str) -> PrecompileFailure {
+	PrecompileFailure::Error {
+		exit_status: ExitError::Other(reason.into()),
+	}
+}
+
+fn read_fr(input: &[u8]) -> Result<Fr, PrecompileFailure> {
+	let mut limbs = [0u64; 4];
+	for (limb, chunk) in limbs.iter_mut().zip(input[..32].rchunks(8)) {
+		*limb = u64::from_be_bytes(chunk.try_into().expect("chunk is 8 bytes; qed"));
+	}
+
+	// rejects values not less than BLS_MODULUS
+	Fr::from_bigint(BigInt::new(limbs)).ok_or_else(|| failure("Invalid field element"))
+}
+
+fn read_g1(input: &[u8]) -> Result<G1Affine, PrecompileFailure> {
+	// validates the point is on the curve and in the correct subgroup
+	G1Affine::deserialize_compressed(input).map_err(|_| failure("Invalid point"))
+}
+
+/// Returns the versioned hash of a KZG commitment.
+pub fn kzg_to_versioned_hash(commitment: &[u8]) -> [u8; 32] {
+	let mut hash = sp_io::hashing::sha2_256(commitment);
+	hash[0] = VERSIONED_HASH_VERSION_KZG;
+	hash
+}
+
+/// Checks `e(commitment - [y]G1, G2) == e(proof, [tau]G2 - [z]G2)`.
+fn verify_kzg_proof(commitment: G1Affine, z: Fr, y: Fr, proof: G1Affine, tau_g2: G2Affine) -> bool {
+	let g1 = G1Affine::generator();
+	let g2 = G2Affine::generator();
+
+	let lhs = (commitment.into_group() - g1 * y).into_affine();
+	let rhs = (tau_g2.into_group() - g2 * z).into_affine();
+
+	Bls12_381::multi_pairing([lhs, -proof], [g2, rhs]).0.is_one()
+}
+
+impl<TauG2: Get<[u8; 96]>> Precompile for KzgPointEvaluation<TauG2> {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Self::GAS_COST)?;
+
+		let input = handle.input();
+		if input.len() != Self::INPUT_LENGTH {
+			return Err(failure("Invalid input length"));
+		}
+
+		let versioned_hash = &input[0..32];
+		let commitment = &input[96..144];
+		if kzg_to_versioned_hash(commitment) != versioned_hash {
+			return Err(failure("Mismatched versioned hash"));
+		}
+
+		let z = read_fr(&input[32..64])?;
+		let y = read_fr(&input[64..96])?;
+		let commitment = read_g1(commitment)?;
+		let proof = read_g1(&input[144..192])?;
+		let tau_g2 = G2Affine::deserialize_compressed(&TauG2::get()[..])
+			.map_err(|_| failure("Invalid trusted setup"))?;
+
+		if !verify_kzg_proof(commitment, z, y, proof, tau_g2) {
+			return Err(failure("Invalid proof"));
+		}
+
+		let mut output = [0u8; 64];
+		output[24..32].copy_from_slice(&FIELD_ELEMENTS_PER_BLOB.to_be_bytes());
+		output[32..64].copy_from_slice(&BLS_MODULUS);
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output: output.to_vec(),
+		})
+	}
+
+	#[cfg(feature = "evm-tests")]
+	fn execute_ext(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &crate::Context,
+		is_static: bool,
+	) -> Result<(PrecompileOutput, u64), PrecompileFailure> {
+		let mut handle = crate::precompiles::tests::MockPrecompileHandle::new(&input, target_gas, context, is_static);
+		let output = Self::execute(&mut handle)?;
+
+		Ok((output, handle.gas_used))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::precompiles::tests::MockPrecompileHandle;
+	use ark_ff::BigInteger;
+	use ark_serialize::CanonicalSerialize;
+	use module_evm_utility::evm::Context;
+	use sp_core::U256;
+
+	// insecure setup with a known tau, only used to produce proofs in tests
+	const TAU: u64 = 5;
+
+	pub struct TestTauG2;
+	impl Get<[u8; 96]> for TestTauG2 {
+		fn get() -> [u8; 96] {
+			let mut output = [0u8; 96];
+			(G2Affine::generator() * Fr::from(TAU))
+				.into_affine()
+				.serialize_compressed(&mut output[..])
+				.unwrap();
+			output
+		}
+	}
+
+	type KzgPointEvaluationPrecompile = KzgPointEvaluation<TestTauG2>;
+
+	fn get_context() -> Context {
+		Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: U256::zero(),
+		}
+	}
+
+	fn encode_fr(fr: Fr) -> [u8; 32] {
+		let mut output = [0u8; 32];
+		output.copy_from_slice(&fr.into_bigint().to_bytes_be());
+		output
+	}
+
+	fn encode_g1(point: G1Affine) -> [u8; 48] {
+		let mut output = [0u8; 48];
+		point.serialize_compressed(&mut output[..]).unwrap();
+		output
+	}
+
+	// p(X) = a + b * X, opened at z: y = p(z), quotient q(X) = (p(X) - y) / (X - z) = b
+	fn build_input(a: u64, b: u64, z: u64) -> Vec<u8> {
+		let (a, b, z) = (Fr::from(a), Fr::from(b), Fr::from(z));
+		let g1 = G1Affine::generator();
+		let commitment = encode_g1((g1 * (a + b * Fr::from(TAU))).into_affine());
+		let proof = encode_g1((g1 * b).into_affine());
+
+		let mut input = Vec::new();
+		input.extend_from_slice(&kzg_to_versioned_hash(&commitment));
+		input.extend_from_slice(&encode_fr(z));
+		input.extend_from_slice(&encode_fr(a + b * z));
+		input.extend_from_slice(&commitment);
+		input.extend_from_slice(&proof);
+		input
+	}
+
+	#[test]
+	fn kzg_point_evaluation_works() {
+		let input = build_input(3, 7, 11);
+
+		let mut handle = MockPrecompileHandle::new(&input, None, &get_context(), false);
+		let output = KzgPointEvaluationPrecompile::execute(&mut handle).unwrap().output;
+		assert_eq!(
+			output,
+			hex!(
+				"0000000000000000000000000000000000000000000000000000000000001000
+				73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001"
+			)
+			.to_vec()
+		);
+		assert_eq!(handle.gas_used, 50_000);
+	}
+
+	#[test]
+	fn ethereum_trusted_setup_is_valid() {
+		let tau_g2 = G2Affine::deserialize_compressed(&EthereumKzgTauG2::get()[..]).unwrap();
+		assert_ne!(tau_g2, G2Affine::generator());
+
+		// opening against the mainnet setup with a proof from the test setup must fail
+		let input = build_input(3, 7, 11);
+		assert_eq!(
+			KzgPointEvaluation::<EthereumKzgTauG2>::execute(&mut MockPrecompileHandle::new(
+				&input,
+				None,
+				&get_context(),
+				false
+			)),
+			Err(failure("Invalid proof"))
+		);
+	}
+
+	#[test]
+	fn kzg_point_evaluation_fails() {
+		// wrong evaluation
+		let mut input = build_input(3, 7, 11);
+		input[95] ^= 1;
+		assert_eq!(
+			KzgPointEvaluationPrecompile::execute(&mut MockPrecompileHandle::new(&input, None, &get_context(), false)),
+			Err(failure("Invalid proof"))
+		);
+
+		// wrong versioned hash
+		let mut input = build_input(3, 7, 11);
+		input[0] = 0x02;
+		assert_eq!(
+			KzgPointEvaluationPrecompile::execute(&mut MockPrecompileHandle::new(&input, None, &get_context(), false)),
+			Err(failure("Mismatched versioned hash"))
+		);
+
+		// z not less than BLS_MODULUS
+		let mut input = build_input(3, 7, 11);
+		input[32..64].copy_from_slice(&BLS_MODULUS);
+		assert_eq!(
+			KzgPointEvaluationPrecompile::execute(&mut MockPrecompileHandle::new(&input, None, &get_context(), false)),
+			Err(failure("Invalid field element"))
+		);
+
+		// invalid length
+		let input = build_input(3, 7, 11);
+		assert_eq!(
+			KzgPointEvaluationPrecompile::execute(&mut MockPrecompileHandle::new(
+				&input[..191],
+				None,
+				&get_context(),
+				false
+			)),
+			Err(failure("Invalid input length"))
+		);
+	}
+}
@@ -25,22 +25,30 @@ use module_evm_utility::evm::{ExitError, ExitSucceed};
 use sp_std::vec::Vec;
 
 mod blake2;
+mod bls12_381;
 mod bn128;
 mod ecrecover;
 mod ecrecover_publickey;
 mod identity;
+mod kzg_point_evaluation;
 mod modexp;
+mod p256;
 mod ripemd;
 mod sha256;
 mod sha3fips;
 
 pub use self::ripemd::Ripemd160;
 pub use blake2::Blake2F;
+pub use bls12_381::{
+	Bls12381G1Add, Bls12381G1Msm, Bls12381G2Add, Bls12381G2Msm, Bls12381MapFp2ToG2, Bls12381MapFpToG1, Bls12381Pairing,
+};
 pub use bn128::{Bn128Add, Bn128Mul, Bn128Pairing};
 pub use ecrecover::ECRecover;
 pub use ecrecover_publickey::ECRecoverPublicKey;
 pub use identity::Identity;
+pub use kzg_point_evaluation::{kzg_to_versioned_hash, EthereumKzgTauG2, KzgPointEvaluation, ETHEREUM_KZG_TAU_G2};
 pub use modexp::{IstanbulModexp, Modexp};
+pub use p256::P256Verify;
 pub use sha256::Sha256;
 pub use sha3fips::{Sha3FIPS256, Sha3FIPS512};
 
@@ -0,0 +1,127 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::LinearCostPrecompile;
+use crate::PrecompileFailure;
+use module_evm_utility::evm::ExitSucceed;
+use p256::ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey};
+use sp_std::vec::Vec;
+
+/// The P256Verify precompile.
+///
+/// Verifies a secp256r1 (P-256) signature over a message hash as specified by RIP-7212.
+/// Input is `hash || r || s || x || y` (160 bytes). Returns 32-byte `1` on success and empty
+/// output on failure.
+pub struct P256Verify;
+
+impl P256Verify {
+	/// https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md
+	pub const INPUT_LENGTH: usize = 160;
+}
+
+impl LinearCostPrecompile for P256Verify {
+	const BASE: u64 = 3450;
+	const WORD: u64 = 0;
+
+	fn execute(input: &[u8], _: u64) -> core::result::Result<(ExitSucceed, Vec<u8>), PrecompileFailure> {
+		if input.len() != Self::INPUT_LENGTH {
+			return Ok((ExitSucceed::Returned, [0u8; 0].to_vec()));
+		}
+
+		let msg = &input[0..32];
+
+		// uncompressed SEC1 encoding of the public key
+		let mut public_key = [0u8; 65];
+		public_key[0] = 0x04;
+		public_key[1..65].copy_from_slice(&input[96..160]);
+
+		let verified = match (
+			Signature::from_slice(&input[32..96]),
+			VerifyingKey::from_sec1_bytes(&public_key),
+		) {
+			(Ok(signature), Ok(verifying_key)) => verifying_key.verify_prehash(msg, &signature).is_ok(),
+			_ => false,
+		};
+
+		let result = if verified {
+			let mut output = [0u8; 32];
+			output[31] = 1;
+			output.to_vec()
+		} else {
+			[0u8; 0].to_vec()
+		};
+
+		Ok((ExitSucceed::Returned, result))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use p256::ecdsa::{signature::hazmat::PrehashSigner, SigningKey};
+
+	fn signed_input() -> Vec<u8> {
+		let signing_key = SigningKey::from_slice(&[0x11u8; 32]).unwrap();
+		let msg = sp_io::hashing::sha2_256(b"setheum passkey");
+		let signature: Signature = signing_key.sign_prehash(&msg).unwrap();
+		let public_key = signing_key.verifying_key().to_encoded_point(false);
+
+		let mut input = Vec::new();
+		input.extend_from_slice(&msg);
+		input.extend_from_slice(&signature.to_bytes());
+		input.extend_from_slice(&public_key.as_bytes()[1..65]);
+		input
+	}
+
+	#[test]
+	fn verify_valid_signature() {
+		let input = signed_input();
+		let (exit, output) = P256Verify::execute(&input, 0).unwrap();
+		assert_eq!(exit, ExitSucceed::Returned);
+
+		let mut expected = [0u8; 32];
+		expected[31] = 1;
+		assert_eq!(output, expected.to_vec());
+	}
+
+	#[test]
+	fn verify_invalid_signature_returns_empty() {
+		// tampered message hash
+		let mut input = signed_input();
+		input[0] ^= 0xff;
+		let (exit, output) = P256Verify::execute(&input, 0).unwrap();
+		assert_eq!(exit, ExitSucceed::Returned);
+		assert_eq!(output, [0u8; 0].to_vec());
+
+		// public key not on curve
+		let mut input = signed_input();
+		input[159] ^= 0xff;
+		assert_eq!(P256Verify::execute(&input, 0).unwrap().1, [0u8; 0].to_vec());
+
+		// zero signature
+		let mut input = signed_input();
+		input[32..96].copy_from_slice(&[0u8; 64]);
+		assert_eq!(P256Verify::execute(&input, 0).unwrap().1, [0u8; 0].to_vec());
+
+		// invalid input length
+		let input = signed_input();
+		assert_eq!(P256Verify::execute(&input[..159], 0).unwrap().1, [0u8; 0].to_vec());
+	}
+}
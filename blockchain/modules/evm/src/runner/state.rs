@@ -47,39 +47,19 @@ use sp_core::{H160, H256, U256};
 use sp_runtime::traits::Zero;
 use sp_std::{collections::btree_set::BTreeSet, rc::Rc, vec::Vec};
 
+#[cfg(not(feature = "tracing"))]
 macro_rules! event {
 	($x:expr) => {};
 }
 
 #[cfg(feature = "tracing")]
-mod tracing {
-	pub struct Tracer;
-	impl module_evm_utility::evm::tracing::EventListener for Tracer {
-		fn event(&mut self, event: module_evm_utility::evm::tracing::Event) {
-			frame_support::log::debug!(
-				target: "evm", "evm tracing: {:?}", event
-			);
-		}
-	}
-	impl module_evm_utility::evm_runtime::tracing::EventListener for Tracer {
-		fn event(&mut self, event: module_evm_utility::evm_runtime::tracing::Event) {
-			frame_support::log::debug!(
-				target: "evm", "evm_runtime tracing: {:?}", event
-			);
-		}
-	}
-	impl module_evm_utility::evm_gasometer::tracing::EventListener for Tracer {
-		fn event(&mut self, event: module_evm_utility::evm_gasometer::tracing::Event) {
-			frame_support::log::debug!(
-				target: "evm", "evm_gasometer tracing: {:?}", event
-			);
-		}
-	}
+macro_rules! event {
+	($x:expr) => {{
+		use crate::runner::tracing::Event::*;
+		crate::runner::tracing::emit($x);
+	}};
 }
 
-#[cfg(feature = "tracing")]
-use tracing::*;
-
 macro_rules! emit_exit {
 	($reason:expr) => {{
 		let reason = $reason;
@@ -423,6 +403,7 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 
 	/// Create a substate executor from the current executor.
 	pub fn enter_substate(&mut self, gas_limit: u64, is_static: bool) {
+		event!(Enter { gas_limit });
 		self.state.enter(gas_limit, is_static);
 	}
 
@@ -562,7 +543,7 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 			value,
 			init_code: &init_code,
 			gas_limit,
-			address: self.create_address(CreateScheme::Legacy { caller }),
+			address: self.create_address(CreateScheme::Legacy { caller }).unwrap_or_default(),
 		});
 
 		if let Some(limit) = self.config.max_initcode_size {
@@ -619,11 +600,13 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 			init_code: &init_code,
 			salt,
 			gas_limit,
-			address: self.create_address(CreateScheme::Create2 {
-				caller,
-				code_hash,
-				salt,
-			}),
+			address: self
+				.create_address(CreateScheme::Create2 {
+					caller,
+					code_hash,
+					salt,
+				})
+				.unwrap_or_default(),
 		});
 
 		if let Err(e) = self.record_create_transaction_cost(&init_code, &access_list) {
@@ -107,6 +107,8 @@ impl StorageMeter {
 			"StorageMeter: charge: storage {:?}",
 			storage
 		);
+		#[cfg(feature = "tracing")]
+		crate::runner::tracing::emit(crate::runner::tracing::Event::StorageCharge { storage });
 		self.used = self.used.saturating_add(storage);
 	}
 
@@ -116,6 +118,8 @@ impl StorageMeter {
 			"StorageMeter: uncharge: storage {:?}",
 			storage
 		);
+		#[cfg(feature = "tracing")]
+		crate::runner::tracing::emit(crate::runner::tracing::Event::StorageUncharge { storage });
 		self.used = self.used.saturating_sub(storage);
 	}
 
@@ -125,6 +129,8 @@ impl StorageMeter {
 			"StorageMeter: refund: storage {:?}",
 			storage
 		);
+		#[cfg(feature = "tracing")]
+		crate::runner::tracing::emit(crate::runner::tracing::Event::StorageRefund { storage });
 		self.refunded = self.refunded.saturating_add(storage);
 	}
 
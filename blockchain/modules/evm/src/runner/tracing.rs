@@ -0,0 +1,508 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Structured EVM tracing.
+//!
+//! The stack executor in `runner::state` and the `StorageMeter` emit [`Event`]s, the
+//! interpreter and gasometer emit their own `tracing` events. [`Tracer`] listens to all
+//! of them and builds Geth compatible call traces or struct logs.
+
+use module_evm_utility::{
+	evm::{Context, CreateScheme, ExitReason, ExitSucceed, Transfer},
+	evm_gasometer,
+	evm_runtime,
+};
+pub use primitives::evm::tracing::{CallTrace, CallType, OpcodeTrace, OpcodeTracerConfig, Step, TraceOutcome, TracerConfig};
+use sp_core::{H160, H256, U256};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+pub enum Event<'a> {
+	Call {
+		code_address: H160,
+		transfer: &'a Option<Transfer>,
+		input: &'a [u8],
+		target_gas: Option<u64>,
+		is_static: bool,
+		context: &'a Context,
+	},
+	Create {
+		caller: H160,
+		address: H160,
+		scheme: CreateScheme,
+		value: U256,
+		init_code: &'a [u8],
+		target_gas: Option<u64>,
+	},
+	Suicide {
+		address: H160,
+		target: H160,
+		balance: U256,
+	},
+	Enter {
+		gas_limit: u64,
+	},
+	Exit {
+		reason: &'a ExitReason,
+		return_value: &'a [u8],
+	},
+	TransactCall {
+		caller: H160,
+		address: H160,
+		value: U256,
+		data: &'a [u8],
+		gas_limit: u64,
+	},
+	TransactCreate {
+		caller: H160,
+		value: U256,
+		init_code: &'a [u8],
+		gas_limit: u64,
+		address: H160,
+	},
+	TransactCreate2 {
+		caller: H160,
+		value: U256,
+		init_code: &'a [u8],
+		salt: H256,
+		gas_limit: u64,
+		address: H160,
+	},
+	PrecompileSubcall {
+		code_address: H160,
+		transfer: &'a Option<Transfer>,
+		input: &'a [u8],
+		target_gas: Option<u64>,
+		is_static: bool,
+		context: &'a Context,
+	},
+	StorageCharge {
+		storage: u32,
+	},
+	StorageUncharge {
+		storage: u32,
+	},
+	StorageRefund {
+		storage: u32,
+	},
+}
+
+environmental::environmental!(tracer: Tracer);
+
+/// Forward an event to the active tracer, if any.
+pub fn emit(event: Event) {
+	tracer::with(|tracer| tracer.on_event(event));
+}
+
+struct Frame {
+	trace: CallTrace,
+	/// Gas limit of the gasometer executing this frame, set once its substate is entered.
+	gasometer_limit: Option<u64>,
+	/// Gas left in that gasometer after its last recorded event.
+	remaining_gas: Option<u64>,
+}
+
+pub struct Tracer {
+	config: TracerConfig,
+	frames: Vec<Frame>,
+	/// The next `Call` / `Create` is the one started by a `Transact*` event.
+	entering_transaction: bool,
+	/// Gas left in the gasometer of the last recorded event.
+	remaining_gas: u64,
+	storage: BTreeMap<H160, BTreeMap<H256, H256>>,
+	steps: Vec<Step>,
+	step_open: bool,
+	calls: Vec<CallTrace>,
+	opcode_traces: Vec<OpcodeTrace>,
+}
+
+impl Tracer {
+	pub fn new(config: TracerConfig) -> Self {
+		Self {
+			config,
+			frames: Vec::new(),
+			entering_transaction: false,
+			remaining_gas: 0,
+			storage: BTreeMap::new(),
+			steps: Vec::new(),
+			step_open: false,
+			calls: Vec::new(),
+			opcode_traces: Vec::new(),
+		}
+	}
+
+	pub fn finalize(self) -> TraceOutcome {
+		match self.config {
+			TracerConfig::CallTracer => TraceOutcome::Calls(self.calls),
+			TracerConfig::OpcodeTracer(_) => TraceOutcome::Steps(self.opcode_traces),
+		}
+	}
+
+	fn opcode_config(&self) -> Option<OpcodeTracerConfig> {
+		match self.config {
+			TracerConfig::CallTracer => None,
+			TracerConfig::OpcodeTracer(config) => Some(config),
+		}
+	}
+
+	fn enter(&mut self, trace: CallTrace) {
+		if self.entering_transaction {
+			// the root frame was already opened by the `Transact*` event
+			self.entering_transaction = false;
+			if let Some(frame) = self.frames.last_mut() {
+				if matches!(trace.call_type, CallType::CREATE | CallType::CREATE2) {
+					frame.trace.to = trace.to;
+				}
+			}
+		} else {
+			self.frames.push(Frame {
+				trace,
+				gasometer_limit: None,
+				remaining_gas: None,
+			});
+		}
+		self.step_open = false;
+	}
+
+	fn exit(&mut self, reason: &ExitReason, return_value: &[u8]) {
+		self.entering_transaction = false;
+		self.step_open = false;
+
+		let Some(Frame {
+			mut trace,
+			remaining_gas,
+			..
+		}) = self.frames.pop()
+		else {
+			return;
+		};
+
+		trace.gas_used = match reason {
+			ExitReason::Succeed(_) | ExitReason::Revert(_) => {
+				remaining_gas.map_or(0, |remaining| trace.gas.saturating_sub(remaining))
+			}
+			_ => trace.gas,
+		};
+		trace.output = return_value.to_vec();
+		trace.exit_reason = Some(reason.clone());
+
+		match self.frames.last_mut() {
+			Some(parent) => parent.trace.calls.push(trace),
+			None => {
+				if self.opcode_config().is_some() {
+					self.opcode_traces.push(OpcodeTrace {
+						gas: trace.gas_used,
+						exit_reason: trace.exit_reason.clone(),
+						return_value: trace.output.clone(),
+						used_storage: total_used_storage(&trace),
+						steps: sp_std::mem::take(&mut self.steps),
+					});
+				}
+				self.storage.clear();
+				self.calls.push(trace);
+			}
+		}
+	}
+
+	fn charge_storage(&mut self, storage: i32) {
+		if let Some(frame) = self.frames.last_mut() {
+			frame.trace.used_storage = frame.trace.used_storage.saturating_add(storage);
+		}
+		if self.step_open {
+			if let Some(step) = self.steps.last_mut() {
+				step.storage_charge = step.storage_charge.saturating_add(storage);
+			}
+		}
+	}
+
+	fn on_event(&mut self, event: Event) {
+		match event {
+			Event::Call {
+				code_address,
+				transfer,
+				input,
+				is_static,
+				context,
+				..
+			} => {
+				let call_type = if is_static {
+					CallType::STATICCALL
+				} else if context.address == code_address {
+					CallType::CALL
+				} else if transfer.is_some() {
+					CallType::CALLCODE
+				} else {
+					CallType::DELEGATECALL
+				};
+				let from = match call_type {
+					CallType::CALLCODE | CallType::DELEGATECALL => context.address,
+					_ => context.caller,
+				};
+				self.enter(CallTrace {
+					call_type,
+					from,
+					to: code_address,
+					input: input.to_vec(),
+					value: transfer.as_ref().map(|t| t.value).unwrap_or_default(),
+					gas: 0,
+					gas_used: 0,
+					output: Vec::new(),
+					exit_reason: None,
+					used_storage: 0,
+					calls: Vec::new(),
+				});
+			}
+			// the subcall goes through `Handler::call` and emits its own `Call`
+			Event::PrecompileSubcall { .. } => {}
+			Event::Create {
+				caller,
+				address,
+				scheme,
+				value,
+				init_code,
+				..
+			} => {
+				let call_type = match scheme {
+					CreateScheme::Create2 { .. } => CallType::CREATE2,
+					_ => CallType::CREATE,
+				};
+				self.enter(CallTrace {
+					call_type,
+					from: caller,
+					to: address,
+					input: init_code.to_vec(),
+					value,
+					gas: 0,
+					gas_used: 0,
+					output: Vec::new(),
+					exit_reason: None,
+					used_storage: 0,
+					calls: Vec::new(),
+				});
+			}
+			Event::Suicide {
+				address,
+				target,
+				balance,
+			} => {
+				if let Some(frame) = self.frames.last_mut() {
+					frame.trace.calls.push(CallTrace {
+						call_type: CallType::SUICIDE,
+						from: address,
+						to: target,
+						input: Vec::new(),
+						value: balance,
+						gas: 0,
+						gas_used: 0,
+						output: Vec::new(),
+						exit_reason: Some(ExitReason::Succeed(ExitSucceed::Suicided)),
+						used_storage: 0,
+						calls: Vec::new(),
+					});
+				}
+			}
+			Event::Enter { gas_limit } => {
+				let is_subcall = self.frames.len() > 1;
+				if let Some(frame) = self.frames.last_mut() {
+					// the root frame keeps the gas limit of the transaction
+					if is_subcall {
+						frame.trace.gas = gas_limit;
+					}
+					frame.gasometer_limit = Some(gas_limit);
+				}
+			}
+			Event::Exit { reason, return_value } => self.exit(reason, return_value),
+			Event::TransactCall {
+				caller,
+				address,
+				value,
+				data,
+				gas_limit,
+			} => {
+				self.transact(CallType::CALL, caller, address, data, value, gas_limit);
+			}
+			Event::TransactCreate {
+				caller,
+				value,
+				init_code,
+				gas_limit,
+				address,
+			} => {
+				self.transact(CallType::CREATE, caller, address, init_code, value, gas_limit);
+			}
+			Event::TransactCreate2 {
+				caller,
+				value,
+				init_code,
+				gas_limit,
+				address,
+				..
+			} => {
+				self.transact(CallType::CREATE2, caller, address, init_code, value, gas_limit);
+			}
+			Event::StorageCharge { storage } => self.charge_storage(storage as i32),
+			Event::StorageUncharge { storage } | Event::StorageRefund { storage } => {
+				self.charge_storage(-(storage as i32))
+			}
+		}
+	}
+
+	fn transact(&mut self, call_type: CallType, from: H160, to: H160, input: &[u8], value: U256, gas: u64) {
+		self.frames.push(Frame {
+			trace: CallTrace {
+				call_type,
+				from,
+				to,
+				input: input.to_vec(),
+				value,
+				gas,
+				gas_used: 0,
+				output: Vec::new(),
+				exit_reason: None,
+				used_storage: 0,
+				calls: Vec::new(),
+			},
+			gasometer_limit: None,
+			remaining_gas: None,
+		});
+		self.entering_transaction = true;
+	}
+
+	fn on_gasometer_event(&mut self, event: evm_gasometer::tracing::Event) {
+		use evm_gasometer::tracing::Event::*;
+
+		// snapshots are taken before the event is applied to the gasometer
+		let (cost, stipend, snapshot) = match event {
+			RecordCost { cost, snapshot } => (cost, 0, snapshot),
+			RecordDynamicCost {
+				gas_cost,
+				memory_gas,
+				snapshot,
+				..
+			} => {
+				let memory_cost = snapshot
+					.map(|s| memory_gas.saturating_sub(s.memory_gas))
+					.unwrap_or_default();
+				(gas_cost.saturating_add(memory_cost), 0, snapshot)
+			}
+			RecordStipend { stipend, snapshot } => (0, stipend, snapshot),
+			RecordRefund { snapshot, .. } => (0, 0, snapshot),
+			RecordTransaction { cost, snapshot } => (cost, 0, snapshot),
+		};
+
+		if self.step_open && self.opcode_config().is_some() {
+			if let Some(step) = self.steps.last_mut() {
+				step.gas_cost = step.gas_cost.saturating_add(cost);
+			}
+		}
+
+		let Some(snapshot) = snapshot else {
+			return;
+		};
+		self.remaining_gas = snapshot
+			.gas_limit
+			.saturating_sub(snapshot.used_gas)
+			.saturating_sub(snapshot.memory_gas)
+			.saturating_sub(cost)
+			.saturating_add(stipend);
+		if let Some(frame) = self.frames.last_mut() {
+			// ignore events of the parent gasometer, e.g. paying for the subcall being entered
+			if frame.gasometer_limit == Some(snapshot.gas_limit) {
+				frame.remaining_gas = Some(self.remaining_gas);
+			}
+		}
+	}
+
+	fn on_runtime_event(&mut self, event: evm_runtime::tracing::Event) {
+		use evm_runtime::tracing::Event as RuntimeEvent;
+
+		let Some(config) = self.opcode_config() else {
+			return;
+		};
+
+		match event {
+			RuntimeEvent::Step {
+				opcode,
+				position,
+				stack,
+				memory,
+				..
+			} => {
+				self.steps.push(Step {
+					op: opcode.0,
+					pc: position.as_ref().map(|pc| *pc as u32).unwrap_or_default(),
+					depth: self.frames.len() as u32,
+					gas: self.remaining_gas,
+					gas_cost: 0,
+					stack: (!config.disable_stack).then(|| stack.data().clone()),
+					memory: config.enable_memory.then(|| memory.data().clone()),
+					storage: None,
+					storage_charge: 0,
+				});
+				self.step_open = true;
+			}
+			RuntimeEvent::SLoad { address, index, value } | RuntimeEvent::SStore { address, index, value } => {
+				if config.disable_storage {
+					return;
+				}
+				let storage = self.storage.entry(address).or_default();
+				storage.insert(index, value);
+				let storage = storage.iter().map(|(k, v)| (*k, *v)).collect();
+				if let Some(step) = self.steps.last_mut() {
+					step.storage = Some(storage);
+				}
+			}
+			RuntimeEvent::StepResult { .. } => {}
+		}
+	}
+}
+
+fn total_used_storage(trace: &CallTrace) -> i32 {
+	trace
+		.calls
+		.iter()
+		.fold(trace.used_storage, |acc, call| acc.saturating_add(total_used_storage(call)))
+}
+
+struct RuntimeListener;
+
+impl evm_runtime::tracing::EventListener for RuntimeListener {
+	fn event(&mut self, event: evm_runtime::tracing::Event) {
+		tracer::with(|tracer| tracer.on_runtime_event(event));
+	}
+}
+
+struct GasometerListener;
+
+impl evm_gasometer::tracing::EventListener for GasometerListener {
+	fn event(&mut self, event: evm_gasometer::tracing::Event) {
+		tracer::with(|tracer| tracer.on_gasometer_event(event));
+	}
+}
+
+/// Run `f` with `tracer` collecting every EVM execution it performs.
+pub fn using<R, F: FnOnce() -> R>(tracer: &mut Tracer, f: F) -> R {
+	let mut runtime_listener = RuntimeListener;
+	let mut gasometer_listener = GasometerListener;
+	tracer::using(tracer, || {
+		evm_gasometer::tracing::using(&mut gasometer_listener, || {
+			evm_runtime::tracing::using(&mut runtime_listener, f)
+		})
+	})
+}
@@ -2967,3 +2967,116 @@ fn should_not_allow_system_contracts_send_tx() {
 		);
 	});
 }
+
+#[cfg(feature = "tracing")]
+#[test]
+fn call_tracer_should_work() {
+	use crate::runner::tracing::{self, CallType, TraceOutcome, Tracer, TracerConfig};
+
+	// same contract as `should_create_and_call_contract`
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let mut tracer = Tracer::new(TracerConfig::CallTracer);
+		let result = tracing::using(&mut tracer, || {
+			<Runtime as Config>::Runner::create(
+				alice(),
+				contract,
+				0,
+				1000000,
+				1000000,
+				vec![],
+				<Runtime as Config>::config(),
+			)
+		})
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+
+		let TraceOutcome::Calls(calls) = tracer.finalize() else {
+			panic!("expected call traces");
+		};
+		assert_eq!(calls.len(), 1);
+		assert_eq!(calls[0].call_type, CallType::CREATE);
+		assert_eq!(calls[0].from, alice());
+		assert_eq!(calls[0].to, result.value);
+		assert_eq!(calls[0].gas, 1000000);
+		assert!(calls[0].gas_used > 0);
+		assert_eq!(calls[0].exit_reason, Some(ExitReason::Succeed(ExitSucceed::Returned)));
+		assert_eq!(calls[0].used_storage, result.used_storage);
+		assert!(calls[0].calls.is_empty());
+	});
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn opcode_tracer_should_work() {
+	use crate::runner::tracing::{self, OpcodeTracerConfig, TraceOutcome, Tracer, TracerConfig};
+
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let contract_address = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			1000000,
+			1000000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap()
+		.value;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(contract_address);
+
+		// multiply(2, 3)
+		let multiply = from_hex(
+			"0x165c4a1600000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003"
+		).unwrap();
+
+		let mut tracer = Tracer::new(TracerConfig::OpcodeTracer(OpcodeTracerConfig {
+			enable_memory: true,
+			..Default::default()
+		}));
+		let result = tracing::using(&mut tracer, || {
+			<Runtime as Config>::Runner::call(
+				alice(),
+				alice(),
+				contract_address,
+				multiply,
+				0,
+				1000000,
+				1000000,
+				vec![],
+				<Runtime as Config>::config(),
+			)
+		})
+		.unwrap();
+		assert_eq!(U256::from(result.value.as_slice()), 6.into());
+
+		let TraceOutcome::Steps(traces) = tracer.finalize() else {
+			panic!("expected struct logs");
+		};
+		assert_eq!(traces.len(), 1);
+		assert_eq!(traces[0].exit_reason, Some(ExitReason::Succeed(ExitSucceed::Returned)));
+		assert_eq!(traces[0].return_value, result.value);
+
+		let steps = &traces[0].steps;
+		// PUSH1 0x80
+		assert_eq!(steps[0].op, 0x60);
+		assert_eq!(steps[0].pc, 0);
+		assert_eq!(steps[0].depth, 1);
+		assert_eq!(steps[0].stack, Some(vec![]));
+		assert_eq!(steps[0].gas_cost, 3);
+		assert_eq!(steps[1].stack, Some(vec![H256::from_low_u64_be(0x80)]));
+		assert_eq!(steps[1].gas, steps[0].gas - 3);
+		assert!(steps.iter().all(|step| step.memory.is_some()));
+		// RETURN
+		assert_eq!(steps.last().unwrap().op, 0xf3);
+	});
+}
@@ -150,6 +150,40 @@ benchmarks! {
 
 		let module_account = create_token_class::<T>(caller)?;
 	}: _(RawOrigin::Signed(module_account), 0u32.into(), Properties(ClassProperty::Transferable.into()))
+
+	// approve an account to transfer NFT token
+	approve {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+
+		let module_account = create_token_class::<T>(caller)?;
+
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![1], test_attr(), 1)?;
+	}: _(RawOrigin::Signed(to), Some(caller_lookup), (0u32.into(), 0u32.into()))
+
+	// allow an operator to manage all tokens of a class
+	set_approval_for_all {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+
+		create_token_class::<T>(caller.clone())?;
+	}: _(RawOrigin::Signed(caller), 0u32.into(), to_lookup, true)
+
+	// transfer NFT token on behalf of its owner
+	transfer_from {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+
+		let module_account = create_token_class::<T>(caller.clone())?;
+
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup.clone(), 0u32.into(), vec![1], test_attr(), 1)?;
+		crate::Pallet::<T>::approve(RawOrigin::Signed(to).into(), Some(caller_lookup.clone()), (0u32.into(), 0u32.into()))?;
+	}: _(RawOrigin::Signed(caller), to_lookup, caller_lookup, (0u32.into(), 0u32.into()))
 }
 
 #[cfg(test)]
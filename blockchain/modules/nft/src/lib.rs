@@ -35,6 +35,7 @@ use frame_support::{
 	PalletId,
 };
 use frame_system::pallet_prelude::*;
+use module_support::NFTManager;
 use orml_traits::InspectExtended;
 use primitives::{
 	nft::{Attributes, ClassProperty, NFTBalance, Properties, CID},
@@ -80,12 +81,17 @@ pub type ClassIdOf<T> = <T as orml_nft::Config>::ClassId;
 pub type BalanceOf<T> =
 	<<T as pallet_proxy::Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+/// The token attribute holding the URI returned by the ERC-721 `tokenURI`.
+pub const TOKEN_URI_ATTRIBUTE: &[u8] = b"tokenURI";
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
 
 	pub const RESERVE_ID: ReserveIdentifier = ReserveIdentifier::Nft;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::config]
 	pub trait Config:
 		frame_system::Config
@@ -150,6 +156,8 @@ pub mod module {
 		AttributesTooLarge,
 		/// The given token ID is not correct
 		IncorrectTokenId,
+		/// Can not approve the owner as operator
+		ApproveToOwner,
 	}
 
 	#[pallet::event]
@@ -192,9 +200,55 @@ pub mod module {
 			owner: T::AccountId,
 			class_id: ClassIdOf<T>,
 		},
+		/// Approved an account to transfer NFT token, `None` cleared the approval.
+		Approval {
+			owner: T::AccountId,
+			approved: Option<T::AccountId>,
+			class_id: ClassIdOf<T>,
+			token_id: TokenIdOf<T>,
+		},
+		/// Allowed or disallowed an operator to manage all tokens of a class.
+		ApprovalForAll {
+			owner: T::AccountId,
+			operator: T::AccountId,
+			class_id: ClassIdOf<T>,
+			approved: bool,
+		},
 	}
 
+	/// The account approved to transfer a token.
+	///
+	/// TokenApprovals: double_map ClassId, TokenId => Option<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn token_approvals)]
+	pub type TokenApprovals<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, TokenIdOf<T>, T::AccountId, OptionQuery>;
+
+	/// Whether an operator can manage all tokens of a class owned by an account.
+	///
+	/// OperatorApprovals: double_map (Owner, ClassId), Operator => bool
+	#[pallet::storage]
+	#[pallet::getter(fn operator_approvals)]
+	pub type OperatorApprovals<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(T::AccountId, ClassIdOf<T>),
+		Twox64Concat,
+		T::AccountId,
+		bool,
+		ValueQuery,
+	>;
+
+	/// The number of tokens of a class owned by an account.
+	///
+	/// ClassBalances: double_map AccountId, ClassId => u32
+	#[pallet::storage]
+	#[pallet::getter(fn class_balances)]
+	pub type ClassBalances<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, ClassIdOf<T>, u32, ValueQuery>;
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -385,6 +439,61 @@ pub mod module {
 				Ok(())
 			})
 		}
+
+		/// Approve an account to transfer NFT token, the origin must be the owner or an
+		/// operator of the owner.
+		///
+		/// - `approved`: the approved account, `None` to clear the approval
+		/// - `token`: (class_id, token_id)
+		#[pallet::call_index(7)]
+		#[pallet::weight(<T as Config>::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			approved: Option<<T::Lookup as StaticLookup>::Source>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let approved = approved.map(T::Lookup::lookup).transpose()?;
+			Self::do_approve(&who, approved, token)
+		}
+
+		/// Allow or disallow an operator to manage all tokens of the class owned by origin.
+		///
+		/// - `class_id`: the class ID
+		/// - `operator`: the operator account
+		/// - `approved`: allow or disallow
+		#[pallet::call_index(8)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_approval_for_all())]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			operator: <T::Lookup as StaticLookup>::Source,
+			approved: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let operator = T::Lookup::lookup(operator)?;
+			Self::do_set_approval_for_all(&who, class_id, &operator, approved)
+		}
+
+		/// Transfer NFT token on behalf of its owner, the origin must be the owner, the
+		/// approved account or an operator of the owner.
+		///
+		/// - `from`: the token owner's account
+		/// - `to`: the receiver's account
+		/// - `token`: (class_id, token_id)
+		#[pallet::call_index(9)]
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_from())]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			from: <T::Lookup as StaticLookup>::Source,
+			to: <T::Lookup as StaticLookup>::Source,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let from = T::Lookup::lookup(from)?;
+			let to = T::Lookup::lookup(to)?;
+			Self::do_transfer_from(&who, &from, &to, token)
+		}
 	}
 }
 
@@ -401,6 +510,11 @@ impl<T: Config> Pallet<T> {
 		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
 
 		orml_nft::Pallet::<T>::transfer(from, to, token)?;
+		TokenApprovals::<T>::remove(token.0, token.1);
+		if from != to {
+			ClassBalances::<T>::mutate(from, token.0, |balance| *balance = balance.saturating_sub(1));
+			ClassBalances::<T>::mutate(to, token.0, |balance| *balance = balance.saturating_add(1));
+		}
 
 		let reserve_balance = token_info.data.deposit;
 
@@ -480,6 +594,7 @@ impl<T: Config> Pallet<T> {
 				data.clone(),
 			)?);
 		}
+		ClassBalances::<T>::mutate(to, class_id, |balance| *balance = balance.saturating_add(quantity));
 
 		Self::deposit_event(Event::MintedToken {
 			from: who.clone(),
@@ -502,6 +617,8 @@ impl<T: Config> Pallet<T> {
 		ensure!(who == token_info.owner, Error::<T>::NoPermission);
 
 		orml_nft::Pallet::<T>::burn(&who, token)?;
+		TokenApprovals::<T>::remove(token.0, token.1);
+		ClassBalances::<T>::mutate(&who, token.0, |balance| *balance = balance.saturating_sub(1));
 
 		<T as module::Config>::Currency::unreserve_named(&RESERVE_ID, &who, token_info.data.deposit);
 
@@ -524,6 +641,82 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	#[require_transactional]
+	fn do_transfer_from(
+		operator: &T::AccountId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+	) -> DispatchResult {
+		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
+		ensure!(*from == token_info.owner, Error::<T>::NoPermission);
+		ensure!(
+			Self::is_approved_or_owner(operator, from, token),
+			Error::<T>::NoPermission
+		);
+
+		Self::do_transfer(from, to, token)
+	}
+
+	fn do_approve(
+		who: &T::AccountId,
+		approved: Option<T::AccountId>,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+	) -> DispatchResult {
+		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
+		let owner = token_info.owner;
+		ensure!(
+			*who == owner || Self::operator_approvals((owner.clone(), token.0), who),
+			Error::<T>::NoPermission
+		);
+
+		match &approved {
+			Some(approved) => TokenApprovals::<T>::insert(token.0, token.1, approved),
+			None => TokenApprovals::<T>::remove(token.0, token.1),
+		}
+
+		Self::deposit_event(Event::Approval {
+			owner,
+			approved,
+			class_id: token.0,
+			token_id: token.1,
+		});
+		Ok(())
+	}
+
+	fn do_set_approval_for_all(
+		owner: &T::AccountId,
+		class_id: ClassIdOf<T>,
+		operator: &T::AccountId,
+		approved: bool,
+	) -> DispatchResult {
+		ensure!(
+			orml_nft::Pallet::<T>::classes(class_id).is_some(),
+			Error::<T>::ClassIdNotFound
+		);
+		ensure!(owner != operator, Error::<T>::ApproveToOwner);
+
+		if approved {
+			OperatorApprovals::<T>::insert((owner.clone(), class_id), operator, true);
+		} else {
+			OperatorApprovals::<T>::remove((owner.clone(), class_id), operator);
+		}
+
+		Self::deposit_event(Event::ApprovalForAll {
+			owner: owner.clone(),
+			operator: operator.clone(),
+			class_id,
+			approved,
+		});
+		Ok(())
+	}
+
+	fn is_approved_or_owner(who: &T::AccountId, owner: &T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>)) -> bool {
+		who == owner
+			|| Self::token_approvals(token.0, token.1).as_ref() == Some(who)
+			|| Self::operator_approvals((owner.clone(), token.0), who)
+	}
+
 	fn data_deposit(metadata: &[u8], attributes: &Attributes) -> Result<BalanceOf<T>, DispatchError> {
 		// Addition can't overflow because we will be out of memory before that
 		let attributes_len = attributes.iter().fold(0, |acc, (k, v)| {
@@ -540,6 +733,26 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+/// Seed `ClassBalances` with the tokens owned before it was tracked, by counting the tokens
+/// of `orml_nft::TokensByOwner` per owner and class.
+pub struct MigrateClassBalances<T>(PhantomData<T>);
+impl<T: Config> frame_support::traits::OnRuntimeUpgrade for MigrateClassBalances<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 1 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut count: u64 = 0;
+		for ((owner, class_id, _), _) in orml_nft::TokensByOwner::<T>::iter() {
+			count += 1;
+			ClassBalances::<T>::mutate(owner, class_id, |balance| *balance = balance.saturating_add(1));
+		}
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(count.saturating_mul(2).saturating_add(1), count.saturating_add(1))
+	}
+}
+
 impl<T: Config> InspectExtended<T::AccountId> for Pallet<T> {
 	type Balance = NFTBalance;
 
@@ -604,3 +817,93 @@ impl<T: Config> Transfer<T::AccountId> for Pallet<T> {
 		Self::do_transfer(&owner, destination, (*class, *instance))
 	}
 }
+
+impl<T: Config> NFTManager<T::AccountId> for Pallet<T> {
+	type ClassId = ClassIdOf<T>;
+	type TokenId = TokenIdOf<T>;
+
+	fn balance(who: &T::AccountId) -> NFTBalance {
+		<Self as InspectExtended<T::AccountId>>::balance(who)
+	}
+
+	fn balance_of_class(who: &T::AccountId, class_id: Self::ClassId) -> NFTBalance {
+		Self::class_balances(who, class_id).into()
+	}
+
+	fn owner(token: (Self::ClassId, Self::TokenId)) -> Option<T::AccountId> {
+		<Self as Inspect<T::AccountId>>::owner(&token.0, &token.1)
+	}
+
+	fn token_uri(token: (Self::ClassId, Self::TokenId)) -> Option<Vec<u8>> {
+		orml_nft::Pallet::<T>::tokens(token.0, token.1).map(|token_info| {
+			token_info
+				.data
+				.attributes
+				.get(TOKEN_URI_ATTRIBUTE)
+				.cloned()
+				.unwrap_or_else(|| token_info.metadata.to_vec())
+		})
+	}
+
+	fn approved(token: (Self::ClassId, Self::TokenId)) -> Option<T::AccountId> {
+		Self::token_approvals(token.0, token.1)
+	}
+
+	fn is_approved_for_all(owner: &T::AccountId, class_id: Self::ClassId, operator: &T::AccountId) -> bool {
+		Self::operator_approvals((owner.clone(), class_id), operator)
+	}
+
+	fn transfer(from: &T::AccountId, to: &T::AccountId, token: (Self::ClassId, Self::TokenId)) -> DispatchResult {
+		Self::do_transfer(from, to, token)
+	}
+
+	fn transfer_from(
+		operator: &T::AccountId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		token: (Self::ClassId, Self::TokenId),
+	) -> DispatchResult {
+		Self::do_transfer_from(operator, from, to, token)
+	}
+
+	fn approve(
+		who: &T::AccountId,
+		approved: Option<T::AccountId>,
+		token: (Self::ClassId, Self::TokenId),
+	) -> DispatchResult {
+		Self::do_approve(who, approved, token)
+	}
+
+	fn set_approval_for_all(
+		owner: &T::AccountId,
+		class_id: Self::ClassId,
+		operator: &T::AccountId,
+		approved: bool,
+	) -> DispatchResult {
+		Self::do_set_approval_for_all(owner, class_id, operator, approved)
+	}
+
+	fn mint(who: &T::AccountId, to: &T::AccountId, class_id: Self::ClassId) -> Result<Self::TokenId, DispatchError> {
+		let class_owner =
+			<Self as Inspect<T::AccountId>>::collection_owner(&class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+		// the class owner is a module account, the class creator mints through its proxy
+		ensure!(
+			*who == class_owner
+				|| pallet_proxy::Pallet::<T>::find_proxy(&class_owner, who, Some(Default::default())).is_ok(),
+			Error::<T>::NoPermission
+		);
+
+		let mut token_ids = Self::do_mint(&class_owner, to, class_id, Default::default(), Default::default(), 1u32)?;
+		token_ids.pop().ok_or_else(|| Error::<T>::InvalidQuantity.into())
+	}
+
+	fn burn(who: &T::AccountId, token: (Self::ClassId, Self::TokenId)) -> DispatchResult {
+		let owner = <Self as Inspect<T::AccountId>>::owner(&token.0, &token.1).ok_or(Error::<T>::TokenIdNotFound)?;
+		ensure!(
+			Self::is_approved_or_owner(who, &owner, token),
+			Error::<T>::NoPermission
+		);
+
+		Self::do_burn(owner, token, None)
+	}
+}
@@ -329,6 +329,8 @@ fn transfer_should_work() {
 			reserved_balance(&ALICE),
 			1 * (CREATE_TOKEN_DEPOSIT + DATA_DEPOSIT_PER_BYTE)
 		);
+		assert_eq!(NFTModule::class_balances(BOB, CLASS_ID), 1);
+		assert_eq!(NFTModule::class_balances(ALICE, CLASS_ID), 1);
 
 		assert_ok!(NFTModule::transfer(
 			RuntimeOrigin::signed(ALICE),
@@ -346,6 +348,8 @@ fn transfer_should_work() {
 			2 * (CREATE_TOKEN_DEPOSIT + DATA_DEPOSIT_PER_BYTE)
 		);
 		assert_eq!(reserved_balance(&ALICE), 0);
+		assert_eq!(NFTModule::class_balances(BOB, CLASS_ID), 2);
+		assert_eq!(NFTModule::class_balances(ALICE, CLASS_ID), 0);
 	});
 }
 
@@ -738,3 +742,307 @@ fn update_class_properties_should_work() {
 		);
 	});
 }
+
+#[test]
+fn approve_and_transfer_from_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_ok!(NFTModule::create_class(
+			RuntimeOrigin::signed(ALICE),
+			metadata.clone(),
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			Default::default(),
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			CREATE_TOKEN_DEPOSIT + DATA_DEPOSIT_PER_BYTE + Balances::minimum_balance()
+		));
+		assert_ok!(NFTModule::mint(
+			RuntimeOrigin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			metadata,
+			Default::default(),
+			1
+		));
+
+		assert_ok!(NFTModule::approve(
+			RuntimeOrigin::signed(BOB),
+			Some(CHARLIE),
+			(CLASS_ID, TOKEN_ID)
+		));
+		System::assert_last_event(RuntimeEvent::NFTModule(crate::Event::Approval {
+			owner: BOB,
+			approved: Some(CHARLIE),
+			class_id: CLASS_ID,
+			token_id: TOKEN_ID,
+		}));
+		assert_eq!(NFTModule::token_approvals(CLASS_ID, TOKEN_ID), Some(CHARLIE));
+
+		assert_ok!(NFTModule::transfer_from(
+			RuntimeOrigin::signed(CHARLIE),
+			BOB,
+			ALICE,
+			(CLASS_ID, TOKEN_ID)
+		));
+		System::assert_last_event(RuntimeEvent::NFTModule(crate::Event::TransferredToken {
+			from: BOB,
+			to: ALICE,
+			class_id: CLASS_ID,
+			token_id: TOKEN_ID,
+		}));
+		assert_eq!(
+			<NFTModule as NFTManager<AccountId>>::owner((CLASS_ID, TOKEN_ID)),
+			Some(ALICE)
+		);
+		// the approval is cleared by the transfer
+		assert_eq!(NFTModule::token_approvals(CLASS_ID, TOKEN_ID), None);
+		assert_noop!(
+			NFTModule::transfer_from(RuntimeOrigin::signed(CHARLIE), ALICE, BOB, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+	});
+}
+
+#[test]
+fn transfer_from_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_ok!(NFTModule::create_class(
+			RuntimeOrigin::signed(ALICE),
+			metadata.clone(),
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			Default::default(),
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			CREATE_TOKEN_DEPOSIT + DATA_DEPOSIT_PER_BYTE + Balances::minimum_balance()
+		));
+		assert_ok!(NFTModule::mint(
+			RuntimeOrigin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			metadata,
+			Default::default(),
+			1
+		));
+
+		assert_noop!(
+			NFTModule::transfer_from(RuntimeOrigin::signed(CHARLIE), BOB, ALICE, (CLASS_ID, TOKEN_ID_NOT_EXIST)),
+			Error::<Runtime>::TokenIdNotFound
+		);
+		assert_noop!(
+			NFTModule::transfer_from(RuntimeOrigin::signed(CHARLIE), BOB, ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::approve(RuntimeOrigin::signed(CHARLIE), Some(CHARLIE), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(NFTModule::approve(
+			RuntimeOrigin::signed(BOB),
+			Some(CHARLIE),
+			(CLASS_ID, TOKEN_ID)
+		));
+		// `from` must be the owner
+		assert_noop!(
+			NFTModule::transfer_from(RuntimeOrigin::signed(CHARLIE), ALICE, CHARLIE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(NFTModule::approve(RuntimeOrigin::signed(BOB), None, (CLASS_ID, TOKEN_ID)));
+		assert_noop!(
+			NFTModule::transfer_from(RuntimeOrigin::signed(CHARLIE), BOB, ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+	});
+}
+
+#[test]
+fn set_approval_for_all_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_ok!(NFTModule::create_class(
+			RuntimeOrigin::signed(ALICE),
+			metadata.clone(),
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			Default::default(),
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			CREATE_TOKEN_DEPOSIT + DATA_DEPOSIT_PER_BYTE + Balances::minimum_balance()
+		));
+		assert_ok!(NFTModule::mint(
+			RuntimeOrigin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			metadata,
+			Default::default(),
+			1
+		));
+
+		assert_noop!(
+			NFTModule::set_approval_for_all(RuntimeOrigin::signed(BOB), CLASS_ID_NOT_EXIST, CHARLIE, true),
+			Error::<Runtime>::ClassIdNotFound
+		);
+		assert_noop!(
+			NFTModule::set_approval_for_all(RuntimeOrigin::signed(BOB), CLASS_ID, BOB, true),
+			Error::<Runtime>::ApproveToOwner
+		);
+
+		assert_ok!(NFTModule::set_approval_for_all(
+			RuntimeOrigin::signed(BOB),
+			CLASS_ID,
+			CHARLIE,
+			true
+		));
+		System::assert_last_event(RuntimeEvent::NFTModule(crate::Event::ApprovalForAll {
+			owner: BOB,
+			operator: CHARLIE,
+			class_id: CLASS_ID,
+			approved: true,
+		}));
+		assert!(NFTModule::operator_approvals((BOB, CLASS_ID), CHARLIE));
+
+		// the operator can approve on behalf of the owner
+		assert_ok!(NFTModule::approve(
+			RuntimeOrigin::signed(CHARLIE),
+			Some(ALICE),
+			(CLASS_ID, TOKEN_ID)
+		));
+		System::assert_last_event(RuntimeEvent::NFTModule(crate::Event::Approval {
+			owner: BOB,
+			approved: Some(ALICE),
+			class_id: CLASS_ID,
+			token_id: TOKEN_ID,
+		}));
+
+		assert_ok!(NFTModule::set_approval_for_all(
+			RuntimeOrigin::signed(BOB),
+			CLASS_ID,
+			CHARLIE,
+			false
+		));
+		assert!(!NFTModule::operator_approvals((BOB, CLASS_ID), CHARLIE));
+		assert_noop!(
+			NFTModule::transfer_from(RuntimeOrigin::signed(CHARLIE), BOB, CHARLIE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+	});
+}
+
+#[test]
+fn nft_manager_mint_and_burn_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let mut attributes: Attributes = BTreeMap::new();
+		attributes.insert(TOKEN_URI_ATTRIBUTE.to_vec(), b"u1".to_vec());
+		assert_ok!(NFTModule::create_class(
+			RuntimeOrigin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			Default::default(),
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			2 * CREATE_TOKEN_DEPOSIT + 11 * DATA_DEPOSIT_PER_BYTE + Balances::minimum_balance()
+		));
+
+		// only the class owner and its proxy can mint
+		assert_noop!(
+			<NFTModule as NFTManager<AccountId>>::mint(&BOB, &BOB, CLASS_ID),
+			Error::<Runtime>::NoPermission
+		);
+		assert_eq!(
+			<NFTModule as NFTManager<AccountId>>::mint(&ALICE, &BOB, CLASS_ID),
+			Ok(TOKEN_ID)
+		);
+		assert_eq!(<NFTModule as NFTManager<AccountId>>::balance_of_class(&BOB, CLASS_ID), 1);
+		assert_eq!(
+			<NFTModule as NFTManager<AccountId>>::balance_of_class(&BOB, CLASS_ID_NOT_EXIST),
+			0
+		);
+		assert_eq!(
+			<NFTModule as NFTManager<AccountId>>::token_uri((CLASS_ID, TOKEN_ID)),
+			Some(vec![])
+		);
+
+		// the token uri is read from the attributes
+		assert_ok!(NFTModule::mint(
+			RuntimeOrigin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![2],
+			attributes,
+			1
+		));
+		assert_eq!(
+			<NFTModule as NFTManager<AccountId>>::token_uri((CLASS_ID, TOKEN_ID + 1)),
+			Some(b"u1".to_vec())
+		);
+		assert_eq!(<NFTModule as NFTManager<AccountId>>::balance_of_class(&BOB, CLASS_ID), 2);
+
+		assert_noop!(
+			<NFTModule as NFTManager<AccountId>>::burn(&CHARLIE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(<NFTModule as NFTManager<AccountId>>::approve(
+			&BOB,
+			Some(CHARLIE),
+			(CLASS_ID, TOKEN_ID)
+		));
+		assert_ok!(<NFTModule as NFTManager<AccountId>>::burn(&CHARLIE, (CLASS_ID, TOKEN_ID)));
+		System::assert_last_event(RuntimeEvent::NFTModule(crate::Event::BurnedToken {
+			owner: BOB,
+			class_id: CLASS_ID,
+			token_id: TOKEN_ID,
+		}));
+		assert_eq!(NFTModule::token_approvals(CLASS_ID, TOKEN_ID), None);
+		assert_eq!(<NFTModule as NFTManager<AccountId>>::balance_of_class(&BOB, CLASS_ID), 1);
+	});
+}
+
+#[test]
+fn migrate_class_balances_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_ok!(NFTModule::create_class(
+			RuntimeOrigin::signed(ALICE),
+			metadata.clone(),
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			Default::default(),
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			3 * (CREATE_TOKEN_DEPOSIT + DATA_DEPOSIT_PER_BYTE) + Balances::minimum_balance()
+		));
+		assert_ok!(NFTModule::mint(
+			RuntimeOrigin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			metadata,
+			Default::default(),
+			3
+		));
+		assert_ok!(NFTModule::transfer(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			(CLASS_ID, TOKEN_ID)
+		));
+
+		// the tokens were owned before `ClassBalances` was tracked
+		ClassBalances::<Runtime>::remove(BOB, CLASS_ID);
+		ClassBalances::<Runtime>::remove(ALICE, CLASS_ID);
+		StorageVersion::new(0).put::<NFTModule>();
+
+		<MigrateClassBalances<Runtime> as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(NFTModule::class_balances(BOB, CLASS_ID), 2);
+		assert_eq!(NFTModule::class_balances(ALICE, CLASS_ID), 1);
+		assert_eq!(StorageVersion::get::<NFTModule>(), 1);
+
+		// runs once
+		ClassBalances::<Runtime>::remove(BOB, CLASS_ID);
+		<MigrateClassBalances<Runtime> as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(NFTModule::class_balances(BOB, CLASS_ID), 0);
+	});
+}
@@ -56,6 +56,9 @@ pub trait WeightInfo {
 	fn burn_with_remark(b: u32, ) -> Weight;
 	fn destroy_class() -> Weight;
 	fn update_class_properties() -> Weight;
+	fn approve() -> Weight;
+	fn set_approval_for_all() -> Weight;
+	fn transfer_from() -> Weight;
 }
 
 /// Weights for module_nft using the Setheum node and recommended hardware.
@@ -101,6 +104,21 @@ impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as u64))
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	fn approve() -> Weight {
+		Weight::from_parts(38_126_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_approval_for_all() -> Weight {
+		Weight::from_parts(31_482_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn transfer_from() -> Weight {
+		Weight::from_parts(281_507_000, 0)
+			.saturating_add(T::DbWeight::get().reads(9 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -145,4 +163,19 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as u64))
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
+	fn approve() -> Weight {
+		Weight::from_parts(38_126_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_approval_for_all() -> Weight {
+		Weight::from_parts(31_482_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn transfer_from() -> Weight {
+		Weight::from_parts(281_507_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(9 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+	}
 }
@@ -27,21 +27,25 @@
 //!   - specify a fixed price for USSD
 //!   - feed price in USD or related price bewteen two currencies
 //!   - lock/unlock the price data got from oracle
+//!   - serve delayed, circuit-breaker prices through the oracle security module (OSM)
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::pallet_prelude::*;
+use frame_support::{pallet_prelude::*, traits::Time};
 use frame_system::pallet_prelude::*;
-use module_support::{SwapManager, Erc20InfoMapping, ExchangeRateProvider, LockablePrice, Price, PriceProvider, Rate};
-use orml_traits::{DataFeeder, DataProvider, GetByKey, MultiCurrency};
-use primitives::{Balance, CurrencyId, Lease};
+use module_support::{
+	Erc20InfoMapping, ExchangeRateProvider, LockablePrice, Price, PriceProvider, Rate, Ratio, SwapManager,
+};
+use orml_oracle::TimestampedValue;
+use orml_traits::{DataFeeder, DataProvider, DataProviderExtended, GetByKey, MultiCurrency};
+use primitives::{Balance, CurrencyId, Lease, Moment};
 use sp_core::U256;
 use sp_runtime::{
-	traits::{CheckedMul, One, Saturating, UniqueSaturatedInto},
+	traits::{CheckedMul, One, Saturating, UniqueSaturatedInto, Zero},
 	FixedPointNumber,
 };
-use sp_std::marker::PhantomData;
+use sp_std::{marker::PhantomData, prelude::*};
 
 mod mock;
 mod tests;
@@ -50,6 +54,17 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// The prices tracked by the oracle security module for a currency.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct OsmPrice<BlockNumber> {
+	/// The price served to the system, it is the oracle price of the previous period.
+	pub current: Price,
+	/// The oracle price queued to become `current` at the next update.
+	pub next: Price,
+	/// The block number of the last accepted update.
+	pub updated_at: BlockNumber,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -59,7 +74,12 @@ pub mod module {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
 		/// The data source, such as Oracle.
-		type Source: DataProvider<CurrencyId, Price> + DataFeeder<CurrencyId, Price, Self::AccountId>;
+		type Source: DataProvider<CurrencyId, Price>
+			+ DataProviderExtended<CurrencyId, TimestampedValue<Price, Moment>>
+			+ DataFeeder<CurrencyId, Price, Self::AccountId>;
+
+		/// Time provider, to check the age of the oracle prices fed to the OSM.
+		type Time: Time<Moment = Moment>;
 
 		/// The fixed prices of USSD, it should be 1 USD in Setheum.
 		#[pallet::constant]
@@ -93,6 +113,21 @@ pub mod module {
 		/// equal to the price of another.
 		type PricingPegged: GetByKey<CurrencyId, Option<CurrencyId>>;
 
+		/// The number of blocks between two updates of the OSM prices, the price served by
+		/// `OsmPriceProvider` is delayed by one period.
+		#[pallet::constant]
+		type OsmUpdatePeriod: Get<BlockNumberFor<Self>>;
+
+		/// The max change ratio of a new oracle price compared to the queued OSM price, larger
+		/// changes are held until `LockOrigin` confirms them.
+		#[pallet::constant]
+		type OsmMaxPriceChange: Get<Ratio>;
+
+		/// The OSM only accepts oracle prices fed within this number of milliseconds, and the
+		/// OSM price is considered stale while the oracle price is older.
+		#[pallet::constant]
+		type OsmStalePeriod: Get<Moment>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -103,6 +138,14 @@ pub mod module {
 		AccessPriceFailed,
 		/// There's no locked price
 		NoLockedPrice,
+		/// The currency is already tracked by the OSM
+		OsmAlreadyEnabled,
+		/// The currency is not tracked by the OSM
+		OsmNotEnabled,
+		/// There's no pending OSM price to confirm
+		NoPendingOsmPrice,
+		/// The oracle price is older than `OsmStalePeriod`
+		StaleOraclePrice,
 	}
 
 	#[pallet::event]
@@ -115,6 +158,22 @@ pub mod module {
 		},
 		/// Unlock price.
 		UnlockPrice { currency_id: CurrencyId },
+		/// The currency is tracked by the OSM.
+		OsmEnabled { currency_id: CurrencyId, price: Price },
+		/// The currency is no longer tracked by the OSM.
+		OsmDisabled { currency_id: CurrencyId },
+		/// The OSM price is updated.
+		OsmPriceUpdated {
+			currency_id: CurrencyId,
+			current_price: Price,
+			next_price: Price,
+		},
+		/// The oracle price changed more than `OsmMaxPriceChange` and waits for confirmation.
+		OsmPriceChangeHeld {
+			currency_id: CurrencyId,
+			queued_price: Price,
+			pending_price: Price,
+		},
 	}
 
 	/// Mapping from currency id to it's locked price
@@ -124,11 +183,39 @@ pub mod module {
 	#[pallet::getter(fn locked_price)]
 	pub type LockedPrice<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
 
+	/// Mapping from currency id to it's OSM prices
+	///
+	/// map CurrencyId => Option<OsmPrice>
+	#[pallet::storage]
+	#[pallet::getter(fn osm_price)]
+	pub type OsmPrices<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, OsmPrice<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Mapping from currency id to the oracle price held by the OSM circuit breaker
+	///
+	/// map CurrencyId => Option<Price>
+	#[pallet::storage]
+	#[pallet::getter(fn pending_osm_price)]
+	pub type PendingOsmPrices<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let period = T::OsmUpdatePeriod::get();
+			if period.is_zero() || !(now % period).is_zero() {
+				return Weight::zero();
+			}
+
+			let currency_ids = OsmPrices::<T>::iter_keys().collect::<Vec<_>>();
+			for currency_id in currency_ids.iter() {
+				Self::update_osm_price(*currency_id, now);
+			}
+			T::WeightInfo::on_initialize(currency_ids.len() as u32)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -157,6 +244,70 @@ pub mod module {
 			<Pallet<T> as LockablePrice<CurrencyId>>::unlock_price(currency_id)?;
 			Ok(())
 		}
+
+		/// Track the price of the currency by the OSM, the current oracle price is used as
+		/// both the current and the next OSM price.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		#[pallet::call_index(2)]
+		#[pallet::weight((T::WeightInfo::enable_osm(), DispatchClass::Operational))]
+		pub fn enable_osm(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResult {
+			T::LockOrigin::ensure_origin(origin)?;
+			ensure!(
+				!OsmPrices::<T>::contains_key(currency_id),
+				Error::<T>::OsmAlreadyEnabled
+			);
+			ensure!(!Self::is_oracle_price_stale(currency_id), Error::<T>::StaleOraclePrice);
+			let price = Self::access_price(currency_id).ok_or(Error::<T>::AccessPriceFailed)?;
+			OsmPrices::<T>::insert(
+				currency_id,
+				OsmPrice {
+					current: price,
+					next: price,
+					updated_at: <frame_system::Pallet<T>>::block_number(),
+				},
+			);
+			Self::deposit_event(Event::OsmEnabled { currency_id, price });
+			Ok(())
+		}
+
+		/// Stop tracking the price of the currency by the OSM.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		#[pallet::call_index(3)]
+		#[pallet::weight((T::WeightInfo::disable_osm(), DispatchClass::Operational))]
+		pub fn disable_osm(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResult {
+			T::LockOrigin::ensure_origin(origin)?;
+			let _ = OsmPrices::<T>::take(currency_id).ok_or(Error::<T>::OsmNotEnabled)?;
+			PendingOsmPrices::<T>::remove(currency_id);
+			Self::deposit_event(Event::OsmDisabled { currency_id });
+			Ok(())
+		}
+
+		/// Confirm the oracle price held by the OSM circuit breaker and queue it as the next
+		/// OSM price.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		#[pallet::call_index(4)]
+		#[pallet::weight((T::WeightInfo::confirm_osm_price(), DispatchClass::Operational))]
+		pub fn confirm_osm_price(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResult {
+			T::LockOrigin::ensure_origin(origin)?;
+			let osm_price = Self::osm_price(currency_id).ok_or(Error::<T>::OsmNotEnabled)?;
+			let pending_price = PendingOsmPrices::<T>::take(currency_id).ok_or(Error::<T>::NoPendingOsmPrice)?;
+			Self::do_update_osm_price(
+				currency_id,
+				osm_price,
+				pending_price,
+				<frame_system::Pallet<T>>::block_number(),
+			);
+			Ok(())
+		}
 	}
 }
 
@@ -206,6 +357,75 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	/// Shift the queued OSM price to current and queue the real-time price from oracle,
+	/// the real-time price is held if it changed more than `OsmMaxPriceChange`.
+	fn update_osm_price(currency_id: CurrencyId, now: BlockNumberFor<T>) {
+		if Self::is_oracle_price_stale(currency_id) {
+			// the feed is outdated or unavailable, leave the OSM price to become stale
+			return;
+		}
+		let (Some(osm_price), Some(price)) = (Self::osm_price(currency_id), Self::access_price(currency_id)) else {
+			return;
+		};
+
+		if Self::exceeds_max_price_change(osm_price.next, price) {
+			PendingOsmPrices::<T>::insert(currency_id, price);
+			Self::deposit_event(Event::OsmPriceChangeHeld {
+				currency_id,
+				queued_price: osm_price.next,
+				pending_price: price,
+			});
+		} else {
+			PendingOsmPrices::<T>::remove(currency_id);
+			Self::do_update_osm_price(currency_id, osm_price, price, now);
+		}
+	}
+
+	fn do_update_osm_price(
+		currency_id: CurrencyId,
+		osm_price: OsmPrice<BlockNumberFor<T>>,
+		next_price: Price,
+		now: BlockNumberFor<T>,
+	) {
+		let current_price = osm_price.next;
+		OsmPrices::<T>::insert(
+			currency_id,
+			OsmPrice {
+				current: current_price,
+				next: next_price,
+				updated_at: now,
+			},
+		);
+		Self::deposit_event(Event::OsmPriceUpdated {
+			currency_id,
+			current_price,
+			next_price,
+		});
+	}
+
+	fn exceeds_max_price_change(queued_price: Price, price: Price) -> bool {
+		let change = queued_price.max(price).saturating_sub(queued_price.min(price));
+		Ratio::checked_from_rational(change.into_inner(), queued_price.into_inner())
+			.map_or(!change.is_zero(), |change_ratio| change_ratio > T::OsmMaxPriceChange::get())
+	}
+
+	/// Whether the oracle price of the currency, or of the currency it is pegged to, was fed
+	/// more than `OsmStalePeriod` ago, a missing feed is stale as well.
+	fn is_oracle_price_stale(currency_id: CurrencyId) -> bool {
+		let feed_currency_id = T::PricingPegged::get(&currency_id).unwrap_or(currency_id);
+		T::Source::get_no_op(&feed_currency_id).map_or(true, |feed| {
+			T::Time::now().saturating_sub(feed.timestamp) > T::OsmStalePeriod::get()
+		})
+	}
+
+	/// Whether the oracle price feeding the OSM price of the currency is older than
+	/// `OsmStalePeriod`, currencies not tracked by the OSM are never stale.
+	pub fn is_osm_price_stale(currency_id: CurrencyId) -> bool {
+		OsmPrices::<T>::contains_key(currency_id) && Self::is_oracle_price_stale(currency_id)
+	}
+}
+
 impl<T: Config> LockablePrice<CurrencyId> for Pallet<T> {
 	/// Record the real-time price from oracle as the locked price
 	fn lock_price(currency_id: CurrencyId) -> DispatchResult {
@@ -251,6 +471,21 @@ impl<T: Config> PriceProvider<CurrencyId> for LockedPriceProvider<T> {
 	}
 }
 
+/// PriceProvider that priority access to the locked price, then to the delayed OSM price,
+/// and access to real-time price for currencies not tracked by the OSM
+pub struct OsmPriceProvider<T>(PhantomData<T>);
+impl<T: Config> PriceProvider<CurrencyId> for OsmPriceProvider<T> {
+	fn get_price(currency_id: CurrencyId) -> Option<Price> {
+		Pallet::<T>::locked_price(currency_id)
+			.or_else(|| Pallet::<T>::osm_price(currency_id).map(|osm_price| osm_price.current))
+			.or_else(|| Pallet::<T>::access_price(currency_id))
+	}
+
+	fn is_price_stale(currency_id: CurrencyId) -> bool {
+		Pallet::<T>::locked_price(currency_id).is_none() && Pallet::<T>::is_osm_price_stale(currency_id)
+	}
+}
+
 /// The fair price is determined by the external feed price and the size of the liquidity pool:
 /// https://blog.alphafinance.io/fair-lp-token-pricing/
 /// fair_price = (pool_0 * pool_1)^0.5 * (price_0 * price_1)^0.5 / total_shares * 2
@@ -29,8 +29,8 @@ use frame_support::{
 };
 use frame_system::EnsureSignedBy;
 use module_support::{mocks::MockErc20InfoMapping, ExchangeRate, SwapLimit};
-use orml_traits::{parameter_type_with_key, DataFeeder};
-use primitives::{currency::DexShare, Amount, TokenSymbol};
+use orml_traits::{parameter_type_with_key, DataFeeder, DataProviderExtended};
+use primitives::{currency::DexShare, Amount, Moment, TokenSymbol};
 use sp_core::{H160, H256};
 use sp_runtime::{
 	traits::{IdentityLookup, One as OneT, Zero},
@@ -80,15 +80,49 @@ impl frame_system::Config for Runtime {
 
 thread_local! {
 	static CHANGED: RefCell<bool> = RefCell::new(false);
+	static FEEDS: RefCell<Vec<(CurrencyId, Option<Price>, Moment)>> = RefCell::new(vec![]);
+	static NOW: RefCell<Moment> = RefCell::new(0);
 }
 
 pub fn mock_oracle_update() {
 	CHANGED.with(|v| *v.borrow_mut() = true)
 }
 
+/// Feed the price at the current `MockTime`.
+pub fn mock_oracle_feed(currency_id: CurrencyId, price: Option<Price>) {
+	let now = MockTime::now();
+	FEEDS.with(|v| {
+		let mut feeds = v.borrow_mut();
+		feeds.retain(|(id, _, _)| *id != currency_id);
+		feeds.push((currency_id, price, now));
+	})
+}
+
+pub fn set_now(now: Moment) {
+	NOW.with(|v| *v.borrow_mut() = now)
+}
+
+pub struct MockTime;
+impl Time for MockTime {
+	type Moment = Moment;
+
+	fn now() -> Moment {
+		NOW.with(|v| *v.borrow())
+	}
+}
+
 pub struct MockDataProvider;
 impl DataProvider<CurrencyId, Price> for MockDataProvider {
 	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		if let Some(price) = FEEDS.with(|v| {
+			v.borrow()
+				.iter()
+				.find(|(id, _, _)| id == currency_id)
+				.map(|(_, price, _)| *price)
+		}) {
+			return price;
+		}
+
 		if CHANGED.with(|v| *v.borrow_mut()) {
 			match *currency_id {
 				USSD => None,
@@ -107,6 +141,25 @@ impl DataProvider<CurrencyId, Price> for MockDataProvider {
 	}
 }
 
+impl DataProviderExtended<CurrencyId, TimestampedValue<Price, Moment>> for MockDataProvider {
+	/// The prices not fed by `mock_oracle_feed` are always fresh.
+	fn get_no_op(currency_id: &CurrencyId) -> Option<TimestampedValue<Price, Moment>> {
+		let timestamp = FEEDS
+			.with(|v| {
+				v.borrow()
+					.iter()
+					.find(|(id, _, _)| id == currency_id)
+					.map(|(_, _, timestamp)| *timestamp)
+			})
+			.unwrap_or_else(MockTime::now);
+		Self::get(currency_id).map(|value| TimestampedValue { value, timestamp })
+	}
+
+	fn get_all_values() -> Vec<(CurrencyId, Option<TimestampedValue<Price, Moment>>)> {
+		vec![]
+	}
+}
+
 impl DataFeeder<CurrencyId, Price, AccountId> for MockDataProvider {
 	fn feed_value(_: Option<AccountId>, _: CurrencyId, _: Price) -> sp_runtime::DispatchResult {
 		Ok(())
@@ -200,11 +253,15 @@ parameter_types! {
 	pub const GetUSSDCurrencyId: CurrencyId = USSD;
 	pub const GetSEECurrencyId: CurrencyId = SEE;
 	pub USSDFixedPrice: Price = Price::one();
+	pub const OsmUpdatePeriod: BlockNumber = 10;
+	pub OsmMaxPriceChange: Ratio = Ratio::saturating_from_rational(10, 100);
+	pub const OsmStalePeriod: Moment = 30_000;
 }
 
 impl Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Source = MockDataProvider;
+	type Time = MockTime;
 	type GetUSSDCurrencyId = GetUSSDCurrencyId;
 	type USSDFixedPrice = USSDFixedPrice;
 	type GetSEECurrencyId = GetSEECurrencyId;
@@ -213,6 +270,9 @@ impl Config for Runtime {
 	type Currency = Tokens;
 	type Erc20InfoMapping = MockErc20InfoMapping;
 	type PricingPegged = PricingPegged;
+	type OsmUpdatePeriod = OsmUpdatePeriod;
+	type OsmMaxPriceChange = OsmMaxPriceChange;
+	type OsmStalePeriod = OsmStalePeriod;
 	type WeightInfo = ();
 }
 
@@ -385,3 +385,228 @@ fn price_providers_work() {
 		assert_eq!(LockedPriceProvider::<Runtime>::get_price(LP_USSD_SEE), None);
 	});
 }
+
+#[test]
+fn enable_and_disable_osm_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(PricesModule::enable_osm(RuntimeOrigin::signed(5), EDF), BadOrigin);
+
+		// cannot track the price of EDF when the price from oracle is None
+		mock_oracle_feed(EDF, None);
+		assert_noop!(
+			PricesModule::enable_osm(RuntimeOrigin::signed(1), EDF),
+			Error::<Runtime>::StaleOraclePrice
+		);
+
+		// nor when the price from oracle is outdated
+		mock_oracle_feed(EDF, Some(Price::saturating_from_integer(200)));
+		set_now(30_001);
+		assert_noop!(
+			PricesModule::enable_osm(RuntimeOrigin::signed(1), EDF),
+			Error::<Runtime>::StaleOraclePrice
+		);
+
+		mock_oracle_feed(EDF, Some(Price::saturating_from_integer(200)));
+		let price = PricesModule::access_price(EDF).unwrap();
+		assert_ok!(PricesModule::enable_osm(RuntimeOrigin::signed(1), EDF));
+		System::assert_last_event(RuntimeEvent::PricesModule(crate::Event::OsmEnabled {
+			currency_id: EDF,
+			price,
+		}));
+		assert_eq!(
+			PricesModule::osm_price(EDF),
+			Some(OsmPrice {
+				current: price,
+				next: price,
+				updated_at: 1,
+			})
+		);
+		assert_noop!(
+			PricesModule::enable_osm(RuntimeOrigin::signed(1), EDF),
+			Error::<Runtime>::OsmAlreadyEnabled
+		);
+
+		assert_noop!(PricesModule::disable_osm(RuntimeOrigin::signed(5), EDF), BadOrigin);
+		assert_ok!(PricesModule::disable_osm(RuntimeOrigin::signed(1), EDF));
+		System::assert_last_event(RuntimeEvent::PricesModule(crate::Event::OsmDisabled {
+			currency_id: EDF,
+		}));
+		assert_eq!(PricesModule::osm_price(EDF), None);
+		assert_noop!(
+			PricesModule::disable_osm(RuntimeOrigin::signed(1), EDF),
+			Error::<Runtime>::OsmNotEnabled
+		);
+	});
+}
+
+#[test]
+fn osm_price_is_delayed_by_one_period() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		mock_oracle_feed(EDF, Some(Price::saturating_from_integer(200)));
+		let price_0 = PricesModule::access_price(EDF).unwrap();
+		assert_ok!(PricesModule::enable_osm(RuntimeOrigin::signed(1), EDF));
+
+		// the oracle price changes by 5%
+		mock_oracle_feed(EDF, Some(Price::saturating_from_integer(210)));
+		let price_1 = PricesModule::access_price(EDF).unwrap();
+		assert_eq!(RealTimePriceProvider::<Runtime>::get_price(EDF), Some(price_1));
+		assert_eq!(OsmPriceProvider::<Runtime>::get_price(EDF), Some(price_0));
+
+		// not at the update period
+		System::set_block_number(5);
+		PricesModule::on_initialize(5);
+		assert_eq!(PricesModule::osm_price(EDF).unwrap().next, price_0);
+
+		System::set_block_number(10);
+		PricesModule::on_initialize(10);
+		System::assert_last_event(RuntimeEvent::PricesModule(crate::Event::OsmPriceUpdated {
+			currency_id: EDF,
+			current_price: price_0,
+			next_price: price_1,
+		}));
+		assert_eq!(
+			PricesModule::osm_price(EDF),
+			Some(OsmPrice {
+				current: price_0,
+				next: price_1,
+				updated_at: 10,
+			})
+		);
+		assert_eq!(OsmPriceProvider::<Runtime>::get_price(EDF), Some(price_0));
+
+		System::set_block_number(20);
+		PricesModule::on_initialize(20);
+		assert_eq!(OsmPriceProvider::<Runtime>::get_price(EDF), Some(price_1));
+
+		// the locked price takes precedence
+		assert_ok!(PricesModule::lock_price(RuntimeOrigin::signed(1), EDF));
+		mock_oracle_feed(EDF, Some(Price::saturating_from_integer(220)));
+		System::set_block_number(30);
+		PricesModule::on_initialize(30);
+		assert_eq!(OsmPriceProvider::<Runtime>::get_price(EDF), Some(price_1));
+		assert_ok!(PricesModule::unlock_price(RuntimeOrigin::signed(1), EDF));
+		assert_eq!(OsmPriceProvider::<Runtime>::get_price(EDF), Some(price_1));
+
+		// currencies not tracked by the OSM use the real-time price
+		assert_eq!(
+			OsmPriceProvider::<Runtime>::get_price(USSD),
+			RealTimePriceProvider::<Runtime>::get_price(USSD)
+		);
+	});
+}
+
+#[test]
+fn osm_holds_large_price_change_until_confirmed() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		mock_oracle_feed(EDF, Some(Price::saturating_from_integer(200)));
+		let price_0 = PricesModule::access_price(EDF).unwrap();
+		assert_ok!(PricesModule::enable_osm(RuntimeOrigin::signed(1), EDF));
+
+		assert_noop!(
+			PricesModule::confirm_osm_price(RuntimeOrigin::signed(1), EDF),
+			Error::<Runtime>::NoPendingOsmPrice
+		);
+
+		// the oracle price changes by 50%
+		mock_oracle_feed(EDF, Some(Price::saturating_from_integer(300)));
+		let price_1 = PricesModule::access_price(EDF).unwrap();
+		System::set_block_number(10);
+		PricesModule::on_initialize(10);
+		System::assert_last_event(RuntimeEvent::PricesModule(crate::Event::OsmPriceChangeHeld {
+			currency_id: EDF,
+			queued_price: price_0,
+			pending_price: price_1,
+		}));
+		assert_eq!(PricesModule::pending_osm_price(EDF), Some(price_1));
+		assert_eq!(
+			PricesModule::osm_price(EDF),
+			Some(OsmPrice {
+				current: price_0,
+				next: price_0,
+				updated_at: 1,
+			})
+		);
+
+		assert_noop!(PricesModule::confirm_osm_price(RuntimeOrigin::signed(5), EDF), BadOrigin);
+		System::set_block_number(12);
+		assert_ok!(PricesModule::confirm_osm_price(RuntimeOrigin::signed(1), EDF));
+		System::assert_last_event(RuntimeEvent::PricesModule(crate::Event::OsmPriceUpdated {
+			currency_id: EDF,
+			current_price: price_0,
+			next_price: price_1,
+		}));
+		assert_eq!(PricesModule::pending_osm_price(EDF), None);
+		assert_eq!(
+			PricesModule::osm_price(EDF),
+			Some(OsmPrice {
+				current: price_0,
+				next: price_1,
+				updated_at: 12,
+			})
+		);
+
+		// the held price is dropped once the oracle price returns within the range
+		mock_oracle_feed(EDF, Some(Price::saturating_from_integer(100)));
+		System::set_block_number(20);
+		PricesModule::on_initialize(20);
+		assert!(PricesModule::pending_osm_price(EDF).is_some());
+		mock_oracle_feed(EDF, Some(Price::saturating_from_integer(310)));
+		System::set_block_number(30);
+		PricesModule::on_initialize(30);
+		assert_eq!(PricesModule::pending_osm_price(EDF), None);
+		assert_eq!(OsmPriceProvider::<Runtime>::get_price(EDF), Some(price_1));
+	});
+}
+
+#[test]
+fn osm_price_becomes_stale() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		mock_oracle_feed(EDF, Some(Price::saturating_from_integer(200)));
+		let price = PricesModule::access_price(EDF).unwrap();
+		assert_ok!(PricesModule::enable_osm(RuntimeOrigin::signed(1), EDF));
+		assert!(!OsmPriceProvider::<Runtime>::is_price_stale(EDF));
+
+		// the oracle stops feeding, the outdated price is not accepted by the OSM
+		mock_oracle_feed(EDF, Some(Price::saturating_from_integer(210)));
+		set_now(30_000);
+		System::set_block_number(10);
+		PricesModule::on_initialize(10);
+		assert_eq!(PricesModule::osm_price(EDF).unwrap().updated_at, 10);
+		assert!(!OsmPriceProvider::<Runtime>::is_price_stale(EDF));
+
+		set_now(30_001);
+		assert!(OsmPriceProvider::<Runtime>::is_price_stale(EDF));
+		System::set_block_number(20);
+		PricesModule::on_initialize(20);
+		assert_eq!(PricesModule::osm_price(EDF).unwrap().updated_at, 10);
+		assert_eq!(OsmPriceProvider::<Runtime>::get_price(EDF), Some(price));
+
+		// the feed is unavailable
+		mock_oracle_feed(EDF, None);
+		assert!(OsmPriceProvider::<Runtime>::is_price_stale(EDF));
+
+		// the locked price is never stale
+		mock_oracle_feed(EDF, Some(Price::saturating_from_integer(200)));
+		assert_ok!(PricesModule::lock_price(RuntimeOrigin::signed(1), EDF));
+		set_now(60_002);
+		assert!(!OsmPriceProvider::<Runtime>::is_price_stale(EDF));
+		assert_ok!(PricesModule::unlock_price(RuntimeOrigin::signed(1), EDF));
+		assert!(OsmPriceProvider::<Runtime>::is_price_stale(EDF));
+
+		// the feed recovers
+		mock_oracle_feed(EDF, Some(Price::saturating_from_integer(200)));
+		System::set_block_number(30);
+		PricesModule::on_initialize(30);
+		assert_eq!(PricesModule::osm_price(EDF).unwrap().updated_at, 30);
+		assert!(!OsmPriceProvider::<Runtime>::is_price_stale(EDF));
+
+		// currencies not tracked by the OSM are never stale
+		assert!(!OsmPriceProvider::<Runtime>::is_price_stale(SEE));
+		assert!(!RealTimePriceProvider::<Runtime>::is_price_stale(EDF));
+	});
+}
@@ -52,6 +52,10 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn lock_price() -> Weight;
 	fn unlock_price() -> Weight;
+	fn enable_osm() -> Weight;
+	fn disable_osm() -> Weight;
+	fn confirm_osm_price() -> Weight;
+	fn on_initialize(c: u32, ) -> Weight;
 }
 
 /// Weights for module_prices using the Setheum node and recommended hardware.
@@ -66,6 +70,28 @@ impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
 		Weight::from_parts(12_000_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	fn enable_osm() -> Weight {
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn disable_osm() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn confirm_osm_price() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn on_initialize(c: u32, ) -> Weight {
+		Weight::from_parts(3_000_000, 0)
+			// Standard Error: 18_000
+			.saturating_add(Weight::from_parts(42_000_000, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((10 as u64).saturating_mul(c as u64)))
+			.saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(c as u64)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -79,4 +105,26 @@ impl WeightInfo for () {
 		Weight::from_parts(12_000_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
+	fn enable_osm() -> Weight {
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(10 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn disable_osm() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn confirm_osm_price() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn on_initialize(c: u32, ) -> Weight {
+		Weight::from_parts(3_000_000, 0)
+			// Standard Error: 18_000
+			.saturating_add(Weight::from_parts(42_000_000, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((10 as u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(c as u64)))
+	}
 }
@@ -18,10 +18,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use frame_support::traits::{EitherOfDiverse, EnsureOrigin};
+use frame_system::{EnsureNone, EnsureSigned};
 use parity_scale_codec::FullCodec;
-use primitives::EcdpEcdpPosition;
-use sp_core::U256;
-use sp_runtime::{DispatchError, DispatchResult};
+use primitives::EcdpPosition;
+use sp_core::{H160, U256};
+use sp_runtime::{traits::BadOrigin, DispatchError, DispatchResult};
 use sp_std::{
 	cmp::{Eq, PartialEq},
 	fmt::Debug,
@@ -30,10 +32,24 @@ use sp_std::{
 
 use crate::{dex::*, ExchangeRate, Ratio};
 
+/// Ensure the origin is _None_ or _Signed_ by a keeper, return the keeper if signed.
+pub fn ensure_none_or_keeper<T: frame_system::Config>(
+	origin: T::RuntimeOrigin,
+) -> sp_std::result::Result<Option<T::AccountId>, BadOrigin> {
+	EitherOfDiverse::<EnsureNone<T::AccountId>, EnsureSigned<T::AccountId>>::try_origin(origin)
+		.map_or(Err(BadOrigin), |either| Ok(either.right()))
+}
+
 pub trait EcdpEmergencyShutdown {
 	fn is_shutdown() -> bool;
 }
 
+impl EcdpEmergencyShutdown for () {
+	fn is_shutdown() -> bool {
+		false
+	}
+}
+
 pub trait EcdpAuctionsManager<AccountId> {
 	type CurrencyId;
 	type Balance;
@@ -45,9 +61,19 @@ pub trait EcdpAuctionsManager<AccountId> {
 		amount: Self::Balance,
 		target: Self::Balance,
 	) -> DispatchResult;
+	fn new_dutch_auction(
+		refund_recipient: &AccountId,
+		currency_id: Self::CurrencyId,
+		amount: Self::Balance,
+		target: Self::Balance,
+	) -> DispatchResult;
 	fn cancel_auction(id: Self::AuctionId) -> DispatchResult;
 	fn get_total_collateral_in_auction(id: Self::CurrencyId) -> Self::Balance;
 	fn get_total_target_in_auction() -> Self::Balance;
+	fn new_surplus_auction(amount: Self::Balance) -> DispatchResult;
+	fn new_debit_auction(initial_amount: Self::Balance, fix_debit: Self::Balance) -> DispatchResult;
+	fn get_total_surplus_in_auction() -> Self::Balance;
+	fn get_total_debit_in_auction() -> Self::Balance;
 }
 
 pub trait EcdpUssdRiskManager<AccountId, CurrencyId, Balance, DebitBalance> {
@@ -60,6 +86,18 @@ pub trait EcdpUssdRiskManager<AccountId, CurrencyId, Balance, DebitBalance> {
 		check_required_ratio: bool,
 	) -> DispatchResult;
 
+	/// Check the position of `who` under `currency_id`, positions in the
+	/// multi-collateral basket of `who` are checked across the whole basket.
+	fn check_account_position_valid(
+		_who: &AccountId,
+		currency_id: CurrencyId,
+		collateral_balance: Balance,
+		debit_balance: DebitBalance,
+		check_required_ratio: bool,
+	) -> DispatchResult {
+		Self::check_position_valid(currency_id, collateral_balance, debit_balance, check_required_ratio)
+	}
+
 	fn check_debit_cap(currency_id: CurrencyId, total_debit_balance: DebitBalance) -> DispatchResult;
 }
 
@@ -144,6 +182,13 @@ pub trait EcdpUssdTreasuryExtended<AccountId>: SlickUsdTreasury<AccountId> {
 		splited: bool,
 	) -> sp_std::result::Result<u32, DispatchError>;
 
+	fn create_dutch_auction(
+		currency_id: Self::CurrencyId,
+		amount: Self::Balance,
+		target: Self::Balance,
+		refund_receiver: AccountId,
+	) -> DispatchResult;
+
 	fn remove_liquidity_for_lp_collateral(
 		currency_id: Self::CurrencyId,
 		amount: Self::Balance,
@@ -164,11 +209,85 @@ pub trait EcdpUssdManager<AccountId, CurrencyId, Amount, Balance> {
 	/// Close ECDP loan using DEX
 	fn close_loan_by_dex(who: AccountId, currency_id: CurrencyId, max_collateral_amount: Balance) -> DispatchResult;
 	/// Get open ECDP corresponding to an account and collateral `CurrencyId`
-	fn get_position(who: &AccountId, currency_id: CurrencyId) -> EcdpEcdpPosition;
-	/// Get liquidation ratio for collateral `CurrencyId`
+	fn get_position(who: &AccountId, currency_id: CurrencyId) -> EcdpPosition;
+	/// Get the risk parameters for collateral `CurrencyId`
 	fn get_collateral_parameters(currency_id: CurrencyId) -> Vec<U256>;
+	/// Get liquidation ratio for collateral `CurrencyId`
+	fn get_liquidation_ratio(currency_id: CurrencyId) -> Option<Ratio>;
 	/// Get current ratio of collateral to debit of open ECDP
 	fn get_current_collateral_ratio(who: &AccountId, currency_id: CurrencyId) -> Option<Ratio>;
 	/// Get exchange rate of debit units to debit value for a currency_id
 	fn get_debit_exchange_rate(currency_id: CurrencyId) -> ExchangeRate;
 }
+
+#[cfg(feature = "std")]
+impl<AccountId, CurrencyId, Amount, Balance> EcdpUssdManager<AccountId, CurrencyId, Amount, Balance> for () {
+	fn adjust_loan(
+		_who: &AccountId,
+		_currency_id: CurrencyId,
+		_collateral_adjustment: Amount,
+		_debit_adjustment: Amount,
+	) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented ecdp manager"))
+	}
+
+	fn close_loan_by_dex(_who: AccountId, _currency_id: CurrencyId, _max_collateral_amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented ecdp manager"))
+	}
+
+	fn get_position(_who: &AccountId, _currency_id: CurrencyId) -> EcdpPosition {
+		Default::default()
+	}
+
+	fn get_collateral_parameters(_currency_id: CurrencyId) -> Vec<U256> {
+		Default::default()
+	}
+
+	fn get_liquidation_ratio(_currency_id: CurrencyId) -> Option<Ratio> {
+		None
+	}
+
+	fn get_current_collateral_ratio(_who: &AccountId, _currency_id: CurrencyId) -> Option<Ratio> {
+		None
+	}
+
+	fn get_debit_exchange_rate(_currency_id: CurrencyId) -> ExchangeRate {
+		Default::default()
+	}
+}
+
+/// Functionality of USSD flash minting to be exposed to EVM.
+pub trait FlashMint<AccountId, Balance> {
+	/// Get the maximum amount of USSD that can be flash minted currently
+	fn max_flash_mint() -> Balance;
+	/// Get the fee charged for flash minting `amount` of USSD
+	fn flash_fee(amount: Balance) -> Balance;
+	/// Flash mint `amount` of USSD to the `receiver` contract on behalf of `initiator`, the contract
+	/// must hold `amount` plus the fee when its callback returns. Returns the fee charged.
+	fn flash_mint_to_contract(
+		initiator: &AccountId,
+		receiver: H160,
+		amount: Balance,
+		data: Vec<u8>,
+	) -> sp_std::result::Result<Balance, DispatchError>;
+}
+
+#[cfg(feature = "std")]
+impl<AccountId, Balance: Default> FlashMint<AccountId, Balance> for () {
+	fn max_flash_mint() -> Balance {
+		Default::default()
+	}
+
+	fn flash_fee(_amount: Balance) -> Balance {
+		Default::default()
+	}
+
+	fn flash_mint_to_contract(
+		_initiator: &AccountId,
+		_receiver: H160,
+		_amount: Balance,
+		_data: Vec<u8>,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented flash mint"))
+	}
+}
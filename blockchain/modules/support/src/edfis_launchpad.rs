@@ -21,11 +21,13 @@
 //! Traits for the Launchpad Crowdsales Pallet.
 
 use codec::{Decode, Encode};
+use primitives::{edfis_launchpad::CampaignInfo, Balance, CurrencyId};
 use sp_runtime::{
 	DispatchError, DispatchResult,
 };
 use sp_std::{
 	cmp::{Eq, PartialEq},
+	vec::Vec,
 };
 
 /// Abstraction over th Launchpad Proposal system.
@@ -99,3 +101,60 @@ pub trait CampaignManager<AccountId, BlockNumber> {
 	/// Get the total amounts raised in protocol
 	fn get_total_amounts_raised() -> Vec<(CurrencyId, Balance)>;
 }
+
+#[cfg(feature = "std")]
+impl<AccountId, BlockNumber> CampaignManager<AccountId, BlockNumber> for () {
+	type CurrencyId = CurrencyId;
+
+	fn campaign_info(_id: Self::CurrencyId) -> Option<CampaignInfo<AccountId, Balance, BlockNumber>> {
+		None
+	}
+
+	fn all_campaigns() -> Vec<CampaignInfo<AccountId, Balance, BlockNumber>> {
+		Default::default()
+	}
+
+	fn on_contribution(_who: AccountId, _id: Self::CurrencyId, _amount: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn on_claim_allocation(_who: AccountId, _id: Self::CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn on_claim_campaign(_who: AccountId, _id: Self::CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn on_claim_failed_campaign(_who: AccountId, _id: Self::CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn activate_campaign(_id: Self::CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn ensure_successfully_ended_campaign(_id: Self::CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn on_successful_campaign(_now: BlockNumber, _id: Self::CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn on_failed_campaign(_now: BlockNumber, _id: Self::CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn on_retire(_id: Self::CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn get_contributors_count(_id: Self::CurrencyId) -> u32 {
+		0
+	}
+
+	fn get_total_amounts_raised() -> Vec<(CurrencyId, Balance)> {
+		Default::default()
+	}
+}
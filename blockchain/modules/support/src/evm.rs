@@ -44,6 +44,12 @@ pub trait PrecompilePauseFilter {
 	fn is_paused(address: H160) -> bool;
 }
 
+impl PrecompilePauseFilter for () {
+	fn is_paused(_address: H160) -> bool {
+		false
+	}
+}
+
 /// An abstraction of EVM for EVMBridge
 pub trait EVM<AccountId> {
 	type Balance: AtLeast32BitUnsigned + Copy + MaybeSerializeDeserialize + Default;
@@ -185,6 +191,30 @@ impl LiquidationEvmBridge for () {
 	fn on_repayment_refund(_context: InvokeContext, _collateral: EvmAddress, _repayment: Balance) {}
 }
 
+/// EVM bridge for USSD flash minting.
+pub trait FlashMintEvmBridge {
+	/// Notify the receiver contract of a flash mint. The contract is expected to hold `amount`
+	/// plus `fee` of USSD when the call returns, otherwise the flash mint would be reverted.
+	fn on_flash_mint(
+		context: InvokeContext,
+		initiator: EvmAddress,
+		amount: Balance,
+		fee: Balance,
+		data: Vec<u8>,
+	) -> DispatchResult;
+}
+impl FlashMintEvmBridge for () {
+	fn on_flash_mint(
+		_context: InvokeContext,
+		_initiator: EvmAddress,
+		_amount: Balance,
+		_fee: Balance,
+		_data: Vec<u8>,
+	) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+}
+
 /// An abstraction of EVMManager
 pub trait EVMManager<AccountId, Balance> {
 	/// Query the constants `NewContractExtraBytes` value from evm module.
@@ -193,6 +223,8 @@ pub trait EVMManager<AccountId, Balance> {
 	fn query_storage_deposit_per_byte() -> Balance;
 	/// Query the maintainer address from the ERC20 contract.
 	fn query_maintainer(contract: H160) -> Result<H160, DispatchError>;
+	/// Query the deployed code of the contract, empty if it's not a contract.
+	fn query_code(contract: H160) -> Vec<u8>;
 	/// Query the constants `DeveloperDeposit` value from evm module.
 	fn query_developer_deposit() -> Balance;
 	/// Query the constants `PublicationFee` value from evm module.
@@ -328,4 +360,10 @@ pub mod limits {
 		pub const ON_COLLATERAL_TRANSFER: Limit = Limit::new(200_000, 1_000);
 		pub const ON_REPAYMENT_REFUND: Limit = Limit::new(200_000, 1_000);
 	}
+
+	pub mod flash_mint {
+		use super::*;
+
+		pub const ON_FLASH_MINT: Limit = Limit::new(2_000_000, 10_000);
+	}
 }
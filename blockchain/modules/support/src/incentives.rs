@@ -49,9 +49,9 @@ pub trait IncentivesManager<AccountId, Balance, CurrencyId, PoolId> {
 	/// Gets reward amount for the given reward currency added per period
 	fn get_incentive_reward_amount(pool_id: PoolId, currency_id: CurrencyId) -> Balance;
 	/// Stake LP token to add shares to pool
-	fn deposit_edfis_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
+	fn deposit_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
 	/// Unstake LP token to remove shares from pool
-	fn withdraw_edfis_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
+	fn withdraw_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
 	/// Claim all available rewards for specific `PoolId`
 	fn claim_rewards(who: AccountId, pool_id: PoolId) -> DispatchResult;
 	/// Gets deduction reate for claiming reward early
@@ -60,18 +60,45 @@ pub trait IncentivesManager<AccountId, Balance, CurrencyId, PoolId> {
 	fn get_pending_rewards(pool_id: PoolId, who: AccountId, reward_currency: Vec<CurrencyId>) -> Vec<Balance>;
 }
 
+#[cfg(feature = "std")]
+impl<AccountId, Balance: Default, CurrencyId, PoolId> IncentivesManager<AccountId, Balance, CurrencyId, PoolId> for () {
+	fn get_incentive_reward_amount(_pool_id: PoolId, _currency_id: CurrencyId) -> Balance {
+		Default::default()
+	}
+
+	fn deposit_dex_share(_who: &AccountId, _lp_currency_id: CurrencyId, _amount: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn withdraw_dex_share(_who: &AccountId, _lp_currency_id: CurrencyId, _amount: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn claim_rewards(_who: AccountId, _pool_id: PoolId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn get_claim_reward_deduction_rate(_pool_id: PoolId) -> Rate {
+		Default::default()
+	}
+
+	fn get_pending_rewards(_pool_id: PoolId, _who: AccountId, reward_currency: Vec<CurrencyId>) -> Vec<Balance> {
+		reward_currency.iter().map(|_| Default::default()).collect()
+	}
+}
+
 pub trait Incentives<AccountId, CurrencyId, Balance> {
-	fn do_deposit_edfis_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
-	fn do_withdraw_edfis_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
+	fn do_deposit_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
+	fn do_withdraw_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
 }
 
 #[cfg(feature = "std")]
 impl<AccountId, CurrencyId, Balance> Incentives<AccountId, CurrencyId, Balance> for () {
-	fn do_deposit_edfis_share(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
+	fn do_deposit_dex_share(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
 		Ok(())
 	}
 
-	fn do_withdraw_edfis_share(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
+	fn do_withdraw_dex_share(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
 		Ok(())
 	}
 }
@@ -48,8 +48,10 @@ pub mod edfis_mining;
 pub mod edfis_swap;
 pub mod edfis_swap_legacy;
 pub mod evm;
+pub mod incentives;
 pub mod migration;
 pub mod mocks;
+pub mod nft;
 
 pub use crate::bounded::*;
 pub use crate::ecdp::*;
@@ -58,7 +60,9 @@ pub use crate::edfis_mining::*;
 pub use crate::edfis_swap::*;
 pub use crate::edfis_swap_legacy::*;
 pub use crate::evm::*;
+pub use crate::incentives::*;
 pub use crate::migration::*;
+pub use crate::nft::*;
 
 pub type Price = FixedU128;
 pub type ExchangeRate = FixedU128;
@@ -109,6 +113,11 @@ pub trait PriceProvider<CurrencyId> {
 			None
 		}
 	}
+	/// Whether the price feed of the currency is stale, consumers relying on a fresh price,
+	/// such as liquidation, should pause for the currency.
+	fn is_price_stale(_currency_id: CurrencyId) -> bool {
+		false
+	}
 }
 
 pub trait SwapPriceProvider<CurrencyId> {
@@ -144,6 +153,21 @@ pub trait TransactionPayment<AccountId, Balance, NegativeImbalance> {
 	fn apply_multiplier_to_fee(fee: Balance, multiplier: Option<Multiplier>) -> Balance;
 }
 
+/// Swap the fee currency of an account to native token, for fees paid outside of the
+/// transaction's own fee payment.
+pub trait FeeCurrencySwap<AccountId, Balance> {
+	/// Swap `currency_id` of `who` to `fee` of native token through the charge fee pool or dex,
+	/// the same way as `with_fee_currency`. Returns the surplus charged.
+	fn swap_to_native_fee(who: &AccountId, fee: Balance, currency_id: CurrencyId) -> Result<Balance, DispatchError>;
+}
+
+#[cfg(feature = "std")]
+impl<AccountId, Balance> FeeCurrencySwap<AccountId, Balance> for () {
+	fn swap_to_native_fee(_who: &AccountId, _fee: Balance, _currency_id: CurrencyId) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented fee currency swap"))
+	}
+}
+
 /// Dispatchable tasks
 pub trait DispatchableTask {
 	fn dispatch(self, weight: Weight) -> TaskResult;
@@ -0,0 +1,66 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Traits for the ERC-721 surface of the NFT module.
+
+use primitives::nft::NFTBalance;
+use sp_runtime::{DispatchError, DispatchResult};
+use sp_std::vec::Vec;
+
+/// Abstraction over the NFT module, used by the NFT precompile.
+pub trait NFTManager<AccountId> {
+	type ClassId;
+	type TokenId;
+
+	/// The number of tokens owned by `who` across all classes.
+	fn balance(who: &AccountId) -> NFTBalance;
+	/// The number of tokens of `class_id` owned by `who`.
+	fn balance_of_class(who: &AccountId, class_id: Self::ClassId) -> NFTBalance;
+	/// The owner of `token`.
+	fn owner(token: (Self::ClassId, Self::TokenId)) -> Option<AccountId>;
+	/// The URI of `token`, read from its `tokenURI` attribute or its metadata.
+	fn token_uri(token: (Self::ClassId, Self::TokenId)) -> Option<Vec<u8>>;
+	/// The account approved to transfer `token`.
+	fn approved(token: (Self::ClassId, Self::TokenId)) -> Option<AccountId>;
+	/// Whether `operator` can manage all tokens of `class_id` owned by `owner`.
+	fn is_approved_for_all(owner: &AccountId, class_id: Self::ClassId, operator: &AccountId) -> bool;
+	/// Transfer `token` owned by `from` to `to`.
+	fn transfer(from: &AccountId, to: &AccountId, token: (Self::ClassId, Self::TokenId)) -> DispatchResult;
+	/// Transfer `token` from `from` to `to` on behalf of `operator`, who must be the owner or approved.
+	fn transfer_from(
+		operator: &AccountId,
+		from: &AccountId,
+		to: &AccountId,
+		token: (Self::ClassId, Self::TokenId),
+	) -> DispatchResult;
+	/// Approve `approved` to transfer `token`, `None` clears the approval.
+	fn approve(who: &AccountId, approved: Option<AccountId>, token: (Self::ClassId, Self::TokenId)) -> DispatchResult;
+	/// Allow or disallow `operator` to manage all tokens of `class_id` owned by `owner`.
+	fn set_approval_for_all(
+		owner: &AccountId,
+		class_id: Self::ClassId,
+		operator: &AccountId,
+		approved: bool,
+	) -> DispatchResult;
+	/// Mint a token of `class_id` to `to`, `who` must be the class owner or its proxy.
+	fn mint(who: &AccountId, to: &AccountId, class_id: Self::ClassId) -> Result<Self::TokenId, DispatchError>;
+	/// Burn `token`, `who` must be the owner or approved.
+	fn burn(who: &AccountId, token: (Self::ClassId, Self::TokenId)) -> DispatchResult;
+}
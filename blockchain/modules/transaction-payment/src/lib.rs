@@ -42,7 +42,7 @@ use frame_support::{
 	BoundedVec, PalletId,
 };
 use frame_system::pallet_prelude::*;
-use module_support::{BuyWeightRate, PriceProvider, Ratio, Swap, SwapLimit, TransactionPayment};
+use module_support::{BuyWeightRate, FeeCurrencySwap, PriceProvider, Ratio, Swap, SwapLimit, TransactionPayment};
 use orml_traits::MultiCurrency;
 use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
 use pallet_transaction_payment_rpc_runtime_api::{FeeDetails, InclusionFee};
@@ -866,14 +866,22 @@ where
 		);
 
 		match call.is_sub_type() {
-			Some(Call::with_fee_currency { currency_id, .. }) => {
-				let fee = Self::check_native_is_not_enough(who, fee, reason).map_or_else(|| fee, |amount| amount);
-				Self::charge_fee_currency(who, fee, *currency_id)
-			}
+			Some(Call::with_fee_currency { currency_id, .. }) => Self::swap_with_fee_currency(who, fee, *currency_id, reason),
 			_ => Self::native_then_alternative_or_default(who, fee, reason).map(|surplus| (who.clone(), surplus)),
 		}
 	}
 
+	/// Charge fee with the given currency, the same way as `with_fee_currency`.
+	fn swap_with_fee_currency(
+		who: &T::AccountId,
+		fee: PalletBalanceOf<T>,
+		fee_currency_id: CurrencyId,
+		reason: WithdrawReasons,
+	) -> Result<(T::AccountId, Balance), DispatchError> {
+		let fee = Self::check_native_is_not_enough(who, fee, reason).map_or_else(|| fee, |amount| amount);
+		Self::charge_fee_currency(who, fee, fee_currency_id)
+	}
+
 	/// If native is enough, do nothing, return `Ok(0)` means there are none extra surplus fee.
 	/// If native is not enough, try swap from tx fee pool or dex:
 	/// - As user can set his own `AlternativeFeeSwapPath`, this will direct swap from dex. Notice:
@@ -1110,6 +1118,17 @@ where
 	}
 }
 
+impl<T: Config> FeeCurrencySwap<T::AccountId, Balance> for Pallet<T>
+where
+	PalletBalanceOf<T>: FixedPointOperand,
+{
+	#[transactional]
+	fn swap_to_native_fee(who: &T::AccountId, fee: Balance, currency_id: CurrencyId) -> Result<Balance, DispatchError> {
+		Self::swap_with_fee_currency(who, fee, currency_id, WithdrawReasons::TRANSACTION_PAYMENT)
+			.map(|(_, surplus)| surplus)
+	}
+}
+
 impl<T> Convert<Weight, PalletBalanceOf<T>> for Pallet<T>
 where
 	T: Config,
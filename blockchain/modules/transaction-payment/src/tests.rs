@@ -1015,6 +1015,27 @@ fn payment_reserve_fee() {
 	});
 }
 
+#[test]
+fn swap_to_native_fee_works() {
+	builder_with_dex_and_fee_pool(true).execute_with(|| {
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(USSD, &ALICE, &BOB, 5000));
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(SEE, &BOB), 0);
+
+		let fee: Balance = 100;
+		let ed = <Currencies as MultiCurrency<_>>::minimum_balance(SEE);
+		let surplus = <TransactionPayment as FeeCurrencySwap<AccountId, Balance>>::swap_to_native_fee(&BOB, fee, USSD)
+			.unwrap();
+
+		// native is not enough, swap fee, existential deposit and surplus from the USSD charge fee pool
+		let native_amount = fee + ed + surplus;
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(SEE, &BOB), native_amount);
+		assert_eq!(
+			<Currencies as MultiCurrency<_>>::free_balance(USSD, &BOB),
+			5000 - TokenExchangeRate::<Runtime>::get(USSD).unwrap().saturating_mul_int(native_amount)
+		);
+	});
+}
+
 #[test]
 fn charges_fee_failed_by_slippage_limit() {
 	builder_with_dex_and_fee_pool(true).execute_with(|| {
@@ -24,6 +24,15 @@
 //!
 //! Unified Accounts module provide a two way mapping between Substrate accounts and
 //! EVM Accounts so the user only has to deal with one account / private key.
+//!
+//! The mapping can be rotated to a new EVM address, or removed with `unlink_account`
+//! and `execute_unlink` once `UnlinkDelay` has passed. Accounts can also prove the
+//! ownership of secondary EVM addresses, which serve as identity only and are not
+//! mapped to the account.
+//!
+//! Every call taking an EIP-712 signature has its own typed struct, which includes the
+//! purpose of the signature and a nonce of the account, so a signature cannot be
+//! replayed for another call or after the account is unlinked.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -46,7 +55,7 @@ use sp_io::{
 	hashing::{blake2_256, keccak_256},
 };
 use sp_runtime::{
-	traits::{LookupError, StaticLookup, Zero},
+	traits::{LookupError, Saturating, StaticLookup, Zero},
 	MultiAddress,
 };
 use sp_std::{marker::PhantomData, vec::Vec};
@@ -61,6 +70,40 @@ pub use weights::WeightInfo;
 /// A signature (a 512-bit value, plus 8 bits for recovery ID).
 pub type Eip712Signature = [u8; 65];
 
+/// The call an EIP-712 signature is made for.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum SignaturePurpose {
+	ClaimAccount,
+	RotateEvmAddress,
+	AddSecondaryAddress,
+}
+
+impl SignaturePurpose {
+	fn type_hash(&self) -> &'static [u8; 32] {
+		match self {
+			SignaturePurpose::ClaimAccount => {
+				keccak256!("ClaimAccount(string purpose,bytes substrateAddress,uint256 nonce)")
+			},
+			SignaturePurpose::RotateEvmAddress => {
+				keccak256!("RotateEvmAddress(string purpose,bytes substrateAddress,uint256 nonce)")
+			},
+			SignaturePurpose::AddSecondaryAddress => {
+				keccak256!("AddSecondaryAddress(string purpose,bytes substrateAddress,uint256 nonce)")
+			},
+		}
+	}
+
+	fn purpose(&self) -> &'static [u8] {
+		match self {
+			SignaturePurpose::ClaimAccount => b"Link this EVM address to the Substrate account",
+			SignaturePurpose::RotateEvmAddress => b"Rotate the EVM address of the Substrate account to this address",
+			SignaturePurpose::AddSecondaryAddress => {
+				b"Add this EVM address as a secondary address of the Substrate account"
+			},
+		}
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -82,6 +125,19 @@ pub mod module {
 		/// Merge free balance from source to dest.
 		type TransferAll: TransferAll<Self::AccountId>;
 
+		/// The number of blocks an unlink request must wait before execution.
+		#[pallet::constant]
+		type UnlinkDelay: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of secondary EVM addresses of an account.
+		#[pallet::constant]
+		type MaxSecondaryAddresses: Get<u32>;
+
+		/// The maximum number of currencies expected to be merged by `TransferAll` from the
+		/// padded account of a linked address, used for the weight of the linking calls.
+		#[pallet::constant]
+		type MaxMergedCurrencies: Get<u32>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -95,6 +151,35 @@ pub mod module {
 			account_id: T::AccountId,
 			evm_address: EvmAddress,
 		},
+		/// Unlinking the EVM address of an account is requested.
+		UnlinkRequested {
+			account_id: T::AccountId,
+			evm_address: EvmAddress,
+			unlock_at: BlockNumberFor<T>,
+		},
+		/// The unlink request of an account is cancelled.
+		UnlinkCancelled { account_id: T::AccountId },
+		/// The EVM address of an account is unlinked.
+		AccountUnlinked {
+			account_id: T::AccountId,
+			evm_address: EvmAddress,
+		},
+		/// The EVM address of an account is rotated.
+		EvmAddressRotated {
+			account_id: T::AccountId,
+			old_evm_address: EvmAddress,
+			new_evm_address: EvmAddress,
+		},
+		/// A secondary EVM address is added to an account.
+		SecondaryAddressAdded {
+			account_id: T::AccountId,
+			evm_address: EvmAddress,
+		},
+		/// A secondary EVM address is removed from an account.
+		SecondaryAddressRemoved {
+			account_id: T::AccountId,
+			evm_address: EvmAddress,
+		},
 	}
 
 	/// Error for Unified Accounts module.
@@ -110,6 +195,18 @@ pub mod module {
 		InvalidSignature,
 		/// Account ref count is not zero
 		NonZeroRefCount,
+		/// AccountId has not mapped
+		AccountIdNotMapped,
+		/// Unlink has been requested
+		UnlinkAlreadyRequested,
+		/// Unlink has not been requested
+		UnlinkNotRequested,
+		/// The unlink delay has not passed
+		UnlinkDelayNotPassed,
+		/// Too many secondary addresses
+		TooManySecondaryAddresses,
+		/// Secondary address not found
+		SecondaryAddressNotFound,
 	}
 
 	/// The Substrate Account for EvmAddresses
@@ -126,6 +223,38 @@ pub mod module {
 	#[pallet::getter(fn evm_addresses)]
 	pub type EvmAddresses<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, EvmAddress, OptionQuery>;
 
+	/// The block number from which the requested unlink of accounts can be executed
+	///
+	/// PendingUnlinks: map AccountId => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn pending_unlinks)]
+	pub type PendingUnlinks<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, BlockNumberFor<T>, OptionQuery>;
+
+	/// The secondary EvmAddresses of Substrate Accounts, which are not mapped to the
+	/// accounts
+	///
+	/// SecondaryAddresses: map AccountId => BoundedVec<EvmAddress, MaxSecondaryAddresses>
+	#[pallet::storage]
+	#[pallet::getter(fn secondary_addresses)]
+	pub type SecondaryAddresses<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BoundedVec<EvmAddress, T::MaxSecondaryAddresses>, ValueQuery>;
+
+	/// The Substrate Account for secondary EvmAddresses
+	///
+	/// SecondaryAccounts: map EvmAddress => Option<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn secondary_accounts)]
+	pub type SecondaryAccounts<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, T::AccountId, OptionQuery>;
+
+	/// The nonce of the EIP-712 signatures of Substrate Accounts, increased every time a
+	/// signature is used. It is kept when the account is reaped so that signatures cannot
+	/// be replayed.
+	///
+	/// SignatureNonces: map AccountId => u32
+	#[pallet::storage]
+	#[pallet::getter(fn signature_nonces)]
+	pub type SignatureNonces<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -140,7 +269,7 @@ pub mod module {
 		/// - `eth_address`: The address to bind to the caller's account
 		/// - `eth_signature`: A signature generated by the address to prove ownership
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::claim_account())]
+		#[pallet::weight(T::WeightInfo::claim_account(T::MaxMergedCurrencies::get()))]
 		pub fn claim_account(
 			origin: OriginFor<T>,
 			eth_address: EvmAddress,
@@ -150,21 +279,7 @@ pub mod module {
 
 			// ensure account_id and eth_address has not been mapped
 			ensure!(!EvmAddresses::<T>::contains_key(&who), Error::<T>::AccountIdHasMapped);
-			ensure!(
-				!Accounts::<T>::contains_key(eth_address),
-				Error::<T>::EthAddressHasMapped
-			);
-
-			// recover evm address from signature
-			let address = Self::verify_eip712_signature(&who, &eth_signature).ok_or(Error::<T>::BadSignature)?;
-			ensure!(eth_address == address, Error::<T>::InvalidSignature);
-
-			// check if the evm padded address already exists
-			let account_id = T::AddressMapping::get_account_id(&eth_address);
-			if frame_system::Pallet::<T>::account_exists(&account_id) {
-				// merge balance from `evm padded address` to `origin`
-				T::TransferAll::transfer_all(&account_id, &who)?;
-			}
+			Self::ensure_can_link(&who, eth_address, &eth_signature, SignaturePurpose::ClaimAccount)?;
 
 			Accounts::<T>::insert(eth_address, &who);
 			EvmAddresses::<T>::insert(&who, eth_address);
@@ -187,6 +302,157 @@ pub mod module {
 			let _ = Self::do_claim_default_evm_address(who)?;
 			Ok(())
 		}
+
+		/// Request to unlink the EVM address of the caller's account. The mapping is
+		/// removed by `execute_unlink` after `UnlinkDelay`, and can be kept with
+		/// `cancel_unlink` until then.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::unlink_account())]
+		pub fn unlink_account(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let evm_address = Self::evm_addresses(&who).ok_or(Error::<T>::AccountIdNotMapped)?;
+			ensure!(
+				!PendingUnlinks::<T>::contains_key(&who),
+				Error::<T>::UnlinkAlreadyRequested
+			);
+
+			let unlock_at = frame_system::Pallet::<T>::block_number().saturating_add(T::UnlinkDelay::get());
+			PendingUnlinks::<T>::insert(&who, unlock_at);
+
+			Self::deposit_event(Event::UnlinkRequested {
+				account_id: who,
+				evm_address,
+				unlock_at,
+			});
+			Ok(())
+		}
+
+		/// Cancel the unlink request of the caller's account.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::cancel_unlink())]
+		pub fn cancel_unlink(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			PendingUnlinks::<T>::take(&who).ok_or(Error::<T>::UnlinkNotRequested)?;
+
+			Self::deposit_event(Event::UnlinkCancelled { account_id: who });
+			Ok(())
+		}
+
+		/// Remove the mapping between the caller's account and its EVM address, once
+		/// the unlink delay has passed. The EVM address maps to its padded account
+		/// again, and can be claimed by any account.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::execute_unlink())]
+		pub fn execute_unlink(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let unlock_at = Self::pending_unlinks(&who).ok_or(Error::<T>::UnlinkNotRequested)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= unlock_at,
+				Error::<T>::UnlinkDelayNotPassed
+			);
+
+			PendingUnlinks::<T>::remove(&who);
+			let evm_address = EvmAddresses::<T>::take(&who).ok_or(Error::<T>::AccountIdNotMapped)?;
+			Accounts::<T>::remove(evm_address);
+
+			Self::deposit_event(Event::AccountUnlinked {
+				account_id: who,
+				evm_address,
+			});
+			Ok(())
+		}
+
+		/// Rotate the EVM address of the caller's account to a new address, for
+		/// instance when the key of the old address is lost. The free balances of the
+		/// padded account of the new address are merged into the caller's account, and
+		/// any pending unlink request is cancelled.
+		///
+		/// - `eth_address`: The new address to bind to the caller's account
+		/// - `eth_signature`: A signature generated by the new address to prove ownership
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::rotate_evm_address(T::MaxMergedCurrencies::get()))]
+		pub fn rotate_evm_address(
+			origin: OriginFor<T>,
+			eth_address: EvmAddress,
+			eth_signature: Eip712Signature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let old_evm_address = Self::evm_addresses(&who).ok_or(Error::<T>::AccountIdNotMapped)?;
+			Self::ensure_can_link(&who, eth_address, &eth_signature, SignaturePurpose::RotateEvmAddress)?;
+
+			PendingUnlinks::<T>::remove(&who);
+			Accounts::<T>::remove(old_evm_address);
+			Accounts::<T>::insert(eth_address, &who);
+			EvmAddresses::<T>::insert(&who, eth_address);
+
+			Self::deposit_event(Event::EvmAddressRotated {
+				account_id: who,
+				old_evm_address,
+				new_evm_address: eth_address,
+			});
+			Ok(())
+		}
+
+		/// Add a secondary EVM address to the caller's account. Secondary addresses
+		/// serve as identity only, they are not mapped to the account and hold no
+		/// balances of it.
+		///
+		/// - `eth_address`: The address to add to the caller's account
+		/// - `eth_signature`: A signature generated by the address to prove ownership
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::add_secondary_address())]
+		pub fn add_secondary_address(
+			origin: OriginFor<T>,
+			eth_address: EvmAddress,
+			eth_signature: Eip712Signature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_address_is_free(eth_address)?;
+			Self::ensure_signed_by(&who, eth_address, &eth_signature, SignaturePurpose::AddSecondaryAddress)?;
+
+			SecondaryAddresses::<T>::try_mutate(&who, |addresses| -> DispatchResult {
+				addresses
+					.try_push(eth_address)
+					.map_err(|_| Error::<T>::TooManySecondaryAddresses)?;
+				Ok(())
+			})?;
+			SecondaryAccounts::<T>::insert(eth_address, &who);
+
+			Self::deposit_event(Event::SecondaryAddressAdded {
+				account_id: who,
+				evm_address: eth_address,
+			});
+			Ok(())
+		}
+
+		/// Remove a secondary EVM address from the caller's account.
+		///
+		/// - `eth_address`: The address to remove from the caller's account
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::remove_secondary_address())]
+		pub fn remove_secondary_address(origin: OriginFor<T>, eth_address: EvmAddress) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			SecondaryAddresses::<T>::try_mutate_exists(&who, |maybe_addresses| -> DispatchResult {
+				let addresses = maybe_addresses.as_mut().ok_or(Error::<T>::SecondaryAddressNotFound)?;
+				let index = addresses
+					.iter()
+					.position(|address| *address == eth_address)
+					.ok_or(Error::<T>::SecondaryAddressNotFound)?;
+				addresses.remove(index);
+				if addresses.is_empty() {
+					*maybe_addresses = None;
+				}
+				Ok(())
+			})?;
+			SecondaryAccounts::<T>::remove(eth_address);
+
+			Self::deposit_event(Event::SecondaryAddressRemoved {
+				account_id: who,
+				evm_address: eth_address,
+			});
+			Ok(())
+		}
 	}
 }
 
@@ -205,9 +471,13 @@ impl<T: Config> Pallet<T> {
 	}
 
 	#[cfg(any(feature = "runtime-benchmarks", feature = "std"))]
-	// Constructs a message and signs it.
-	pub fn eth_sign(secret: &libsecp256k1::SecretKey, who: &T::AccountId) -> Eip712Signature {
-		let msg = keccak_256(&Self::eip712_signable_message(who));
+	// Constructs the message of `purpose` with the current nonce of `who` and signs it.
+	pub fn eth_sign(
+		secret: &libsecp256k1::SecretKey,
+		who: &T::AccountId,
+		purpose: SignaturePurpose,
+	) -> Eip712Signature {
+		let msg = keccak_256(&Self::eip712_signable_message(who, purpose, Self::signature_nonces(who)));
 		let (sig, recovery_id) = libsecp256k1::sign(&libsecp256k1::Message::parse(&msg), secret);
 		let mut r = [0u8; 65];
 		r[0..64].copy_from_slice(&sig.serialize()[..]);
@@ -215,17 +485,22 @@ impl<T: Config> Pallet<T> {
 		r
 	}
 
-	fn verify_eip712_signature(who: &T::AccountId, sig: &[u8; 65]) -> Option<H160> {
-		let msg = Self::eip712_signable_message(who);
+	fn verify_eip712_signature(
+		who: &T::AccountId,
+		sig: &[u8; 65],
+		purpose: SignaturePurpose,
+		nonce: u32,
+	) -> Option<H160> {
+		let msg = Self::eip712_signable_message(who, purpose, nonce);
 		let msg_hash = keccak_256(msg.as_slice());
 
 		recover_signer(sig, &msg_hash)
 	}
 
 	// Eip-712 message to be signed
-	fn eip712_signable_message(who: &T::AccountId) -> Vec<u8> {
+	fn eip712_signable_message(who: &T::AccountId, purpose: SignaturePurpose, nonce: u32) -> Vec<u8> {
 		let domain_separator = Self::evm_account_domain_separator();
-		let payload_hash = Self::evm_account_payload_hash(who);
+		let payload_hash = Self::evm_account_payload_hash(who, purpose, nonce);
 
 		let mut msg = b"\x19\x01".to_vec();
 		msg.extend_from_slice(&domain_separator);
@@ -233,10 +508,11 @@ impl<T: Config> Pallet<T> {
 		msg
 	}
 
-	fn evm_account_payload_hash(who: &T::AccountId) -> [u8; 32] {
-		let tx_type_hash = keccak256!("Transaction(bytes substrateAddress)");
-		let mut tx_msg = tx_type_hash.to_vec();
+	fn evm_account_payload_hash(who: &T::AccountId, purpose: SignaturePurpose, nonce: u32) -> [u8; 32] {
+		let mut tx_msg = purpose.type_hash().to_vec();
+		tx_msg.extend_from_slice(&keccak_256(purpose.purpose()));
 		tx_msg.extend_from_slice(&keccak_256(&who.encode()));
+		tx_msg.extend_from_slice(&to_bytes(nonce));
 		keccak_256(tx_msg.as_slice())
 	}
 
@@ -251,6 +527,53 @@ impl<T: Config> Pallet<T> {
 		keccak_256(domain_seperator_msg.as_slice())
 	}
 
+	// Ensure `eth_address` is not used as a mapped or secondary address.
+	fn ensure_address_is_free(eth_address: EvmAddress) -> DispatchResult {
+		ensure!(
+			!Accounts::<T>::contains_key(eth_address) && !SecondaryAccounts::<T>::contains_key(eth_address),
+			Error::<T>::EthAddressHasMapped
+		);
+		Ok(())
+	}
+
+	// Ensure `eth_signature` is signed by `eth_address` for `purpose` with the current nonce
+	// of `who`, and use up the nonce.
+	fn ensure_signed_by(
+		who: &T::AccountId,
+		eth_address: EvmAddress,
+		eth_signature: &Eip712Signature,
+		purpose: SignaturePurpose,
+	) -> DispatchResult {
+		// recover evm address from signature
+		let nonce = Self::signature_nonces(who);
+		let address =
+			Self::verify_eip712_signature(who, eth_signature, purpose, nonce).ok_or(Error::<T>::BadSignature)?;
+		ensure!(eth_address == address, Error::<T>::InvalidSignature);
+
+		SignatureNonces::<T>::insert(who, nonce.wrapping_add(1));
+		Ok(())
+	}
+
+	// Ensure `eth_address` can be mapped to `who` and merge the balances of its padded
+	// account into `who`.
+	fn ensure_can_link(
+		who: &T::AccountId,
+		eth_address: EvmAddress,
+		eth_signature: &Eip712Signature,
+		purpose: SignaturePurpose,
+	) -> DispatchResult {
+		Self::ensure_address_is_free(eth_address)?;
+		Self::ensure_signed_by(who, eth_address, eth_signature, purpose)?;
+
+		// check if the evm padded address already exists
+		let account_id = T::AddressMapping::get_account_id(&eth_address);
+		if frame_system::Pallet::<T>::account_exists(&account_id) {
+			// merge balance from `evm padded address` to `origin`
+			T::TransferAll::transfer_all(&account_id, who)?;
+		}
+		Ok(())
+	}
+
 	fn do_claim_default_evm_address(who: T::AccountId) -> Result<EvmAddress, DispatchError> {
 		// ensure account_id has not been mapped
 		ensure!(!EvmAddresses::<T>::contains_key(&who), Error::<T>::AccountIdHasMapped);
@@ -351,6 +674,10 @@ impl<T: Config> OnKilledAccount<T::AccountId> for CallKillAccount<T> {
 			Accounts::<T>::remove(evm_addr);
 			EvmAddresses::<T>::remove(who);
 		}
+		PendingUnlinks::<T>::remove(who);
+		for evm_addr in SecondaryAddresses::<T>::take(who) {
+			SecondaryAccounts::<T>::remove(evm_addr);
+		}
 	}
 }
 
@@ -123,6 +123,9 @@ impl Config for Runtime {
 	type ChainId = ();
 	type AddressMapping = EvmAddressMapping<Runtime>;
 	type TransferAll = Currencies;
+	type UnlinkDelay = ConstU64<10>;
+	type MaxSecondaryAddresses = ConstU32<1>;
+	type MaxMergedCurrencies = ConstU32<4>;
 	type WeightInfo = ();
 }
 
@@ -24,7 +24,10 @@
 
 use super::*;
 use frame_support::{assert_noop, assert_ok};
-use mock::{alice, bob, EvmAccountsModule, ExtBuilder, Runtime, RuntimeEvent, RuntimeOrigin, System, ALICE, BOB};
+use mock::{
+	alice, bob, bob_account_id, Balances, EvmAccountsModule, ExtBuilder, Runtime, RuntimeEvent, RuntimeOrigin, System,
+	ALICE, BOB,
+};
 use std::str::FromStr;
 
 #[test]
@@ -33,7 +36,7 @@ fn claim_account_work() {
 		assert_ok!(EvmAccountsModule::claim_account(
 			RuntimeOrigin::signed(ALICE),
 			EvmAccountsModule::eth_address(&alice()),
-			EvmAccountsModule::eth_sign(&alice(), &ALICE)
+			EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::ClaimAccount)
 		));
 		System::assert_last_event(RuntimeEvent::EvmAccountsModule(crate::Event::ClaimAccount {
 			account_id: ALICE,
@@ -53,7 +56,7 @@ fn claim_account_should_not_work() {
 			EvmAccountsModule::claim_account(
 				RuntimeOrigin::signed(ALICE),
 				EvmAccountsModule::eth_address(&bob()),
-				EvmAccountsModule::eth_sign(&bob(), &BOB)
+				EvmAccountsModule::eth_sign(&bob(), &BOB, SignaturePurpose::ClaimAccount)
 			),
 			Error::<Runtime>::InvalidSignature
 		);
@@ -61,20 +64,20 @@ fn claim_account_should_not_work() {
 			EvmAccountsModule::claim_account(
 				RuntimeOrigin::signed(ALICE),
 				EvmAccountsModule::eth_address(&bob()),
-				EvmAccountsModule::eth_sign(&alice(), &ALICE)
+				EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::ClaimAccount)
 			),
 			Error::<Runtime>::InvalidSignature
 		);
 		assert_ok!(EvmAccountsModule::claim_account(
 			RuntimeOrigin::signed(ALICE),
 			EvmAccountsModule::eth_address(&alice()),
-			EvmAccountsModule::eth_sign(&alice(), &ALICE)
+			EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::ClaimAccount)
 		));
 		assert_noop!(
 			EvmAccountsModule::claim_account(
 				RuntimeOrigin::signed(ALICE),
 				EvmAccountsModule::eth_address(&alice()),
-				EvmAccountsModule::eth_sign(&alice(), &ALICE)
+				EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::ClaimAccount)
 			),
 			Error::<Runtime>::AccountIdHasMapped
 		);
@@ -82,7 +85,7 @@ fn claim_account_should_not_work() {
 			EvmAccountsModule::claim_account(
 				RuntimeOrigin::signed(BOB),
 				EvmAccountsModule::eth_address(&alice()),
-				EvmAccountsModule::eth_sign(&alice(), &BOB)
+				EvmAccountsModule::eth_sign(&alice(), &BOB, SignaturePurpose::ClaimAccount)
 			),
 			Error::<Runtime>::EthAddressHasMapped
 		);
@@ -106,7 +109,7 @@ fn evm_get_account_id() {
 		assert_ok!(EvmAccountsModule::claim_account(
 			RuntimeOrigin::signed(ALICE),
 			EvmAccountsModule::eth_address(&alice()),
-			EvmAccountsModule::eth_sign(&alice(), &ALICE)
+			EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::ClaimAccount)
 		));
 
 		assert_eq!(EvmAddressMapping::<Runtime>::get_account_id(&evm_account), ALICE);
@@ -150,7 +153,7 @@ fn account_to_evm() {
 		assert_ok!(EvmAccountsModule::claim_account(
 			RuntimeOrigin::signed(ALICE),
 			alice_evm_account,
-			EvmAccountsModule::eth_sign(&alice(), &ALICE)
+			EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::ClaimAccount)
 		));
 
 		assert_eq!(EvmAddressMapping::<Runtime>::get_account_id(&alice_evm_account), ALICE);
@@ -199,9 +202,292 @@ fn account_to_evm_with_create_default() {
 			EvmAccountsModule::claim_account(
 				RuntimeOrigin::signed(ALICE),
 				alice_evm_account,
-				EvmAccountsModule::eth_sign(&alice(), &ALICE)
+				EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::ClaimAccount)
 			),
 			Error::<Runtime>::AccountIdHasMapped
 		);
 	});
 }
+
+#[test]
+fn unlink_account_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let alice_evm_account = EvmAccountsModule::eth_address(&alice());
+		assert_noop!(
+			EvmAccountsModule::unlink_account(RuntimeOrigin::signed(ALICE)),
+			Error::<Runtime>::AccountIdNotMapped
+		);
+		assert_ok!(EvmAccountsModule::claim_account(
+			RuntimeOrigin::signed(ALICE),
+			alice_evm_account,
+			EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::ClaimAccount)
+		));
+
+		assert_noop!(
+			EvmAccountsModule::execute_unlink(RuntimeOrigin::signed(ALICE)),
+			Error::<Runtime>::UnlinkNotRequested
+		);
+		assert_ok!(EvmAccountsModule::unlink_account(RuntimeOrigin::signed(ALICE)));
+		System::assert_last_event(RuntimeEvent::EvmAccountsModule(crate::Event::UnlinkRequested {
+			account_id: ALICE,
+			evm_address: alice_evm_account,
+			unlock_at: 11,
+		}));
+		assert_eq!(EvmAccountsModule::pending_unlinks(ALICE), Some(11));
+		assert_noop!(
+			EvmAccountsModule::unlink_account(RuntimeOrigin::signed(ALICE)),
+			Error::<Runtime>::UnlinkAlreadyRequested
+		);
+
+		// the mapping is kept during the delay
+		System::set_block_number(10);
+		assert_noop!(
+			EvmAccountsModule::execute_unlink(RuntimeOrigin::signed(ALICE)),
+			Error::<Runtime>::UnlinkDelayNotPassed
+		);
+		assert_eq!(EvmAddressMapping::<Runtime>::get_account_id(&alice_evm_account), ALICE);
+
+		assert_ok!(EvmAccountsModule::cancel_unlink(RuntimeOrigin::signed(ALICE)));
+		System::assert_last_event(RuntimeEvent::EvmAccountsModule(crate::Event::UnlinkCancelled {
+			account_id: ALICE,
+		}));
+		assert_eq!(EvmAccountsModule::pending_unlinks(ALICE), None);
+		assert_noop!(
+			EvmAccountsModule::cancel_unlink(RuntimeOrigin::signed(ALICE)),
+			Error::<Runtime>::UnlinkNotRequested
+		);
+
+		assert_ok!(EvmAccountsModule::unlink_account(RuntimeOrigin::signed(ALICE)));
+		System::set_block_number(20);
+		assert_ok!(EvmAccountsModule::execute_unlink(RuntimeOrigin::signed(ALICE)));
+		System::assert_last_event(RuntimeEvent::EvmAccountsModule(crate::Event::AccountUnlinked {
+			account_id: ALICE,
+			evm_address: alice_evm_account,
+		}));
+		assert_eq!(EvmAccountsModule::pending_unlinks(ALICE), None);
+		assert_eq!(EvmAccountsModule::evm_addresses(ALICE), None);
+		assert_eq!(EvmAccountsModule::accounts(alice_evm_account), None);
+		assert_ne!(EvmAddressMapping::<Runtime>::get_account_id(&alice_evm_account), ALICE);
+
+		// the address can be linked again
+		assert_ok!(EvmAccountsModule::claim_account(
+			RuntimeOrigin::signed(BOB),
+			alice_evm_account,
+			EvmAccountsModule::eth_sign(&alice(), &BOB, SignaturePurpose::ClaimAccount)
+		));
+		assert_eq!(EvmAddressMapping::<Runtime>::get_account_id(&alice_evm_account), BOB);
+	});
+}
+
+#[test]
+fn rotate_evm_address_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let alice_evm_account = EvmAccountsModule::eth_address(&alice());
+		let bob_evm_account = EvmAccountsModule::eth_address(&bob());
+		assert_noop!(
+			EvmAccountsModule::rotate_evm_address(
+				RuntimeOrigin::signed(ALICE),
+				bob_evm_account,
+				EvmAccountsModule::eth_sign(&bob(), &ALICE, SignaturePurpose::RotateEvmAddress)
+			),
+			Error::<Runtime>::AccountIdNotMapped
+		);
+		assert_ok!(EvmAccountsModule::claim_account(
+			RuntimeOrigin::signed(ALICE),
+			alice_evm_account,
+			EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::ClaimAccount)
+		));
+		assert_ok!(EvmAccountsModule::unlink_account(RuntimeOrigin::signed(ALICE)));
+
+		assert_noop!(
+			EvmAccountsModule::rotate_evm_address(
+				RuntimeOrigin::signed(ALICE),
+				bob_evm_account,
+				EvmAccountsModule::eth_sign(&bob(), &BOB, SignaturePurpose::RotateEvmAddress)
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_noop!(
+			EvmAccountsModule::rotate_evm_address(
+				RuntimeOrigin::signed(ALICE),
+				alice_evm_account,
+				EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::RotateEvmAddress)
+			),
+			Error::<Runtime>::EthAddressHasMapped
+		);
+
+		assert_eq!(Balances::free_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(bob_account_id()), 100000);
+		assert_ok!(EvmAccountsModule::rotate_evm_address(
+			RuntimeOrigin::signed(ALICE),
+			bob_evm_account,
+			EvmAccountsModule::eth_sign(&bob(), &ALICE, SignaturePurpose::RotateEvmAddress)
+		));
+		System::assert_last_event(RuntimeEvent::EvmAccountsModule(crate::Event::EvmAddressRotated {
+			account_id: ALICE,
+			old_evm_address: alice_evm_account,
+			new_evm_address: bob_evm_account,
+		}));
+
+		// balances of the padded account are merged and the unlink request is cancelled
+		assert_eq!(Balances::free_balance(ALICE), 100000);
+		assert_eq!(Balances::free_balance(bob_account_id()), 0);
+		assert_eq!(EvmAccountsModule::pending_unlinks(ALICE), None);
+		assert_eq!(EvmAccountsModule::evm_addresses(ALICE), Some(bob_evm_account));
+		assert_eq!(EvmAccountsModule::accounts(alice_evm_account), None);
+		assert_eq!(EvmAddressMapping::<Runtime>::get_account_id(&bob_evm_account), ALICE);
+	});
+}
+
+#[test]
+fn secondary_addresses_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let alice_evm_account = EvmAccountsModule::eth_address(&alice());
+		let bob_evm_account = EvmAccountsModule::eth_address(&bob());
+		let default_evm_account = EvmAddressMapping::<Runtime>::get_default_evm_address(&ALICE);
+
+		assert_noop!(
+			EvmAccountsModule::add_secondary_address(
+				RuntimeOrigin::signed(ALICE),
+				bob_evm_account,
+				EvmAccountsModule::eth_sign(&bob(), &BOB, SignaturePurpose::AddSecondaryAddress)
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_ok!(EvmAccountsModule::add_secondary_address(
+			RuntimeOrigin::signed(ALICE),
+			bob_evm_account,
+			EvmAccountsModule::eth_sign(&bob(), &ALICE, SignaturePurpose::AddSecondaryAddress)
+		));
+		System::assert_last_event(RuntimeEvent::EvmAccountsModule(crate::Event::SecondaryAddressAdded {
+			account_id: ALICE,
+			evm_address: bob_evm_account,
+		}));
+		assert_eq!(EvmAccountsModule::secondary_addresses(ALICE).to_vec(), vec![bob_evm_account]);
+		assert_eq!(EvmAccountsModule::secondary_accounts(bob_evm_account), Some(ALICE));
+		assert_noop!(
+			EvmAccountsModule::add_secondary_address(
+				RuntimeOrigin::signed(ALICE),
+				alice_evm_account,
+				EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::AddSecondaryAddress)
+			),
+			Error::<Runtime>::TooManySecondaryAddresses
+		);
+
+		// secondary addresses are not mapped to the account
+		assert_eq!(
+			EvmAddressMapping::<Runtime>::get_account_id(&bob_evm_account),
+			bob_account_id()
+		);
+		assert_eq!(EvmAddressMapping::<Runtime>::get_evm_address(&ALICE), None);
+
+		// secondary addresses cannot be claimed or added again
+		assert_noop!(
+			EvmAccountsModule::claim_account(
+				RuntimeOrigin::signed(BOB),
+				bob_evm_account,
+				EvmAccountsModule::eth_sign(&bob(), &BOB, SignaturePurpose::ClaimAccount)
+			),
+			Error::<Runtime>::EthAddressHasMapped
+		);
+		assert_noop!(
+			EvmAccountsModule::add_secondary_address(
+				RuntimeOrigin::signed(ALICE),
+				bob_evm_account,
+				EvmAccountsModule::eth_sign(&bob(), &ALICE, SignaturePurpose::AddSecondaryAddress)
+			),
+			Error::<Runtime>::EthAddressHasMapped
+		);
+
+		assert_ok!(EvmAccountsModule::claim_account(
+			RuntimeOrigin::signed(BOB),
+			alice_evm_account,
+			EvmAccountsModule::eth_sign(&alice(), &BOB, SignaturePurpose::ClaimAccount)
+		));
+		assert_noop!(
+			EvmAccountsModule::add_secondary_address(
+				RuntimeOrigin::signed(ALICE),
+				alice_evm_account,
+				EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::AddSecondaryAddress)
+			),
+			Error::<Runtime>::EthAddressHasMapped
+		);
+
+		assert_noop!(
+			EvmAccountsModule::remove_secondary_address(RuntimeOrigin::signed(ALICE), default_evm_account),
+			Error::<Runtime>::SecondaryAddressNotFound
+		);
+		assert_noop!(
+			EvmAccountsModule::remove_secondary_address(RuntimeOrigin::signed(BOB), bob_evm_account),
+			Error::<Runtime>::SecondaryAddressNotFound
+		);
+		assert_ok!(EvmAccountsModule::remove_secondary_address(
+			RuntimeOrigin::signed(ALICE),
+			bob_evm_account
+		));
+		System::assert_last_event(RuntimeEvent::EvmAccountsModule(crate::Event::SecondaryAddressRemoved {
+			account_id: ALICE,
+			evm_address: bob_evm_account,
+		}));
+		assert!(!SecondaryAddresses::<Runtime>::contains_key(ALICE));
+		assert_eq!(EvmAccountsModule::secondary_accounts(bob_evm_account), None);
+	});
+}
+
+#[test]
+fn signatures_cannot_be_replayed() {
+	ExtBuilder::default().build().execute_with(|| {
+		let alice_evm_account = EvmAccountsModule::eth_address(&alice());
+		let bob_evm_account = EvmAccountsModule::eth_address(&bob());
+
+		// a signature is only valid for the call it is made for
+		assert_noop!(
+			EvmAccountsModule::add_secondary_address(
+				RuntimeOrigin::signed(ALICE),
+				alice_evm_account,
+				EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::ClaimAccount)
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_noop!(
+			EvmAccountsModule::claim_account(
+				RuntimeOrigin::signed(ALICE),
+				alice_evm_account,
+				EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::AddSecondaryAddress)
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+
+		let signature = EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::ClaimAccount);
+		assert_eq!(EvmAccountsModule::signature_nonces(ALICE), 0);
+		assert_ok!(EvmAccountsModule::claim_account(
+			RuntimeOrigin::signed(ALICE),
+			alice_evm_account,
+			signature
+		));
+		assert_eq!(EvmAccountsModule::signature_nonces(ALICE), 1);
+		assert_noop!(
+			EvmAccountsModule::rotate_evm_address(
+				RuntimeOrigin::signed(ALICE),
+				bob_evm_account,
+				EvmAccountsModule::eth_sign(&bob(), &ALICE, SignaturePurpose::ClaimAccount)
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+
+		// the signature cannot be used again once the address is unlinked
+		assert_ok!(EvmAccountsModule::unlink_account(RuntimeOrigin::signed(ALICE)));
+		System::set_block_number(20);
+		assert_ok!(EvmAccountsModule::execute_unlink(RuntimeOrigin::signed(ALICE)));
+		assert_noop!(
+			EvmAccountsModule::claim_account(RuntimeOrigin::signed(ALICE), alice_evm_account, signature),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_ok!(EvmAccountsModule::claim_account(
+			RuntimeOrigin::signed(ALICE),
+			alice_evm_account,
+			EvmAccountsModule::eth_sign(&alice(), &ALICE, SignaturePurpose::ClaimAccount)
+		));
+		assert_eq!(EvmAccountsModule::signature_nonces(ALICE), 2);
+	});
+}
@@ -50,35 +50,113 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for module_unified_accounts.
 pub trait WeightInfo {
-	fn claim_account() -> Weight;
+	fn claim_account(c: u32, ) -> Weight;
 	fn claim_default_account() -> Weight;
+	fn unlink_account() -> Weight;
+	fn cancel_unlink() -> Weight;
+	fn execute_unlink() -> Weight;
+	fn rotate_evm_address(c: u32, ) -> Weight;
+	fn add_secondary_address() -> Weight;
+	fn remove_secondary_address() -> Weight;
 }
 
 /// Weights for module_unified_accounts using the Setheum node and recommended hardware.
 pub struct SetheumWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SetheumWeight<T> {
-	fn claim_account() -> Weight {
+	fn claim_account(c: u32, ) -> Weight {
 		Weight::from_parts(340_000_000, 0)
-			.saturating_add(T::DbWeight::get().reads(3 as u64))
-			.saturating_add(T::DbWeight::get().writes(2 as u64))
+			.saturating_add(Weight::from_parts(45_000_000, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(c as u64)))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+			.saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(c as u64)))
 	}
 	fn claim_default_account() -> Weight {
 		Weight::from_parts(19_000_000, 0)
 			.saturating_add(T::DbWeight::get().reads(1 as u64))
 			.saturating_add(T::DbWeight::get().writes(2 as u64))
 	}
+	fn unlink_account() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn cancel_unlink() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn execute_unlink() -> Weight {
+		Weight::from_parts(24_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	fn rotate_evm_address(c: u32, ) -> Weight {
+		Weight::from_parts(345_000_000, 0)
+			.saturating_add(Weight::from_parts(45_000_000, 0).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(c as u64)))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+			.saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(c as u64)))
+	}
+	fn add_secondary_address() -> Weight {
+		Weight::from_parts(320_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	fn remove_secondary_address() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	fn claim_account() -> Weight {
+	fn claim_account(c: u32, ) -> Weight {
 		Weight::from_parts(340_000_000, 0)
-			.saturating_add(RocksDbWeight::get().reads(3 as u64))
-			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+			.saturating_add(Weight::from_parts(45_000_000, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(c as u64)))
 	}
 	fn claim_default_account() -> Weight {
 		Weight::from_parts(19_000_000, 0)
 			.saturating_add(RocksDbWeight::get().reads(1 as u64))
 			.saturating_add(RocksDbWeight::get().writes(2 as u64))
 	}
+	fn unlink_account() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn cancel_unlink() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn execute_unlink() -> Weight {
+		Weight::from_parts(24_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn rotate_evm_address(c: u32, ) -> Weight {
+		Weight::from_parts(345_000_000, 0)
+			.saturating_add(Weight::from_parts(45_000_000, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(c as u64)))
+	}
+	fn add_secondary_address() -> Weight {
+		Weight::from_parts(320_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn remove_secondary_address() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
 }
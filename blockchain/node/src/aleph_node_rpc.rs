@@ -0,0 +1,310 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC API of the Aleph finality gadget.
+
+use std::{convert::TryFrom, sync::Arc};
+
+use finality_aleph::{
+	AlephJustification, BlockId, ExternalAddresses, Justification,
+	JustificationNotification as AlephJustificationNotification, JustificationNotifications, JustificationTranslator,
+	PinnedBranch, SessionPeriod, TranslateError,
+};
+use futures::{channel::mpsc::UnboundedSender, FutureExt, SinkExt, StreamExt};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result as RpcResult};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::generic::BlockId as SubstrateBlockId;
+
+use setheum_primitives::aleph::{AlephSessionApi, AuthorityId, AuthoritySignature};
+use setheum_runtime::{opaque::Block, BlockNumber, Hash};
+
+use sc_rpc::SubscriptionTaskExecutor;
+use sc_rpc_api::DenyUnsafe;
+
+/// Base code for all Aleph node RPC errors.
+const BASE_ERROR: i64 = 2000;
+/// The justification passed to emergency finalization is not a valid signature.
+const MALFORMED_JUSTIFICATION_ERROR: i64 = BASE_ERROR + 1;
+/// The block to be finalized is not known to the node.
+const UNKNOWN_BLOCK_ERROR: i64 = BASE_ERROR + 2;
+/// The justification could not be passed on to the finality gadget.
+const SUBMISSION_ERROR: i64 = BASE_ERROR + 3;
+/// The chain state could not be read.
+const BACKEND_ERROR: i64 = BASE_ERROR + 4;
+
+/// Aleph node RPC errors.
+#[derive(Debug)]
+pub enum Error {
+	/// The justification is not a valid emergency signature.
+	MalformedJustification,
+	/// The block to be finalized is not known.
+	UnknownBlock(BlockId),
+	/// The finality gadget is not accepting justifications.
+	SubmissionFailed,
+	/// Reading the chain state failed.
+	Backend(String),
+}
+
+impl From<Error> for RpcError {
+	fn from(error: Error) -> Self {
+		let (code, message) = match error {
+			Error::MalformedJustification => (
+				MALFORMED_JUSTIFICATION_ERROR,
+				"Justification argument is not a valid emergency signature.".to_string(),
+			),
+			Error::UnknownBlock(block) => (UNKNOWN_BLOCK_ERROR, format!("Block {:?} is not known.", block)),
+			Error::SubmissionFailed => (
+				SUBMISSION_ERROR,
+				"Failed to submit the justification to the finality gadget.".to_string(),
+			),
+			Error::Backend(e) => (BACKEND_ERROR, format!("Failed to read the chain state: {}", e)),
+		};
+		RpcError {
+			code: ErrorCode::ServerError(code),
+			message,
+			data: None,
+		}
+	}
+}
+
+/// Finality status of the node.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalityStatus {
+	/// Hash of the best block.
+	pub best_hash: Hash,
+	/// Number of the best block.
+	pub best_number: BlockNumber,
+	/// Hash of the highest finalized block.
+	pub finalized_hash: Hash,
+	/// Number of the highest finalized block.
+	pub finalized_number: BlockNumber,
+	/// How many blocks the finalized block is behind the best one.
+	pub lag: BlockNumber,
+	/// Whether the lag exceeds the alert threshold of the node.
+	pub lagging: bool,
+	/// The session of the best block.
+	pub session: u32,
+	/// The finality committee of the session.
+	pub committee: Vec<AuthorityId>,
+}
+
+/// Justification of a finalized block, as sent to subscribers.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JustificationNotification {
+	/// Hash of the finalized block.
+	pub hash: Hash,
+	/// Number of the finalized block.
+	pub number: BlockNumber,
+	/// Versioned SCALE encoding of the justification, as stored in the database.
+	pub justification: Vec<u8>,
+}
+
+impl From<AlephJustificationNotification> for JustificationNotification {
+	fn from(notification: AlephJustificationNotification) -> Self {
+		let AlephJustificationNotification { block, justification } = notification;
+		let (_, justification) = sp_runtime::Justification::from(justification);
+		JustificationNotification {
+			hash: block.hash(),
+			number: block.number(),
+			justification,
+		}
+	}
+}
+
+/// Aleph node RPC methods.
+#[rpc]
+pub trait AlephNodeApi {
+	/// RPC metadata
+	type Metadata;
+
+	/// Returns the best and finalized blocks, the lag between them, and the current session with its
+	/// finality committee.
+	#[rpc(name = "alephNode_finalityStatus")]
+	fn finality_status(&self) -> RpcResult<FinalityStatus>;
+
+	/// Finalizes the block with the given hash and number using the emergency finalizer signature.
+	#[rpc(name = "alephNode_emergencyFinalize")]
+	fn emergency_finalize(&self, justification: Vec<u8>, hash: Hash, number: BlockNumber) -> RpcResult<()>;
+
+	/// Makes the node propose the branch ending in the block with the given hash and number.
+	#[rpc(name = "alephNode_pinBranch")]
+	fn pin_branch(&self, hash: Hash, number: BlockNumber) -> RpcResult<()>;
+
+	/// Makes the node go back to proposing according to its configured policy.
+	#[rpc(name = "alephNode_unpinBranch")]
+	fn unpin_branch(&self) -> RpcResult<()>;
+
+	/// Replaces the external addresses of the validator network, other validators learn about them
+	/// on the next address refresh.
+	#[rpc(name = "alephNode_setExternalAddresses")]
+	fn set_external_addresses(&self, addresses: Vec<String>) -> RpcResult<()>;
+
+	/// Subscribe to justifications of blocks finalized by the node.
+	#[pubsub(
+		subscription = "alephNode_justifications",
+		subscribe,
+		name = "alephNode_subscribeJustifications"
+	)]
+	fn subscribe_justifications(&self, metadata: Self::Metadata, subscriber: Subscriber<JustificationNotification>);
+
+	/// Unsubscribe from justifications.
+	#[pubsub(
+		subscription = "alephNode_justifications",
+		unsubscribe,
+		name = "alephNode_unsubscribeJustifications"
+	)]
+	fn unsubscribe_justifications(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> RpcResult<bool>;
+}
+
+/// Dependencies of the Aleph node RPC, shared with the finality gadget.
+#[derive(Clone)]
+pub struct AlephDeps {
+	/// Notifications about justifications of finalized blocks.
+	pub justification_notifications: JustificationNotifications,
+	/// Translates emergency justifications into ones the sync understands.
+	pub justification_translator: JustificationTranslator,
+	/// Submits justifications to be verified and finalized.
+	pub justifications_for_sync: UnboundedSender<Justification>,
+	/// The length of an Aleph session.
+	pub session_period: SessionPeriod,
+	/// Finality lag above which the node reports that finality is lagging.
+	pub max_finality_lag: BlockNumber,
+	/// Branch pinned by the operator, followed by the proposal policy of the finality gadget.
+	pub pinned_branch: PinnedBranch,
+	/// External addresses of the validator network of the finality gadget.
+	pub external_addresses: ExternalAddresses,
+}
+
+/// Implementation of the Aleph node RPC.
+pub struct AlephNode<C> {
+	client: Arc<C>,
+	deps: AlephDeps,
+	manager: SubscriptionManager,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<C> AlephNode<C> {
+	pub fn new(client: Arc<C>, deps: AlephDeps, executor: SubscriptionTaskExecutor, deny_unsafe: DenyUnsafe) -> Self {
+		AlephNode {
+			client,
+			deps,
+			manager: SubscriptionManager::new(Arc::new(executor)),
+			deny_unsafe,
+		}
+	}
+}
+
+impl<C> AlephNodeApi for AlephNode<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AlephSessionApi<Block>,
+{
+	type Metadata = sc_rpc::Metadata;
+
+	fn finality_status(&self) -> RpcResult<FinalityStatus> {
+		let info = self.client.info();
+		let lag = info.best_number.saturating_sub(info.finalized_number);
+		let committee = self
+			.client
+			.runtime_api()
+			.authorities(&SubstrateBlockId::Hash(info.best_hash))
+			.map_err(|e| Error::Backend(format!("{:?}", e)))?;
+		let lagging = lag > self.deps.max_finality_lag;
+		if lagging {
+			warn!(
+				target: "aleph-rpc",
+				"Finality is lagging {} blocks behind the best block #{}.", lag, info.best_number
+			);
+		}
+		Ok(FinalityStatus {
+			best_hash: info.best_hash,
+			best_number: info.best_number,
+			finalized_hash: info.finalized_hash,
+			finalized_number: info.finalized_number,
+			lag,
+			lagging,
+			session: info.best_number / self.deps.session_period.0,
+			committee,
+		})
+	}
+
+	fn emergency_finalize(&self, justification: Vec<u8>, hash: Hash, number: BlockNumber) -> RpcResult<()> {
+		self.deny_unsafe.check_if_safe()?;
+		let signature =
+			AuthoritySignature::try_from(justification.as_slice()).map_err(|_| Error::MalformedJustification)?;
+		let block = BlockId::new(hash, number);
+		let justification = self
+			.deps
+			.justification_translator
+			.translate(AlephJustification::EmergencySignature(signature), block.clone())
+			.map_err(|e| match e {
+				TranslateError::NoBlock => Error::UnknownBlock(block),
+				e => Error::Backend(e.to_string()),
+			})?;
+		self.deps
+			.justifications_for_sync
+			.unbounded_send(justification)
+			.map_err(|_| Error::SubmissionFailed)?;
+		Ok(())
+	}
+
+	fn pin_branch(&self, hash: Hash, number: BlockNumber) -> RpcResult<()> {
+		self.deny_unsafe.check_if_safe()?;
+		// The block does not have to be imported yet, nothing is proposed until it is.
+		self.deps.pinned_branch.pin(BlockId::new(hash, number));
+		Ok(())
+	}
+
+	fn unpin_branch(&self) -> RpcResult<()> {
+		self.deny_unsafe.check_if_safe()?;
+		self.deps.pinned_branch.unpin();
+		Ok(())
+	}
+
+	fn set_external_addresses(&self, addresses: Vec<String>) -> RpcResult<()> {
+		self.deny_unsafe.check_if_safe()?;
+		self.deps.external_addresses.set(addresses);
+		Ok(())
+	}
+
+	fn subscribe_justifications(&self, _metadata: Self::Metadata, subscriber: Subscriber<JustificationNotification>) {
+		let stream = self
+			.deps
+			.justification_notifications
+			.subscribe()
+			.map(|notification| Ok(Ok::<_, RpcError>(JustificationNotification::from(notification))));
+		self.manager.add(subscriber, |sink| {
+			stream
+				.forward(sink.sink_map_err(|e| warn!("Error sending notifications: {:?}", e)))
+				.map(|_| ())
+		});
+	}
+
+	fn unsubscribe_justifications(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> RpcResult<bool> {
+		Ok(self.manager.cancel(id))
+	}
+}
@@ -24,7 +24,7 @@ use setheum_runtime::{
 	AccountId,
 	//
 	BabeConfig, BalancesConfig, GenesisConfig, SystemConfig,
-	SS58Prefix, opaque::SessionKeys, get_all_module_accounts,
+	SS58Prefix, opaque::SessionKeys, get_all_module_accounts, aleph_key_of,
 	ImOnlineId, IndicesConfig, SessionConfig, StakingConfig,
 	AuthorityDiscoveryId, EVMConfig, AuthorityDiscoveryConfig,
 	StakerStatus,  VestingConfig,
@@ -89,7 +89,8 @@ fn get_session_keys(
 	im_online: ImOnlineId,
 	authority_discovery: AuthorityDiscoveryId,
 	) -> SessionKeys {
-	SessionKeys { babe, grandpa, im_online, authority_discovery }
+	let aleph = aleph_key_of(&grandpa);
+	SessionKeys { babe, grandpa, im_online, authority_discovery, aleph }
 }
 
 /// Helper function to generate a crypto pair from seed
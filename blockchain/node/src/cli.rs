@@ -20,6 +20,7 @@
 
 use structopt::StructOpt;
 use sc_cli::RunCmd;
+use crate::evm_verification_rpc::SolcRelease;
 
 
 #[derive(Debug, StructOpt)]
@@ -29,6 +30,11 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	/// Allow a solc release to verify EVM contracts, given as `<version>=<sha256>`. The binary
+	/// is `solc/solc-v<version>` next to the node binary.
+	#[structopt(long = "evm-verification-solc", value_name = "VERSION=SHA256")]
+	pub evm_verification_solc: Vec<SolcRelease>,
 }
 
 #[derive(Debug, StructOpt)]
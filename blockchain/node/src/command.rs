@@ -138,10 +138,11 @@ pub fn run() -> sc_cli::Result<()> {
 		},
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
+			let solc_releases = cli.evm_verification_solc.clone();
 			runner.run_node_until_exit(|config| async move {
 				match config.role {
 					Role::Light => service::new_light(config),
-					_ => service::new_full(config),
+					_ => service::new_full(config, solc_releases),
 				}.map_err(sc_cli::Error::Service)
 			})
 		}
@@ -0,0 +1,1329 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Ethereum compatible `eth_*`, `net_*` and `web3_*` RPC for module-evm.
+//!
+//! Substrate blocks are served as Ethereum blocks whose transactions are the extrinsics that
+//! executed the EVM. Transactions built from a raw Ethereum transaction by
+//! `eth_sendRawTransaction` are identified by the hash of the raw transaction, rebuilt from
+//! the signature and the message held by the extrinsic, the other ones by their extrinsic hash.
+
+use std::sync::Arc;
+
+use futures::{future, FutureExt};
+use jsonrpc_core::{BoxFuture, Error as RpcError, ErrorCode, Result as RpcResult};
+use jsonrpc_derive::rpc;
+use sc_client_api::BlockBackend;
+use sc_transaction_pool_api::{TransactionPool, TransactionSource};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::keccak_256, Bytes, Get, H160, H256, U256};
+use sp_runtime::{
+	codec::{Compact, Decode, Encode},
+	generic::BlockId,
+	traits::{BlakeTwo256, Block as BlockT, Hash as HashT, Header as HeaderT},
+};
+
+pub use module_evm_rpc_runtime_api::EthereumRuntimeRPCApi;
+use module_evm_rpc_runtime_api::EVMRuntimeRPCApi;
+use setheum_primitives::{
+	convert_decimals_from_evm, convert_decimals_to_evm,
+	evm::{decode_gas_price, encode_gas_limit, AccessListItem, EstimateResourcesRequest, EvmReceipt, ExitReason, Log},
+	signature::SetheumMultiSignature,
+	unchecked_extrinsic::EthereumTransaction,
+};
+use setheum_runtime::{opaque::Block, Address, Balance, BlockNumber, ChainId, Hash, Nonce, UncheckedExtrinsic};
+
+use crate::evm_tracing_rpc::{decode_revert_reason, exit_error};
+
+/// Error code used by Ethereum clients for reverted calls, the revert data is set as `data`.
+const REVERT_ERROR: i64 = 3;
+/// Error code used by Ethereum clients for every other server error.
+const SERVER_ERROR: i64 = -32000;
+
+/// Most blocks `eth_getLogs` scans in one request.
+const MAX_LOGS_BLOCK_RANGE: u32 = 1024;
+/// Most blocks scanned back from the best block to find a transaction by hash.
+pub(crate) const MAX_TRANSACTION_LOOKUP_BLOCKS: u32 = 512;
+/// Blocks for which the gas price returned by `eth_gasPrice` keeps a transaction valid.
+const GAS_PRICE_VALIDITY: u32 = 100;
+/// Most blocks `eth_feeHistory` returns.
+const MAX_FEE_HISTORY_BLOCKS: u32 = 1024;
+/// Blocks sampled by `eth_maxPriorityFeePerGas`.
+const PRIORITY_FEE_SAMPLE_BLOCKS: u32 = 20;
+/// Percentile of the gas used in a block whose priority fee `eth_maxPriorityFeePerGas` samples.
+const PRIORITY_FEE_PERCENTILE: f64 = 60.0;
+
+/// Ethereum RPC errors.
+#[derive(Debug)]
+pub enum Error {
+	/// The block is not known.
+	UnknownBlock,
+	/// A parameter is out of range or inconsistent.
+	InvalidParams(String),
+	/// The call reverted with the given output.
+	Reverted(Vec<u8>),
+	/// The call failed.
+	Execution(String),
+	/// The raw transaction could not be converted or was rejected by the pool.
+	InvalidTransaction(String),
+	/// The client or a runtime api call failed.
+	Runtime(String),
+}
+
+impl From<Error> for RpcError {
+	fn from(error: Error) -> Self {
+		match error {
+			Error::UnknownBlock => RpcError {
+				code: ErrorCode::ServerError(SERVER_ERROR),
+				message: "header not found".to_string(),
+				data: None,
+			},
+			Error::InvalidParams(message) => RpcError::invalid_params(message),
+			Error::Reverted(output) => RpcError {
+				code: ErrorCode::ServerError(REVERT_ERROR),
+				message: match decode_revert_reason(&output) {
+					Some(reason) => format!("execution reverted: {}", reason),
+					None => "execution reverted".to_string(),
+				},
+				data: Some(serde_json::to_value(Bytes(output)).expect("bytes serialize to a string; qed")),
+			},
+			Error::Execution(message) => RpcError {
+				code: ErrorCode::ServerError(SERVER_ERROR),
+				message,
+				data: None,
+			},
+			Error::InvalidTransaction(message) => RpcError {
+				code: ErrorCode::ServerError(SERVER_ERROR),
+				message: format!("invalid transaction: {}", message),
+				data: None,
+			},
+			Error::Runtime(message) => RpcError {
+				code: ErrorCode::InternalError,
+				message,
+				data: None,
+			},
+		}
+	}
+}
+
+fn runtime_error<E: std::fmt::Debug>(error: E) -> Error {
+	Error::Runtime(format!("{:?}", error))
+}
+
+/// Block number parameter, a quantity or one of the Ethereum block tags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockNumberOrTag {
+	Number(BlockNumber),
+	Earliest,
+	Latest,
+	Finalized,
+	Pending,
+}
+
+impl<'de> Deserialize<'de> for BlockNumberOrTag {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let value = String::deserialize(deserializer)?;
+		match value.as_str() {
+			"earliest" => Ok(BlockNumberOrTag::Earliest),
+			"latest" => Ok(BlockNumberOrTag::Latest),
+			"safe" | "finalized" => Ok(BlockNumberOrTag::Finalized),
+			"pending" => Ok(BlockNumberOrTag::Pending),
+			number => BlockNumber::from_str_radix(number.trim_start_matches("0x"), 16)
+				.map(BlockNumberOrTag::Number)
+				.map_err(|e| D::Error::custom(format!("invalid block number {}: {}", number, e))),
+		}
+	}
+}
+
+/// A single value or a list of values, as accepted by the `eth_getLogs` filter.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum ValueOrArray<T> {
+	Value(T),
+	Array(Vec<T>),
+}
+
+impl<T: PartialEq> ValueOrArray<T> {
+	fn contains(&self, value: &T) -> bool {
+		match self {
+			ValueOrArray::Value(v) => v == value,
+			ValueOrArray::Array(values) => values.is_empty() || values.contains(value),
+		}
+	}
+}
+
+/// `eth_getLogs` filter.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Filter {
+	pub from_block: Option<BlockNumberOrTag>,
+	pub to_block: Option<BlockNumberOrTag>,
+	/// Excludes `from_block` and `to_block`.
+	pub block_hash: Option<H256>,
+	pub address: Option<ValueOrArray<H160>>,
+	/// Topics by position, `None` matches any topic.
+	pub topics: Option<Vec<Option<ValueOrArray<H256>>>>,
+}
+
+impl Filter {
+	fn matches(&self, log: &EthLog) -> bool {
+		if let Some(address) = &self.address {
+			if !address.contains(&log.address) {
+				return false;
+			}
+		}
+		self.topics.iter().flatten().enumerate().all(|(i, topics)| match topics {
+			None => true,
+			Some(topics) => log.topics.get(i).map_or(false, |topic| topics.contains(topic)),
+		})
+	}
+}
+
+/// Call for `eth_call` and `eth_estimateGas`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CallRequest {
+	pub from: Option<H160>,
+	/// `None` to create a contract.
+	pub to: Option<H160>,
+	/// Plain gas limit, defaults to the block gas limit.
+	pub gas: Option<U256>,
+	pub gas_price: Option<U256>,
+	/// In EVM decimals.
+	pub value: Option<U256>,
+	pub data: Option<Bytes>,
+	/// Alias of `data`.
+	pub input: Option<Bytes>,
+	pub access_list: Option<Vec<AccessListItem>>,
+}
+
+/// Ethereum log object.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthLog {
+	pub address: H160,
+	pub topics: Vec<H256>,
+	pub data: Bytes,
+	pub block_hash: H256,
+	pub block_number: U256,
+	pub transaction_hash: H256,
+	pub transaction_index: U256,
+	pub log_index: U256,
+	pub removed: bool,
+}
+
+/// Ethereum transaction object.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+	pub hash: H256,
+	pub nonce: U256,
+	pub block_hash: H256,
+	pub block_number: U256,
+	pub transaction_index: U256,
+	pub from: H160,
+	pub to: Option<H160>,
+	pub value: U256,
+	/// Effective gas price for EIP-1559 transactions.
+	pub gas_price: U256,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub max_fee_per_gas: Option<U256>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub max_priority_fee_per_gas: Option<U256>,
+	pub gas: U256,
+	pub input: Bytes,
+	pub chain_id: U256,
+	pub v: U256,
+	pub r: U256,
+	pub s: U256,
+	#[serde(rename = "type")]
+	pub transaction_type: U256,
+}
+
+/// Ethereum transaction receipt object.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Receipt {
+	pub transaction_hash: H256,
+	pub transaction_index: U256,
+	pub block_hash: H256,
+	pub block_number: U256,
+	pub from: H160,
+	pub to: Option<H160>,
+	pub cumulative_gas_used: U256,
+	pub gas_used: U256,
+	pub effective_gas_price: U256,
+	pub contract_address: Option<H160>,
+	pub logs: Vec<EthLog>,
+	pub logs_bloom: Bytes,
+	pub status: U256,
+	#[serde(rename = "type")]
+	pub transaction_type: U256,
+	/// Net storage used by the transaction, specific to Setheum.
+	pub storage_used: i32,
+}
+
+/// Transactions of a block, as hashes or full objects.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum BlockTransactions {
+	Hashes(Vec<H256>),
+	Full(Vec<Transaction>),
+}
+
+/// Ethereum block object.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthBlock {
+	pub hash: H256,
+	pub parent_hash: H256,
+	pub number: U256,
+	pub timestamp: U256,
+	pub miner: H160,
+	pub state_root: H256,
+	pub transactions_root: H256,
+	pub receipts_root: H256,
+	pub sha3_uncles: H256,
+	pub logs_bloom: Bytes,
+	pub difficulty: U256,
+	pub total_difficulty: U256,
+	pub extra_data: Bytes,
+	pub nonce: Bytes,
+	pub mix_hash: H256,
+	pub size: U256,
+	pub gas_limit: U256,
+	pub gas_used: U256,
+	pub base_fee_per_gas: U256,
+	pub uncles: Vec<H256>,
+	pub transactions: BlockTransactions,
+}
+
+/// `eth_feeHistory` response.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistory {
+	pub oldest_block: U256,
+	/// Base fees per gas of the blocks and of the block after the newest one.
+	pub base_fee_per_gas: Vec<U256>,
+	/// EVM gas used over the gas limit of the blocks.
+	pub gas_used_ratio: Vec<f64>,
+	/// Priority fees per gas at the requested percentiles of the gas used in the blocks.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub reward: Option<Vec<Vec<U256>>>,
+}
+
+/// Keccak hash of the RLP of an empty list, the uncles hash of every block.
+const EMPTY_UNCLES_HASH: [u8; 32] = hex_literal::hex!("1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347");
+
+/// Add `input` to an Ethereum logs bloom.
+fn accrue_bloom(bloom: &mut [u8; 256], input: &[u8]) {
+	let hash = keccak_256(input);
+	for i in [0usize, 2, 4] {
+		let bit = ((usize::from(hash[i]) << 8) | usize::from(hash[i + 1])) & 2047;
+		bloom[255 - bit / 8] |= 1 << (bit % 8);
+	}
+}
+
+fn logs_bloom<'a>(logs: impl IntoIterator<Item = &'a Log>) -> Bytes {
+	let mut bloom = [0u8; 256];
+	for log in logs {
+		accrue_bloom(&mut bloom, log.address.as_bytes());
+		for topic in log.topics.iter() {
+			accrue_bloom(&mut bloom, topic.as_bytes());
+		}
+	}
+	bloom.to_vec().into()
+}
+
+/// A block with the EVM transactions it contains.
+struct BlockData {
+	hash: Hash,
+	header: <Block as BlockT>::Header,
+	extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+	/// One receipt per extrinsic that executed the EVM, merging the executions of an
+	/// extrinsic that runs several.
+	transactions: Vec<EvmReceipt>,
+	timestamp: u64,
+}
+
+impl BlockData {
+	fn number(&self) -> U256 {
+		(*self.header.number()).into()
+	}
+
+	fn transaction_hash(&self, receipt: &EvmReceipt) -> H256 {
+		transaction_hash(&self.extrinsics[receipt.extrinsic_index as usize])
+	}
+
+	fn gas_used(&self) -> u64 {
+		self.transactions
+			.iter()
+			.fold(0u64, |gas, receipt| gas.saturating_add(receipt.used_gas))
+	}
+
+	fn extrinsic(&self, receipt: &EvmReceipt) -> Result<UncheckedExtrinsic, Error> {
+		UncheckedExtrinsic::decode(&mut &*self.extrinsics[receipt.extrinsic_index as usize].encode())
+			.map_err(runtime_error)
+	}
+}
+
+/// The Ethereum transaction an `eth_call_v2` extrinsic was built from by
+/// `eth_sendRawTransaction`, rebuilt from its signature and message.
+fn ethereum_transaction(extrinsic: &<Block as BlockT>::Extrinsic) -> Option<EthereumTransaction> {
+	let extrinsic = UncheckedExtrinsic::decode(&mut &*extrinsic.encode()).ok()?;
+	let (action, input, value, gas_price, gas_limit, access_list) = match extrinsic.function {
+		setheum_runtime::Call::EVM(module_evm::Call::eth_call_v2(action, input, value, gas_price, gas_limit, access_list)) => {
+			(action, input, value, gas_price, gas_limit, access_list)
+		}
+		_ => return None,
+	};
+	let (address, signature, extra) = extrinsic.signature?;
+	let signer = match address {
+		Address::Address20(address) => address.into(),
+		_ => return None,
+	};
+	// `CheckNonce` only exposes its nonce through its encoding
+	let nonce = Compact::<Nonce>::decode(&mut &*extra.4.encode()).ok()?.0;
+	let max_priority_fee_per_gas = match signature {
+		SetheumMultiSignature::Eip1559(_) => {
+			// the tip is `max_priority_fee_per_gas * gas_limit`, in native decimals
+			let tip = extra.6 .0;
+			Some(convert_decimals_to_evm(tip.checked_div(gas_limit.into())?).try_into().ok()?)
+		}
+		_ => None,
+	};
+
+	Some(EthereumTransaction {
+		signer,
+		signature,
+		chain_id: ChainId::get(),
+		nonce,
+		max_priority_fee_per_gas,
+		action,
+		input,
+		value,
+		gas_price,
+		gas_limit,
+		access_list,
+	})
+}
+
+/// Hash of the Ethereum transaction an extrinsic was built from, or the extrinsic hash for the
+/// extrinsics not built from an Ethereum transaction.
+pub(crate) fn transaction_hash(extrinsic: &<Block as BlockT>::Extrinsic) -> H256 {
+	ethereum_transaction(extrinsic)
+		.and_then(|transaction| transaction.hash())
+		.unwrap_or_else(|| BlakeTwo256::hash_of(extrinsic))
+}
+
+/// Gas prices of an `eth_call_v2` extrinsic.
+struct GasPrices {
+	/// The signed gas price of legacy and EIP-2930 transactions, which encodes their validity.
+	effective_gas_price: U256,
+	/// The priority fee per gas paid above the base fee.
+	priority_fee_per_gas: U256,
+	/// `max_fee_per_gas` and `max_priority_fee_per_gas` of EIP-1559 transactions.
+	eip1559: Option<(U256, U256)>,
+}
+
+fn gas_prices(extrinsic: &UncheckedExtrinsic, base_fee_per_gas: U256, tx_fee_per_gas: U256) -> Option<GasPrices> {
+	let (gas_price, gas_limit) = match extrinsic.function {
+		setheum_runtime::Call::EVM(module_evm::Call::eth_call_v2(_, _, _, gas_price, gas_limit, _)) => {
+			(gas_price, gas_limit)
+		}
+		_ => return None,
+	};
+
+	match &extrinsic.signature {
+		Some((_, SetheumMultiSignature::Eip1559(_), extra)) => {
+			// the tip is `max_priority_fee_per_gas * gas_limit`, in native decimals
+			let tip = extra.6 .0;
+			let max_priority_fee_per_gas =
+				U256::from(convert_decimals_to_evm(tip.checked_div(gas_limit.into()).unwrap_or_default()));
+			let effective_gas_price =
+				U256::from(gas_price).min(base_fee_per_gas.saturating_add(max_priority_fee_per_gas));
+			Some(GasPrices {
+				effective_gas_price,
+				priority_fee_per_gas: effective_gas_price.saturating_sub(base_fee_per_gas),
+				eip1559: Some((gas_price.into(), max_priority_fee_per_gas)),
+			})
+		}
+		_ => {
+			// the tip is encoded in the gas price, as a percentage of the fee
+			let tip = decode_gas_price(gas_price, gas_limit, tx_fee_per_gas.low_u128())
+				.map(|(tip, _)| tip)
+				.unwrap_or_default();
+			Some(GasPrices {
+				effective_gas_price: gas_price.into(),
+				priority_fee_per_gas: convert_decimals_to_evm(tip.checked_div(gas_limit.into()).unwrap_or_default())
+					.into(),
+				eip1559: None,
+			})
+		}
+	}
+}
+
+/// Priority fees per gas at the given percentiles of the gas used, from the priority fees per
+/// gas of the transactions of a block and the gas they used.
+fn rewards(mut priority_fees: Vec<(U256, u64)>, percentiles: &[f64]) -> Vec<U256> {
+	if priority_fees.is_empty() {
+		return vec![U256::zero(); percentiles.len()];
+	}
+	priority_fees.sort_by_key(|(priority_fee, _)| *priority_fee);
+	let total_gas = priority_fees.iter().fold(0u64, |gas, (_, used_gas)| gas.saturating_add(*used_gas));
+
+	let mut index = 0;
+	let mut cumulative_gas = priority_fees[0].1;
+	percentiles
+		.iter()
+		.map(|percentile| {
+			let threshold = (total_gas as f64 * percentile / 100.0) as u64;
+			while cumulative_gas < threshold && index < priority_fees.len() - 1 {
+				index += 1;
+				cumulative_gas = cumulative_gas.saturating_add(priority_fees[index].1);
+			}
+			priority_fees[index].0
+		})
+		.collect()
+}
+
+fn merge_receipts(receipts: Vec<EvmReceipt>) -> Vec<EvmReceipt> {
+	let mut transactions: Vec<EvmReceipt> = Vec::new();
+	for receipt in receipts {
+		match transactions.last_mut() {
+			Some(last) if last.extrinsic_index == receipt.extrinsic_index => {
+				last.logs.extend(receipt.logs);
+				last.used_gas = last.used_gas.saturating_add(receipt.used_gas);
+				last.used_storage = last.used_storage.saturating_add(receipt.used_storage);
+				last.succeeded &= receipt.succeeded;
+			}
+			_ => transactions.push(receipt),
+		}
+	}
+	transactions
+}
+
+/// Ethereum RPC methods.
+#[rpc]
+pub trait EthApi {
+	#[rpc(name = "web3_clientVersion")]
+	fn client_version(&self) -> RpcResult<String>;
+
+	#[rpc(name = "web3_sha3")]
+	fn sha3(&self, data: Bytes) -> RpcResult<H256>;
+
+	#[rpc(name = "net_version")]
+	fn net_version(&self) -> RpcResult<String>;
+
+	#[rpc(name = "net_listening")]
+	fn net_listening(&self) -> RpcResult<bool>;
+
+	#[rpc(name = "eth_chainId")]
+	fn chain_id(&self) -> RpcResult<U256>;
+
+	#[rpc(name = "eth_syncing")]
+	fn syncing(&self) -> RpcResult<bool>;
+
+	#[rpc(name = "eth_accounts")]
+	fn accounts(&self) -> RpcResult<Vec<H160>>;
+
+	#[rpc(name = "eth_blockNumber")]
+	fn block_number(&self) -> RpcResult<U256>;
+
+	/// Gas price accepted by `eth_call_v2` for `GAS_PRICE_VALIDITY` blocks.
+	#[rpc(name = "eth_gasPrice")]
+	fn gas_price(&self) -> RpcResult<U256>;
+
+	/// Priority fee per gas paid by recent EIP-1559 transactions.
+	#[rpc(name = "eth_maxPriorityFeePerGas")]
+	fn max_priority_fee_per_gas(&self) -> RpcResult<U256>;
+
+	/// Base fees, gas used and priority fees of the `block_count` blocks up to `newest_block`.
+	#[rpc(name = "eth_feeHistory")]
+	fn fee_history(
+		&self,
+		block_count: U256,
+		newest_block: BlockNumberOrTag,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> RpcResult<FeeHistory>;
+
+	#[rpc(name = "eth_getBalance")]
+	fn balance(&self, address: H160, number: Option<BlockNumberOrTag>) -> RpcResult<U256>;
+
+	#[rpc(name = "eth_getTransactionCount")]
+	fn transaction_count(&self, address: H160, number: Option<BlockNumberOrTag>) -> RpcResult<U256>;
+
+	#[rpc(name = "eth_getCode")]
+	fn code_at(&self, address: H160, number: Option<BlockNumberOrTag>) -> RpcResult<Bytes>;
+
+	#[rpc(name = "eth_getStorageAt")]
+	fn storage_at(&self, address: H160, index: U256, number: Option<BlockNumberOrTag>) -> RpcResult<H256>;
+
+	#[rpc(name = "eth_call")]
+	fn call(&self, request: CallRequest, number: Option<BlockNumberOrTag>) -> RpcResult<Bytes>;
+
+	/// Estimate the resources used by the call, returned as an encoded gas limit that
+	/// covers both gas and storage.
+	#[rpc(name = "eth_estimateGas")]
+	fn estimate_gas(&self, request: CallRequest, number: Option<BlockNumberOrTag>) -> RpcResult<U256>;
+
+	/// Submit a signed, RLP encoded Ethereum transaction and return its Ethereum hash.
+	#[rpc(name = "eth_sendRawTransaction")]
+	fn send_raw_transaction(&self, transaction: Bytes) -> BoxFuture<RpcResult<H256>>;
+
+	#[rpc(name = "eth_getBlockByNumber")]
+	fn block_by_number(&self, number: BlockNumberOrTag, full: bool) -> RpcResult<Option<EthBlock>>;
+
+	#[rpc(name = "eth_getBlockByHash")]
+	fn block_by_hash(&self, hash: H256, full: bool) -> RpcResult<Option<EthBlock>>;
+
+	#[rpc(name = "eth_getBlockTransactionCountByNumber")]
+	fn block_transaction_count_by_number(&self, number: BlockNumberOrTag) -> RpcResult<Option<U256>>;
+
+	#[rpc(name = "eth_getBlockTransactionCountByHash")]
+	fn block_transaction_count_by_hash(&self, hash: H256) -> RpcResult<Option<U256>>;
+
+	#[rpc(name = "eth_getTransactionByHash")]
+	fn transaction_by_hash(&self, hash: H256) -> RpcResult<Option<Transaction>>;
+
+	#[rpc(name = "eth_getTransactionReceipt")]
+	fn transaction_receipt(&self, hash: H256) -> RpcResult<Option<Receipt>>;
+
+	#[rpc(name = "eth_getLogs")]
+	fn logs(&self, filter: Filter) -> RpcResult<Vec<EthLog>>;
+}
+
+/// Implementation of the Ethereum RPC.
+pub struct Eth<C, P> {
+	client: Arc<C>,
+	pool: Arc<P>,
+}
+
+impl<C, P> Eth<C, P> {
+	pub fn new(client: Arc<C>, pool: Arc<P>) -> Self {
+		Eth { client, pool }
+	}
+}
+
+impl<C, P> Eth<C, P>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block> + Send + Sync + 'static,
+	C::Api: EVMRuntimeRPCApi<Block, Balance>,
+	C::Api: EthereumRuntimeRPCApi<Block>,
+	P: TransactionPool<Block = Block> + 'static,
+{
+	fn block_hash(&self, number: Option<BlockNumberOrTag>) -> Result<Option<Hash>, Error> {
+		let info = self.client.info();
+		match number.unwrap_or(BlockNumberOrTag::Latest) {
+			BlockNumberOrTag::Latest | BlockNumberOrTag::Pending => Ok(Some(info.best_hash)),
+			BlockNumberOrTag::Finalized => Ok(Some(info.finalized_hash)),
+			BlockNumberOrTag::Earliest => Ok(Some(info.genesis_hash)),
+			BlockNumberOrTag::Number(number) => self.client.hash(number).map_err(runtime_error),
+		}
+	}
+
+	/// Block to read the state at.
+	fn state_at(&self, number: Option<BlockNumberOrTag>) -> Result<BlockId<Block>, Error> {
+		self.block_hash(number)?.map(BlockId::Hash).ok_or(Error::UnknownBlock)
+	}
+
+	fn block_data(&self, hash: Hash) -> Result<Option<BlockData>, Error> {
+		let id = BlockId::Hash(hash);
+		let header = match self.client.header(id).map_err(runtime_error)? {
+			Some(header) => header,
+			None => return Ok(None),
+		};
+		let extrinsics = match self.client.block_body(&id).map_err(runtime_error)? {
+			Some(extrinsics) => extrinsics,
+			None => return Ok(None),
+		};
+		let api = self.client.runtime_api();
+
+		Ok(Some(BlockData {
+			hash,
+			header,
+			extrinsics,
+			transactions: merge_receipts(api.receipts(&id).map_err(runtime_error)?),
+			timestamp: api.timestamp(&id).map_err(runtime_error)?,
+		}))
+	}
+
+	/// Find the block and the position in the block of the transaction with the given hash.
+	fn find_transaction(&self, hash: H256) -> Result<Option<(BlockData, usize)>, Error> {
+		let best_number = self.client.info().best_number;
+		for number in (best_number.saturating_sub(MAX_TRANSACTION_LOOKUP_BLOCKS)..=best_number).rev() {
+			let block_hash = match self.client.hash(number).map_err(runtime_error)? {
+				Some(block_hash) => block_hash,
+				None => continue,
+			};
+			let extrinsics = match self.client.block_body(&BlockId::Hash(block_hash)).map_err(runtime_error)? {
+				Some(extrinsics) => extrinsics,
+				None => continue,
+			};
+			if let Some(extrinsic_index) = extrinsics
+				.iter()
+				.position(|extrinsic| transaction_hash(extrinsic) == hash)
+			{
+				let block = self.block_data(block_hash)?.ok_or(Error::UnknownBlock)?;
+				return Ok(block
+					.transactions
+					.iter()
+					.position(|receipt| receipt.extrinsic_index as usize == extrinsic_index)
+					.map(|index| (block, index)));
+			}
+		}
+
+		Ok(None)
+	}
+
+	/// Base fee per gas of the transactions of a block, set by the state of its parent.
+	fn base_fee_per_gas(&self, block: &BlockData) -> Result<U256, Error> {
+		let at = match *block.header.number() {
+			0 => block.hash,
+			_ => *block.header.parent_hash(),
+		};
+		self.client
+			.runtime_api()
+			.base_fee_per_gas(&BlockId::Hash(at))
+			.map_err(runtime_error)
+	}
+
+	fn logs_of(&self, block: &BlockData) -> Vec<EthLog> {
+		let mut log_index = 0u32;
+		let mut logs = Vec::new();
+		for (transaction_index, receipt) in block.transactions.iter().enumerate() {
+			let transaction_hash = block.transaction_hash(receipt);
+			for log in receipt.logs.iter() {
+				logs.push(EthLog {
+					address: log.address,
+					topics: log.topics.clone(),
+					data: log.data.clone().into(),
+					block_hash: block.hash,
+					block_number: block.number(),
+					transaction_hash,
+					transaction_index: transaction_index.into(),
+					log_index: log_index.into(),
+					removed: false,
+				});
+				log_index += 1;
+			}
+		}
+		logs
+	}
+
+	fn transaction(&self, block: &BlockData, index: usize) -> Result<Transaction, Error> {
+		let receipt = &block.transactions[index];
+		let extrinsic = &block.extrinsics[receipt.extrinsic_index as usize];
+		let id = BlockId::Hash(block.hash);
+		let api = self.client.runtime_api();
+
+		let chain_id = api.chain_id(&id).map_err(runtime_error)?;
+		let tx_fee_per_gas = api.tx_fee_per_gas(&id).map_err(runtime_error)?;
+		let base_fee_per_gas = self.base_fee_per_gas(block)?;
+		let request = api
+			.get_estimate_resources_request(&id, extrinsic.encode())
+			.map_err(runtime_error)?
+			.map_err(runtime_error)?;
+
+		let mut transaction = Transaction {
+			hash: block.transaction_hash(receipt),
+			nonce: U256::zero(),
+			block_hash: block.hash,
+			block_number: block.number(),
+			transaction_index: index.into(),
+			from: receipt.from,
+			to: receipt.to,
+			value: convert_decimals_to_evm(request.value.unwrap_or_default()).into(),
+			gas_price: tx_fee_per_gas,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			gas: request.gas_limit.unwrap_or_default().into(),
+			input: request.data.unwrap_or_default().into(),
+			chain_id: chain_id.into(),
+			v: U256::zero(),
+			r: U256::zero(),
+			s: U256::zero(),
+			transaction_type: U256::zero(),
+		};
+
+		let extrinsic = block.extrinsic(receipt)?;
+		if let Some(gas_prices) = gas_prices(&extrinsic, base_fee_per_gas, tx_fee_per_gas) {
+			transaction.gas_price = gas_prices.effective_gas_price;
+			if let Some((max_fee_per_gas, max_priority_fee_per_gas)) = gas_prices.eip1559 {
+				transaction.max_fee_per_gas = Some(max_fee_per_gas);
+				transaction.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+			}
+		}
+		if let Some((_, signature, extra)) = extrinsic.signature {
+			// `CheckNonce` only exposes its nonce through its encoding
+			transaction.nonce = Compact::<Nonce>::decode(&mut &*extra.4.encode())
+				.map_err(runtime_error)?
+				.0
+				.into();
+
+			let (sig, transaction_type) = match signature {
+				SetheumMultiSignature::Ethereum(sig) => (Some(sig), 0u8),
+				SetheumMultiSignature::Eip2930(sig) => (Some(sig), 1),
+				SetheumMultiSignature::Eip1559(sig) => (Some(sig), 2),
+				_ => (None, 0),
+			};
+			if let Some(sig) = sig {
+				transaction.r = U256::from_big_endian(&sig[0..32]);
+				transaction.s = U256::from_big_endian(&sig[32..64]);
+				transaction.v = match transaction_type {
+					// EIP-155
+					0 => U256::from(chain_id) * 2 + 35 + sig[64],
+					_ => sig[64].into(),
+				};
+				transaction.transaction_type = transaction_type.into();
+			}
+		}
+
+		Ok(transaction)
+	}
+
+	fn receipt(&self, block: &BlockData, index: usize) -> Result<Receipt, Error> {
+		let transaction = self.transaction(block, index)?;
+		let receipt = &block.transactions[index];
+		let cumulative_gas_used = block.transactions[..=index]
+			.iter()
+			.fold(0u64, |gas, receipt| gas.saturating_add(receipt.used_gas));
+
+		Ok(Receipt {
+			transaction_hash: transaction.hash,
+			transaction_index: index.into(),
+			block_hash: block.hash,
+			block_number: block.number(),
+			from: receipt.from,
+			to: receipt.to,
+			cumulative_gas_used: cumulative_gas_used.into(),
+			gas_used: receipt.used_gas.into(),
+			effective_gas_price: transaction.gas_price,
+			contract_address: receipt.contract_address,
+			logs: self
+				.logs_of(block)
+				.into_iter()
+				.filter(|log| log.transaction_index == U256::from(index))
+				.collect(),
+			logs_bloom: logs_bloom(receipt.logs.iter()),
+			status: (receipt.succeeded as u8).into(),
+			transaction_type: transaction.transaction_type,
+			storage_used: receipt.used_storage,
+		})
+	}
+
+	fn block(&self, hash: Hash, full: bool) -> Result<Option<EthBlock>, Error> {
+		let block = match self.block_data(hash)? {
+			Some(block) => block,
+			None => return Ok(None),
+		};
+		let block_limits = self
+			.client
+			.runtime_api()
+			.block_limits(&BlockId::Hash(hash))
+			.map_err(runtime_error)?;
+
+		let transactions = if full {
+			BlockTransactions::Full(
+				(0..block.transactions.len())
+					.map(|index| self.transaction(&block, index))
+					.collect::<Result<_, _>>()?,
+			)
+		} else {
+			BlockTransactions::Hashes(
+				block
+					.transactions
+					.iter()
+					.map(|receipt| block.transaction_hash(receipt))
+					.collect(),
+			)
+		};
+		let size = block.header.encoded_size() + block.extrinsics.encoded_size();
+
+		Ok(Some(EthBlock {
+			hash: block.hash,
+			parent_hash: *block.header.parent_hash(),
+			number: block.number(),
+			timestamp: (block.timestamp / 1000).into(),
+			miner: H160::zero(),
+			state_root: *block.header.state_root(),
+			transactions_root: *block.header.extrinsics_root(),
+			receipts_root: H256::zero(),
+			sha3_uncles: H256::from(EMPTY_UNCLES_HASH),
+			logs_bloom: logs_bloom(block.transactions.iter().flat_map(|receipt| receipt.logs.iter())),
+			difficulty: U256::zero(),
+			total_difficulty: U256::zero(),
+			extra_data: Bytes::default(),
+			nonce: vec![0u8; 8].into(),
+			mix_hash: H256::zero(),
+			size: size.into(),
+			gas_limit: block_limits.max_gas_limit.into(),
+			gas_used: block.gas_used().into(),
+			base_fee_per_gas: self.base_fee_per_gas(&block)?,
+			uncles: Vec::new(),
+			transactions,
+		}))
+	}
+
+	/// Lowest gas limit and the storage the request needs to succeed.
+	fn estimate_resources(&self, at: &BlockId<Block>, request: EstimateResourcesRequest) -> Result<(u64, u32), Error> {
+		let api = self.client.runtime_api();
+		let block_limits = api.block_limits(at).map_err(runtime_error)?;
+		let gas_limit = request
+			.gas_limit
+			.unwrap_or(block_limits.max_gas_limit)
+			.min(block_limits.max_gas_limit);
+		let storage_limit = request
+			.storage_limit
+			.unwrap_or(block_limits.max_storage_limit)
+			.min(block_limits.max_storage_limit);
+		let from = request.from.unwrap_or_default();
+		let data = request.data.unwrap_or_default();
+		let value = request.value.unwrap_or_default();
+
+		let execute = |gas_limit: u64, estimate: bool| -> Result<(ExitReason, Vec<u8>, u64, i32), Error> {
+			match request.to {
+				Some(to) => api
+					.call(
+						at,
+						from,
+						to,
+						data.clone(),
+						value,
+						gas_limit,
+						storage_limit,
+						request.access_list.clone(),
+						estimate,
+					)
+					.map_err(runtime_error)?
+					.map(|info| (info.exit_reason, info.value, info.used_gas.low_u64(), info.used_storage)),
+				None => api
+					.create(
+						at,
+						from,
+						data.clone(),
+						value,
+						gas_limit,
+						storage_limit,
+						request.access_list.clone(),
+						estimate,
+					)
+					.map_err(runtime_error)?
+					.map(|info| (info.exit_reason, Vec::new(), info.used_gas.low_u64(), info.used_storage)),
+			}
+			.map_err(|e| Error::Execution(format!("{:?}", e)))
+		};
+		let succeeds = |gas_limit: u64| -> Result<bool, Error> {
+			Ok(matches!(execute(gas_limit, false)?.0, ExitReason::Succeed(_)))
+		};
+
+		let (exit_reason, output, used_gas, used_storage) = execute(gas_limit, true)?;
+		match exit_reason {
+			ExitReason::Succeed(_) => {}
+			ExitReason::Revert(_) => return Err(Error::Reverted(output)),
+			reason => return Err(Error::Execution(exit_error(&reason).unwrap_or_default())),
+		}
+
+		// calls forwarding 63/64 of their gas can need more gas than they use
+		let (mut lowest, mut highest) = (used_gas.saturating_sub(1), gas_limit);
+		if succeeds(used_gas)? {
+			highest = used_gas;
+		}
+		while highest.saturating_sub(lowest) > 1 {
+			let middle = lowest + (highest - lowest) / 2;
+			if succeeds(middle)? {
+				highest = middle;
+			} else {
+				lowest = middle;
+			}
+		}
+
+		Ok((highest, used_storage.max(0) as u32))
+	}
+}
+
+fn call_request_to_estimate_request(request: CallRequest) -> Result<EstimateResourcesRequest, Error> {
+	let value = match request.value {
+		Some(value) if value > U256::from(Balance::MAX) => {
+			return Err(Error::InvalidParams("value overflows the balance type".to_string()));
+		}
+		Some(value) => Some(
+			convert_decimals_from_evm(value.low_u128())
+				.ok_or_else(|| Error::InvalidParams("value is not a multiple of the native decimals".to_string()))?,
+		),
+		None => None,
+	};
+	let gas_limit = match request.gas {
+		Some(gas) if gas > U256::from(u64::MAX) => {
+			return Err(Error::InvalidParams("gas overflows u64".to_string()));
+		}
+		gas => gas.map(|gas| gas.low_u64()),
+	};
+
+	Ok(EstimateResourcesRequest {
+		from: request.from,
+		to: request.to,
+		gas_limit,
+		storage_limit: None,
+		value,
+		data: request.data.or(request.input).map(|data| data.0),
+		access_list: request.access_list,
+	})
+}
+
+impl<C, P> EthApi for Eth<C, P>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block> + Send + Sync + 'static,
+	C::Api: EVMRuntimeRPCApi<Block, Balance>,
+	C::Api: EthereumRuntimeRPCApi<Block>,
+	P: TransactionPool<Block = Block> + 'static,
+{
+	fn client_version(&self) -> RpcResult<String> {
+		Ok(format!("setheum-node/v{}", env!("CARGO_PKG_VERSION")))
+	}
+
+	fn sha3(&self, data: Bytes) -> RpcResult<H256> {
+		Ok(keccak_256(&data.0).into())
+	}
+
+	fn net_version(&self) -> RpcResult<String> {
+		let best = BlockId::Hash(self.client.info().best_hash);
+		Ok(self
+			.client
+			.runtime_api()
+			.chain_id(&best)
+			.map_err(runtime_error)?
+			.to_string())
+	}
+
+	fn net_listening(&self) -> RpcResult<bool> {
+		Ok(true)
+	}
+
+	fn chain_id(&self) -> RpcResult<U256> {
+		let best = BlockId::Hash(self.client.info().best_hash);
+		Ok(self.client.runtime_api().chain_id(&best).map_err(runtime_error)?.into())
+	}
+
+	fn syncing(&self) -> RpcResult<bool> {
+		Ok(false)
+	}
+
+	fn accounts(&self) -> RpcResult<Vec<H160>> {
+		Ok(Vec::new())
+	}
+
+	fn block_number(&self) -> RpcResult<U256> {
+		Ok(self.client.info().best_number.into())
+	}
+
+	fn gas_price(&self) -> RpcResult<U256> {
+		let info = self.client.info();
+		let tx_fee_per_gas = self
+			.client
+			.runtime_api()
+			.tx_fee_per_gas(&BlockId::Hash(info.best_hash))
+			.map_err(runtime_error)?;
+		// the part above `tx_fee_per_gas` is the block number the transaction is valid until
+		Ok(tx_fee_per_gas + info.best_number.saturating_add(GAS_PRICE_VALIDITY))
+	}
+
+	fn max_priority_fee_per_gas(&self) -> RpcResult<U256> {
+		let history = self.fee_history(
+			PRIORITY_FEE_SAMPLE_BLOCKS.into(),
+			BlockNumberOrTag::Latest,
+			Some(vec![PRIORITY_FEE_PERCENTILE]),
+		)?;
+		let mut rewards: Vec<U256> = history.reward.unwrap_or_default().into_iter().flatten().collect();
+		rewards.sort();
+		Ok(rewards.get(rewards.len() / 2).copied().unwrap_or_default())
+	}
+
+	fn fee_history(
+		&self,
+		block_count: U256,
+		newest_block: BlockNumberOrTag,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> RpcResult<FeeHistory> {
+		if let Some(percentiles) = &reward_percentiles {
+			if percentiles.iter().any(|percentile| !(0.0..=100.0).contains(percentile))
+				|| percentiles.windows(2).any(|pair| pair[0] > pair[1])
+			{
+				return Err(Error::InvalidParams(
+					"reward percentiles must be increasing values between 0 and 100".to_string(),
+				)
+				.into());
+			}
+		}
+
+		let newest_hash = self.block_hash(Some(newest_block))?.ok_or(Error::UnknownBlock)?;
+		let newest = self
+			.client
+			.number(newest_hash)
+			.map_err(runtime_error)?
+			.ok_or(Error::UnknownBlock)?;
+		let block_count = block_count.min(MAX_FEE_HISTORY_BLOCKS.into()).low_u32();
+		if block_count == 0 {
+			return Ok(FeeHistory {
+				oldest_block: U256::zero(),
+				base_fee_per_gas: Vec::new(),
+				gas_used_ratio: Vec::new(),
+				reward: reward_percentiles.map(|_| Vec::new()),
+			});
+		}
+		let oldest = newest.saturating_sub(block_count - 1);
+
+		let api = self.client.runtime_api();
+		let mut base_fee_per_gas = Vec::new();
+		let mut gas_used_ratio = Vec::new();
+		let mut reward = Vec::new();
+		for number in oldest..=newest {
+			let hash = self
+				.client
+				.hash(number)
+				.map_err(runtime_error)?
+				.ok_or(Error::UnknownBlock)?;
+			let block = self.block_data(hash)?.ok_or(Error::UnknownBlock)?;
+			let base_fee = self.base_fee_per_gas(&block)?;
+			let tx_fee_per_gas = api.tx_fee_per_gas(&BlockId::Hash(hash)).map_err(runtime_error)?;
+			let max_gas_limit = api.block_limits(&BlockId::Hash(hash)).map_err(runtime_error)?.max_gas_limit;
+
+			base_fee_per_gas.push(base_fee);
+			gas_used_ratio.push(match max_gas_limit {
+				0 => 0.0,
+				max_gas_limit => block.gas_used() as f64 / max_gas_limit as f64,
+			});
+			if let Some(percentiles) = &reward_percentiles {
+				let priority_fees = block
+					.transactions
+					.iter()
+					.map(|receipt| {
+						let extrinsic = block.extrinsic(receipt)?;
+						let priority_fee = gas_prices(&extrinsic, base_fee, tx_fee_per_gas)
+							.map(|gas_prices| gas_prices.priority_fee_per_gas)
+							.unwrap_or_default();
+						Ok((priority_fee, receipt.used_gas))
+					})
+					.collect::<Result<Vec<_>, Error>>()?;
+				reward.push(rewards(priority_fees, percentiles));
+			}
+		}
+		// the base fee of the next block is set by the state of the newest one
+		base_fee_per_gas.push(
+			api.base_fee_per_gas(&BlockId::Hash(newest_hash))
+				.map_err(runtime_error)?,
+		);
+
+		Ok(FeeHistory {
+			oldest_block: oldest.into(),
+			base_fee_per_gas,
+			gas_used_ratio,
+			reward: reward_percentiles.map(|_| reward),
+		})
+	}
+
+	fn balance(&self, address: H160, number: Option<BlockNumberOrTag>) -> RpcResult<U256> {
+		let at = self.state_at(number)?;
+		Ok(self.client.runtime_api().balance(&at, address).map_err(runtime_error)?)
+	}
+
+	fn transaction_count(&self, address: H160, number: Option<BlockNumberOrTag>) -> RpcResult<U256> {
+		let at = self.state_at(number)?;
+		Ok(self
+			.client
+			.runtime_api()
+			.transaction_count(&at, address)
+			.map_err(runtime_error)?)
+	}
+
+	fn code_at(&self, address: H160, number: Option<BlockNumberOrTag>) -> RpcResult<Bytes> {
+		let at = self.state_at(number)?;
+		Ok(self.client.runtime_api().code_at(&at, address).map_err(runtime_error)?.into())
+	}
+
+	fn storage_at(&self, address: H160, index: U256, number: Option<BlockNumberOrTag>) -> RpcResult<H256> {
+		let at = self.state_at(number)?;
+		let mut key = [0u8; 32];
+		index.to_big_endian(&mut key);
+		Ok(self
+			.client
+			.runtime_api()
+			.storage_at(&at, address, H256::from(key))
+			.map_err(runtime_error)?)
+	}
+
+	fn call(&self, request: CallRequest, number: Option<BlockNumberOrTag>) -> RpcResult<Bytes> {
+		let at = self.state_at(number)?;
+		let request = call_request_to_estimate_request(request)?;
+		let api = self.client.runtime_api();
+		let block_limits = api.block_limits(&at).map_err(runtime_error)?;
+		let gas_limit = request
+			.gas_limit
+			.unwrap_or(block_limits.max_gas_limit)
+			.min(block_limits.max_gas_limit);
+		let from = request.from.unwrap_or_default();
+		let data = request.data.unwrap_or_default();
+		let value = request.value.unwrap_or_default();
+
+		let (exit_reason, output) = match request.to {
+			Some(to) => api
+				.call(
+					&at,
+					from,
+					to,
+					data,
+					value,
+					gas_limit,
+					block_limits.max_storage_limit,
+					request.access_list,
+					false,
+				)
+				.map_err(runtime_error)?
+				.map(|info| (info.exit_reason, info.value)),
+			None => api
+				.create(
+					&at,
+					from,
+					data,
+					value,
+					gas_limit,
+					block_limits.max_storage_limit,
+					request.access_list,
+					false,
+				)
+				.map_err(runtime_error)?
+				.map(|info| (info.exit_reason, info.value.as_bytes().to_vec())),
+		}
+		.map_err(|e| Error::Execution(format!("{:?}", e)))?;
+
+		match exit_reason {
+			ExitReason::Succeed(_) => Ok(output.into()),
+			ExitReason::Revert(_) => Err(Error::Reverted(output).into()),
+			reason => Err(Error::Execution(exit_error(&reason).unwrap_or_default()).into()),
+		}
+	}
+
+	fn estimate_gas(&self, request: CallRequest, number: Option<BlockNumberOrTag>) -> RpcResult<U256> {
+		let at = self.state_at(number)?;
+		let (gas, storage) = self.estimate_resources(&at, call_request_to_estimate_request(request)?)?;
+		encode_gas_limit(gas, storage)
+			.map(Into::into)
+			.ok_or_else(|| Error::Execution("gas or storage exceeds the encodable limits".to_string()).into())
+	}
+
+	fn send_raw_transaction(&self, transaction: Bytes) -> BoxFuture<RpcResult<H256>> {
+		let best = BlockId::Hash(self.client.info().best_hash);
+		let hash = H256::from(keccak_256(&transaction.0));
+		let extrinsic = match self
+			.client
+			.runtime_api()
+			.ethereum_transaction_to_extrinsic(&best, transaction.0)
+		{
+			Ok(Ok(extrinsic)) => extrinsic,
+			Ok(Err(e)) => return Box::pin(future::err(Error::InvalidTransaction(format!("{:?}", e)).into())),
+			Err(e) => return Box::pin(future::err(runtime_error(e).into())),
+		};
+
+		Box::pin(
+			self.pool
+				.submit_one(&best, TransactionSource::External, extrinsic)
+				.map(move |result| match result {
+					Ok(_) => Ok(hash),
+					Err(e) => Err(Error::InvalidTransaction(format!("{:?}", e)).into()),
+				}),
+		)
+	}
+
+	fn block_by_number(&self, number: BlockNumberOrTag, full: bool) -> RpcResult<Option<EthBlock>> {
+		match self.block_hash(Some(number))? {
+			Some(hash) => Ok(self.block(hash, full)?),
+			None => Ok(None),
+		}
+	}
+
+	fn block_by_hash(&self, hash: H256, full: bool) -> RpcResult<Option<EthBlock>> {
+		Ok(self.block(hash, full)?)
+	}
+
+	fn block_transaction_count_by_number(&self, number: BlockNumberOrTag) -> RpcResult<Option<U256>> {
+		match self.block_hash(Some(number))? {
+			Some(hash) => self.block_transaction_count_by_hash(hash),
+			None => Ok(None),
+		}
+	}
+
+	fn block_transaction_count_by_hash(&self, hash: H256) -> RpcResult<Option<U256>> {
+		Ok(self.block_data(hash)?.map(|block| block.transactions.len().into()))
+	}
+
+	fn transaction_by_hash(&self, hash: H256) -> RpcResult<Option<Transaction>> {
+		match self.find_transaction(hash)? {
+			Some((block, index)) => Ok(Some(self.transaction(&block, index)?)),
+			None => Ok(None),
+		}
+	}
+
+	fn transaction_receipt(&self, hash: H256) -> RpcResult<Option<Receipt>> {
+		match self.find_transaction(hash)? {
+			Some((block, index)) => Ok(Some(self.receipt(&block, index)?)),
+			None => Ok(None),
+		}
+	}
+
+	fn logs(&self, filter: Filter) -> RpcResult<Vec<EthLog>> {
+		let blocks = match filter.block_hash {
+			Some(hash) => vec![hash],
+			None => {
+				let number_of = |number: Option<BlockNumberOrTag>| -> Result<BlockNumber, Error> {
+					let hash = self.block_hash(number)?.ok_or(Error::UnknownBlock)?;
+					self.client
+						.number(hash)
+						.map_err(runtime_error)?
+						.ok_or(Error::UnknownBlock)
+				};
+				let from = number_of(filter.from_block)?;
+				let to = number_of(filter.to_block)?;
+				if to.saturating_sub(from) >= MAX_LOGS_BLOCK_RANGE {
+					return Err(Error::InvalidParams(format!(
+						"block range is limited to {} blocks",
+						MAX_LOGS_BLOCK_RANGE
+					))
+					.into());
+				}
+				(from..=to)
+					.map(|number| self.client.hash(number).map_err(runtime_error))
+					.collect::<Result<Vec<_>, _>>()?
+					.into_iter()
+					.flatten()
+					.collect()
+			}
+		};
+
+		let mut logs = Vec::new();
+		for hash in blocks {
+			let block = self.block_data(hash)?.ok_or(Error::UnknownBlock)?;
+			logs.extend(self.logs_of(&block).into_iter().filter(|log| filter.matches(log)));
+		}
+
+		Ok(logs)
+	}
+}
@@ -0,0 +1,434 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Geth compatible `debug_*` tracing RPC for module-evm.
+//!
+//! Traces are produced by re-executing the block up to the traced extrinsic on a runtime
+//! built with the `tracing` feature. Re-execution is expensive, so the methods are unsafe RPC.
+
+use std::{collections::BTreeMap, convert::TryFrom, sync::Arc};
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result as RpcResult};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sc_client_api::BlockBackend;
+use sc_rpc_api::DenyUnsafe;
+use sp_api::{Core, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H160, H256, U256};
+use sp_runtime::{generic::BlockId, traits::Header as HeaderT};
+
+pub use module_evm_rpc_runtime_api::EVMTraceApi;
+use module_evm_rpc_runtime_api::EVMRuntimeRPCApi;
+use setheum_primitives::evm::{
+	tracing::{opcode_name, CallTrace, OpcodeTrace, OpcodeTracerConfig, Step, TraceOutcome, TracerConfig},
+	AccessListItem, ExitError, ExitReason,
+};
+use setheum_runtime::{opaque::Block, Balance, Hash};
+
+use crate::eth_rpc::{transaction_hash, MAX_TRANSACTION_LOOKUP_BLOCKS};
+
+/// Base code for all EVM tracing RPC errors.
+const BASE_ERROR: i64 = 3000;
+/// The block or the transaction is not known to the node.
+const UNKNOWN_ERROR: i64 = BASE_ERROR + 1;
+/// The requested tracer is not supported.
+const UNSUPPORTED_TRACER_ERROR: i64 = BASE_ERROR + 2;
+/// Re-executing the block failed, e.g. because the runtime was built without `tracing`.
+const EXECUTION_ERROR: i64 = BASE_ERROR + 3;
+
+/// EVM tracing RPC errors.
+#[derive(Debug)]
+pub enum Error {
+	/// The block is not known.
+	UnknownBlock(Hash),
+	/// The transaction is not part of the recent blocks.
+	UnknownTransaction(H256),
+	/// Only `callTracer` and the default struct logger are supported.
+	UnsupportedTracer(String),
+	/// Re-executing the block or the call failed.
+	Execution(String),
+}
+
+impl From<Error> for RpcError {
+	fn from(error: Error) -> Self {
+		let (code, message) = match error {
+			Error::UnknownBlock(hash) => (UNKNOWN_ERROR, format!("Block {:?} is not known.", hash)),
+			Error::UnknownTransaction(hash) => (UNKNOWN_ERROR, format!("Transaction {:?} is not known.", hash)),
+			Error::UnsupportedTracer(tracer) => (UNSUPPORTED_TRACER_ERROR, format!("Tracer {} is not supported.", tracer)),
+			Error::Execution(e) => (EXECUTION_ERROR, format!("Failed to re-execute: {}", e)),
+		};
+		RpcError {
+			code: ErrorCode::ServerError(code),
+			message,
+			data: None,
+		}
+	}
+}
+
+/// Geth `TraceConfig`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TraceConfig {
+	/// `callTracer`, or `None` for the struct logger.
+	pub tracer: Option<String>,
+	pub disable_stack: bool,
+	pub enable_memory: bool,
+	pub disable_storage: bool,
+}
+
+impl TryFrom<TraceConfig> for TracerConfig {
+	type Error = Error;
+
+	fn try_from(config: TraceConfig) -> Result<Self, Error> {
+		match config.tracer.as_deref() {
+			None => Ok(TracerConfig::OpcodeTracer(OpcodeTracerConfig {
+				disable_stack: config.disable_stack,
+				enable_memory: config.enable_memory,
+				disable_storage: config.disable_storage,
+			})),
+			Some("callTracer") => Ok(TracerConfig::CallTracer),
+			Some(tracer) => Err(Error::UnsupportedTracer(tracer.to_string())),
+		}
+	}
+}
+
+/// Call to trace with `debug_traceCall`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TraceCallRequest {
+	pub from: H160,
+	pub to: H160,
+	/// Defaults to the block gas limit.
+	pub gas: Option<u64>,
+	/// Defaults to the block storage limit.
+	pub storage_limit: Option<u32>,
+	pub value: Option<U256>,
+	pub data: Option<Bytes>,
+	pub access_list: Option<Vec<AccessListItem>>,
+}
+
+/// Geth `callTracer` frame. `storageUsed` is specific to Setheum.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+	#[serde(rename = "type")]
+	pub call_type: String,
+	pub from: H160,
+	pub to: H160,
+	pub value: U256,
+	pub gas: U256,
+	pub gas_used: U256,
+	pub input: Bytes,
+	pub output: Bytes,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub revert_reason: Option<String>,
+	pub storage_used: i32,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub calls: Vec<CallFrame>,
+}
+
+impl From<CallTrace> for CallFrame {
+	fn from(trace: CallTrace) -> Self {
+		let revert_reason = match trace.exit_reason {
+			Some(ExitReason::Revert(_)) => decode_revert_reason(&trace.output),
+			_ => None,
+		};
+		CallFrame {
+			call_type: format!("{:?}", trace.call_type),
+			from: trace.from,
+			to: trace.to,
+			value: trace.value,
+			gas: trace.gas.into(),
+			gas_used: trace.gas_used.into(),
+			input: trace.input.into(),
+			error: trace.exit_reason.as_ref().and_then(exit_error),
+			revert_reason,
+			output: trace.output.into(),
+			storage_used: trace.used_storage,
+			calls: trace.calls.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// Geth struct log. `storageCharge` is specific to Setheum.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+	pub pc: u32,
+	pub op: String,
+	pub gas: u64,
+	pub gas_cost: u64,
+	pub depth: u32,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stack: Option<Vec<U256>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub memory: Option<Vec<String>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<BTreeMap<String, String>>,
+	#[serde(skip_serializing_if = "is_zero")]
+	pub storage_charge: i32,
+}
+
+impl From<Step> for StructLog {
+	fn from(step: Step) -> Self {
+		StructLog {
+			pc: step.pc,
+			op: opcode_name(step.op).to_string(),
+			gas: step.gas,
+			gas_cost: step.gas_cost,
+			depth: step.depth,
+			stack: step
+				.stack
+				.map(|stack| stack.iter().map(|v| U256::from_big_endian(v.as_bytes())).collect()),
+			memory: step.memory.map(|memory| memory.chunks(32).map(to_hex).collect()),
+			storage: step.storage.map(|storage| {
+				storage
+					.iter()
+					.map(|(k, v)| (to_hex(k.as_bytes()), to_hex(v.as_bytes())))
+					.collect()
+			}),
+			storage_charge: step.storage_charge,
+		}
+	}
+}
+
+/// Geth struct logger result. `storageUsed` is specific to Setheum.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogs {
+	pub gas: u64,
+	pub failed: bool,
+	pub return_value: String,
+	pub storage_used: i32,
+	pub struct_logs: Vec<StructLog>,
+}
+
+impl From<OpcodeTrace> for StructLogs {
+	fn from(trace: OpcodeTrace) -> Self {
+		StructLogs {
+			gas: trace.gas,
+			failed: !matches!(trace.exit_reason, Some(ExitReason::Succeed(_))),
+			return_value: to_hex(&trace.return_value),
+			storage_used: trace.used_storage,
+			struct_logs: trace.steps.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// Trace of one EVM execution.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Trace {
+	Call(CallFrame),
+	StructLogs(StructLogs),
+}
+
+fn into_traces(outcome: TraceOutcome) -> Vec<Trace> {
+	match outcome {
+		TraceOutcome::Calls(calls) => calls.into_iter().map(|c| Trace::Call(c.into())).collect(),
+		TraceOutcome::Steps(traces) => traces.into_iter().map(|t| Trace::StructLogs(t.into())).collect(),
+	}
+}
+
+fn is_zero(value: &i32) -> bool {
+	*value == 0
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+	bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Error message of a failed call, worded like Geth where there is an equivalent.
+pub(crate) fn exit_error(reason: &ExitReason) -> Option<String> {
+	match reason {
+		ExitReason::Succeed(_) => None,
+		ExitReason::Revert(_) => Some("execution reverted".to_string()),
+		ExitReason::Error(ExitError::OutOfGas) => Some("out of gas".to_string()),
+		ExitReason::Error(ExitError::StackUnderflow) => Some("stack underflow".to_string()),
+		ExitReason::Error(ExitError::StackOverflow) => Some("stack limit reached".to_string()),
+		ExitReason::Error(ExitError::InvalidJump) => Some("invalid jump destination".to_string()),
+		ExitReason::Error(ExitError::InvalidRange) => Some("return data out of bounds".to_string()),
+		ExitReason::Error(ExitError::DesignatedInvalid) => Some("invalid opcode: INVALID".to_string()),
+		ExitReason::Error(ExitError::CallTooDeep) => Some("max call depth exceeded".to_string()),
+		ExitReason::Error(ExitError::CreateCollision) => Some("contract address collision".to_string()),
+		ExitReason::Error(ExitError::CreateContractLimit) => Some("max code size exceeded".to_string()),
+		ExitReason::Error(ExitError::OutOfFund) => Some("insufficient balance for transfer".to_string()),
+		ExitReason::Error(ExitError::Other(e)) => Some(e.to_string()),
+		ExitReason::Error(e) => Some(format!("{:?}", e)),
+		ExitReason::Fatal(e) => Some(format!("fatal: {:?}", e)),
+	}
+}
+
+/// Decode the message of a Solidity `Error(string)` revert.
+pub(crate) fn decode_revert_reason(output: &[u8]) -> Option<String> {
+	const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+	if output.len() < 68 || output[..4] != ERROR_SELECTOR {
+		return None;
+	}
+	let len = U256::from_big_endian(&output[36..68]);
+	if len > U256::from(output.len() - 68) {
+		return None;
+	}
+	String::from_utf8(output[68..68 + len.as_usize()].to_vec()).ok()
+}
+
+/// EVM tracing RPC methods.
+#[rpc]
+pub trait EvmTracingApi {
+	/// Re-execute the block up to the transaction with the given hash, as returned by the
+	/// Ethereum RPC, and trace it, returning one trace per EVM execution performed by the
+	/// transaction.
+	#[rpc(name = "debug_traceTransaction")]
+	fn trace_transaction(&self, tx_hash: H256, config: Option<TraceConfig>) -> RpcResult<Vec<Trace>>;
+
+	/// Trace a call executed on top of the given block, or the best block if none is given.
+	#[rpc(name = "debug_traceCall")]
+	fn trace_call(&self, call: TraceCallRequest, at: Option<Hash>, config: Option<TraceConfig>) -> RpcResult<Trace>;
+}
+
+/// Implementation of the EVM tracing RPC.
+pub struct EvmTracing<C> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<C> EvmTracing<C> {
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		EvmTracing { client, deny_unsafe }
+	}
+}
+
+impl<C> EvmTracing<C>
+where
+	C: HeaderBackend<Block> + BlockBackend<Block>,
+{
+	/// Find the block and the position in the block of the transaction with the given hash.
+	fn find_transaction(&self, tx_hash: H256) -> Result<Option<(Hash, usize)>, Error> {
+		let best_number = self.client.info().best_number;
+		for number in (best_number.saturating_sub(MAX_TRANSACTION_LOOKUP_BLOCKS)..=best_number).rev() {
+			let block_hash = match self.client.hash(number).map_err(|e| Error::Execution(format!("{:?}", e)))? {
+				Some(block_hash) => block_hash,
+				None => continue,
+			};
+			let extrinsics = match self
+				.client
+				.block_body(&BlockId::Hash(block_hash))
+				.map_err(|e| Error::Execution(format!("{:?}", e)))?
+			{
+				Some(extrinsics) => extrinsics,
+				None => continue,
+			};
+			if let Some(index) = extrinsics
+				.iter()
+				.position(|extrinsic| transaction_hash(extrinsic) == tx_hash)
+			{
+				return Ok(Some((block_hash, index)));
+			}
+		}
+
+		Ok(None)
+	}
+}
+
+impl<C> EvmTracingApi for EvmTracing<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block> + Send + Sync + 'static,
+	C::Api: BlockBuilder<Block>,
+	C::Api: EVMRuntimeRPCApi<Block, Balance>,
+	C::Api: EVMTraceApi<Block, Balance>,
+{
+	fn trace_transaction(&self, tx_hash: H256, config: Option<TraceConfig>) -> RpcResult<Vec<Trace>> {
+		self.deny_unsafe.check_if_safe()?;
+		let tracer_config = TracerConfig::try_from(config.unwrap_or_default())?;
+		let (block_hash, index) = self
+			.find_transaction(tx_hash)?
+			.ok_or(Error::UnknownTransaction(tx_hash))?;
+
+		let header = self
+			.client
+			.header(BlockId::Hash(block_hash))
+			.map_err(|e| Error::Execution(format!("{:?}", e)))?
+			.ok_or(Error::UnknownBlock(block_hash))?;
+		let extrinsics = self
+			.client
+			.block_body(&BlockId::Hash(block_hash))
+			.map_err(|e| Error::Execution(format!("{:?}", e)))?
+			.ok_or(Error::UnknownBlock(block_hash))?;
+
+		// execute on top of the parent state, like the block import did
+		let parent = BlockId::Hash(*header.parent_hash());
+
+		let api = self.client.runtime_api();
+		api.initialize_block(&parent, &header)
+			.map_err(|e| Error::Execution(format!("{:?}", e)))?;
+		for extrinsic in extrinsics[..index].iter() {
+			// the outcome is already part of the chain, only the state changes matter
+			let _ = api
+				.apply_extrinsic(&parent, extrinsic.clone())
+				.map_err(|e| Error::Execution(format!("{:?}", e)))?;
+		}
+		let outcome = api
+			.trace_extrinsic(&parent, extrinsics[index].clone(), tracer_config)
+			.map_err(|e| Error::Execution(format!("{:?}", e)))?
+			.map_err(|e| Error::Execution(format!("{:?}", e)))?;
+
+		Ok(into_traces(outcome))
+	}
+
+	fn trace_call(&self, call: TraceCallRequest, at: Option<Hash>, config: Option<TraceConfig>) -> RpcResult<Trace> {
+		self.deny_unsafe.check_if_safe()?;
+		let tracer_config = TracerConfig::try_from(config.unwrap_or_default())?;
+		let at = BlockId::Hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let api = self.client.runtime_api();
+		let block_limits = api
+			.block_limits(&at)
+			.map_err(|e| Error::Execution(format!("{:?}", e)))?;
+		let value = call.value.unwrap_or_default();
+		if value > U256::from(Balance::MAX) {
+			return Err(Error::Execution("value overflows the balance type".to_string()).into());
+		}
+
+		let outcome = api
+			.trace_call(
+				&at,
+				call.from,
+				call.to,
+				call.data.map(|data| data.0).unwrap_or_default(),
+				value.low_u128(),
+				call.gas.unwrap_or(block_limits.max_gas_limit),
+				call.storage_limit.unwrap_or(block_limits.max_storage_limit),
+				call.access_list,
+				tracer_config,
+			)
+			.map_err(|e| Error::Execution(format!("{:?}", e)))?
+			.map_err(|e| Error::Execution(format!("{:?}", e)))?;
+
+		into_traces(outcome)
+			.into_iter()
+			.next()
+			.ok_or_else(|| Error::Execution("the call did not execute".to_string()).into())
+	}
+}
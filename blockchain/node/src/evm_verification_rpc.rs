@@ -0,0 +1,503 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! EVM contract verification RPC for module-evm-verification.
+//!
+//! `evm_verifyContract` compiles a solc standard-JSON input with the requested solc release
+//! and checks the runtime code against the deployed code. Its outcome is what verifiers
+//! register on-chain with `EvmVerification::verify_contract`. Only the releases pinned by the
+//! node operator with `--evm-verification-solc <version>=<sha256>` are run, after checking
+//! the checksum and the version of the binary.
+//!
+//! The ABIs of verified contracts are kept in the base path of the node, by their hash, and
+//! served with `evm_getContractAbi`. ABIs verified by other nodes are accepted with
+//! `evm_submitContractAbi` when they match the hash registered on-chain.
+
+use std::{
+	collections::BTreeMap,
+	fs,
+	io::Write,
+	path::PathBuf,
+	process::{Command, Stdio},
+	str::FromStr,
+	sync::Arc,
+};
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result as RpcResult};
+use jsonrpc_derive::rpc;
+use sc_rpc_api::DenyUnsafe;
+use serde::Serialize;
+use serde_json::{json, Value};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	hashing::{keccak_256, sha2_256},
+	Bytes, H160, H256,
+};
+use sp_runtime::generic::BlockId;
+
+pub use module_evm_rpc_runtime_api::EVMVerificationApi;
+use module_evm_rpc_runtime_api::EthereumRuntimeRPCApi;
+use setheum_primitives::evm::{is_code_reproduced, VerifiedContract};
+use setheum_runtime::{opaque::Block, AccountId, BlockNumber, Hash};
+
+/// Base code for all EVM verification RPC errors.
+const BASE_ERROR: i64 = 3100;
+/// The standard-JSON input is invalid or doesn't compile.
+const COMPILATION_ERROR: i64 = BASE_ERROR + 1;
+/// The compiled code or ABI doesn't match the chain.
+const MISMATCH_ERROR: i64 = BASE_ERROR + 2;
+/// The node failed to run solc, query the runtime or access the ABI store.
+const INTERNAL_ERROR: i64 = BASE_ERROR + 3;
+
+/// EVM verification RPC errors.
+#[derive(Debug)]
+pub enum Error {
+	/// The input is not a valid standard-JSON input for the contract.
+	InvalidInput(String),
+	/// solc reported errors.
+	Compilation(String),
+	/// The solc release is not pinned by the node.
+	UnknownCompiler(String),
+	/// The solc binary is not the pinned release.
+	CompilerMismatch(String),
+	/// The compiled runtime code is not the deployed code.
+	CodeMismatch(H160),
+	/// The ABI doesn't match the verification of the contract.
+	AbiMismatch(H160),
+	/// Running solc, querying the runtime or accessing the ABI store failed.
+	Internal(String),
+}
+
+impl From<Error> for RpcError {
+	fn from(error: Error) -> Self {
+		let (code, message) = match error {
+			Error::InvalidInput(e) => (COMPILATION_ERROR, format!("Invalid input: {}", e)),
+			Error::Compilation(e) => (COMPILATION_ERROR, format!("Compilation failed: {}", e)),
+			Error::UnknownCompiler(version) => (
+				COMPILATION_ERROR,
+				format!("solc {} is not available on this node.", version),
+			),
+			Error::CompilerMismatch(e) => (INTERNAL_ERROR, e),
+			Error::CodeMismatch(address) => (
+				MISMATCH_ERROR,
+				format!("Compiled code does not match the code deployed at {:?}.", address),
+			),
+			Error::AbiMismatch(address) => (
+				MISMATCH_ERROR,
+				format!("ABI does not match the verification of {:?}.", address),
+			),
+			Error::Internal(e) => (INTERNAL_ERROR, e),
+		};
+		RpcError {
+			code: ErrorCode::ServerError(code),
+			message,
+			data: None,
+		}
+	}
+}
+
+/// A solc release allowed to compile contracts, given as `<version>=<sha256>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolcRelease {
+	/// solc version, packed as `major << 16 | minor << 8 | patch`.
+	pub version: u32,
+	/// sha256 of the binary.
+	pub sha256: [u8; 32],
+}
+
+impl FromStr for SolcRelease {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (version, sha256) = s
+			.split_once('=')
+			.ok_or_else(|| "expected `<version>=<sha256>`".to_string())?;
+		let version = pack_version(version).ok_or_else(|| format!("invalid solc version `{}`", version))?;
+		let sha256 = from_hex(sha256)
+			.and_then(|sha256| <[u8; 32]>::try_from(sha256).ok())
+			.ok_or_else(|| format!("invalid sha256 `{}`", sha256))?;
+		Ok(SolcRelease { version, sha256 })
+	}
+}
+
+/// Node side configuration of contract verification.
+#[derive(Clone, Debug)]
+pub struct EvmVerificationDeps {
+	/// The directory of the solc releases, named `solc-v<major>.<minor>.<patch>`.
+	pub solc_dir: PathBuf,
+	/// The sha256 of the solc releases allowed to compile contracts, by version.
+	pub solc_releases: BTreeMap<u32, [u8; 32]>,
+	/// The directory ABIs are kept in.
+	pub abi_dir: PathBuf,
+}
+
+/// Outcome of a successful `evm_verifyContract`, to register with
+/// `EvmVerification::verify_contract`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationOutcome {
+	pub contract: H160,
+	pub compiled_code: Bytes,
+	pub immutable_references: Vec<(u32, u32)>,
+	pub abi_hash: H256,
+	pub source_hash: H256,
+	/// solc version, packed as `major << 16 | minor << 8 | patch`.
+	pub compiler_version: u32,
+	pub abi: Value,
+}
+
+/// Keccak-256 hash of `value` serialized as compact JSON with sorted keys.
+pub fn json_hash(value: &Value) -> H256 {
+	fn sorted(value: &Value) -> Value {
+		match value {
+			Value::Object(map) => {
+				let mut entries: Vec<_> = map.iter().collect();
+				entries.sort_by(|a, b| a.0.cmp(b.0));
+				Value::Object(entries.into_iter().map(|(k, v)| (k.clone(), sorted(v))).collect())
+			}
+			Value::Array(values) => Value::Array(values.iter().map(sorted).collect()),
+			value => value.clone(),
+		}
+	}
+
+	let json = serde_json::to_vec(&sorted(value)).expect("serializing a json value never fails; qed");
+	H256(keccak_256(&json))
+}
+
+/// Pack a `major.minor.patch` version, ignoring any suffix.
+fn pack_version(version: &str) -> Option<u32> {
+	let version = version.split(|c| c == '+' || c == '-').next()?;
+	let mut parts = version.split('.').map(|part| part.parse::<u8>().ok());
+	let (major, minor, patch) = (parts.next()??, parts.next()??, parts.next()??);
+	if parts.next().is_some() {
+		return None;
+	}
+	Some(u32::from(major) << 16 | u32::from(minor) << 8 | u32::from(patch))
+}
+
+/// Format a packed version as `major.minor.patch`.
+fn format_version(version: u32) -> String {
+	format!("{}.{}.{}", version >> 16 & 0xff, version >> 8 & 0xff, version & 0xff)
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+	let hex = hex.trim_start_matches("0x");
+	if hex.len() % 2 != 0 {
+		return None;
+	}
+	(0..hex.len())
+		.step_by(2)
+		.map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+		.collect()
+}
+
+/// Compiled artifacts of a contract.
+struct Compiled {
+	code: Vec<u8>,
+	immutable_references: Vec<(u32, u32)>,
+	abi: Value,
+}
+
+/// Compile `contract`, given as `<source name>:<contract name>`, from the standard-JSON `input`.
+fn compile(solc: &PathBuf, input: &Value, contract: &str) -> Result<Compiled, Error> {
+	let (source_name, contract_name) = contract
+		.rsplit_once(':')
+		.ok_or_else(|| Error::InvalidInput("the contract must be given as `<source name>:<contract name>`".into()))?;
+
+	// everything must be part of the input, so the compilation is offline and deterministic
+	let sources = input
+		.get("sources")
+		.and_then(Value::as_object)
+		.ok_or_else(|| Error::InvalidInput("`sources` is missing".into()))?;
+	if let Some((name, _)) = sources.iter().find(|(_, source)| !source["content"].is_string()) {
+		return Err(Error::InvalidInput(format!("source `{}` has no `content`", name)));
+	}
+	if input.get("language").and_then(Value::as_str) != Some("Solidity") {
+		return Err(Error::InvalidInput("only `Solidity` is supported".into()));
+	}
+
+	let mut input = input.clone();
+	if !input["settings"].is_object() && !input["settings"].is_null() {
+		return Err(Error::InvalidInput("`settings` must be an object".into()));
+	}
+	input["settings"]["outputSelection"] = json!({
+		source_name: {
+			contract_name: ["abi", "evm.deployedBytecode.object", "evm.deployedBytecode.immutableReferences"]
+		}
+	});
+
+	let mut child = Command::new(solc)
+		.arg("--standard-json")
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()
+		.map_err(|e| Error::Internal(format!("Failed to run solc: {}", e)))?;
+	child
+		.stdin
+		.take()
+		.expect("stdin is piped; qed")
+		.write_all(input.to_string().as_bytes())
+		.map_err(|e| Error::Internal(format!("Failed to run solc: {}", e)))?;
+	let output = child
+		.wait_with_output()
+		.map_err(|e| Error::Internal(format!("Failed to run solc: {}", e)))?;
+	let output: Value = serde_json::from_slice(&output.stdout)
+		.map_err(|e| Error::Internal(format!("Invalid solc output: {}", e)))?;
+
+	let errors: Vec<_> = output["errors"]
+		.as_array()
+		.into_iter()
+		.flatten()
+		.filter(|error| error["severity"] == "error")
+		.filter_map(|error| error["formattedMessage"].as_str().or_else(|| error["message"].as_str()))
+		.collect();
+	if !errors.is_empty() {
+		return Err(Error::Compilation(errors.join("\n")));
+	}
+
+	let artifacts = &output["contracts"][source_name][contract_name];
+	if artifacts.is_null() {
+		return Err(Error::InvalidInput(format!("contract `{}` not found", contract)));
+	}
+	// unlinked libraries are left as placeholders, which are not hex
+	let code = artifacts["evm"]["deployedBytecode"]["object"]
+		.as_str()
+		.and_then(from_hex)
+		.ok_or_else(|| Error::InvalidInput("the code is not complete, are all libraries linked?".into()))?;
+	let mut immutable_references = artifacts["evm"]["deployedBytecode"]["immutableReferences"]
+		.as_object()
+		.into_iter()
+		.flat_map(|references| references.values())
+		.filter_map(Value::as_array)
+		.flatten()
+		.map(|reference| {
+			let start = reference["start"].as_u64().and_then(|v| u32::try_from(v).ok());
+			let length = reference["length"].as_u64().and_then(|v| u32::try_from(v).ok());
+			start.zip(length)
+		})
+		.collect::<Option<Vec<_>>>()
+		.ok_or_else(|| Error::Internal("Invalid immutable references in solc output".into()))?;
+	// references are grouped by variable, the chain expects them in code order
+	immutable_references.sort_unstable();
+
+	Ok(Compiled {
+		code,
+		immutable_references,
+		abi: artifacts["abi"].clone(),
+	})
+}
+
+/// The version of the solc binary.
+fn solc_version(solc: &PathBuf) -> Result<u32, Error> {
+	let output = Command::new(solc)
+		.arg("--version")
+		.output()
+		.map_err(|e| Error::Internal(format!("Failed to run solc: {}", e)))?;
+	// the last line is `Version: 0.8.24+commit.e11b9ed9.Linux.g++`
+	String::from_utf8_lossy(&output.stdout)
+		.lines()
+		.find_map(|line| line.strip_prefix("Version: "))
+		.and_then(pack_version)
+		.ok_or_else(|| Error::Internal("Failed to read the solc version".into()))
+}
+
+/// EVM verification RPC methods.
+#[rpc]
+pub trait EvmVerificationApi {
+	/// Compile `contract`, given as `<source name>:<contract name>`, from the solc
+	/// standard-JSON `input` with solc `compiler_version` and check it against the code
+	/// deployed at `address` in the given block, or the best block if none is given. The ABI
+	/// is kept by the node.
+	#[rpc(name = "evm_verifyContract")]
+	fn verify_contract(
+		&self,
+		address: H160,
+		contract: String,
+		input: Value,
+		compiler_version: String,
+		at: Option<Hash>,
+	) -> RpcResult<VerificationOutcome>;
+
+	/// The verification of the current code of `address`.
+	#[rpc(name = "evm_getVerifiedContract")]
+	fn verified_contract(&self, address: H160, at: Option<Hash>) -> RpcResult<Option<VerifiedContract<AccountId, BlockNumber>>>;
+
+	/// The ABI of the contract at `address`, if it's verified and the node has its ABI.
+	#[rpc(name = "evm_getContractAbi")]
+	fn contract_abi(&self, address: H160, at: Option<Hash>) -> RpcResult<Option<Value>>;
+
+	/// Keep the ABI of the verified contract at `address`, which must match the verification
+	/// in the best block. Returns the hash of the ABI.
+	#[rpc(name = "evm_submitContractAbi")]
+	fn submit_contract_abi(&self, address: H160, abi: Value) -> RpcResult<H256>;
+}
+
+/// Implementation of the EVM verification RPC.
+pub struct EvmVerification<C> {
+	client: Arc<C>,
+	deps: EvmVerificationDeps,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<C> EvmVerification<C> {
+	pub fn new(client: Arc<C>, deps: EvmVerificationDeps, deny_unsafe: DenyUnsafe) -> Self {
+		EvmVerification {
+			client,
+			deps,
+			deny_unsafe,
+		}
+	}
+
+	/// The binary of the pinned solc `version`, checked against its checksum and version.
+	fn solc(&self, version: u32) -> Result<PathBuf, Error> {
+		let sha256 = self
+			.deps
+			.solc_releases
+			.get(&version)
+			.ok_or_else(|| Error::UnknownCompiler(format_version(version)))?;
+		let solc = self.deps.solc_dir.join(format!("solc-v{}", format_version(version)));
+
+		let binary = fs::read(&solc).map_err(|e| Error::Internal(format!("Failed to read {:?}: {}", solc, e)))?;
+		if sha2_256(&binary) != *sha256 {
+			return Err(Error::CompilerMismatch(format!(
+				"{:?} does not match the checksum pinned for solc {}",
+				solc,
+				format_version(version)
+			)));
+		}
+		let actual_version = solc_version(&solc)?;
+		if actual_version != version {
+			return Err(Error::CompilerMismatch(format!(
+				"{:?} is solc {}, not {}",
+				solc,
+				format_version(actual_version),
+				format_version(version)
+			)));
+		}
+
+		Ok(solc)
+	}
+
+	fn abi_path(&self, abi_hash: &H256) -> PathBuf {
+		self.deps.abi_dir.join(format!("{:x}.json", abi_hash))
+	}
+
+	fn store_abi(&self, abi_hash: &H256, abi: &Value) -> Result<(), Error> {
+		fs::create_dir_all(&self.deps.abi_dir)
+			.and_then(|_| fs::write(self.abi_path(abi_hash), abi.to_string()))
+			.map_err(|e| Error::Internal(format!("Failed to store the ABI: {}", e)))
+	}
+}
+
+impl<C> EvmVerification<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: EVMVerificationApi<Block, AccountId, BlockNumber>,
+{
+	fn query_verification(&self, address: H160, at: Option<Hash>) -> Result<Option<VerifiedContract<AccountId, BlockNumber>>, Error> {
+		let at = BlockId::Hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client
+			.runtime_api()
+			.verified_contract(&at, address)
+			.map_err(|e| Error::Internal(format!("{:?}", e)))
+	}
+}
+
+impl<C> EvmVerificationApi for EvmVerification<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: EVMVerificationApi<Block, AccountId, BlockNumber>,
+	C::Api: EthereumRuntimeRPCApi<Block>,
+{
+	fn verify_contract(
+		&self,
+		address: H160,
+		contract: String,
+		input: Value,
+		compiler_version: String,
+		at: Option<Hash>,
+	) -> RpcResult<VerificationOutcome> {
+		// compiling is expensive, only verifiers are expected to do it on their node
+		self.deny_unsafe.check_if_safe()?;
+
+		let compiler_version = pack_version(&compiler_version)
+			.ok_or_else(|| Error::InvalidInput(format!("invalid solc version `{}`", compiler_version)))?;
+		let solc = self.solc(compiler_version)?;
+
+		let at = BlockId::Hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let deployed = self
+			.client
+			.runtime_api()
+			.code_at(&at, address)
+			.map_err(|e| Error::Internal(format!("{:?}", e)))?;
+
+		let compiled = compile(&solc, &input, &contract)?;
+		if !is_code_reproduced(&deployed, &compiled.code, &compiled.immutable_references) {
+			return Err(Error::CodeMismatch(address).into());
+		}
+
+		let abi_hash = json_hash(&compiled.abi);
+		self.store_abi(&abi_hash, &compiled.abi)?;
+
+		Ok(VerificationOutcome {
+			contract: address,
+			compiled_code: compiled.code.into(),
+			immutable_references: compiled.immutable_references,
+			abi_hash,
+			source_hash: json_hash(&input),
+			compiler_version,
+			abi: compiled.abi,
+		})
+	}
+
+	fn verified_contract(&self, address: H160, at: Option<Hash>) -> RpcResult<Option<VerifiedContract<AccountId, BlockNumber>>> {
+		Ok(self.query_verification(address, at)?)
+	}
+
+	fn contract_abi(&self, address: H160, at: Option<Hash>) -> RpcResult<Option<Value>> {
+		let verification = match self.query_verification(address, at)? {
+			Some(verification) => verification,
+			None => return Ok(None),
+		};
+
+		// a missing or corrupted ABI is as good as unknown
+		let abi = fs::read(self.abi_path(&verification.abi_hash))
+			.ok()
+			.and_then(|abi| serde_json::from_slice::<Value>(&abi).ok())
+			.filter(|abi| json_hash(abi) == verification.abi_hash);
+		Ok(abi)
+	}
+
+	fn submit_contract_abi(&self, address: H160, abi: Value) -> RpcResult<H256> {
+		let verification = self
+			.query_verification(address, None)?
+			.ok_or(Error::AbiMismatch(address))?;
+
+		let abi_hash = json_hash(&abi);
+		if abi_hash != verification.abi_hash {
+			return Err(Error::AbiMismatch(address).into());
+		}
+		self.store_abi(&abi_hash, &abi)?;
+
+		Ok(abi_hash)
+	}
+}
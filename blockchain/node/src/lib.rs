@@ -22,6 +22,10 @@
 #![allow(unused_parens)]
 #![allow(unused_imports)]
 
+pub mod aleph_node_rpc;
 pub mod chain_spec;
+pub mod eth_rpc;
+pub mod evm_tracing_rpc;
+pub mod evm_verification_rpc;
 pub mod service;
 pub mod rpc;
@@ -21,7 +21,11 @@
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
 #![allow(unused_imports)]
+mod aleph_node_rpc;
 mod chain_spec;
+mod eth_rpc;
+mod evm_tracing_rpc;
+mod evm_verification_rpc;
 #[macro_use]
 mod service;
 mod cli;
@@ -45,6 +45,12 @@ use sc_finality_grandpa::{
 };
 pub use evm_rpc::{EVMApi, EVMApiServer, EVMRuntimeRPCApi};
 pub use sc_rpc::SubscriptionTaskExecutor;
+use setheum_primitives::aleph::AlephSessionApi;
+
+pub use crate::aleph_node_rpc::AlephDeps;
+pub use crate::eth_rpc::EthereumRuntimeRPCApi;
+pub use crate::evm_tracing_rpc::EVMTraceApi;
+pub use crate::evm_verification_rpc::{EVMVerificationApi, EvmVerificationDeps};
 
 /// Extra dependencies for BABE.
 pub struct BabeDeps {
@@ -84,6 +90,10 @@ pub struct FullDeps<C, P, SC, B> {
 	pub babe: BabeDeps,
 	/// GRANDPA specific dependencies.
 	pub grandpa: GrandpaDeps<B>,
+	/// Aleph specific dependencies, present if the node runs the Aleph finality gadget.
+	pub aleph: Option<AlephDeps>,
+	/// Contract verification dependencies, present if the node serves the verification RPC.
+	pub evm_verification: Option<EvmVerificationDeps>,
 }
 
 /// Instantiate all full RPC extensions.
@@ -92,14 +102,19 @@ pub fn create_full<C, P, SC, B>(
 ) -> Result<jsonrpc_core::IoHandler<sc_rpc::Metadata>, Box<dyn std::error::Error + Send + Sync>> where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error=BlockChainError> + 'static,
+	C: sc_client_api::BlockBackend<Block>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>,
 	C::Api: EVMRuntimeRPCApi<Block, Balance>,
+	C::Api: EVMTraceApi<Block, Balance>,
+	C::Api: EthereumRuntimeRPCApi<Block>,
+	C::Api: EVMVerificationApi<Block, AccountId, BlockNumber>,
 	C::Api: sp_consensus_babe::BabeApi<Block>,
+	C::Api: AlephSessionApi<Block>,
 	C::Api: BlockBuilder<Block>,
-	P: TransactionPool + Sync + Send + 'static,
+	P: TransactionPool<Block = Block> + Sync + Send + 'static,
 	SC: sp_consensus::SelectChain<Block> + 'static,
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 	B::State: sc_client_api::StateBackend<sp_runtime::traits::HashFor<Block>>,
@@ -110,6 +125,10 @@ pub fn create_full<C, P, SC, B>(
 
 	use sc_consensus_babe_rpc::BabeRpcHandler;
 	use sc_finality_grandpa_rpc::{GrandpaApi, GrandpaRpcHandler};
+	use crate::aleph_node_rpc::{AlephNode, AlephNodeApi};
+	use crate::eth_rpc::{Eth, EthApi};
+	use crate::evm_tracing_rpc::{EvmTracing, EvmTracingApi};
+	use crate::evm_verification_rpc::{EvmVerification, EvmVerificationApi};
 
 
 	let mut io = jsonrpc_core::IoHandler::default();
@@ -120,6 +139,8 @@ pub fn create_full<C, P, SC, B>(
 		deny_unsafe,
 		babe,
 		grandpa,
+		aleph,
+		evm_verification,
 	} = deps;
 	let BabeDeps {
 		keystore,
@@ -136,7 +157,7 @@ pub fn create_full<C, P, SC, B>(
 
 
 	io.extend_with(
-		SystemApi::to_delegate(FullSystem::new(client.clone(), pool, deny_unsafe))
+		SystemApi::to_delegate(FullSystem::new(client.clone(), pool.clone(), deny_unsafe))
 	);
 
 	io.extend_with(
@@ -156,6 +177,14 @@ pub fn create_full<C, P, SC, B>(
 		select_chain,
 		deny_unsafe,
 	)));
+	if let Some(aleph) = aleph {
+		io.extend_with(AlephNodeApi::to_delegate(AlephNode::new(
+			client.clone(),
+			aleph,
+			subscription_executor.clone(),
+			deny_unsafe,
+		)));
+	}
 	io.extend_with(GrandpaApi::to_delegate(GrandpaRpcHandler::new(
 		shared_authority_set,
 		shared_voter_state,
@@ -163,7 +192,16 @@ pub fn create_full<C, P, SC, B>(
 		subscription_executor,
 		finality_provider,
 	)));
-	io.extend_with(EVMApiServer::to_delegate(EVMApi::new(client, deny_unsafe)));
+	io.extend_with(EVMApiServer::to_delegate(EVMApi::new(client.clone(), deny_unsafe)));
+	io.extend_with(EvmTracingApi::to_delegate(EvmTracing::new(client.clone(), deny_unsafe)));
+	if let Some(evm_verification) = evm_verification {
+		io.extend_with(EvmVerificationApi::to_delegate(EvmVerification::new(
+			client.clone(),
+			evm_verification,
+			deny_unsafe,
+		)));
+	}
+	io.extend_with(EthApi::to_delegate(Eth::new(client, pool)));
 
 	Ok(io)
 }
\ No newline at end of file
@@ -28,6 +28,10 @@ pub use sc_executor::NativeExecutor;
 use sc_finality_grandpa::SharedVoterState;
 use sc_keystore::LocalKeystore;
 use sc_telemetry::{Telemetry, TelemetryWorker};
+use setheum_primitives::aleph::{AlephSessionApi, DEFAULT_MAX_NON_FINALIZED_BLOCKS};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::generic::BlockId;
 
 use sc_finality_grandpa::FinalityProofProvider;
 
@@ -150,7 +154,10 @@ fn remote_keystore(_url: &str) -> Result<Arc<LocalKeystore>, &'static str> {
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn new_full(
+	mut config: Configuration,
+	solc_releases: Vec<crate::evm_verification_rpc::SolcRelease>,
+) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -218,11 +225,57 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 	let babe_config = babe_link.config().clone();
 	let shared_epoch_changes = babe_link.epoch_changes().clone();
 
+	// Blocks are finalized by GRANDPA, so emergency justifications submitted over RPC are verified
+	// and applied by the node itself.
+	let aleph = {
+		let justification_notifications = finality_aleph::JustificationNotifications::new();
+		let justifications = finality_aleph::ChannelProvider::new();
+		let chain_status = finality_aleph::SubstrateChainStatus::new(backend.clone())
+			.map_err(|e| ServiceError::Other(format!("Failed to read the chain status: {:?}", e)))?;
+		let session_period = client
+			.runtime_api()
+			.session_period(&BlockId::Hash(client.info().best_hash))
+			.map_err(|e| ServiceError::Other(format!("Failed to read the session period: {:?}", e)))?;
+		let deps = crate::rpc::AlephDeps {
+			justification_notifications: justification_notifications.clone(),
+			justification_translator: finality_aleph::JustificationTranslator::new(chain_status),
+			justifications_for_sync: justifications.get_sender(),
+			session_period: finality_aleph::SessionPeriod(session_period),
+			max_finality_lag: DEFAULT_MAX_NON_FINALIZED_BLOCKS,
+			// Shared with `ProposalPolicyConfig::pinned_branch` of the gadget once it runs on this node.
+			pinned_branch: finality_aleph::PinnedBranch::default(),
+			// Shared with `AlephConfig::external_addresses` of the gadget once it runs on this node.
+			external_addresses: finality_aleph::ExternalAddresses::default(),
+		};
+		task_manager.spawn_handle().spawn(
+			"aleph-emergency-finalization",
+			finality_aleph::run_emergency_finalization(
+				client.clone(),
+				justifications.into_receiver(),
+				justification_notifications,
+			),
+		);
+		deps
+	};
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
 		let keystore = keystore_container.sync_keystore();
 		let select_chain = select_chain.clone();
+		// ABIs of verified contracts are kept with the node data, and contracts are compiled with
+		// the pinned solc releases shipped in the `solc` directory next to the node binary.
+		let evm_verification = config.base_path.as_ref().and_then(|base_path| {
+			let solc_dir = std::env::current_exe().ok()?.with_file_name("solc");
+			Some(crate::rpc::EvmVerificationDeps {
+				solc_dir,
+				solc_releases: solc_releases
+					.into_iter()
+					.map(|release| (release.version, release.sha256))
+					.collect(),
+				abi_dir: base_path.path().join("evm-verification"),
+			})
+		});
 
 		Box::new(move |deny_unsafe, subscription_executor| {
 			let deps = crate::rpc::FullDeps {
@@ -242,6 +295,8 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 					subscription_executor,
 					finality_provider: finality_proof_provider.clone(),
 				},
+				aleph: Some(aleph.clone()),
+				evm_verification: evm_verification.clone(),
 			};
 
 			crate::rpc::create_full(deps).map_err(Into::into)
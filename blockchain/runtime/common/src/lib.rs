@@ -47,8 +47,8 @@ use static_assertions::const_assert;
 
 pub mod precompile;
 pub use precompile::{
-	AllPrecompiles, DexPrecompile, MultiCurrencyPrecompile, NFTPrecompile, OraclePrecompile, ScheduleCallPrecompile,
-	StateRentPrecompile,
+	AllPrecompiles, DexPrecompile, EcdpPrecompile, FlashMintPrecompile, IncentivesPrecompile, LaunchpadPrecompile,
+	MultiCurrencyPrecompile, NFTPrecompile, OraclePrecompile, ScheduleCallPrecompile, StateRentPrecompile,
 };
 pub use primitives::{
 	currency::{TokenInfo, SEE, SERP, DNAR, HELP, SETR, SETUSD},
@@ -0,0 +1,189 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::input::{Input, InputT, Output};
+use crate::precompile::PrecompileOutput;
+use frame_support::log;
+use module_evm::{Context, ExitError, ExitSucceed, Precompile};
+use module_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, EcdpUssdManager};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use primitives::{Amount, Balance, CurrencyId, EcdpPosition};
+use sp_runtime::{FixedPointNumber, RuntimeDebug};
+use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
+
+/// The `ECDP` impl precompile.
+///
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Adjust loan. Rest `input` bytes: `who`, `currency_id`, `collateral_adjustment`,
+///   `debit_adjustment`.
+/// - Close loan by DEX. Rest `input` bytes: `who`, `currency_id`, `max_collateral_amount`.
+/// - Get position. Rest `input` bytes: `who`, `currency_id`.
+/// - Get collateral params. Rest `input` bytes: `currency_id`.
+/// - Get liquidation ratio. Rest `input` bytes: `currency_id`.
+/// - Get current collateral ratio. Rest `input` bytes: `who`, `currency_id`.
+pub struct EcdpPrecompile<AccountId, AddressMapping, CurrencyIdMapping, EcdpManager>(
+	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, EcdpManager)>,
+);
+
+#[primitives_proc_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	AdjustLoan = "adjustLoan(address,address,int128,int128)",
+	CloseLoanByDex = "closeLoanByDex(address,address,uint256)",
+	GetPosition = "getPosition(address,address)",
+	GetCollateralParams = "getCollateralParams(address)",
+	GetLiquidationRatio = "getLiquidationRatio(address)",
+	GetCurrentCollateralRatio = "getCurrentCollateralRatio(address,address)",
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, EcdpManager> Precompile
+	for EcdpPrecompile<AccountId, AddressMapping, CurrencyIdMapping, EcdpManager>
+where
+	AccountId: Debug + Clone,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	EcdpManager: EcdpUssdManager<AccountId, CurrencyId, Amount, Balance>,
+{
+	fn execute(
+		input: &[u8],
+		_target_gas: Option<u64>,
+		_context: &Context,
+	) -> result::Result<PrecompileOutput, ExitError> {
+		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(input);
+
+		let action = input.action()?;
+
+		match action {
+			Action::AdjustLoan => {
+				let who = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+				let collateral_adjustment = input.amount_at(3)?;
+				let debit_adjustment = input.amount_at(4)?;
+				log::debug!(
+					target: "evm",
+					"ecdp: adjust_loan who: {:?}, currency_id: {:?}, collateral_adjustment: {:?}, debit_adjustment: {:?}",
+					who, currency_id, collateral_adjustment, debit_adjustment
+				);
+
+				EcdpManager::adjust_loan(&who, currency_id, collateral_adjustment, debit_adjustment).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: vec![],
+					logs: Default::default(),
+				})
+			}
+			Action::CloseLoanByDex => {
+				let who = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+				let max_collateral_amount = input.balance_at(3)?;
+				log::debug!(
+					target: "evm",
+					"ecdp: close_loan_by_dex who: {:?}, currency_id: {:?}, max_collateral_amount: {:?}",
+					who, currency_id, max_collateral_amount
+				);
+
+				EcdpManager::close_loan_by_dex(who, currency_id, max_collateral_amount).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: vec![],
+					logs: Default::default(),
+				})
+			}
+			Action::GetPosition => {
+				let who = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+				log::debug!(
+					target: "evm",
+					"ecdp: get_position who: {:?}, currency_id: {:?}",
+					who, currency_id
+				);
+
+				let EcdpPosition { collateral, debit } = EcdpManager::get_position(&who, currency_id);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u128_tuple(collateral, debit),
+					logs: Default::default(),
+				})
+			}
+			Action::GetCollateralParams => {
+				let currency_id = input.currency_id_at(1)?;
+				log::debug!(target: "evm", "ecdp: get_collateral_params currency_id: {:?}", currency_id);
+
+				let params = EcdpManager::get_collateral_parameters(currency_id);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u256_array(params),
+					logs: Default::default(),
+				})
+			}
+			Action::GetLiquidationRatio => {
+				let currency_id = input.currency_id_at(1)?;
+				log::debug!(target: "evm", "ecdp: get_liquidation_ratio currency_id: {:?}", currency_id);
+
+				let ratio = EcdpManager::get_liquidation_ratio(currency_id)
+					.ok_or_else(|| ExitError::Other("ECDP get_liquidation_ratio failed".into()))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u128(ratio.into_inner()),
+					logs: Default::default(),
+				})
+			}
+			Action::GetCurrentCollateralRatio => {
+				let who = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+				log::debug!(
+					target: "evm",
+					"ecdp: get_current_collateral_ratio who: {:?}, currency_id: {:?}",
+					who, currency_id
+				);
+
+				let ratio = EcdpManager::get_current_collateral_ratio(&who, currency_id)
+					.ok_or_else(|| ExitError::Other("ECDP get_current_collateral_ratio failed".into()))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u128(ratio.into_inner()),
+					logs: Default::default(),
+				})
+			}
+		}
+	}
+}
@@ -0,0 +1,127 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+
+use super::input::{Input, InputT, Output};
+use crate::precompile::PrecompileOutput;
+use frame_support::log;
+use module_evm::{Context, ExitError, ExitSucceed, Precompile};
+use module_support::{
+	evm::limits::flash_mint, AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, FlashMint,
+};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use primitives::Balance;
+use sp_runtime::RuntimeDebug;
+use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
+
+/// The `FlashMint` impl precompile.
+///
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Get max flash mint amount.
+/// - Get flash fee. Rest `input` bytes: `amount`.
+/// - Flash mint. Rest `input` bytes: `who`, `receiver`, `amount`, `data`. Costs the gas limit of
+///   the `onFlashMint` callback of the receiver, which runs with that limit whatever gas is left.
+pub struct FlashMintPrecompile<AccountId, AddressMapping, CurrencyIdMapping, FlashMintT>(
+	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, FlashMintT)>,
+);
+
+#[primitives_proc_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	MaxFlashMint = "maxFlashMint()",
+	FlashFee = "flashFee(uint256)",
+	FlashMint = "flashMint(address,address,uint256,bytes)",
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, FlashMintT> Precompile
+	for FlashMintPrecompile<AccountId, AddressMapping, CurrencyIdMapping, FlashMintT>
+where
+	AccountId: Debug + Clone,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	FlashMintT: FlashMint<AccountId, Balance>,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> result::Result<PrecompileOutput, ExitError> {
+		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(input);
+
+		let action = input.action()?;
+
+		match action {
+			Action::MaxFlashMint => {
+				let value = FlashMintT::max_flash_mint();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u128(value),
+					logs: Default::default(),
+				})
+			}
+			Action::FlashFee => {
+				let amount = input.balance_at(1)?;
+				log::debug!(target: "evm", "flash_mint: flash_fee amount: {:?}", amount);
+
+				let value = FlashMintT::flash_fee(amount);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u128(value),
+					logs: Default::default(),
+				})
+			}
+			Action::FlashMint => {
+				let cost = flash_mint::ON_FLASH_MINT.gas;
+				if target_gas.map_or(false, |gas| gas < cost) {
+					return Err(ExitError::OutOfGas);
+				}
+
+				let who = input.account_id_at(1)?;
+				let receiver = input.evm_address_at(2)?;
+				let amount = input.balance_at(3)?;
+				// solidity abi encode bytes will add an offset at input[4]
+				let data_len = input.u32_at(5)?;
+				let data = input.bytes_at(6, data_len as usize)?;
+				log::debug!(
+					target: "evm",
+					"flash_mint: flash_mint who: {:?}, receiver: {:?}, amount: {:?}, data: {:?}",
+					who, receiver, amount, data
+				);
+
+				let fee = FlashMintT::flash_mint_to_contract(&who, receiver, amount, data).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost,
+					output: Output::default().encode_u128(fee),
+					logs: Default::default(),
+				})
+			}
+		}
+	}
+}
@@ -0,0 +1,255 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::input::{Input, InputT, Output};
+use crate::precompile::PrecompileOutput;
+use frame_support::log;
+use module_evm::{Context, ExitError, ExitSucceed, Log, Precompile};
+use module_evm_utility_macro::keccak256;
+use module_support::{
+	AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, IncentivesManager, PoolId,
+};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use primitives::{Balance, CurrencyId};
+use sp_core::{H256, U256};
+use sp_runtime::{FixedPointNumber, RuntimeDebug};
+use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
+
+/// The `Incentives` impl precompile.
+///
+///
+/// `input` data starts with `action`.
+///
+/// Pools are addressed by `pool_type` and `pool_currency_id`, `pool_type` being:
+/// 0 - `EcdpSetrLiquidityRewards`, 1 - `EcdpUssdLiquidityRewards`, 2 - `EdfisLiquidityRewards`,
+/// 3 - `MoyaEarnRewards`.
+///
+/// Actions:
+/// - Get incentive reward amount. Rest `input` bytes: `pool_type`, `pool_currency_id`,
+///   `reward_currency_id`.
+/// - Get claim reward deduction rate. Rest `input` bytes: `pool_type`, `pool_currency_id`.
+/// - Get pending rewards. Rest `input` bytes: `pool_type`, `pool_currency_id`, `who`,
+///   `reward_currency_ids`.
+/// - Deposit DEX share. Rest `input` bytes: `who`, `lp_currency_id`, `amount`.
+/// - Withdraw DEX share. Rest `input` bytes: `who`, `lp_currency_id`, `amount`.
+/// - Claim rewards. Rest `input` bytes: `who`, `pool_type`, `pool_currency_id`.
+pub struct IncentivesPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Incentives>(
+	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, Incentives)>,
+);
+
+#[primitives_proc_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	GetIncentiveRewardAmount = "getIncentiveRewardAmount(uint8,address,address)",
+	GetClaimRewardDeductionRate = "getClaimRewardDeductionRate(uint8,address)",
+	GetPendingRewards = "getPendingRewards(uint8,address,address,address[])",
+	DepositDexShare = "depositDexShare(address,address,uint256)",
+	WithdrawDexShare = "withdrawDexShare(address,address,uint256)",
+	ClaimRewards = "claimRewards(address,uint8,address)",
+}
+
+/// `DepositedShare(address indexed who, address indexed lpCurrency, uint256 amount)`
+const DEPOSITED_SHARE: &[u8; 32] = keccak256!("DepositedShare(address,address,uint256)");
+/// `WithdrawnShare(address indexed who, address indexed lpCurrency, uint256 amount)`
+const WITHDRAWN_SHARE: &[u8; 32] = keccak256!("WithdrawnShare(address,address,uint256)");
+/// `ClaimedRewards(address indexed who, address indexed poolCurrency, uint8 poolType)`
+const CLAIMED_REWARDS: &[u8; 32] = keccak256!("ClaimedRewards(address,address,uint8)");
+
+fn pool_id(pool_type: u32, currency_id: CurrencyId) -> result::Result<PoolId, ExitError> {
+	match pool_type {
+		0 => Ok(PoolId::EcdpSetrLiquidityRewards(currency_id)),
+		1 => Ok(PoolId::EcdpUssdLiquidityRewards(currency_id)),
+		2 => Ok(PoolId::EdfisLiquidityRewards(currency_id)),
+		3 => Ok(PoolId::MoyaEarnRewards(currency_id)),
+		_ => Err(ExitError::Other("invalid pool type".into())),
+	}
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, Incentives> Precompile
+	for IncentivesPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Incentives>
+where
+	AccountId: Debug + Clone,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	Incentives: IncentivesManager<AccountId, Balance, CurrencyId, PoolId>,
+{
+	fn execute(
+		input: &[u8],
+		_target_gas: Option<u64>,
+		context: &Context,
+	) -> result::Result<PrecompileOutput, ExitError> {
+		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(input);
+
+		let action = input.action()?;
+
+		match action {
+			Action::GetIncentiveRewardAmount => {
+				let pool_id = pool_id(input.u32_at(1)?, input.currency_id_at(2)?)?;
+				let reward_currency_id = input.currency_id_at(3)?;
+				log::debug!(
+					target: "evm",
+					"incentives: get_incentive_reward_amount pool_id: {:?}, reward_currency_id: {:?}",
+					pool_id, reward_currency_id
+				);
+
+				let value = Incentives::get_incentive_reward_amount(pool_id, reward_currency_id);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u128(value),
+					logs: Default::default(),
+				})
+			}
+			Action::GetClaimRewardDeductionRate => {
+				let pool_id = pool_id(input.u32_at(1)?, input.currency_id_at(2)?)?;
+				log::debug!(
+					target: "evm",
+					"incentives: get_claim_reward_deduction_rate pool_id: {:?}",
+					pool_id
+				);
+
+				let rate = Incentives::get_claim_reward_deduction_rate(pool_id);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u128(rate.into_inner()),
+					logs: Default::default(),
+				})
+			}
+			Action::GetPendingRewards => {
+				let pool_id = pool_id(input.u32_at(1)?, input.currency_id_at(2)?)?;
+				let who = input.account_id_at(3)?;
+				// solidity abi enocde array will add an offset at input[4]
+				let reward_currencies_len = input.u32_at(5)?;
+				let mut reward_currencies = vec![];
+				for i in 0..reward_currencies_len {
+					reward_currencies.push(input.currency_id_at((6 + i) as usize)?);
+				}
+				log::debug!(
+					target: "evm",
+					"incentives: get_pending_rewards pool_id: {:?}, who: {:?}, reward_currencies: {:?}",
+					pool_id, who, reward_currencies
+				);
+
+				let value = Incentives::get_pending_rewards(pool_id, who, reward_currencies);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u256_array(value.into_iter().map(U256::from).collect()),
+					logs: Default::default(),
+				})
+			}
+			Action::DepositDexShare => {
+				let who = input.account_id_at(1)?;
+				let lp_currency_id = input.currency_id_at(2)?;
+				let amount = input.balance_at(3)?;
+				log::debug!(
+					target: "evm",
+					"incentives: deposit_dex_share who: {:?}, lp_currency_id: {:?}, amount: {:?}",
+					who, lp_currency_id, amount
+				);
+
+				Incentives::deposit_dex_share(&who, lp_currency_id, amount).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: vec![],
+					logs: vec![Log {
+						address: context.address,
+						topics: vec![
+							H256::from_slice(DEPOSITED_SHARE),
+							input.evm_address_at(1)?.into(),
+							input.evm_address_at(2)?.into(),
+						],
+						data: Output::default().encode_u128(amount),
+					}],
+				})
+			}
+			Action::WithdrawDexShare => {
+				let who = input.account_id_at(1)?;
+				let lp_currency_id = input.currency_id_at(2)?;
+				let amount = input.balance_at(3)?;
+				log::debug!(
+					target: "evm",
+					"incentives: withdraw_dex_share who: {:?}, lp_currency_id: {:?}, amount: {:?}",
+					who, lp_currency_id, amount
+				);
+
+				Incentives::withdraw_dex_share(&who, lp_currency_id, amount).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: vec![],
+					logs: vec![Log {
+						address: context.address,
+						topics: vec![
+							H256::from_slice(WITHDRAWN_SHARE),
+							input.evm_address_at(1)?.into(),
+							input.evm_address_at(2)?.into(),
+						],
+						data: Output::default().encode_u128(amount),
+					}],
+				})
+			}
+			Action::ClaimRewards => {
+				let who = input.account_id_at(1)?;
+				let pool_type = input.u32_at(2)?;
+				let pool_id = pool_id(pool_type, input.currency_id_at(3)?)?;
+				log::debug!(
+					target: "evm",
+					"incentives: claim_rewards who: {:?}, pool_id: {:?}",
+					who, pool_id
+				);
+
+				Incentives::claim_rewards(who, pool_id).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: vec![],
+					logs: vec![Log {
+						address: context.address,
+						topics: vec![
+							H256::from_slice(CLAIMED_REWARDS),
+							input.evm_address_at(1)?.into(),
+							input.evm_address_at(3)?.into(),
+						],
+						data: Output::default().encode_u32(pool_type),
+					}],
+				})
+			}
+		}
+	}
+}
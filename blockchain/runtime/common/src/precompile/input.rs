@@ -222,6 +222,11 @@ impl Output {
 		let out = Token::Address(H160::from_slice(b.as_bytes()));
 		ethabi::encode(&[out])
 	}
+
+	pub fn encode_u256_array(&self, b: Vec<U256>) -> Vec<u8> {
+		let out = Token::Array(b.into_iter().map(Token::Uint).collect());
+		ethabi::encode(&[out])
+	}
 }
 
 #[cfg(test)]
@@ -345,4 +350,20 @@ mod tests {
 		let input = TestInput::new(&raw_input[..]);
 		assert_ok!(input.u64_at(1), u64_num);
 	}
+
+	#[test]
+	fn encode_u256_array_works() {
+		let mut expected = [0u8; 4 * 32];
+		// offset
+		U256::from(32).to_big_endian(&mut expected[0..32]);
+		// length
+		U256::from(2).to_big_endian(&mut expected[32..64]);
+		U256::from(1).to_big_endian(&mut expected[64..96]);
+		U256::MAX.to_big_endian(&mut expected[96..128]);
+
+		assert_eq!(
+			Output::default().encode_u256_array(vec![U256::from(1), U256::MAX]),
+			expected.to_vec()
+		);
+	}
 }
@@ -0,0 +1,253 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::input::{Input, InputT, Output};
+use crate::precompile::PrecompileOutput;
+use frame_support::log;
+use module_evm::{Context, ExitError, ExitSucceed, Log, Precompile};
+use module_evm_utility_macro::keccak256;
+use module_support::{AddressMapping as AddressMappingT, CampaignManager, CurrencyIdMapping as CurrencyIdMappingT};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use primitives::{Balance, CurrencyId};
+use sp_core::{H256, U256};
+use sp_runtime::RuntimeDebug;
+use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
+
+/// The `Launchpad` impl precompile.
+///
+///
+/// `input` data starts with `action`.
+///
+/// Campaigns are identified by the currency id of their sale token.
+///
+/// Actions:
+/// - Contribute. Rest `input` bytes: `who`, `campaign_id`, `amount`.
+/// - Claim contribution allocation. Rest `input` bytes: `who`, `campaign_id`.
+/// - Claim campaign fundraise. Rest `input` bytes: `who`, `campaign_id`.
+/// - Get campaign. Rest `input` bytes: `campaign_id`.
+/// - Get contribution. Rest `input` bytes: `who`, `campaign_id`.
+pub struct LaunchpadPrecompile<AccountId, AddressMapping, CurrencyIdMapping, BlockNumber, Launchpad>(
+	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, BlockNumber, Launchpad)>,
+);
+
+#[primitives_proc_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	Contribute = "contribute(address,address,uint256)",
+	ClaimContributionAllocation = "claimContributionAllocation(address,address)",
+	ClaimCampaignFundraise = "claimCampaignFundraise(address,address)",
+	GetCampaign = "getCampaign(address)",
+	GetContribution = "getContribution(address,address)",
+}
+
+/// `Contributed(address indexed who, address indexed campaign, uint256 amount)`
+const CONTRIBUTED: &[u8; 32] = keccak256!("Contributed(address,address,uint256)");
+/// `ClaimedAllocation(address indexed who, address indexed campaign, uint256 allocation)`
+const CLAIMED_ALLOCATION: &[u8; 32] = keccak256!("ClaimedAllocation(address,address,uint256)");
+/// `ClaimedFundraise(address indexed who, address indexed campaign, uint256 raised)`
+const CLAIMED_FUNDRAISE: &[u8; 32] = keccak256!("ClaimedFundraise(address,address,uint256)");
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, BlockNumber, Launchpad> Precompile
+	for LaunchpadPrecompile<AccountId, AddressMapping, CurrencyIdMapping, BlockNumber, Launchpad>
+where
+	AccountId: Debug + Clone + PartialEq,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	BlockNumber: Into<U256>,
+	Launchpad: CampaignManager<AccountId, BlockNumber, CurrencyId = CurrencyId>,
+{
+	fn execute(
+		input: &[u8],
+		_target_gas: Option<u64>,
+		context: &Context,
+	) -> result::Result<PrecompileOutput, ExitError> {
+		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(input);
+
+		let action = input.action()?;
+
+		match action {
+			Action::Contribute => {
+				let who = input.account_id_at(1)?;
+				let campaign_id = input.currency_id_at(2)?;
+				let amount = input.balance_at(3)?;
+				log::debug!(
+					target: "evm",
+					"launchpad: contribute who: {:?}, campaign_id: {:?}, amount: {:?}",
+					who, campaign_id, amount
+				);
+
+				Launchpad::on_contribution(who, campaign_id, amount).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: vec![],
+					logs: vec![Log {
+						address: context.address,
+						topics: vec![
+							H256::from_slice(CONTRIBUTED),
+							input.evm_address_at(1)?.into(),
+							input.evm_address_at(2)?.into(),
+						],
+						data: Output::default().encode_u128(amount),
+					}],
+				})
+			}
+			Action::ClaimContributionAllocation => {
+				let who = input.account_id_at(1)?;
+				let campaign_id = input.currency_id_at(2)?;
+				log::debug!(
+					target: "evm",
+					"launchpad: claim_contribution_allocation who: {:?}, campaign_id: {:?}",
+					who, campaign_id
+				);
+
+				let allocation = Launchpad::campaign_info(campaign_id)
+					.and_then(|campaign| {
+						campaign
+							.contributions
+							.into_iter()
+							.find(|(contributor, _, _, _)| contributor == &who)
+							.map(|(_, _, allocation, _)| allocation)
+					})
+					.unwrap_or_default();
+
+				Launchpad::on_claim_allocation(who, campaign_id).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u128(allocation),
+					logs: vec![Log {
+						address: context.address,
+						topics: vec![
+							H256::from_slice(CLAIMED_ALLOCATION),
+							input.evm_address_at(1)?.into(),
+							input.evm_address_at(2)?.into(),
+						],
+						data: Output::default().encode_u128(allocation),
+					}],
+				})
+			}
+			Action::ClaimCampaignFundraise => {
+				let who = input.account_id_at(1)?;
+				let campaign_id = input.currency_id_at(2)?;
+				log::debug!(
+					target: "evm",
+					"launchpad: claim_campaign_fundraise who: {:?}, campaign_id: {:?}",
+					who, campaign_id
+				);
+
+				Launchpad::on_claim_campaign(who, campaign_id).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				let raised = Launchpad::campaign_info(campaign_id)
+					.map(|campaign| campaign.raised)
+					.unwrap_or_default();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u128(raised),
+					logs: vec![Log {
+						address: context.address,
+						topics: vec![
+							H256::from_slice(CLAIMED_FUNDRAISE),
+							input.evm_address_at(1)?.into(),
+							input.evm_address_at(2)?.into(),
+						],
+						data: Output::default().encode_u128(raised),
+					}],
+				})
+			}
+			Action::GetCampaign => {
+				let campaign_id = input.currency_id_at(1)?;
+				log::debug!(target: "evm", "launchpad: get_campaign campaign_id: {:?}", campaign_id);
+
+				let campaign = Launchpad::campaign_info(campaign_id)
+					.ok_or_else(|| ExitError::Other("Launchpad get_campaign failed".into()))?;
+
+				// goal, raised, token_price, crowd_allocation, contributors_count, campaign_start,
+				// campaign_end, is_approved, is_active, is_ended, is_successful, is_failed, is_claimed
+				let state = vec![
+					U256::from(campaign.goal),
+					U256::from(campaign.raised),
+					U256::from(campaign.token_price),
+					U256::from(campaign.crowd_allocation),
+					U256::from(campaign.contributors_count),
+					campaign.campaign_start.into(),
+					campaign.campaign_end.into(),
+					U256::from(campaign.is_approved as u8),
+					U256::from(campaign.is_active as u8),
+					U256::from(campaign.is_ended as u8),
+					U256::from(campaign.is_successful as u8),
+					U256::from(campaign.is_failed as u8),
+					U256::from(campaign.is_claimed as u8),
+				];
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u256_array(state),
+					logs: Default::default(),
+				})
+			}
+			Action::GetContribution => {
+				let who = input.account_id_at(1)?;
+				let campaign_id = input.currency_id_at(2)?;
+				log::debug!(
+					target: "evm",
+					"launchpad: get_contribution who: {:?}, campaign_id: {:?}",
+					who, campaign_id
+				);
+
+				let (contribution, allocation, claimed): (Balance, Balance, bool) = Launchpad::campaign_info(campaign_id)
+					.and_then(|campaign| {
+						campaign
+							.contributions
+							.into_iter()
+							.find(|(contributor, _, _, _)| contributor == &who)
+							.map(|(_, contribution, allocation, claimed)| (contribution, allocation, claimed))
+					})
+					.unwrap_or_default();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u256_array(vec![
+						U256::from(contribution),
+						U256::from(allocation),
+						U256::from(claimed as u8),
+					]),
+					logs: Default::default(),
+				})
+			}
+		}
+	}
+}
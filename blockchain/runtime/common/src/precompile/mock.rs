@@ -522,6 +522,14 @@ pub type ScheduleCallPrecompile = crate::ScheduleCallPrecompile<
 	Test,
 >;
 pub type DexPrecompile = crate::DexPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, EdfisSwapLegacyModule>;
+pub type FlashMintPrecompile = crate::FlashMintPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, ()>;
+pub type EcdpPrecompile = crate::EcdpPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, ()>;
+pub type IncentivesPrecompile = crate::IncentivesPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, ()>;
+pub type LaunchpadPrecompile =
+	crate::LaunchpadPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, BlockNumber, ()>;
+
+pub type KzgPointEvaluationPrecompile =
+	module_evm::precompiles::KzgPointEvaluation<module_evm::precompiles::EthereumKzgTauG2>;
 
 parameter_types! {
 	pub NetworkContractSource: H160 = alice_evm_addr();
@@ -554,12 +562,18 @@ impl module_evm::Config for Test {
 	type Event = Event;
 	type Precompiles = AllPrecompiles<
 		SystemContractsFilter,
+		(),
 		MultiCurrencyPrecompile,
 		NFTPrecompile,
 		StateRentPrecompile,
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		FlashMintPrecompile,
+		EcdpPrecompile,
+		IncentivesPrecompile,
+		LaunchpadPrecompile,
+		KzgPointEvaluationPrecompile,
 	>;
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
@@ -586,6 +600,12 @@ ord_parameter_types! {
 	pub const One: AccountId = AccountId::new([1u8; 32]);
 }
 
+parameter_types! {
+	pub const OsmUpdatePeriod: BlockNumber = 10;
+	pub OsmMaxPriceChange: Ratio = Ratio::saturating_from_rational(10, 100);
+	pub const OsmStalePeriod: BlockNumber = 30;
+}
+
 impl module_prices::Config for Test {
 	type Event = Event;
 	type Source = Oracle;
@@ -597,6 +617,9 @@ impl module_prices::Config for Test {
 	type DEX = EdfisSwapLegacyModule;
 	type Currency = Currencies;
 	type CurrencyIdMapping = EvmCurrencyIdMapping;
+	type OsmUpdatePeriod = OsmUpdatePeriod;
+	type OsmMaxPriceChange = OsmMaxPriceChange;
+	type OsmStalePeriod = OsmStalePeriod;
 	type WeightInfo = ();
 }
 
@@ -19,7 +19,17 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 //! The precompiles for EVM, includes standard Ethereum precompiles, and more:
+//! - KZG point evaluation (EIP-4844) at address `H160::from_low_u64_be(0x0a)`.
+//! - BLS12-381 operations (EIP-2537) at addresses `H160::from_low_u64_be(0x0b)` to
+//!   `H160::from_low_u64_be(0x11)`.
+//! - P256Verify (RIP-7212) at address `H160::from_low_u64_be(0x100)`.
 //! - MultiCurrency at address `H160::from_low_u64_be(1024)`.
+//! - NFT at address `H160::from_low_u64_be(1025)`, and the ERC-721 virtual contract of each
+//!   NFT class at `nft_class_address(class_id)`.
+//! - FlashMint at address `H160::from_low_u64_be(1030)`.
+//! - ECDP at address `H160::from_low_u64_be(1031)`.
+//! - Incentives at address `H160::from_low_u64_be(1032)`.
+//! - Launchpad at address `H160::from_low_u64_be(1033)`.
 
 #![allow(clippy::upper_case_acronyms)]
 
@@ -30,19 +40,26 @@ use crate::is_setheum_precompile;
 use frame_support::log;
 use module_evm::{
 	precompiles::{
-		ECRecover, ECRecoverPublicKey, EvmPrecompiles, Identity, Precompile, PrecompileSet, Ripemd160, Sha256,
-		Sha3FIPS256, Sha3FIPS512,
+		Bls12381G1Add, Bls12381G1Msm, Bls12381G2Add, Bls12381G2Msm, Bls12381MapFp2ToG2, Bls12381MapFpToG1,
+		Bls12381Pairing, ECRecover, ECRecoverPublicKey, EvmPrecompiles, Identity, P256Verify, Precompile, PrecompileSet,
+		Ripemd160, Sha256, Sha3FIPS256, Sha3FIPS512,
 	},
 	runner::state::PrecompileOutput,
 	Context, ExitError,
 };
-use module_support::PrecompileCallerFilter as PrecompileCallerFilterT;
-use primitives::PRECOMPILE_ADDRESS_START;
+use module_support::{
+	PrecompileCallerFilter as PrecompileCallerFilterT, PrecompilePauseFilter as PrecompilePauseFilterT,
+};
+use primitives::{evm::nft_class_id, PRECOMPILE_ADDRESS_START};
 use sp_core::H160;
 use sp_std::marker::PhantomData;
 
 pub mod dex;
+pub mod ecdp;
+pub mod flash_mint;
+pub mod incentives;
 pub mod input;
+pub mod launchpad;
 pub mod multicurrency;
 pub mod nft;
 pub mod oracle;
@@ -50,57 +67,91 @@ pub mod schedule_call;
 pub mod state_rent;
 
 pub use dex::DexPrecompile;
+pub use ecdp::EcdpPrecompile;
+pub use flash_mint::FlashMintPrecompile;
+pub use incentives::IncentivesPrecompile;
+pub use launchpad::LaunchpadPrecompile;
 pub use multicurrency::MultiCurrencyPrecompile;
-pub use nft::NFTPrecompile;
+pub use nft::{Erc721Precompile, NFTPrecompile};
 pub use oracle::OraclePrecompile;
 pub use schedule_call::ScheduleCallPrecompile;
 pub use state_rent::StateRentPrecompile;
 
 pub struct AllPrecompiles<
 	PrecompileCallerFilter,
+	PrecompilePauseFilter,
 	MultiCurrencyPrecompile,
 	NFTPrecompile,
 	StateRentPrecompile,
 	OraclePrecompile,
 	ScheduleCallPrecompile,
 	DexPrecompile,
+	FlashMintPrecompile,
+	EcdpPrecompile,
+	IncentivesPrecompile,
+	LaunchpadPrecompile,
+	KzgPointEvaluationPrecompile,
 >(
 	PhantomData<(
 		PrecompileCallerFilter,
+		PrecompilePauseFilter,
 		MultiCurrencyPrecompile,
 		NFTPrecompile,
 		StateRentPrecompile,
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		FlashMintPrecompile,
+		EcdpPrecompile,
+		IncentivesPrecompile,
+		LaunchpadPrecompile,
+		KzgPointEvaluationPrecompile,
 	)>,
 );
 
 impl<
 		PrecompileCallerFilter,
+		PrecompilePauseFilter,
 		MultiCurrencyPrecompile,
 		NFTPrecompile,
 		StateRentPrecompile,
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		FlashMintPrecompile,
+		EcdpPrecompile,
+		IncentivesPrecompile,
+		LaunchpadPrecompile,
+		KzgPointEvaluationPrecompile,
 	> PrecompileSet
 	for AllPrecompiles<
 		PrecompileCallerFilter,
+		PrecompilePauseFilter,
 		MultiCurrencyPrecompile,
 		NFTPrecompile,
 		StateRentPrecompile,
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		FlashMintPrecompile,
+		EcdpPrecompile,
+		IncentivesPrecompile,
+		LaunchpadPrecompile,
+		KzgPointEvaluationPrecompile,
 	> where
 	MultiCurrencyPrecompile: Precompile,
-	NFTPrecompile: Precompile,
+	NFTPrecompile: Precompile + Erc721Precompile,
 	StateRentPrecompile: Precompile,
 	OraclePrecompile: Precompile,
 	ScheduleCallPrecompile: Precompile,
 	PrecompileCallerFilter: PrecompileCallerFilterT,
+	PrecompilePauseFilter: PrecompilePauseFilterT,
 	DexPrecompile: Precompile,
+	FlashMintPrecompile: Precompile,
+	EcdpPrecompile: Precompile,
+	IncentivesPrecompile: Precompile,
+	LaunchpadPrecompile: Precompile,
+	KzgPointEvaluationPrecompile: Precompile,
 {
 	#[allow(clippy::type_complexity)]
 	fn execute(
@@ -109,10 +160,50 @@ impl<
 		target_gas: Option<u64>,
 		context: &Context,
 	) -> Option<core::result::Result<PrecompileOutput, ExitError>> {
+		if PrecompilePauseFilter::is_paused(address) {
+			log::debug!(target: "evm", "Precompile paused, address: {:?}", address);
+			return Some(Err(ExitError::Other("precompile is paused".into())));
+		}
+
 		EvmPrecompiles::<ECRecover, Sha256, Ripemd160, Identity, ECRecoverPublicKey, Sha3FIPS256, Sha3FIPS512>::execute(
 			address, input, target_gas, context,
 		)
 		.or_else(|| {
+			let result = if address == H160::from_low_u64_be(0x0a) {
+				KzgPointEvaluationPrecompile::execute(input, target_gas, context)
+			} else if address == H160::from_low_u64_be(0x0b) {
+				Bls12381G1Add::execute(input, target_gas, context)
+			} else if address == H160::from_low_u64_be(0x0c) {
+				Bls12381G1Msm::execute(input, target_gas, context)
+			} else if address == H160::from_low_u64_be(0x0d) {
+				Bls12381G2Add::execute(input, target_gas, context)
+			} else if address == H160::from_low_u64_be(0x0e) {
+				Bls12381G2Msm::execute(input, target_gas, context)
+			} else if address == H160::from_low_u64_be(0x0f) {
+				Bls12381Pairing::execute(input, target_gas, context)
+			} else if address == H160::from_low_u64_be(0x10) {
+				Bls12381MapFpToG1::execute(input, target_gas, context)
+			} else if address == H160::from_low_u64_be(0x11) {
+				Bls12381MapFp2ToG2::execute(input, target_gas, context)
+			} else if address == H160::from_low_u64_be(0x100) {
+				P256Verify::execute(input, target_gas, context)
+			} else {
+				return None;
+			};
+
+			Some(result)
+		})
+		.or_else(|| {
+			// the ERC-721 virtual contracts act on behalf of the caller, no caller filter needed
+			if let Some(class_id) = nft_class_id(&address) {
+				log::debug!(target: "evm", "ERC-721 precompile, address: {:?}, input: {:?}", address, input);
+				// under DELEGATECALL or CALLCODE the calling contract would act as the class
+				if context.address != address {
+					return Some(Err(ExitError::Other("cannot be delegated".into())));
+				}
+				return Some(NFTPrecompile::execute_erc721(class_id, input, target_gas, context));
+			}
+
 			if !is_setheum_precompile(address) {
 				return None;
 			}
@@ -136,6 +227,14 @@ impl<
 				Some(ScheduleCallPrecompile::execute(input, target_gas, context))
 			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 5) {
 				Some(DexPrecompile::execute(input, target_gas, context))
+			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 6) {
+				Some(FlashMintPrecompile::execute(input, target_gas, context))
+			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 7) {
+				Some(EcdpPrecompile::execute(input, target_gas, context))
+			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 8) {
+				Some(IncentivesPrecompile::execute(input, target_gas, context))
+			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 9) {
+				Some(LaunchpadPrecompile::execute(input, target_gas, context))
 			} else {
 				None
 			};
@@ -20,26 +20,30 @@
 
 use crate::precompile::PrecompileOutput;
 use frame_support::log;
-use module_evm::{Context, ExitError, ExitSucceed, Precompile};
-use module_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT};
-use sp_core::H160;
+use module_evm::{Context, ExitError, ExitSucceed, Log, Precompile};
+use module_evm_utility_macro::keccak256;
+use module_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, NFTManager};
+use sp_core::{H160, H256};
 use sp_runtime::RuntimeDebug;
 use sp_std::{borrow::Cow, fmt::Debug, marker::PhantomData, prelude::*, result};
 
-use orml_traits::NFT as NFTT;
-
 use super::input::{Input, InputT, Output};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
-use primitives::NFTBalance;
+use primitives::evm::nft_class_address;
 
 /// The `NFT` impl precompile.
 ///
-/// `input` data starts with `action`.
+/// Called at the precompile address, `input` data starts with `action`.
 ///
 /// Actions:
 /// - Query balance. Rest `input` bytes: `account_id`.
 /// - Query owner. Rest `input` bytes: `class_id`, `token_id`.
 /// - Transfer. Rest `input`bytes: `from`, `to`, `class_id`, `token_id`.
+///
+/// Called at the ERC-721 virtual contract address of a class, see `nft_class_address` and
+/// `Erc721Precompile`, it implements ERC-721 on behalf of the caller, plus `mint(address)`
+/// and `burn(uint256)` gated by the class properties. `safeTransferFrom` does not call
+/// `onERC721Received` of the receiver.
 pub struct NFTPrecompile<AccountId, AddressMapping, CurrencyIdMapping, NFT>(
 	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, NFT)>,
 );
@@ -53,18 +57,74 @@ pub enum Action {
 	Transfer = "transfer(address,address,uint256,uint256)",
 }
 
+#[primitives_proc_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Erc721Action {
+	SupportsInterface = "supportsInterface(bytes4)",
+	BalanceOf = "balanceOf(address)",
+	OwnerOf = "ownerOf(uint256)",
+	TokenURI = "tokenURI(uint256)",
+	GetApproved = "getApproved(uint256)",
+	IsApprovedForAll = "isApprovedForAll(address,address)",
+	Approve = "approve(address,uint256)",
+	SetApprovalForAll = "setApprovalForAll(address,bool)",
+	TransferFrom = "transferFrom(address,address,uint256)",
+	SafeTransferFrom = "safeTransferFrom(address,address,uint256)",
+	SafeTransferFromWithData = "safeTransferFrom(address,address,uint256,bytes)",
+	Mint = "mint(address)",
+	Burn = "burn(uint256)",
+}
+
+/// ERC-165 `supportsInterface(bytes4)`
+const INTERFACE_ID_ERC165: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+/// ERC-721
+const INTERFACE_ID_ERC721: [u8; 4] = [0x80, 0xac, 0x58, 0xcd];
+
+/// The ERC-721 virtual contracts of NFT classes.
+pub trait Erc721Precompile {
+	/// Execute a call to the ERC-721 virtual contract of `class_id`, acting on behalf of
+	/// `context.caller`.
+	fn execute_erc721(
+		class_id: u32,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> result::Result<PrecompileOutput, ExitError>;
+}
+
+/// `Transfer(address indexed from, address indexed to, uint256 indexed tokenId)`
+const TRANSFER: &[u8; 32] = keccak256!("Transfer(address,address,uint256)");
+/// `Approval(address indexed owner, address indexed approved, uint256 indexed tokenId)`
+const APPROVAL: &[u8; 32] = keccak256!("Approval(address,address,uint256)");
+/// `ApprovalForAll(address indexed owner, address indexed operator, bool approved)`
+const APPROVAL_FOR_ALL: &[u8; 32] = keccak256!("ApprovalForAll(address,address,bool)");
+
+fn transfer_log(class_id: u32, from: H160, to: H160, token_id: u64) -> Log {
+	Log {
+		address: nft_class_address(class_id),
+		topics: vec![
+			H256::from_slice(TRANSFER),
+			from.into(),
+			to.into(),
+			H256::from_low_u64_be(token_id),
+		],
+		data: vec![],
+	}
+}
+
 impl<AccountId, AddressMapping, CurrencyIdMapping, NFT> Precompile
 	for NFTPrecompile<AccountId, AddressMapping, CurrencyIdMapping, NFT>
 where
 	AccountId: Clone + Debug,
 	AddressMapping: AddressMappingT<AccountId>,
 	CurrencyIdMapping: CurrencyIdMappingT,
-	NFT: NFTT<AccountId, Balance = NFTBalance, ClassId = u32, TokenId = u64>,
+	NFT: NFTManager<AccountId, ClassId = u32, TokenId = u64>,
 {
 	fn execute(
 		input: &[u8],
-		_target_gas: Option<u64>,
-		_context: &Context,
+		target_gas: Option<u64>,
+		context: &Context,
 	) -> result::Result<PrecompileOutput, ExitError> {
 		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(input);
 
@@ -92,7 +152,7 @@ where
 				log::debug!(target: "evm", "nft: query_owner class_id: {:?}, token_id: {:?}", class_id, token_id);
 
 				let owner: H160 = if let Some(o) = NFT::owner((class_id, token_id)) {
-					AddressMapping::get_evm_address(&o).unwrap_or_else(|| AddressMapping::get_default_evm_address(&o))
+					Self::evm_address(&o)
 				} else {
 					Default::default()
 				};
@@ -120,9 +180,254 @@ where
 					exit_status: ExitSucceed::Returned,
 					cost: 0,
 					output: vec![],
+					logs: vec![transfer_log(
+						class_id,
+						input.evm_address_at(1)?,
+						input.evm_address_at(2)?,
+						token_id,
+					)],
+				})
+			}
+		}
+	}
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, NFT> NFTPrecompile<AccountId, AddressMapping, CurrencyIdMapping, NFT>
+where
+	AccountId: Clone + Debug,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	NFT: NFTManager<AccountId, ClassId = u32, TokenId = u64>,
+{
+	fn evm_address(who: &AccountId) -> H160 {
+		AddressMapping::get_evm_address(who).unwrap_or_else(|| AddressMapping::get_default_evm_address(who))
+	}
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, NFT> Erc721Precompile
+	for NFTPrecompile<AccountId, AddressMapping, CurrencyIdMapping, NFT>
+where
+	AccountId: Clone + Debug,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	NFT: NFTManager<AccountId, ClassId = u32, TokenId = u64>,
+{
+	fn execute_erc721(
+		class_id: u32,
+		input: &[u8],
+		_target_gas: Option<u64>,
+		context: &Context,
+	) -> result::Result<PrecompileOutput, ExitError> {
+		let input = Input::<Erc721Action, AccountId, AddressMapping, CurrencyIdMapping>::new(input);
+		let caller = AddressMapping::get_account_id(&context.caller);
+
+		let action = input.action()?;
+
+		match action {
+			Erc721Action::SupportsInterface => {
+				let interface_id = input.bytes_at(1, 4)?;
+				let supported = interface_id[..] == INTERFACE_ID_ERC165 || interface_id[..] == INTERFACE_ID_ERC721;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u8(supported.into()),
+					logs: Default::default(),
+				})
+			}
+			Erc721Action::BalanceOf => {
+				let who = input.account_id_at(1)?;
+
+				log::debug!(target: "evm", "erc721: balance_of class_id: {:?}, who: {:?}", class_id, who);
+
+				let balance = NFT::balance_of_class(&who, class_id);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u128(balance),
+					logs: Default::default(),
+				})
+			}
+			Erc721Action::OwnerOf => {
+				let token_id = input.u64_at(1)?;
+
+				log::debug!(target: "evm", "erc721: owner_of class_id: {:?}, token_id: {:?}", class_id, token_id);
+
+				let owner = NFT::owner((class_id, token_id))
+					.ok_or_else(|| ExitError::Other("invalid token id".into()))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_address(&Self::evm_address(&owner)),
+					logs: Default::default(),
+				})
+			}
+			Erc721Action::TokenURI => {
+				let token_id = input.u64_at(1)?;
+
+				log::debug!(target: "evm", "erc721: token_uri class_id: {:?}, token_id: {:?}", class_id, token_id);
+
+				let uri = NFT::token_uri((class_id, token_id))
+					.ok_or_else(|| ExitError::Other("invalid token id".into()))?;
+
+				// `string` has the same abi encoding as `bytes`
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_bytes(&uri),
 					logs: Default::default(),
 				})
 			}
+			Erc721Action::GetApproved => {
+				let token_id = input.u64_at(1)?;
+
+				log::debug!(target: "evm", "erc721: get_approved class_id: {:?}, token_id: {:?}", class_id, token_id);
+
+				NFT::owner((class_id, token_id)).ok_or_else(|| ExitError::Other("invalid token id".into()))?;
+				let approved: H160 = if let Some(a) = NFT::approved((class_id, token_id)) {
+					Self::evm_address(&a)
+				} else {
+					Default::default()
+				};
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_address(&approved),
+					logs: Default::default(),
+				})
+			}
+			Erc721Action::IsApprovedForAll => {
+				let owner = input.account_id_at(1)?;
+				let operator = input.account_id_at(2)?;
+
+				log::debug!(target: "evm", "erc721: is_approved_for_all class_id: {:?}, owner: {:?}, operator: {:?}", class_id, owner, operator);
+
+				let approved = NFT::is_approved_for_all(&owner, class_id, &operator);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u8(approved.into()),
+					logs: Default::default(),
+				})
+			}
+			Erc721Action::Approve => {
+				let approved_address = input.evm_address_at(1)?;
+				let token_id = input.u64_at(2)?;
+				// approving the zero address clears the approval
+				let approved = if approved_address == H160::zero() {
+					None
+				} else {
+					Some(AddressMapping::get_account_id(&approved_address))
+				};
+
+				log::debug!(target: "evm", "erc721: approve class_id: {:?}, caller: {:?}, approved: {:?}, token_id: {:?}", class_id, caller, approved, token_id);
+
+				NFT::approve(&caller, approved, (class_id, token_id))
+					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+				let owner = NFT::owner((class_id, token_id))
+					.ok_or_else(|| ExitError::Other("invalid token id".into()))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: vec![],
+					logs: vec![Log {
+						address: context.address,
+						topics: vec![
+							H256::from_slice(APPROVAL),
+							Self::evm_address(&owner).into(),
+							approved_address.into(),
+							H256::from_low_u64_be(token_id),
+						],
+						data: vec![],
+					}],
+				})
+			}
+			Erc721Action::SetApprovalForAll => {
+				let operator_address = input.evm_address_at(1)?;
+				let operator = input.account_id_at(1)?;
+				let approved = input.u32_at(2)? != 0;
+
+				log::debug!(target: "evm", "erc721: set_approval_for_all class_id: {:?}, caller: {:?}, operator: {:?}, approved: {:?}", class_id, caller, operator, approved);
+
+				NFT::set_approval_for_all(&caller, class_id, &operator, approved)
+					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: vec![],
+					logs: vec![Log {
+						address: context.address,
+						topics: vec![
+							H256::from_slice(APPROVAL_FOR_ALL),
+							context.caller.into(),
+							operator_address.into(),
+						],
+						data: Output::default().encode_u8(approved.into()),
+					}],
+				})
+			}
+			Erc721Action::TransferFrom
+			| Erc721Action::SafeTransferFrom
+			| Erc721Action::SafeTransferFromWithData => {
+				let from = input.account_id_at(1)?;
+				let to = input.account_id_at(2)?;
+				let token_id = input.u64_at(3)?;
+
+				log::debug!(target: "evm", "erc721: transfer_from class_id: {:?}, caller: {:?}, from: {:?}, to: {:?}, token_id: {:?}", class_id, caller, from, to, token_id);
+
+				NFT::transfer_from(&caller, &from, &to, (class_id, token_id))
+					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: vec![],
+					logs: vec![transfer_log(
+						class_id,
+						input.evm_address_at(1)?,
+						input.evm_address_at(2)?,
+						token_id,
+					)],
+				})
+			}
+			Erc721Action::Mint => {
+				let to = input.account_id_at(1)?;
+
+				log::debug!(target: "evm", "erc721: mint class_id: {:?}, caller: {:?}, to: {:?}", class_id, caller, to);
+
+				let token_id = NFT::mint(&caller, &to, class_id)
+					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: Output::default().encode_u128(token_id.into()),
+					logs: vec![transfer_log(class_id, H160::zero(), input.evm_address_at(1)?, token_id)],
+				})
+			}
+			Erc721Action::Burn => {
+				let token_id = input.u64_at(1)?;
+
+				log::debug!(target: "evm", "erc721: burn class_id: {:?}, caller: {:?}, token_id: {:?}", class_id, caller, token_id);
+
+				let owner = NFT::owner((class_id, token_id))
+					.ok_or_else(|| ExitError::Other("invalid token id".into()))?;
+				NFT::burn(&caller, (class_id, token_id))
+					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: 0,
+					output: vec![],
+					logs: vec![transfer_log(class_id, Self::evm_address(&owner), H160::zero(), token_id)],
+				})
+			}
 		}
 	}
 }
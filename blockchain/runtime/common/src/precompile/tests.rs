@@ -34,12 +34,13 @@ use codec::Encode;
 use frame_support::{assert_noop, assert_ok};
 use hex_literal::hex;
 use module_evm::{Context, ExitError, ExitSucceed, Precompile};
-use module_support::AddressMapping;
+use module_support::{mocks::MockAddressMapping, AddressMapping, FlashMint, PrecompilePauseFilter};
 use orml_traits::DataFeeder;
-use primitives::{Balance, PREDEPLOY_ADDRESS_START};
-use sp_core::{H160, U256};
+use primitives::{evm::nft_class_address, Balance, PREDEPLOY_ADDRESS_START};
+use sp_core::{H160, H256, U256};
 use sp_runtime::FixedPointNumber;
-use std::str::FromStr;
+use sp_runtime::DispatchError;
+use std::{cell::RefCell, str::FromStr};
 
 pub struct DummyPrecompile;
 impl Precompile for DummyPrecompile {
@@ -57,8 +58,48 @@ impl Precompile for DummyPrecompile {
 	}
 }
 
+impl Erc721Precompile for DummyPrecompile {
+	fn execute_erc721(
+		_class_id: u32,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> core::result::Result<PrecompileOutput, ExitError> {
+		Self::execute(input, target_gas, context)
+	}
+}
+
 pub type WithSystemContractFilter = AllPrecompiles<
 	crate::SystemContractsFilter,
+	(),
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+>;
+
+pub struct PauseMultiCurrencyPrecompile;
+impl PrecompilePauseFilter for PauseMultiCurrencyPrecompile {
+	fn is_paused(address: H160) -> bool {
+		address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START)
+	}
+}
+
+pub type WithPausedPrecompile = AllPrecompiles<
+	crate::SystemContractsFilter,
+	PauseMultiCurrencyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
 	DummyPrecompile,
 	DummyPrecompile,
 	DummyPrecompile,
@@ -85,6 +126,29 @@ fn precompile_filter_works_on_setheum_precompiles() {
 	);
 }
 
+#[test]
+fn precompile_pause_filter_works() {
+	let paused = H160::from_low_u64_be(PRECOMPILE_ADDRESS_START);
+	let not_paused = H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 1);
+
+	let mut non_system = [0u8; 20];
+	non_system[0] = 1;
+
+	let context = Context {
+		address: paused,
+		caller: non_system.into(),
+		apparent_value: 0.into(),
+	};
+	assert_eq!(
+		WithPausedPrecompile::execute(paused, &[0u8; 1], None, &context),
+		Some(Err(ExitError::Other("precompile is paused".into()))),
+	);
+	assert_eq!(
+		WithPausedPrecompile::execute(not_paused, &[0u8; 1], None, &context),
+		Some(Err(ExitError::Other("no permission".into()))),
+	);
+}
+
 #[test]
 fn precompile_filter_does_not_work_on_system_contracts() {
 	let system = H160::from_low_u64_be(PREDEPLOY_ADDRESS_START);
@@ -118,6 +182,48 @@ fn precompile_filter_does_not_work_on_non_system_contracts() {
 		WithSystemContractFilter::execute(non_system.into(), &[0u8; 1], None, &non_system_caller_context).is_none()
 	);
 }
+
+#[test]
+fn precompile_filter_does_not_work_on_nft_class_contracts() {
+	let nft_class = nft_class_address(0);
+
+	let mut non_system = [0u8; 20];
+	non_system[0] = 1;
+
+	let non_system_caller_context = Context {
+		address: nft_class,
+		caller: non_system.into(),
+		apparent_value: 0.into(),
+	};
+	assert_eq!(
+		WithSystemContractFilter::execute(nft_class, &[0u8; 1], None, &non_system_caller_context),
+		Some(Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: 0,
+			output: vec![],
+			logs: Default::default(),
+		})),
+	);
+}
+
+#[test]
+fn nft_class_contracts_cannot_be_delegated() {
+	let nft_class = nft_class_address(0);
+
+	let mut non_system = [0u8; 20];
+	non_system[0] = 1;
+
+	// DELEGATECALL from a contract keeps the address of the calling contract
+	let delegate_call_context = Context {
+		address: non_system.into(),
+		caller: non_system.into(),
+		apparent_value: 0.into(),
+	};
+	assert_eq!(
+		WithSystemContractFilter::execute(nft_class, &[0u8; 1], None, &delegate_call_context),
+		Some(Err(ExitError::Other("cannot be delegated".into()))),
+	);
+}
 // TODO - FIXME: Fix Mock Prefix
 // #[test]
 // fn multicurrency_precompile_should_work() {
@@ -903,3 +1009,87 @@ fn task_id_max_and_min() {
 
 	assert_eq!(38, task_id.len());
 }
+
+thread_local! {
+	static FLASH_MINTS: RefCell<Vec<(AccountId, H160, Balance, Vec<u8>)>> = RefCell::new(vec![]);
+}
+
+pub struct MockFlashMint;
+impl FlashMint<AccountId, Balance> for MockFlashMint {
+	fn max_flash_mint() -> Balance {
+		1_000_000
+	}
+
+	fn flash_fee(amount: Balance) -> Balance {
+		amount / 100
+	}
+
+	fn flash_mint_to_contract(
+		initiator: &AccountId,
+		receiver: H160,
+		amount: Balance,
+		data: Vec<u8>,
+	) -> Result<Balance, DispatchError> {
+		FLASH_MINTS.with(|v| v.borrow_mut().push((initiator.clone(), receiver, amount, data)));
+		Ok(Self::flash_fee(amount))
+	}
+}
+
+type TestFlashMintPrecompile =
+	crate::FlashMintPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, MockFlashMint>;
+
+fn word(value: usize) -> Vec<u8> {
+	H256::from_low_u64_be(value as u64).as_bytes().to_vec()
+}
+
+fn flash_mint_context() -> Context {
+	Context {
+		address: Default::default(),
+		caller: alice_evm_addr(),
+		apparent_value: Default::default(),
+	}
+}
+
+#[test]
+fn flash_mint_precompile_views_should_work() {
+	let mut input = Into::<u32>::into(flash_mint::Action::MaxFlashMint).to_be_bytes().to_vec();
+	let resp = TestFlashMintPrecompile::execute(&input, None, &flash_mint_context()).unwrap();
+	assert_eq!(resp.output, word(1_000_000));
+	assert_eq!(resp.cost, 0);
+
+	input = Into::<u32>::into(flash_mint::Action::FlashFee).to_be_bytes().to_vec();
+	input.extend_from_slice(&word(500));
+	let resp = TestFlashMintPrecompile::execute(&input, None, &flash_mint_context()).unwrap();
+	assert_eq!(resp.output, word(5));
+}
+
+#[test]
+fn flash_mint_precompile_should_decode_and_charge_callback_gas() {
+	let receiver = bob_evm_addr();
+	let data = vec![0xab; 40];
+	let mut input = Into::<u32>::into(flash_mint::Action::FlashMint).to_be_bytes().to_vec();
+	input.extend_from_slice(H256::from(alice_evm_addr()).as_bytes());
+	input.extend_from_slice(H256::from(receiver).as_bytes());
+	input.extend_from_slice(&word(1_000));
+	// offset of the bytes, its length and the data padded to 2 words
+	input.extend_from_slice(&word(4 * 32));
+	input.extend_from_slice(&word(data.len()));
+	input.extend_from_slice(&data);
+	input.resize(input.len() + 24, 0);
+
+	let callback_gas = module_support::evm::limits::flash_mint::ON_FLASH_MINT.gas;
+	assert_eq!(
+		TestFlashMintPrecompile::execute(&input, Some(callback_gas - 1), &flash_mint_context()),
+		Err(ExitError::OutOfGas)
+	);
+	assert!(FLASH_MINTS.with(|v| v.borrow().is_empty()));
+
+	let resp = TestFlashMintPrecompile::execute(&input, Some(callback_gas), &flash_mint_context()).unwrap();
+	assert_eq!(resp.exit_status, ExitSucceed::Returned);
+	assert_eq!(resp.cost, callback_gas);
+	assert_eq!(resp.output, word(10));
+	assert_eq!(
+		FLASH_MINTS.with(|v| v.borrow().clone()),
+		vec![(MockAddressMapping::get_account_id(&alice_evm_addr()), receiver, 1_000, data)]
+	);
+}
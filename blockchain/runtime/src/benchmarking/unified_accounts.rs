@@ -0,0 +1,150 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	dollar, AccountId, CurrencyId, EvmAccounts, GetNativeCurrencyId, MaxMergedCurrencies, MaxSecondaryAddresses,
+	Origin, Runtime, System, TokenSymbol, UnlinkDelay,
+};
+
+use super::utils::set_balance;
+use frame_benchmarking::whitelisted_caller;
+use frame_support::{assert_ok, BoundedVec};
+use frame_system::RawOrigin;
+use module_unified_accounts::{SecondaryAddresses, SignaturePurpose};
+use orml_benchmarking::runtime_benchmarks;
+use primitives::{evm::EvmAddress, DexShare};
+use sp_io::hashing::keccak_256;
+use sp_std::prelude::*;
+
+const NATIVE: CurrencyId = GetNativeCurrencyId::get();
+
+fn alice() -> libsecp256k1::SecretKey {
+	libsecp256k1::SecretKey::parse(&keccak_256(b"Alice")).unwrap()
+}
+
+fn bob() -> libsecp256k1::SecretKey {
+	libsecp256k1::SecretKey::parse(&keccak_256(b"Bob")).unwrap()
+}
+
+fn padded_account_id(address: EvmAddress) -> AccountId {
+	let mut data = [0u8; 32];
+	data[0..4].copy_from_slice(b"evm:");
+	data[4..24].copy_from_slice(&address[..]);
+	AccountId::from(Into::<[u8; 32]>::into(data))
+}
+
+// Endow the padded account of `address` with the native currency and `c` other currencies,
+// all merged by `TransferAll` when the address is linked.
+fn endow_padded_account(address: EvmAddress, c: u32) {
+	let who = padded_account_id(address);
+	set_balance(NATIVE, &who, 1_000 * dollar(NATIVE));
+	for i in 0..c {
+		let currency_id = CurrencyId::DexShare(DexShare::Token(TokenSymbol::SEE), DexShare::ForeignAsset(i as u16));
+		set_balance(currency_id, &who, 1_000 * dollar(NATIVE));
+	}
+}
+
+fn claim_alice(caller: &AccountId) {
+	assert_ok!(EvmAccounts::claim_account(
+		Origin::signed(caller.clone()),
+		EvmAccounts::eth_address(&alice()),
+		EvmAccounts::eth_sign(&alice(), caller, SignaturePurpose::ClaimAccount)
+	));
+}
+
+// Fill the secondary addresses of `who` up to one below the limit.
+fn fill_secondary_addresses(who: &AccountId) {
+	let addresses: Vec<EvmAddress> =
+		(1..MaxSecondaryAddresses::get()).map(|i| EvmAddress::from_low_u64_be(i as u64)).collect();
+	SecondaryAddresses::<Runtime>::insert(who, BoundedVec::try_from(addresses).unwrap());
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_unified_accounts }
+
+	claim_account {
+		let c in 0 .. MaxMergedCurrencies::get();
+
+		let caller: AccountId = whitelisted_caller();
+		let eth_address = EvmAccounts::eth_address(&alice());
+		endow_padded_account(eth_address, c);
+		let eth_signature = EvmAccounts::eth_sign(&alice(), &caller, SignaturePurpose::ClaimAccount);
+	}: _(RawOrigin::Signed(caller), eth_address, eth_signature)
+
+	claim_default_account {
+		let caller = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller))
+
+	unlink_account {
+		let caller: AccountId = whitelisted_caller();
+		claim_alice(&caller);
+	}: _(RawOrigin::Signed(caller))
+
+	cancel_unlink {
+		let caller: AccountId = whitelisted_caller();
+		claim_alice(&caller);
+		EvmAccounts::unlink_account(Origin::signed(caller.clone()))?;
+	}: _(RawOrigin::Signed(caller))
+
+	execute_unlink {
+		let caller: AccountId = whitelisted_caller();
+		claim_alice(&caller);
+		EvmAccounts::unlink_account(Origin::signed(caller.clone()))?;
+		System::set_block_number(System::block_number() + UnlinkDelay::get());
+	}: _(RawOrigin::Signed(caller))
+
+	rotate_evm_address {
+		let c in 0 .. MaxMergedCurrencies::get();
+
+		let caller: AccountId = whitelisted_caller();
+		claim_alice(&caller);
+		EvmAccounts::unlink_account(Origin::signed(caller.clone()))?;
+		let eth_address = EvmAccounts::eth_address(&bob());
+		endow_padded_account(eth_address, c);
+		let eth_signature = EvmAccounts::eth_sign(&bob(), &caller, SignaturePurpose::RotateEvmAddress);
+	}: _(RawOrigin::Signed(caller), eth_address, eth_signature)
+
+	add_secondary_address {
+		let caller: AccountId = whitelisted_caller();
+		fill_secondary_addresses(&caller);
+		let eth_address = EvmAccounts::eth_address(&bob());
+		let eth_signature = EvmAccounts::eth_sign(&bob(), &caller, SignaturePurpose::AddSecondaryAddress);
+	}: _(RawOrigin::Signed(caller), eth_address, eth_signature)
+
+	remove_secondary_address {
+		let caller: AccountId = whitelisted_caller();
+		fill_secondary_addresses(&caller);
+		let eth_address = EvmAccounts::eth_address(&bob());
+		EvmAccounts::add_secondary_address(
+			Origin::signed(caller.clone()),
+			eth_address,
+			EvmAccounts::eth_sign(&bob(), &caller, SignaturePurpose::AddSecondaryAddress),
+		)?;
+	}: _(RawOrigin::Signed(caller), eth_address)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
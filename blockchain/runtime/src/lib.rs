@@ -27,11 +27,11 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use codec::{Compact, Decode, Encode};
-use sp_std::prelude::*;
+use sp_std::{convert::TryFrom, prelude::*};
 use sp_core::{
 	crypto::KeyTypeId,
 	// u32_trait::{_2, _3, _4},
-	H160, OpaqueMetadata,
+	H160, H256, U256, OpaqueMetadata,
 };
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
@@ -55,6 +55,12 @@ use pallet_grandpa::fg_primitives;
 use frame_election_provider_support::onchain;
 pub use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 pub use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
+pub use primitives::aleph::AuthorityId as AlephId;
+use primitives::aleph::{
+	ApiError as AlephApiError, AuraId, FinalityCommitteeManager, SessionAuthorityData, SessionCommittee, SessionIndex,
+	SessionInfoProvider, SessionValidatorError,
+};
+use primitives::Version as FinalityVersion;
 
 use sp_version::RuntimeVersion;
 #[cfg(feature = "std")]
@@ -89,6 +95,7 @@ use module_unified_accounts::EvmAddressMapping;
 pub use module_evm_manager::EvmCurrencyIdMapping;
 use module_currencies::BasicCurrencyAdapter;
 use module_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+use module_support::FractionalRate;
 
 // re-exports
 
@@ -130,11 +137,17 @@ pub mod constants;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+#[cfg(test)]
+mod tests;
+
 // Pallet accounts of runtime
 parameter_types! {
 	pub const AirdropPalletId: PalletId = PalletId(*b"set/drop");		// 5EYCAe5jKgkuY1B3CkWQF41wzN62tTt8ptfmao31qYvMiVRD
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"set/cdpt");	// 5EYCAe5jKgkuXyJQ3G8CXrRfmmqqe54Tye5wJDqim8cvHQi7
 	pub const DEXPalletId: PalletId = PalletId(*b"edf/swap");			// 5EYCAe5jKgkuYTiXRpXnghiur9sW2zJCp91xQRKKzhwjS2DC
+	pub const EcdpUssdEnginePalletId: PalletId = PalletId(*b"set/ussd");	// 5EYCAe5jKgkuYXNbpJw5KdGC4J36kGXZTzHaXBbB48r54D5Y
+	pub const IncentivesPalletId: PalletId = PalletId(*b"set/incv");	// 5EYCAe5jKgkuYA2TP5yYXmXQRPMzvDtoM5LmrsrcSdCMdT8p
+	pub const LaunchpadPalletId: PalletId = PalletId(*b"edf/lnch");		// 5EYCAe5gWwDJNZxy2ZKQZ11vgmVF1fdy5Y8gbqoJ3yaigdqC
 	pub const LoansPalletId: PalletId = PalletId(*b"set/loan");			// 5EYCAe5jKgkuYFMt7CDpD9JGyD8eLr9DKZZ9mBNibUbs5xXo
 	pub const NftPalletId: PalletId = PalletId(*b"set/sNFT");			// 5EYCAe5jKgkuYTZd9to8S5wCPjCUQnDg57tU9BDgakrywBM2
 	pub const SerpTreasuryPalletId: PalletId = PalletId(*b"set/serp");	// 5EYCAe5jKgkuYTiwwziYLaTt4ZTSEikGfWNVyZ1PUdkBg78Z
@@ -146,6 +159,9 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 		AirdropPalletId::get().into_account(),
 		CDPTreasuryPalletId::get().into_account(),
 		DEXPalletId::get().into_account(),
+		EcdpUssdEnginePalletId::get().into_account(),
+		IncentivesPalletId::get().into_account(),
+		LaunchpadPalletId::get().into_account(),
 		LoansPalletId::get().into_account(),
 		SerpTreasuryPalletId::get().into_account(),
 		TreasuryPalletId::get().into_account(),
@@ -238,8 +254,24 @@ pub mod opaque {
 			pub grandpa: Grandpa,
 			pub im_online: ImOnline,
 			pub authority_discovery: AuthorityDiscovery,
+			pub aleph: Aleph,
 		}
 	}
+
+	impl_opaque_keys! {
+		/// Session keys before the Aleph key was added, used by the migration of stored keys.
+		pub struct SessionKeysWithoutAleph {
+			pub babe: Babe,
+			pub grandpa: Grandpa,
+			pub im_online: ImOnline,
+			pub authority_discovery: AuthorityDiscovery,
+		}
+	}
+}
+
+/// The Aleph key of a validator, which is the same ed25519 key as its GRANDPA key.
+pub fn aleph_key_of(grandpa: &GrandpaId) -> AlephId {
+	AlephId::from(AsRef::<sp_core::ed25519::Public>::as_ref(grandpa).clone())
 }
 
 pub const VERSION: RuntimeVersion = RuntimeVersion {
@@ -337,13 +369,65 @@ impl pallet_session::Config for Runtime {
 	type ValidatorIdOf = pallet_staking::StashOf<Self>;
 	type ShouldEndSession = Babe;
 	type NextSessionRotation = Babe;
-	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Self, Staking>;
+	type SessionManager = Aleph;
 	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
 	type Keys = opaque::SessionKeys;
 	type DisabledValidatorsThreshold = DisabledValidatorsThreshold;
 	type WeightInfo = ();
 }
 
+pub struct SessionInfoImpl;
+impl SessionInfoProvider<BlockNumber> for SessionInfoImpl {
+	fn current_session() -> SessionIndex {
+		Session::current_index()
+	}
+
+	fn next_session_block_number(current_block: BlockNumber) -> Option<BlockNumber> {
+		<Runtime as pallet_session::Config>::NextSessionRotation::estimate_next_session_rotation(current_block).0
+	}
+}
+
+/// Makes the validators elected by staking the finality committee of the session they are elected for.
+pub struct FinalityCommitteeFromStaking;
+impl FinalityCommitteeFromStaking {
+	fn note_committee(validators: Option<Vec<AccountId>>) -> Option<Vec<AccountId>> {
+		if let Some(validators) = &validators {
+			<Aleph as FinalityCommitteeManager<AccountId>>::on_next_session_finality_committee(validators.clone());
+		}
+		validators
+	}
+}
+
+impl pallet_session::SessionManager<AccountId> for FinalityCommitteeFromStaking {
+	fn new_session(new_index: SessionIndex) -> Option<Vec<AccountId>> {
+		Self::note_committee(pallet_session::historical::NoteHistoricalRoot::<Runtime, Staking>::new_session(
+			new_index,
+		))
+	}
+
+	fn new_session_genesis(new_index: SessionIndex) -> Option<Vec<AccountId>> {
+		Self::note_committee(
+			pallet_session::historical::NoteHistoricalRoot::<Runtime, Staking>::new_session_genesis(new_index),
+		)
+	}
+
+	fn end_session(end_index: SessionIndex) {
+		pallet_session::historical::NoteHistoricalRoot::<Runtime, Staking>::end_session(end_index)
+	}
+
+	fn start_session(start_index: SessionIndex) {
+		pallet_session::historical::NoteHistoricalRoot::<Runtime, Staking>::start_session(start_index)
+	}
+}
+
+impl module_aleph::Config for Runtime {
+	type AuthorityId = AlephId;
+	type RuntimeEvent = Event;
+	type SessionInfoProvider = SessionInfoImpl;
+	type SessionManager = FinalityCommitteeFromStaking;
+	type NextSessionAuthorityProvider = Session;
+}
+
 parameter_types! {
 	pub const EpochDuration: u64 = EPOCH_DURATION_IN_SLOTS;
 	pub const ExpectedBlockTime: Moment = MILLISECS_PER_BLOCK;
@@ -648,11 +732,15 @@ impl orml_tokens::Config for Runtime {
 parameter_types! {
 	pub SetUSDFixedPrice: Price = Price::saturating_from_rational(1, 1); // $1
 	pub SetterFixedPrice: Price = Price::saturating_from_rational(1, 4); // $0.25
+	pub const OsmUpdatePeriod: BlockNumber = HOURS;
+	pub OsmMaxPriceChange: Ratio = Ratio::saturating_from_rational(20, 100); // 20%
+	pub const OsmStalePeriod: Moment = 1000 * 60 * 60 * 3; // 3 hours
 }
 
 impl module_prices::Config for Runtime {
 	type Event = Event;
 	type Source = AggregatedDataProvider;
+	type Time = Timestamp;
 	type GetSetUSDId = GetSetUSDId;
 	type SetterCurrencyId = SetterCurrencyId;
 	type SetUSDFixedPrice = SetUSDFixedPrice;
@@ -661,6 +749,9 @@ impl module_prices::Config for Runtime {
 	type DEX = Dex;
 	type Currency = Currencies;
 	type CurrencyIdMapping = EvmCurrencyIdMapping<Runtime>;
+	type OsmUpdatePeriod = OsmUpdatePeriod;
+	type OsmMaxPriceChange = OsmMaxPriceChange;
+	type OsmStalePeriod = OsmStalePeriod;
 	type WeightInfo = weights::module_prices::WeightInfo<Runtime>;
 }
 
@@ -921,6 +1012,106 @@ parameter_types! {
 // 	type WeightInfo = weights::module_cdp_treasury::WeightInfo<Runtime>;
 // }
 
+parameter_types! {
+	pub const GetUSSDCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::USSD);
+	pub const GetAuctionCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::EDF);
+	pub EcdpDefaultLiquidationPenalty: FractionalRate =
+		FractionalRate::try_from(Rate::saturating_from_rational(5, 100)).expect("the rate is below one; qed");
+	pub MaxLiquidationContractSlippage: Ratio = Ratio::saturating_from_rational(15, 100);
+	pub const MaxLiquidationContracts: u32 = 10;
+	pub const MaxFlashMintCalls: u32 = 4;
+	pub const MaxBasketCurrencies: u32 = 4;
+	pub EcdpDepositPerAuthorization: Balance = dollar(SEE);
+	// Only ever used as the origin of EVM calls settling ERC20 collaterals, nobody holds its key.
+	pub SettleErc20EvmOrigin: AccountId = AccountId::from([255u8; 32]);
+}
+
+parameter_type_with_key! {
+	pub MinimumCollateralAmount: |currency_id: CurrencyId| -> Balance {
+		dollar(*currency_id) / 10
+	};
+}
+
+impl module_ecdp_loans::Config for Runtime {
+	type RuntimeEvent = Event;
+	type Currency = Currencies;
+	type EcdpUssdRiskManager = EcdpUssdEngine;
+	type EcdpUssdTreasury = EcdpUssdTreasury;
+	type PalletId = LoansPalletId;
+}
+
+impl module_ecdp_ussd_engine::Config for Runtime {
+	type RuntimeEvent = Event;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type DefaultLiquidationRatio = DefaultLiquidationRatio;
+	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
+	type DefaultLiquidationPenalty = EcdpDefaultLiquidationPenalty;
+	type MinimumDebitValue = MinimumDebitValue;
+	type MinimumCollateralAmount = MinimumCollateralAmount;
+	type GetUSSDCurrencyId = GetUSSDCurrencyId;
+	type MaxSwapSlippageCompareToOracle = MaxSwapSlippageComparedToOracle;
+	type EcdpUssdTreasury = EcdpUssdTreasury;
+	type PriceSource = module_prices::OsmPriceProvider<Runtime>;
+	type UnsignedPriority = runtime_common::CdpEngineUnsignedPriority;
+	type EcdpEmergencyShutdown = ();
+	type UnixTime = Timestamp;
+	type Currency = Currencies;
+	type DEX = Dex;
+	type Swap = module_support::SpecificJointsSwap<Dex, AlternativeSwapPathJointList>;
+	type LiquidationContractsUpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type MaxLiquidationContractSlippage = MaxLiquidationContractSlippage;
+	type MaxLiquidationContracts = MaxLiquidationContracts;
+	type LiquidationEvmBridge = module_evm_bridge::LiquidationEvmBridge<Runtime>;
+	type PalletId = EcdpUssdEnginePalletId;
+	type EvmAddressMapping = EvmAddressMapping<Runtime>;
+	type EVMBridge = module_evm_bridge::EVMBridge<Runtime>;
+	type SettleErc20EvmOrigin = SettleErc20EvmOrigin;
+	type RuntimeCall = Call;
+	type FlashMintEvmBridge = module_evm_bridge::FlashMintEvmBridge<Runtime>;
+	type MaxFlashMintCalls = MaxFlashMintCalls;
+	type MaxBasketCurrencies = MaxBasketCurrencies;
+	type WeightInfo = module_ecdp_ussd_engine::weights::SetheumWeight<Runtime>;
+}
+
+impl module_ecdp_ussd_treasury::Config for Runtime {
+	type RuntimeEvent = Event;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type Currency = Currencies;
+	type GetUSSDCurrencyId = GetUSSDCurrencyId;
+	type EcdpAuctionsManagerHandler = EcdpAuctions;
+	type DEX = Dex;
+	type Swap = module_support::SpecificJointsSwap<Dex, AlternativeSwapPathJointList>;
+	type MaxAuctionsCount = MaxAuctionsCount;
+	type TreasuryAccount = TreasuryAccount;
+	type PalletId = CDPTreasuryPalletId;
+	type WeightInfo = module_ecdp_ussd_treasury::weights::SetheumWeight<Runtime>;
+}
+
+impl module_ecdp_core::Config for Runtime {
+	type RuntimeEvent = Event;
+	type Currency = Balances;
+	type DepositPerAuthorization = EcdpDepositPerAuthorization;
+	type CollateralCurrencyIds = module_ecdp_ussd_engine::CollateralCurrencyIds<Runtime>;
+	type WeightInfo = module_ecdp_core::weights::SetheumWeight<Runtime>;
+}
+
+impl module_ecdp_auctions::Config for Runtime {
+	type RuntimeEvent = Event;
+	type MinimumIncrementSize = MinimumIncrementSize;
+	type AuctionTimeToClose = AuctionTimeToClose;
+	type AuctionDurationSoftCap = AuctionDurationSoftCap;
+	type GetUSSDCurrencyId = GetUSSDCurrencyId;
+	type GetAuctionCurrencyId = GetAuctionCurrencyId;
+	type Currency = Currencies;
+	type Auction = Auction;
+	type EcdpUssdTreasury = EcdpUssdTreasury;
+	type PriceSource = module_prices::RealTimePriceProvider<Runtime>;
+	type UnsignedPriority = runtime_common::AuctionManagerUnsignedPriority;
+	type EcdpEmergencyShutdown = ();
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type WeightInfo = module_ecdp_auctions::weights::SetheumWeight<Runtime>;
+}
+
 parameter_types! {
 	// Sort by fee charge order
 	pub DefaultFeeSwapPathList: Vec<Vec<CurrencyId>> = vec![
@@ -974,11 +1165,21 @@ impl module_transaction_payment::Config for Runtime {
 	type WeightInfo = weights::module_transaction_payment::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const UnlinkDelay: BlockNumber = 7 * DAYS;
+	pub const MaxSecondaryAddresses: u32 = 8;
+	pub const MaxMergedCurrencies: u32 = 16;
+}
+
 impl module_unified_accounts::Config for Runtime {
-	type Event = Event;
+	type RuntimeEvent = Event;
 	type Currency = Balances;
 	type AddressMapping = EvmAddressMapping<Runtime>;
+	type ChainId = ChainId;
 	type TransferAll = Currencies;
+	type UnlinkDelay = UnlinkDelay;
+	type MaxSecondaryAddresses = MaxSecondaryAddresses;
+	type MaxMergedCurrencies = MaxMergedCurrencies;
 	type WeightInfo = weights::module_unified_accounts::WeightInfo<Runtime>;
 }
 
@@ -992,7 +1193,14 @@ static ISTANBUL_CONFIG: evm::Config = evm::Config::istanbul();
 
 parameter_types! {
 	pub const ChainId: u64 = 258;
+	// 100 Gwei
+	pub const TxFeePerGas: Balance = 100_000_000_000;
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(0);
+	// `TxFeePerGas` scaled by the fee multiplier, EIP-1559 transactions must cover it.
+	pub BaseFeePerGas: Balance = primitives::evm::base_fee_per_gas(
+		TxFeePerGas::get(),
+		TransactionPayment::next_fee_multiplier(),
+	);
 }
 
 parameter_types! {
@@ -1030,8 +1238,31 @@ pub type ScheduleCallPrecompile = runtime_common::ScheduleCallPrecompile<
 	OriginCaller,
 	Runtime,
 >;
-// pub type DexPrecompile =
-// 	runtime_common::DexPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Dex>;
+pub type DexPrecompile =
+	runtime_common::DexPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Dex>;
+pub type FlashMintPrecompile = runtime_common::FlashMintPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	EcdpUssdEngine,
+>;
+pub type EcdpPrecompile =
+	runtime_common::EcdpPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Ecdp>;
+pub type IncentivesPrecompile = runtime_common::IncentivesPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	Incentives,
+>;
+pub type LaunchpadPrecompile = runtime_common::LaunchpadPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	BlockNumber,
+	Launchpad,
+>;
+pub type KzgPointEvaluationPrecompile =
+	module_evm::precompiles::KzgPointEvaluation<module_evm::precompiles::EthereumKzgTauG2>;
 
 impl module_evm::Config for Runtime {
 	type AddressMapping = EvmAddressMapping<Runtime>;
@@ -1042,14 +1273,21 @@ impl module_evm::Config for Runtime {
 	type Event = Event;
 	type Precompiles = runtime_common::AllPrecompiles<
 		SystemContractsFilter,
+		module_transaction_pause::PausedPrecompileFilter<Runtime>,
 		MultiCurrencyPrecompile,
 		NFTPrecompile,
 		StateRentPrecompile,
 		OraclePrecompile,
 		ScheduleCallPrecompile,
-		// DexPrecompile,
+		DexPrecompile,
+		FlashMintPrecompile,
+		EcdpPrecompile,
+		IncentivesPrecompile,
+		LaunchpadPrecompile,
+		KzgPointEvaluationPrecompile,
 	>;
 	type ChainId = ChainId;
+	type TxFeePerGas = TxFeePerGas;
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Runtime>;
 	type NetworkContractOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
@@ -1072,6 +1310,40 @@ impl module_evm_bridge::Config for Runtime {
 	type EVM = EVM;
 }
 
+parameter_types! {
+	pub const MaxImmutableReferences: u32 = 64;
+}
+
+impl module_evm_verification::Config for Runtime {
+	type Event = Event;
+	type EVMManager = EVM;
+	type UpdateOrigin = EnsureRootOrHalfTechnicalCommittee;
+	type MaxCodeSize = module_evm::MaxCodeSize;
+	type MaxImmutableReferences = MaxImmutableReferences;
+	type WeightInfo = module_evm_verification::weights::SetheumWeight<Runtime>;
+}
+
+parameter_types! {
+	// the last address of the predeploy range, no contract is deployed there
+	pub AccountAbstractionEntryPoint: H160 = H160::from_low_u64_be(0xfff);
+	pub const MaxUserOperations: u32 = 16;
+}
+
+impl module_account_abstraction::Config for Runtime {
+	type RuntimeEvent = Event;
+	type Currency = Balances;
+	type FeeCurrencySwap = TransactionPayment;
+	type NativeCurrencyId = GetNativeCurrencyId;
+	type EVM = EVM;
+	type AddressMapping = EvmAddressMapping<Runtime>;
+	type EntryPoint = AccountAbstractionEntryPoint;
+	type ChainId = ChainId;
+	type GasToWeight = GasToWeight;
+	type WeightToFee = WeightToFee;
+	type MaxUserOperations = MaxUserOperations;
+	type WeightInfo = module_account_abstraction::weights::SetheumWeight<Runtime>;
+}
+
 parameter_types! {
 	pub CreateClassDeposit: Balance = 11 * dollar(SEE);
 	pub CreateTokenDeposit: Balance = 7 * dollar(SEE);
@@ -1494,13 +1766,79 @@ impl pallet_recovery::Config for Runtime {
 	type RecoveryDeposit = RecoveryDeposit;
 }
 
-// impl orml_auction::Config for Runtime {
-// 	type Event = Event;
-// 	type Balance = Balance;
-// 	type AuctionId = AuctionId;
-// 	type Handler = AuctionManager;
-// 	type WeightInfo = weights::orml_auction::WeightInfo<Runtime>;
-// }
+impl orml_auction::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AuctionId = AuctionId;
+	type Handler = EcdpAuctions;
+	type WeightInfo = weights::orml_auction::WeightInfo<Runtime>;
+}
+
+impl orml_rewards::Config for Runtime {
+	type Share = Balance;
+	type Balance = Balance;
+	type PoolId = module_support::PoolId;
+	type CurrencyId = CurrencyId;
+	type Handler = Incentives;
+}
+
+parameter_types! {
+	pub const AccumulatePeriod: BlockNumber = MINUTES;
+}
+
+impl module_incentives::Config for Runtime {
+	type RuntimeEvent = Event;
+	type RewardsSource = TreasuryAccount;
+	type AccumulatePeriod = AccumulatePeriod;
+	type NativeCurrencyId = GetNativeCurrencyId;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type Currency = Currencies;
+	type EmergencyShutdown = ();
+	type PalletId = IncentivesPalletId;
+	type WeightInfo = module_incentives::weights::SetheumWeight<Runtime>;
+}
+
+parameter_type_with_key! {
+	pub LaunchpadMinRaise: |currency_id: CurrencyId| -> Balance {
+		1_000 * dollar(*currency_id)
+	};
+}
+
+parameter_type_with_key! {
+	pub LaunchpadMinContribution: |currency_id: CurrencyId| -> Balance {
+		dollar(*currency_id)
+	};
+}
+
+parameter_types! {
+	pub const LaunchpadCommission: (u32, u32) = (5, 100); // 5%
+	pub LaunchpadSubmissionDeposit: Balance = 100 * dollar(SEE);
+	pub const MaxLaunchpadProposals: u32 = 100;
+	pub const MaxLaunchpadCampaigns: u32 = 50;
+	pub const MaxCampaignActivePeriod: BlockNumber = 30 * DAYS;
+	pub const CampaignStartDelay: BlockNumber = DAYS;
+	pub const CampaignRetirementPeriod: BlockNumber = 30 * DAYS;
+	pub const ProposalRetirementPeriod: BlockNumber = 7 * DAYS;
+}
+
+impl module_edfis_launchpad::Config for Runtime {
+	type Event = Event;
+	type MultiCurrency = Currencies;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type GetCommission = LaunchpadCommission;
+	type SubmissionDeposit = LaunchpadSubmissionDeposit;
+	type MinRaise = LaunchpadMinRaise;
+	type MinContribution = LaunchpadMinContribution;
+	type MaxProposalsCount = MaxLaunchpadProposals;
+	type MaxCampaignsCount = MaxLaunchpadCampaigns;
+	type MaxActivePeriod = MaxCampaignActivePeriod;
+	type CampaignStartDelay = CampaignStartDelay;
+	type CampaignRetirementPeriod = CampaignRetirementPeriod;
+	type ProposalRetirementPeriod = ProposalRetirementPeriod;
+	type UpdateOrigin = EnsureRootOrHalfShuraCouncil;
+	type PalletId = LaunchpadPalletId;
+	type WeightInfo = module_edfis_launchpad::weights::SetheumWeight<Runtime>;
+}
 
 impl pallet_randomness_collective_flip::Config for Runtime {}
 
@@ -1531,7 +1869,7 @@ construct_runtime!(
 		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>} = 9,
 
 		// ORML Core
-		// Auction: orml_auction::{Pallet, Storage, Call, Event<T>} = 10,
+		Auction: orml_auction::{Pallet, Storage, Call, Event<T>} = 10,
 		OrmlNFT: orml_nft::{Pallet, Storage, Config<T>} = 11,
 
 		// Governance
@@ -1562,6 +1900,20 @@ construct_runtime!(
 		// CdpEngine: cdp_engine::{Pallet, Storage, Call, Event<T>, Config, ValidateUnsigned} = 28,
 		// EmergencyShutdown: emergency_shutdown::{Pallet, Storage, Call, Event<T>} = 29,
 
+		// ECDP
+		EcdpLoans: module_ecdp_loans::{Pallet, Storage, Event<T>} = 59,
+		EcdpUssdEngine: module_ecdp_ussd_engine::{Pallet, Storage, Call, Event<T>, ValidateUnsigned} = 60,
+		EcdpUssdTreasury: module_ecdp_ussd_treasury::{Pallet, Storage, Call, Event<T>} = 61,
+		EcdpAuctions: module_ecdp_auctions::{Pallet, Storage, Call, Event<T>, ValidateUnsigned} = 62,
+		Ecdp: module_ecdp_core::{Pallet, Storage, Call, Event<T>} = 63,
+
+		// Incentives
+		Rewards: orml_rewards::{Pallet, Storage} = 64,
+		Incentives: module_incentives::{Pallet, Storage, Call, Event<T>} = 65,
+
+		// Launchpad
+		Launchpad: module_edfis_launchpad::{Pallet, Storage, Call, Event<T>} = 66,
+
 		// Treasury
 		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>} = 30,
 		// Bounties
@@ -1592,6 +1944,8 @@ construct_runtime!(
 		EvmAccounts: module_unified_accounts::{Pallet, Call, Storage, Event<T>} = 44,
 		EVMBridge: module_evm_bridge::{Pallet} = 45,
 		EvmManager: module_evm_manager::{Pallet, Storage} = 46,
+		EvmVerification: module_evm_verification::{Pallet, Call, Storage, Event<T>} = 56,
+		AccountAbstraction: module_account_abstraction::{Pallet, Call, Storage, Event<T>} = 58,
 
 		// Consensus
 		Authorship: pallet_authorship::{Pallet, Call, Storage, Inherent} = 47,
@@ -1603,59 +1957,91 @@ construct_runtime!(
 		Offences: pallet_offences::{Pallet, Storage, Event} = 53,
 		ImOnline: pallet_im_online::{Pallet, Call, Storage, Event<T>, ValidateUnsigned, Config<T>} = 54,
 		AuthorityDiscovery: pallet_authority_discovery::{Pallet, Config} = 55,
+		Aleph: module_aleph::{Pallet, Call, Storage, Event<T>} = 57,
 	}
 );
 
 pub struct OnRuntimeUpgrade;
 impl frame_support::traits::OnRuntimeUpgrade for OnRuntimeUpgrade {
 	fn on_runtime_upgrade() -> u64 {
-		// no migration
-		0
+		// Validators registered their keys before the Aleph key existed, reuse their GRANDPA key for it.
+		Session::upgrade_keys::<opaque::SessionKeysWithoutAleph, _>(|_, old| opaque::SessionKeys {
+			aleph: aleph_key_of(&old.grandpa),
+			babe: old.babe,
+			grandpa: old.grandpa,
+			im_online: old.im_online,
+			authority_discovery: old.authority_discovery,
+		});
+		BlockWeights::get().max_block
 	}
 }
 
 #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug)]
 pub struct ConvertEthereumTx;
 
-impl Convert<(Call, SignedExtra), Result<EthereumTransactionMessage, InvalidTransaction>> for ConvertEthereumTx {
-	fn convert((call, extra): (Call, SignedExtra)) -> Result<EthereumTransactionMessage, InvalidTransaction> {
-		match call {
-			Call::EVM(module_evm::Call::eth_call(action, input, value, gas_limit, storage_limit, valid_until)) => {
+impl Convert<(Call, SignedExtra), Result<(EthereumTransactionMessage, SignedExtra), InvalidTransaction>>
+	for ConvertEthereumTx
+{
+	fn convert(
+		(call, extra): (Call, SignedExtra),
+	) -> Result<(EthereumTransactionMessage, SignedExtra), InvalidTransaction> {
+		let (action, input, value, gas_price, gas_limit, storage_limit, access_list, valid_until) = match call {
+			Call::EVM(module_evm::Call::eth_call(
+				action,
+				input,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list,
+				valid_until,
+			)) => {
 				if System::block_number() > valid_until {
 					return Err(InvalidTransaction::Stale);
 				}
-
-				let era: frame_system::CheckEra<Runtime> = extra.3;
-				if era != frame_system::CheckEra::from(sp_runtime::generic::Era::Immortal) {
-					// require immortal
-					return Err(InvalidTransaction::BadProof);
-				}
-
-				let nonce: frame_system::CheckNonce<Runtime> = extra.4;
-				// TODO: this is a hack access private nonce field
-				// remove this after https://github.com/paritytech/substrate/pull/9810
-				let nonce = nonce
-					.using_encoded(|mut encoded| Compact::<Nonce>::decode(&mut encoded))
-					.map_err(|_| InvalidTransaction::BadProof)?;
-
-				let tip: module_transaction_payment::ChargeTransactionPayment<Runtime> = extra.6;
-				let tip = tip.0;
-
-				Ok(EthereumTransactionMessage {
-					nonce: nonce.into(),
-					tip,
-					gas_limit,
-					storage_limit,
-					action,
-					value,
-					input,
-					chain_id: ChainId::get(),
-					genesis: System::block_hash(0),
-					valid_until,
-				})
+				// the gas price is recovered from the tx fee per gas and `valid_until`
+				(action, input, value, 0, gas_limit, storage_limit, access_list, valid_until)
 			}
-			_ => Err(InvalidTransaction::BadProof),
+			Call::EVM(module_evm::Call::eth_call_v2(action, input, value, gas_price, gas_limit, access_list)) => {
+				// the signed gas price and gas limit are encoded, `valid_until` is not signed
+				let (_, storage_limit) = primitives::evm::decode_gas_limit(gas_limit);
+				(action, input, value, gas_price, gas_limit, storage_limit, access_list, 0)
+			}
+			_ => return Err(InvalidTransaction::BadProof),
+		};
+
+		let era: frame_system::CheckEra<Runtime> = extra.3.clone();
+		if era != frame_system::CheckEra::from(sp_runtime::generic::Era::Immortal) {
+			// require immortal
+			return Err(InvalidTransaction::BadProof);
 		}
+
+		let nonce: frame_system::CheckNonce<Runtime> = extra.4.clone();
+		// TODO: this is a hack access private nonce field
+		// remove this after https://github.com/paritytech/substrate/pull/9810
+		let nonce = nonce
+			.using_encoded(|mut encoded| Compact::<Nonce>::decode(&mut encoded))
+			.map_err(|_| InvalidTransaction::BadProof)?;
+
+		let tip: module_transaction_payment::ChargeTransactionPayment<Runtime> = extra.6.clone();
+		let tip = tip.0;
+
+		Ok((
+			EthereumTransactionMessage {
+				nonce: nonce.into(),
+				tip,
+				gas_price,
+				gas_limit,
+				storage_limit,
+				action,
+				value,
+				input,
+				chain_id: ChainId::get(),
+				genesis: System::block_hash(0),
+				valid_until,
+				access_list,
+			},
+			extra,
+		))
 	}
 }
 
@@ -1680,12 +2066,27 @@ pub type SignedExtra = (
 	module_transaction_payment::ChargeTransactionPayment<Runtime>,
 	module_evm::SetEvmOrigin<Runtime>,
 );
-/// Unchecked extrinsic type as expected by this runtime.
-pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
+/// Unchecked extrinsic type as expected by this runtime, Ethereum signatures are checked against
+/// the `eth_call` and `eth_call_v2` calls converted by `ConvertEthereumTx`.
+pub type UncheckedExtrinsic = primitives::unchecked_extrinsic::SetheumUncheckedExtrinsic<
+	Call,
+	SignedExtra,
+	ConvertEthereumTx,
+	StorageDepositPerByte,
+	TxFeePerGas,
+	BaseFeePerGas,
+>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
+/// Migrations run on runtime upgrade, in order.
+pub type Migrations = (
+	OnRuntimeUpgrade,
+	module_ecdp_ussd_engine::MigrateRiskManagementParamsToV1<Runtime>,
+	module_ecdp_ussd_engine::MigrateRiskManagementParamsToV2<Runtime>,
+	module_nft::MigrateClassBalances<Runtime>,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -1693,7 +2094,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPallets,
-	OnRuntimeUpgrade,
+	Migrations,
 >;
 
 impl frame_system::offchain::SigningTypes for Runtime {
@@ -1978,6 +2379,21 @@ impl_runtime_apis! {
 						data: Some(data),
 					})
 				}
+				Call::EVM(module_evm::Call::eth_call_v2(action, input, value, _gas_price, gas_limit, access_list)) => {
+					let (gas_limit, storage_limit) = primitives::evm::decode_gas_limit(gas_limit);
+					Some(EstimateResourcesRequest {
+						from: None,
+						to: match action {
+							primitives::evm::TransactionAction::Call(to) => Some(to),
+							primitives::evm::TransactionAction::Create => None,
+						},
+						gas_limit: Some(gas_limit),
+						storage_limit: Some(storage_limit),
+						value: Some(value),
+						data: Some(input),
+						access_list: Some(access_list),
+					})
+				}
 				_ => None,
 			};
 
@@ -1985,6 +2401,277 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_evm_rpc_runtime_api::EVMTraceApi<Block, Balance> for Runtime {
+		fn trace_extrinsic(
+			extrinsic: <Block as BlockT>::Extrinsic,
+			tracer_config: primitives::evm::tracing::TracerConfig,
+		) -> Result<primitives::evm::tracing::TraceOutcome, sp_runtime::DispatchError> {
+			#[cfg(feature = "tracing")]
+			{
+				let mut tracer = module_evm::runner::tracing::Tracer::new(tracer_config);
+				module_evm::runner::tracing::using(&mut tracer, || Executive::apply_extrinsic(extrinsic))
+					.map_err(|_| sp_runtime::DispatchError::Other("Invalid extrinsic"))?;
+				Ok(tracer.finalize())
+			}
+
+			#[cfg(not(feature = "tracing"))]
+			{
+				let _ = (extrinsic, tracer_config);
+				Err(sp_runtime::DispatchError::Other("Runtime built without tracing"))
+			}
+		}
+
+		fn trace_call(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<primitives::evm::AccessListItem>>,
+			tracer_config: primitives::evm::tracing::TracerConfig,
+		) -> Result<primitives::evm::tracing::TraceOutcome, sp_runtime::DispatchError> {
+			#[cfg(feature = "tracing")]
+			{
+				let access_list = access_list
+					.unwrap_or_default()
+					.into_iter()
+					.map(|item| (item.address, item.storage_keys))
+					.collect();
+
+				let mut tracer = module_evm::runner::tracing::Tracer::new(tracer_config);
+				module_evm::runner::tracing::using(&mut tracer, || {
+					module_evm::runner::stack::Runner::<Runtime>::call(
+						from,
+						from,
+						to,
+						data,
+						value,
+						gas_limit,
+						storage_limit,
+						access_list,
+						<Runtime as module_evm::Config>::config(),
+					)
+				})?;
+				Ok(tracer.finalize())
+			}
+
+			#[cfg(not(feature = "tracing"))]
+			{
+				let _ = (from, to, data, value, gas_limit, storage_limit, access_list, tracer_config);
+				Err(sp_runtime::DispatchError::Other("Runtime built without tracing"))
+			}
+		}
+	}
+
+	impl primitives::aleph::AlephSessionApi<Block> for Runtime {
+		fn next_session_authorities() -> Result<Vec<AlephId>, AlephApiError> {
+			let next_authorities = Aleph::next_authorities();
+			if next_authorities.is_empty() {
+				return Err(AlephApiError::DecodeKey)
+			}
+			Ok(next_authorities)
+		}
+
+		fn authorities() -> Vec<AlephId> {
+			Aleph::authorities()
+		}
+
+		fn next_session_authority_data() -> Result<SessionAuthorityData, AlephApiError> {
+			Ok(SessionAuthorityData::new(
+				Self::next_session_authorities()?,
+				Aleph::queued_emergency_finalizer(),
+			))
+		}
+
+		fn authority_data() -> SessionAuthorityData {
+			SessionAuthorityData::new(Aleph::authorities(), Aleph::emergency_finalizer())
+		}
+
+		fn session_period() -> u32 {
+			EpochDuration::get() as u32
+		}
+
+		fn millisecs_per_block() -> u64 {
+			primitives::MILLISECS_PER_BLOCK
+		}
+
+		fn finality_version() -> FinalityVersion {
+			Aleph::finality_version()
+		}
+
+		fn next_session_finality_version() -> FinalityVersion {
+			Aleph::next_session_finality_version()
+		}
+
+		fn predict_session_committee(
+			session: SessionIndex,
+		) -> Result<SessionCommittee<AccountId>, SessionValidatorError> {
+			// Validators only change with staking eras, so the current and the queued validators cover
+			// every session that can be predicted.
+			let current_session = Session::current_index();
+			let validators = if session == current_session {
+				Session::validators()
+			} else if session == current_session.saturating_add(1) {
+				Session::queued_keys().into_iter().map(|(account, _)| account).collect()
+			} else {
+				return Err(SessionValidatorError::SessionNotWithinRange {
+					lower_limit: current_session,
+					upper_limit: current_session.saturating_add(1),
+				})
+			};
+			Ok(SessionCommittee {
+				finality_committee: validators.clone(),
+				block_producers: validators,
+			})
+		}
+
+		fn next_session_aura_authorities() -> Vec<(AccountId, AuraId)> {
+			// Blocks are produced with BABE, whose keys are the same sr25519 keys.
+			Session::queued_keys()
+				.into_iter()
+				.map(|(account, keys)| {
+					(account, AuraId::from(AsRef::<sp_core::sr25519::Public>::as_ref(&keys.babe).clone()))
+				})
+				.collect()
+		}
+
+		fn key_owner(key: AlephId) -> Option<AccountId> {
+			Session::key_owner(primitives::aleph::KEY_TYPE, key.as_ref())
+		}
+	}
+
+	impl module_evm_rpc_runtime_api::EVMVerificationApi<Block, AccountId, BlockNumber> for Runtime {
+		fn verified_contract(address: H160) -> Option<primitives::evm::VerifiedContract<AccountId, BlockNumber>> {
+			EvmVerification::verified_contract(address)
+		}
+	}
+
+	impl module_evm_rpc_runtime_api::EthereumRuntimeRPCApi<Block> for Runtime {
+		fn chain_id() -> u64 {
+			ChainId::get()
+		}
+
+		fn balance(address: H160) -> U256 {
+			EVM::account_basic(&address).balance
+		}
+
+		fn transaction_count(address: H160) -> U256 {
+			use module_support::AddressMapping;
+
+			let account_id = EvmAddressMapping::<Runtime>::get_account_id(&address);
+			System::account_nonce(&account_id).into()
+		}
+
+		fn code_at(address: H160) -> Vec<u8> {
+			EVM::code_at_address(&address).into_inner()
+		}
+
+		fn storage_at(address: H160, index: H256) -> H256 {
+			module_evm::AccountStorages::<Runtime>::get(address, index)
+		}
+
+		fn tx_fee_per_gas() -> U256 {
+			TxFeePerGas::get().into()
+		}
+
+		fn base_fee_per_gas() -> U256 {
+			BaseFeePerGas::get().into()
+		}
+
+		fn timestamp() -> u64 {
+			Timestamp::now()
+		}
+
+		fn receipts() -> Vec<primitives::evm::EvmReceipt> {
+			System::events()
+				.into_iter()
+				.filter_map(|record| {
+					let extrinsic_index = match record.phase {
+						frame_system::Phase::ApplyExtrinsic(index) => index,
+						_ => return None,
+					};
+					let receipt = |from, to, contract_address, logs, used_gas, used_storage, succeeded| {
+						primitives::evm::EvmReceipt {
+							extrinsic_index,
+							from,
+							to,
+							contract_address,
+							logs,
+							used_gas,
+							used_storage,
+							succeeded,
+						}
+					};
+					match record.event {
+						Event::EVM(module_evm::Event::Created { from, contract, logs, used_gas, used_storage }) => {
+							Some(receipt(from, None, Some(contract), logs, used_gas, used_storage, true))
+						}
+						Event::EVM(module_evm::Event::CreatedFailed { from, contract, logs, used_gas, used_storage, .. }) => {
+							Some(receipt(from, None, Some(contract), logs, used_gas, used_storage, false))
+						}
+						Event::EVM(module_evm::Event::Executed { from, contract, logs, used_gas, used_storage }) => {
+							Some(receipt(from, Some(contract), None, logs, used_gas, used_storage, true))
+						}
+						Event::EVM(module_evm::Event::ExecutedFailed { from, contract, logs, used_gas, used_storage, .. }) => {
+							Some(receipt(from, Some(contract), None, logs, used_gas, used_storage, false))
+						}
+						_ => None,
+					}
+				})
+				.collect()
+		}
+
+		fn ethereum_transaction_to_extrinsic(
+			transaction: Vec<u8>,
+		) -> Result<<Block as BlockT>::Extrinsic, sp_runtime::DispatchError> {
+			let tx = primitives::unchecked_extrinsic::decode_ethereum_transaction(&transaction)
+				.ok_or(sp_runtime::DispatchError::Other("Invalid ethereum transaction"))?;
+			if tx.chain_id != ChainId::get() {
+				return Err(sp_runtime::DispatchError::Other("Invalid chain id"));
+			}
+
+			let tip = match tx.max_priority_fee_per_gas {
+				Some(max_priority_fee_per_gas) => primitives::evm::decode_eip1559_gas_price(
+					tx.gas_price,
+					max_priority_fee_per_gas,
+					tx.gas_limit,
+					BaseFeePerGas::get(),
+				)
+				.ok_or(sp_runtime::DispatchError::Other("Invalid max fee per gas"))?,
+				None => primitives::evm::decode_gas_price(tx.gas_price, tx.gas_limit, TxFeePerGas::get())
+					.ok_or(sp_runtime::DispatchError::Other("Invalid gas price"))?
+					.0,
+			};
+			// `eth_call_v2` requires an immortal era, the validity of legacy and EIP-2930 transactions
+			// is encoded in the gas price
+			let extra: SignedExtra = (
+				frame_system::CheckSpecVersion::<Runtime>::new(),
+				frame_system::CheckTxVersion::<Runtime>::new(),
+				frame_system::CheckGenesis::<Runtime>::new(),
+				frame_system::CheckEra::<Runtime>::from(generic::Era::Immortal),
+				frame_system::CheckNonce::<Runtime>::from(tx.nonce),
+				frame_system::CheckWeight::<Runtime>::new(),
+				module_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+				module_evm::SetEvmOrigin::<Runtime>::new(),
+			);
+			let call = Call::EVM(module_evm::Call::eth_call_v2(
+				tx.action,
+				tx.input,
+				tx.value,
+				tx.gas_price,
+				tx.gas_limit,
+				tx.access_list,
+			));
+
+			<UncheckedExtrinsic as sp_runtime::traits::Extrinsic>::new(
+				call,
+				Some((Address::Address20(tx.signer.into()), tx.signature, extra)),
+			)
+			.ok_or(sp_runtime::DispatchError::Other("Invalid ethereum transaction"))
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -0,0 +1,265 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::assert_ok;
+use module_evm::{precompiles::PrecompileSet, Context, ExitSucceed};
+use module_evm_rpc_runtime_api::EVMRuntimeRPCApi;
+use module_evm_utility::ethereum::{
+	EIP1559Transaction, EIP1559TransactionMessage, EnvelopedEncodable, LegacyTransaction, LegacyTransactionMessage,
+	TransactionAction, TransactionSignature, TransactionV2,
+};
+use module_support::AddressMapping;
+use primitives::{
+	evm::EvmAddress, unchecked_extrinsic::decode_ethereum_transaction, EcdpPosition, PRECOMPILE_ADDRESS_START,
+};
+use sp_core::{ecdsa, Pair, H160, H256, U256};
+use sp_runtime::{
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	FixedPointNumber,
+};
+
+type Precompiles = <Runtime as module_evm::Config>::Precompiles;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap()
+		.into()
+}
+
+fn word(value: u128) -> Vec<u8> {
+	H256::from_low_u64_be(value as u64).as_bytes().to_vec()
+}
+
+fn system_contract_context(address: H160) -> Context {
+	Context {
+		address,
+		caller: H160::from_low_u64_be(1),
+		apparent_value: Default::default(),
+	}
+}
+
+/// Sign a legacy transaction valid until block 100 with the `TxFeePerGas` gas price.
+fn sign_legacy_transaction(pair: &ecdsa::Pair) -> Vec<u8> {
+	let msg = LegacyTransactionMessage {
+		nonce: U256::zero(),
+		gas_price: U256::from(TxFeePerGas::get() + 100),
+		gas_limit: U256::from(2_100_000),
+		action: TransactionAction::Call(H160::from_low_u64_be(0x1111)),
+		value: U256::zero(),
+		input: vec![0x12, 0x34],
+		chain_id: Some(ChainId::get()),
+	};
+	let sig = pair.sign_prehashed(msg.hash().as_fixed_bytes()).0;
+	let transaction = TransactionV2::Legacy(LegacyTransaction {
+		nonce: msg.nonce,
+		gas_price: msg.gas_price,
+		gas_limit: msg.gas_limit,
+		action: msg.action,
+		value: msg.value,
+		input: msg.input,
+		signature: TransactionSignature::new(
+			ChainId::get() * 2 + 35 + u64::from(sig[64]),
+			H256::from_slice(&sig[0..32]),
+			H256::from_slice(&sig[32..64]),
+		)
+		.unwrap(),
+	});
+	EnvelopedEncodable::encode(&transaction).to_vec()
+}
+
+fn sign_eip1559_transaction(pair: &ecdsa::Pair, max_fee_per_gas: Balance) -> Vec<u8> {
+	let msg = EIP1559TransactionMessage {
+		chain_id: ChainId::get(),
+		nonce: U256::zero(),
+		max_priority_fee_per_gas: U256::zero(),
+		max_fee_per_gas: U256::from(max_fee_per_gas),
+		gas_limit: U256::from(2_100_000),
+		action: TransactionAction::Call(H160::from_low_u64_be(0x1111)),
+		value: U256::zero(),
+		input: vec![0x12, 0x34],
+		access_list: vec![],
+	};
+	let sig = pair.sign_prehashed(msg.hash().as_fixed_bytes()).0;
+	let transaction = TransactionV2::EIP1559(EIP1559Transaction {
+		chain_id: msg.chain_id,
+		nonce: msg.nonce,
+		max_priority_fee_per_gas: msg.max_priority_fee_per_gas,
+		max_fee_per_gas: msg.max_fee_per_gas,
+		gas_limit: msg.gas_limit,
+		action: msg.action,
+		value: msg.value,
+		input: msg.input,
+		access_list: msg.access_list,
+		odd_y_parity: sig[64] != 0,
+		r: H256::from_slice(&sig[0..32]),
+		s: H256::from_slice(&sig[32..64]),
+	});
+	EnvelopedEncodable::encode(&transaction).to_vec()
+}
+
+fn selector(signature: &str) -> Vec<u8> {
+	sp_io::hashing::keccak_256(signature.as_bytes())[..4].to_vec()
+}
+
+#[test]
+fn flash_mint_precompile_is_registered() {
+	new_test_ext().execute_with(|| {
+		let address = H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 6);
+		module_ecdp_ussd_engine::FlashMintCeiling::<Runtime>::put(1_000_000);
+
+		let resp = Precompiles::execute(address, &selector("maxFlashMint()"), None, &system_contract_context(address))
+			.expect("flash mint precompile is registered")
+			.unwrap();
+		assert_eq!(resp.exit_status, ExitSucceed::Returned);
+		assert_eq!(resp.output, word(1_000_000));
+	});
+}
+
+#[test]
+fn paused_precompile_should_fail() {
+	new_test_ext().execute_with(|| {
+		let address = H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 6);
+		assert_ok!(TransactionPause::pause_evm_precompile(Origin::root(), address));
+
+		assert_eq!(
+			Precompiles::execute(address, &selector("maxFlashMint()"), None, &system_contract_context(address)),
+			Some(Err(module_evm::ExitError::Other("precompile is paused".into())))
+		);
+	});
+}
+
+#[test]
+fn kzg_point_evaluation_precompile_is_registered() {
+	new_test_ext().execute_with(|| {
+		let address = H160::from_low_u64_be(0x0a);
+
+		// the input of the point evaluation is 192 bytes
+		assert!(matches!(
+			Precompiles::execute(address, &[0u8; 32], None, &system_contract_context(address)),
+			Some(Err(_))
+		));
+	});
+}
+
+#[test]
+fn ecdp_precompile_is_registered() {
+	new_test_ext().execute_with(|| {
+		let address = H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 7);
+		let who = H160::from_low_u64_be(0x1234);
+		module_ecdp_loans::EcdpPositions::<Runtime>::insert(
+			SEE,
+			EvmAddressMapping::<Runtime>::get_account_id(&who),
+			EcdpPosition {
+				collateral: 1_000,
+				debit: 500,
+			},
+		);
+
+		let mut input = selector("getPosition(address,address)");
+		input.extend_from_slice(H256::from(who).as_bytes());
+		input.extend_from_slice(H256::from(EvmAddress::try_from(SEE).unwrap()).as_bytes());
+		let resp = Precompiles::execute(address, &input, None, &system_contract_context(address))
+			.expect("ecdp precompile is registered")
+			.unwrap();
+		assert_eq!(resp.exit_status, ExitSucceed::Returned);
+		assert_eq!(resp.output, [word(1_000), word(500)].concat());
+	});
+}
+
+#[test]
+fn incentives_precompile_is_registered() {
+	new_test_ext().execute_with(|| {
+		let address = H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 8);
+
+		let mut input = selector("getClaimRewardDeductionRate(uint8,address)");
+		input.extend_from_slice(&word(1));
+		input.extend_from_slice(H256::from(EvmAddress::try_from(SEE).unwrap()).as_bytes());
+		let resp = Precompiles::execute(address, &input, None, &system_contract_context(address))
+			.expect("incentives precompile is registered")
+			.unwrap();
+		assert_eq!(resp.exit_status, ExitSucceed::Returned);
+		assert_eq!(resp.output, word(0));
+	});
+}
+
+#[test]
+fn launchpad_precompile_is_registered() {
+	new_test_ext().execute_with(|| {
+		let address = H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 9);
+
+		// no campaign has been launched
+		let mut input = selector("getCampaign(address)");
+		input.extend_from_slice(H256::from(EvmAddress::try_from(SEE).unwrap()).as_bytes());
+		assert_eq!(
+			Precompiles::execute(address, &input, None, &system_contract_context(address))
+				.expect("launchpad precompile is registered"),
+			Err(module_evm::ExitError::Other("Launchpad get_campaign failed".into()))
+		);
+	});
+}
+
+#[test]
+fn signed_raw_ethereum_transaction_should_validate() {
+	let pair = ecdsa::Pair::from_seed(&[1u8; 32]);
+	let raw = sign_legacy_transaction(&pair);
+
+	new_test_ext().execute_with(|| {
+		let signer = decode_ethereum_transaction(&raw).unwrap().signer;
+		let who = EvmAddressMapping::<Runtime>::get_account_id(&signer);
+		Balances::make_free_balance_be(&who, 1_000 * dollar(SEE));
+
+		let xt = <Runtime as EVMRuntimeRPCApi<Block, Balance>>::ethereum_transaction_to_extrinsic(raw).unwrap();
+		assert_ok!(Executive::validate_transaction(
+			TransactionSource::External,
+			xt,
+			System::block_hash(0)
+		));
+	});
+}
+
+#[test]
+fn eip1559_extrinsic_should_cover_base_fee() {
+	let pair = ecdsa::Pair::from_seed(&[1u8; 32]);
+	let raw = sign_eip1559_transaction(&pair, TxFeePerGas::get() * 3 / 2);
+
+	new_test_ext().execute_with(|| {
+		let signer = decode_ethereum_transaction(&raw).unwrap().signer;
+		let who = EvmAddressMapping::<Runtime>::get_account_id(&signer);
+		Balances::make_free_balance_be(&who, 1_000 * dollar(SEE));
+
+		let xt = <Runtime as EVMRuntimeRPCApi<Block, Balance>>::ethereum_transaction_to_extrinsic(raw).unwrap();
+		assert_ok!(Executive::validate_transaction(
+			TransactionSource::External,
+			xt.clone(),
+			System::block_hash(0)
+		));
+
+		// the fee multiplier doubles the base fee above the signed max fee
+		module_transaction_payment::NextFeeMultiplier::<Runtime>::put(Multiplier::saturating_from_integer(2));
+		assert_eq!(
+			Executive::validate_transaction(TransactionSource::External, xt, System::block_hash(0)),
+			Err(InvalidTransaction::Payment.into())
+		);
+	});
+}
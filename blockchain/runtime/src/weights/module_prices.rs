@@ -58,4 +58,26 @@ impl<T: frame_system::Config> module_prices::WeightInfo for WeightInfo<T> {
 		(24_114_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn enable_osm() -> Weight {
+		(72_310_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn disable_osm() -> Weight {
+		(26_402_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn confirm_osm_price() -> Weight {
+		(34_876_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn on_initialize(c: u32, ) -> Weight {
+		(4_120_000 as Weight)
+			// Standard Error: 21_000
+			.saturating_add((68_517_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((10 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+	}
 }
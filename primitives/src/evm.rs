@@ -26,16 +26,18 @@ use core::ops::Range;
 use hex_literal::hex;
 pub use module_evm_utility::{
 	ethereum::{AccessListItem, Log, TransactionAction},
-	evm::ExitReason,
+	evm::{ExitError, ExitReason},
 };
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_core::{H160, H256, U256};
-use sp_runtime::{traits::Zero, RuntimeDebug, SaturatedConversion};
+use sp_runtime::{traits::Zero, FixedPointNumber, FixedU128, RuntimeDebug, SaturatedConversion};
 use sp_std::vec::Vec;
 
+pub mod tracing;
+
 /// Evm Address.
 pub type EvmAddress = sp_core::H160;
 
@@ -88,6 +90,40 @@ pub struct ExecutionInfo<T> {
 pub type CallInfo = ExecutionInfo<Vec<u8>>;
 pub type CreateInfo = ExecutionInfo<H160>;
 
+/// Outcome of an EVM execution, rebuilt from the `module_evm` events of an extrinsic.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EvmReceipt {
+	/// Index of the extrinsic in the block.
+	pub extrinsic_index: u32,
+	pub from: EvmAddress,
+	/// `None` for contract creations.
+	pub to: Option<EvmAddress>,
+	/// The created contract, `None` for calls.
+	pub contract_address: Option<EvmAddress>,
+	pub logs: Vec<Log>,
+	pub used_gas: u64,
+	pub used_storage: i32,
+	pub succeeded: bool,
+}
+
+/// Verification of an EVM contract, whose deployed code was reproduced by compiling its source.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VerifiedContract<AccountId, BlockNumber> {
+	/// Hash of the deployed code the verification applies to.
+	pub code_hash: H256,
+	/// Keccak-256 hash of the ABI, serialized as compact JSON with sorted keys.
+	pub abi_hash: H256,
+	/// Keccak-256 hash of the solc standard-JSON input, serialized the same way.
+	pub source_hash: H256,
+	/// Version of solc, packed as `major << 16 | minor << 8 | patch`.
+	pub compiler_version: u32,
+	/// The verifier which reproduced the code.
+	pub verifier: AccountId,
+	pub verified_at: BlockNumber,
+}
+
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct BlockLimits {
@@ -158,6 +194,7 @@ pub const SYSTEM_CONTRACT_ADDRESS_PREFIX: [u8; 9] = [0u8; 9];
 ///   ^^^^^^^^^^^^^^^^^^                       System contract address prefix
 ///                     ^^                     CurrencyId Type: 1-Token 2-DexShare
 ///                                                             3-ForeignAsset(ignore Erc20, without the prefix of system contracts)
+///                                                             FE-NFT Class(ERC-721 virtual contract)
 ///                                                             FF-Erc20 Holding Account
 ///                                         ^^ CurrencyId Type is 1-Token, Token
 ///                                   ^^^^^^^^ CurrencyId Type is 1-Token, NFT
@@ -168,6 +205,7 @@ pub const SYSTEM_CONTRACT_ADDRESS_PREFIX: [u8; 9] = [0u8; 9];
 ///                                                             the same as DexShare Left Type
 ///                                   ^^^^^^^^ CurrencyId Type is 2-DexShare, DexShare right field
 ///                                   ^^^^^^^^ CurrencyId Type is 3-ForeignAsset, ForeignAssetId
+///                                   ^^^^^^^^ CurrencyId Type is FE-NFT Class, ClassId
 
 /// Check if the given `address` is a system contract.
 ///
@@ -184,6 +222,35 @@ pub const H160_POSITION_DEXSHARE_LEFT_FIELD: Range<usize> = 11..15;
 pub const H160_POSITION_DEXSHARE_RIGHT_TYPE: usize = 15;
 pub const H160_POSITION_DEXSHARE_RIGHT_FIELD: Range<usize> = 16..20;
 pub const H160_POSITION_FOREIGN_ASSET: Range<usize> = 18..20;
+pub const H160_POSITION_NFT_CLASS: Range<usize> = 16..20;
+
+/// The type byte of the ERC-721 virtual contract addresses of NFT classes.
+pub const NFT_CLASS_ADDRESS_TYPE: u8 = 0xFE;
+
+/// The ERC-721 virtual contract address of NFT class `class_id`.
+pub fn nft_class_address(class_id: u32) -> EvmAddress {
+	let mut address = [0u8; 20];
+	address[H160_POSITION_CURRENCY_ID_TYPE] = NFT_CLASS_ADDRESS_TYPE;
+	address[H160_POSITION_NFT_CLASS].copy_from_slice(&class_id.to_be_bytes());
+	EvmAddress::from_slice(&address)
+}
+
+/// The NFT class id of an ERC-721 virtual contract `address`, `None` if it's not one.
+pub fn nft_class_id(address: &EvmAddress) -> Option<u32> {
+	let bytes = address.as_bytes();
+	if !is_system_contract(address)
+		|| bytes[H160_POSITION_CURRENCY_ID_TYPE] != NFT_CLASS_ADDRESS_TYPE
+		|| bytes[H160_POSITION_CURRENCY_ID_TYPE + 1..H160_POSITION_NFT_CLASS.start]
+			.iter()
+			.any(|b| *b != 0)
+	{
+		return None;
+	}
+
+	let mut class_id = [0u8; 4];
+	class_id.copy_from_slice(&bytes[H160_POSITION_NFT_CLASS]);
+	Some(u32::from_be_bytes(class_id))
+}
 
 /// Generate the EvmAddress from CurrencyId so that evm contracts can call the erc20 contract.
 /// NOTE: Can not be used directly, need to check the erc20 is mapped.
@@ -249,6 +316,29 @@ pub fn decode_gas_price(gas_price: u64, gas_limit: u64, tx_fee_per_gas: u128) ->
 	Some((tip, valid_until))
 }
 
+/// EIP-1559 base fee per gas: `tx_fee_per_gas` scaled by the fee multiplier of
+/// `TargetedFeeAdjustment`, so it follows the congestion Substrate fees follow.
+pub fn base_fee_per_gas(tx_fee_per_gas: u128, fee_multiplier: FixedU128) -> u128 {
+	fee_multiplier.saturating_mul_int(tx_fee_per_gas)
+}
+
+/// Decode the fees of an EIP-1559 transaction, returns the tip in native decimals.
+///
+/// The tip is signed as `max_priority_fee_per_gas * gas_limit` so it can't be capped to what
+/// `max_fee_per_gas` leaves above the base fee: `max_fee_per_gas` must cover both.
+pub fn decode_eip1559_gas_price(
+	max_fee_per_gas: u64,
+	max_priority_fee_per_gas: u64,
+	gas_limit: u64,
+	base_fee_per_gas: u128,
+) -> Option<u128> {
+	if u128::from(max_fee_per_gas) < base_fee_per_gas.checked_add(max_priority_fee_per_gas.into())? {
+		return None;
+	}
+
+	convert_decimals_from_evm(u128::from(max_priority_fee_per_gas))?.checked_mul(gas_limit.into())
+}
+
 pub fn decode_gas_limit(gas_limit: u64) -> (u64, u32) {
 	let gas_and_storage: u64 = gas_limit.checked_rem(GAS_MASK).expect("constant never failed; qed");
 	let actual_gas_limit: u64 = gas_and_storage
@@ -272,6 +362,85 @@ pub fn decode_gas_limit(gas_limit: u64) -> (u64, u32) {
 	(actual_gas_limit, actual_storage_limit)
 }
 
+/// Encode `gas_limit` and `storage_limit` the way `decode_gas_limit` expects them.
+///
+/// The gas limit is rounded up to `GAS_LIMIT_CHUNK` and the storage limit to a power of two,
+/// so the decoded limits are never lower than the requested ones.
+pub fn encode_gas_limit(gas_limit: u64, storage_limit: u32) -> Option<u64> {
+	let gas_limit_number = gas_limit.checked_add(GAS_LIMIT_CHUNK - 1)?.checked_div(GAS_LIMIT_CHUNK)?;
+	if gas_limit_number >= GAS_MASK.checked_div(STORAGE_MASK)? {
+		return None;
+	}
+
+	let storage_limit_number: u32 = if storage_limit.is_zero() {
+		Zero::zero()
+	} else {
+		// ceil(log2(storage_limit)), at least 1 as 0 means no storage
+		(u32::BITS - storage_limit.saturating_sub(1).leading_zeros()).max(1)
+	};
+	if storage_limit_number > MAX_GAS_LIMIT_CC {
+		return None;
+	}
+
+	gas_limit_number
+		.checked_mul(STORAGE_MASK)?
+		.checked_add(storage_limit_number.into())
+}
+
+/// Strip the CBOR encoded metadata solc appends to runtime code. It hashes the sources and
+/// the compiler settings, so it differs between builds of the same code.
+///
+/// The metadata is followed by its length as 2 big-endian bytes. The code is returned unchanged
+/// if it doesn't end with a CBOR map of that length.
+pub fn strip_code_metadata(code: &[u8]) -> &[u8] {
+	if code.len() < 2 {
+		return code;
+	}
+	let metadata_len = usize::from(u16::from_be_bytes([code[code.len() - 2], code[code.len() - 1]]));
+	match code.len().checked_sub(metadata_len.saturating_add(2)) {
+		// CBOR maps are major type 5
+		Some(start) if metadata_len > 0 && code[start] >> 5 == 5 => &code[..start],
+		_ => code,
+	}
+}
+
+/// The length of an immutable variable in runtime code, a `PUSH32` operand.
+pub const IMMUTABLE_LENGTH: u32 = 32;
+
+/// Check that the `compiled` runtime code output by solc is the `deployed` code, ignoring the
+/// metadata and the values of immutable variables.
+///
+/// `immutable_references` are the `(start, length)` ranges solc reserves for immutable
+/// variables, which are zero in the compiled code and filled in by the constructor. They must
+/// be sorted and each must be the operand of a `PUSH32`, so that no code can be masked.
+pub fn is_code_reproduced(deployed: &[u8], compiled: &[u8], immutable_references: &[(u32, u32)]) -> bool {
+	const PUSH32: u8 = 0x7f;
+
+	let deployed = strip_code_metadata(deployed);
+	let compiled = strip_code_metadata(compiled);
+	if deployed.is_empty() || deployed.len() != compiled.len() {
+		return false;
+	}
+
+	let mut masked = deployed.to_vec();
+	// end of the previous range, the opcode of the next one must follow it
+	let mut previous_end = 0usize;
+	for (start, length) in immutable_references {
+		let start = *start as usize;
+		let end = start.saturating_add(IMMUTABLE_LENGTH as usize);
+		if *length != IMMUTABLE_LENGTH || start <= previous_end || end > masked.len() {
+			return false;
+		}
+		if compiled[start - 1] != PUSH32 || compiled[start..end].iter().any(|b| *b != 0) {
+			return false;
+		}
+		masked[start..end].fill(0);
+		previous_end = end;
+	}
+
+	masked == compiled
+}
+
 #[cfg(not(feature = "evm-tests"))]
 mod convert {
 	use sp_runtime::traits::{CheckedDiv, Saturating, Zero};
@@ -0,0 +1,281 @@
+// بِسْمِ اللَّهِ الرَّحْمَنِ الرَّحِيم
+
+// This file is part of Setheum.
+
+// Copyright (C) 2019-Present Setheum Labs.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Types shared by the EVM tracer in the runtime and the `debug_*` RPC on the node.
+
+use super::ExitReason;
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, H256, U256};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct OpcodeTracerConfig {
+	/// Do not record the stack of each step.
+	pub disable_stack: bool,
+	/// Record the memory of each step.
+	pub enable_memory: bool,
+	/// Do not record the storage touched by `SLOAD` / `SSTORE`.
+	pub disable_storage: bool,
+}
+
+impl Default for OpcodeTracerConfig {
+	fn default() -> Self {
+		Self {
+			disable_stack: false,
+			enable_memory: false,
+			disable_storage: false,
+		}
+	}
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TracerConfig {
+	/// Geth `callTracer`: a tree of the calls made during execution.
+	CallTracer,
+	/// Geth default struct logger: every executed opcode.
+	OpcodeTracer(OpcodeTracerConfig),
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum CallType {
+	CALL,
+	CALLCODE,
+	STATICCALL,
+	DELEGATECALL,
+	CREATE,
+	CREATE2,
+	SUICIDE,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CallTrace {
+	pub call_type: CallType,
+	pub from: H160,
+	pub to: H160,
+	pub input: Vec<u8>,
+	pub value: U256,
+	/// Gas made available to the call.
+	pub gas: u64,
+	pub gas_used: u64,
+	pub output: Vec<u8>,
+	/// `None` while the call is still executing.
+	pub exit_reason: Option<ExitReason>,
+	/// Net storage charged by the `StorageMeter` for this call, excluding subcalls.
+	pub used_storage: i32,
+	pub calls: Vec<CallTrace>,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Step {
+	pub op: u8,
+	pub pc: u32,
+	/// Call depth, starting at 1 for the outermost call.
+	pub depth: u32,
+	/// Gas remaining before the opcode is executed.
+	pub gas: u64,
+	pub gas_cost: u64,
+	pub stack: Option<Vec<H256>>,
+	pub memory: Option<Vec<u8>>,
+	/// Storage of the executing contract seen so far, only set on `SLOAD` / `SSTORE`.
+	pub storage: Option<Vec<(H256, H256)>>,
+	/// Storage charged (or refunded, if negative) by the `StorageMeter` during this step.
+	pub storage_charge: i32,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct OpcodeTrace {
+	pub gas: u64,
+	pub exit_reason: Option<ExitReason>,
+	pub return_value: Vec<u8>,
+	/// Net storage charged by the `StorageMeter` for the whole execution.
+	pub used_storage: i32,
+	pub steps: Vec<Step>,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TraceOutcome {
+	/// One root call per EVM execution in the traced extrinsic.
+	Calls(Vec<CallTrace>),
+	/// One trace per EVM execution in the traced extrinsic.
+	Steps(Vec<OpcodeTrace>),
+}
+
+/// Mnemonic of an EVM opcode, as printed by Geth's struct logger.
+pub fn opcode_name(op: u8) -> &'static str {
+	match op {
+		0x00 => "STOP",
+		0x01 => "ADD",
+		0x02 => "MUL",
+		0x03 => "SUB",
+		0x04 => "DIV",
+		0x05 => "SDIV",
+		0x06 => "MOD",
+		0x07 => "SMOD",
+		0x08 => "ADDMOD",
+		0x09 => "MULMOD",
+		0x0a => "EXP",
+		0x0b => "SIGNEXTEND",
+		0x10 => "LT",
+		0x11 => "GT",
+		0x12 => "SLT",
+		0x13 => "SGT",
+		0x14 => "EQ",
+		0x15 => "ISZERO",
+		0x16 => "AND",
+		0x17 => "OR",
+		0x18 => "XOR",
+		0x19 => "NOT",
+		0x1a => "BYTE",
+		0x1b => "SHL",
+		0x1c => "SHR",
+		0x1d => "SAR",
+		0x20 => "KECCAK256",
+		0x30 => "ADDRESS",
+		0x31 => "BALANCE",
+		0x32 => "ORIGIN",
+		0x33 => "CALLER",
+		0x34 => "CALLVALUE",
+		0x35 => "CALLDATALOAD",
+		0x36 => "CALLDATASIZE",
+		0x37 => "CALLDATACOPY",
+		0x38 => "CODESIZE",
+		0x39 => "CODECOPY",
+		0x3a => "GASPRICE",
+		0x3b => "EXTCODESIZE",
+		0x3c => "EXTCODECOPY",
+		0x3d => "RETURNDATASIZE",
+		0x3e => "RETURNDATACOPY",
+		0x3f => "EXTCODEHASH",
+		0x40 => "BLOCKHASH",
+		0x41 => "COINBASE",
+		0x42 => "TIMESTAMP",
+		0x43 => "NUMBER",
+		0x44 => "PREVRANDAO",
+		0x45 => "GASLIMIT",
+		0x46 => "CHAINID",
+		0x47 => "SELFBALANCE",
+		0x48 => "BASEFEE",
+		0x50 => "POP",
+		0x51 => "MLOAD",
+		0x52 => "MSTORE",
+		0x53 => "MSTORE8",
+		0x54 => "SLOAD",
+		0x55 => "SSTORE",
+		0x56 => "JUMP",
+		0x57 => "JUMPI",
+		0x58 => "PC",
+		0x59 => "MSIZE",
+		0x5a => "GAS",
+		0x5b => "JUMPDEST",
+		0x5f => "PUSH0",
+		0x60 => "PUSH1",
+		0x61 => "PUSH2",
+		0x62 => "PUSH3",
+		0x63 => "PUSH4",
+		0x64 => "PUSH5",
+		0x65 => "PUSH6",
+		0x66 => "PUSH7",
+		0x67 => "PUSH8",
+		0x68 => "PUSH9",
+		0x69 => "PUSH10",
+		0x6a => "PUSH11",
+		0x6b => "PUSH12",
+		0x6c => "PUSH13",
+		0x6d => "PUSH14",
+		0x6e => "PUSH15",
+		0x6f => "PUSH16",
+		0x70 => "PUSH17",
+		0x71 => "PUSH18",
+		0x72 => "PUSH19",
+		0x73 => "PUSH20",
+		0x74 => "PUSH21",
+		0x75 => "PUSH22",
+		0x76 => "PUSH23",
+		0x77 => "PUSH24",
+		0x78 => "PUSH25",
+		0x79 => "PUSH26",
+		0x7a => "PUSH27",
+		0x7b => "PUSH28",
+		0x7c => "PUSH29",
+		0x7d => "PUSH30",
+		0x7e => "PUSH31",
+		0x7f => "PUSH32",
+		0x80 => "DUP1",
+		0x81 => "DUP2",
+		0x82 => "DUP3",
+		0x83 => "DUP4",
+		0x84 => "DUP5",
+		0x85 => "DUP6",
+		0x86 => "DUP7",
+		0x87 => "DUP8",
+		0x88 => "DUP9",
+		0x89 => "DUP10",
+		0x8a => "DUP11",
+		0x8b => "DUP12",
+		0x8c => "DUP13",
+		0x8d => "DUP14",
+		0x8e => "DUP15",
+		0x8f => "DUP16",
+		0x90 => "SWAP1",
+		0x91 => "SWAP2",
+		0x92 => "SWAP3",
+		0x93 => "SWAP4",
+		0x94 => "SWAP5",
+		0x95 => "SWAP6",
+		0x96 => "SWAP7",
+		0x97 => "SWAP8",
+		0x98 => "SWAP9",
+		0x99 => "SWAP10",
+		0x9a => "SWAP11",
+		0x9b => "SWAP12",
+		0x9c => "SWAP13",
+		0x9d => "SWAP14",
+		0x9e => "SWAP15",
+		0x9f => "SWAP16",
+		0xa0 => "LOG0",
+		0xa1 => "LOG1",
+		0xa2 => "LOG2",
+		0xa3 => "LOG3",
+		0xa4 => "LOG4",
+		0xf0 => "CREATE",
+		0xf1 => "CALL",
+		0xf2 => "CALLCODE",
+		0xf3 => "RETURN",
+		0xf4 => "DELEGATECALL",
+		0xf5 => "CREATE2",
+		0xfa => "STATICCALL",
+		0xfd => "REVERT",
+		0xfe => "INVALID",
+		0xff => "SELFDESTRUCT",
+		_ => "UNKNOWN",
+	}
+}
@@ -211,6 +211,7 @@ pub enum ReserveIdentifier {
 	Nft,
 	TransactionPayment,
 	TransactionPaymentDeposit,
+	AccountAbstraction,
 
 	// always the last, indicate number of variants
 	Count,
@@ -20,11 +20,13 @@
 
 use super::*;
 use crate::evm::{
-	decode_gas_limit, decode_gas_price, is_system_contract, EvmAddress, MAX_GAS_LIMIT_CC,
-	SYSTEM_CONTRACT_ADDRESS_PREFIX,
+	base_fee_per_gas, decode_eip1559_gas_price, decode_gas_limit, decode_gas_price, encode_gas_limit,
+	is_code_reproduced, is_system_contract, nft_class_address, nft_class_id, strip_code_metadata, EvmAddress,
+	MAX_GAS_LIMIT_CC, SYSTEM_CONTRACT_ADDRESS_PREFIX,
 };
 use frame_support::assert_ok;
 use sp_core::H160;
+use sp_runtime::{FixedPointNumber, FixedU128};
 use std::str::FromStr;
 
 #[test]
@@ -169,6 +171,33 @@ fn is_system_contract_works() {
 	assert!(!is_system_contract(&bytes.into()));
 }
 
+#[test]
+fn nft_class_address_works() {
+	assert_eq!(
+		nft_class_address(0),
+		H160::from_str("0x000000000000000000fe00000000000000000000").unwrap()
+	);
+	assert_eq!(
+		nft_class_address(0x01020304),
+		H160::from_str("0x000000000000000000fe00000000000001020304").unwrap()
+	);
+
+	assert_eq!(nft_class_id(&nft_class_address(0)), Some(0));
+	assert_eq!(nft_class_id(&nft_class_address(u32::MAX)), Some(u32::MAX));
+	assert_eq!(
+		nft_class_id(&H160::from_str("0x000000000000000000ff00000000000000000001").unwrap()),
+		None
+	);
+	assert_eq!(
+		nft_class_id(&H160::from_str("0x000000000000000000fe00000000010000000001").unwrap()),
+		None
+	);
+	assert_eq!(
+		nft_class_id(&H160::from_str("0x010000000000000000fe00000000000000000001").unwrap()),
+		None
+	);
+}
+
 #[test]
 fn decode_gas_price_works() {
 	const TX_FEE_PRE_GAS: u128 = 100_000_000_000u128; // 100 Gwei
@@ -214,6 +243,39 @@ fn decode_gas_price_works() {
 	);
 }
 
+#[test]
+#[cfg(not(feature = "evm-tests"))]
+fn decode_eip1559_gas_price_works() {
+	const TX_FEE_PRE_GAS: u128 = 100_000_000_000u128; // 100 Gwei
+
+	assert_eq!(base_fee_per_gas(TX_FEE_PRE_GAS, FixedU128::from(1)), TX_FEE_PRE_GAS);
+	assert_eq!(
+		base_fee_per_gas(TX_FEE_PRE_GAS, FixedU128::saturating_from_rational(3, 2)),
+		150_000_000_000
+	);
+	let base_fee = base_fee_per_gas(TX_FEE_PRE_GAS, FixedU128::saturating_from_rational(1, 2));
+	assert_eq!(base_fee, 50_000_000_000);
+
+	// max_fee_per_gas = 50 Gwei, max_priority_fee_per_gas = 0, gas_limit = 21000
+	assert_eq!(decode_eip1559_gas_price(50_000_000_000, 0, 21_000, base_fee), Some(0));
+	// max_fee_per_gas = 49 Gwei, max_priority_fee_per_gas = 0, gas_limit = 21000
+	assert_eq!(decode_eip1559_gas_price(49_999_999_999, 0, 21_000, base_fee), None);
+	// max_fee_per_gas = 52 Gwei, max_priority_fee_per_gas = 2 Gwei, gas_limit = 21000
+	assert_eq!(
+		decode_eip1559_gas_price(52_000_000_000, 2_000_000_000, 21_000, base_fee),
+		Some(42_000_000)
+	);
+	// max_fee_per_gas doesn't cover the whole priority fee
+	assert_eq!(
+		decode_eip1559_gas_price(51_999_999_999, 2_000_000_000, 21_000, base_fee),
+		None
+	);
+	// max_priority_fee_per_gas is not convertible to native decimals
+	assert_eq!(decode_eip1559_gas_price(100_000_000_000, 1, 21_000, base_fee), None);
+	// overflow
+	assert_eq!(decode_eip1559_gas_price(u64::MAX, 1, 21_000, u128::MAX), None);
+}
+
 #[test]
 fn decode_gas_limit_works() {
 	assert_eq!(decode_gas_limit(u64::MAX), (15_480_000, 32768));
@@ -224,3 +286,53 @@ fn decode_gas_limit_works() {
 		(15330000, 2u32.pow(MAX_GAS_LIMIT_CC))
 	);
 }
+
+#[test]
+fn encode_gas_limit_works() {
+	assert_eq!(encode_gas_limit(0, 0), Some(0));
+	assert_eq!(encode_gas_limit(21_000, 0), Some(100));
+	assert_eq!(encode_gas_limit(30_001, 64), Some(206));
+	assert_eq!(encode_gas_limit(0, 1), Some(1));
+	assert_eq!(encode_gas_limit(1_000_000, 10_000), Some(3414));
+	assert_eq!(decode_gas_limit(3414), (1_020_000, 16_384));
+	assert_eq!(encode_gas_limit(29_970_000, 2u32.pow(MAX_GAS_LIMIT_CC)), Some(99_921));
+	assert_eq!(encode_gas_limit(29_970_001, 0), None);
+	assert_eq!(encode_gas_limit(0, 2u32.pow(MAX_GAS_LIMIT_CC) + 1), None);
+}
+
+#[test]
+fn is_code_reproduced_works() {
+	// PUSH1 0x80 PUSH1 0x40 MSTORE, then a CBOR map `{"a": 1}` and its length
+	let code = [0x60, 0x80, 0x60, 0x40, 0x52];
+	let metadata = [0xa1, 0x61, 0x61, 0x01, 0x00, 0x04];
+	let with_metadata = [&code[..], &metadata[..]].concat();
+	let other_metadata = [&code[..], &[0xa1, 0x61, 0x62, 0x02, 0x00, 0x04][..]].concat();
+
+	assert_eq!(strip_code_metadata(&with_metadata), &code[..]);
+	assert_eq!(strip_code_metadata(&code), &code[..]);
+	// not a CBOR map
+	assert_eq!(strip_code_metadata(&[0x60, 0x01, 0x00, 0x01]), &[0x60, 0x01, 0x00, 0x01][..]);
+
+	assert!(is_code_reproduced(&with_metadata, &other_metadata, &[]));
+	assert!(is_code_reproduced(&code, &with_metadata, &[]));
+	assert!(!is_code_reproduced(&with_metadata, &[0x60, 0x80, 0x60, 0x41, 0x52], &[]));
+	assert!(!is_code_reproduced(&[], &[], &[]));
+
+	// immutable values are filled in by the constructor
+	let value = [0x12; 32];
+	let deployed = [&[0x60, 0x80, 0x7f][..], &value[..], &[0x7f][..], &value[..], &[0x50][..]].concat();
+	let compiled = [&[0x60, 0x80, 0x7f][..], &[0u8; 32][..], &[0x7f][..], &[0u8; 32][..], &[0x50][..]].concat();
+	assert!(!is_code_reproduced(&deployed, &compiled, &[]));
+	assert!(!is_code_reproduced(&deployed, &compiled, &[(3, 32)]));
+	assert!(is_code_reproduced(&deployed, &compiled, &[(3, 32), (36, 32)]));
+	assert!(!is_code_reproduced(&deployed, &deployed, &[(3, 32), (36, 32)]));
+
+	// ranges must be sorted, not overlap and only cover PUSH32 operands
+	assert!(!is_code_reproduced(&deployed, &compiled, &[(36, 32), (3, 32)]));
+	assert!(!is_code_reproduced(&deployed, &compiled, &[(3, 32), (3, 32), (36, 32)]));
+	assert!(!is_code_reproduced(&deployed, &compiled, &[(3, 33), (36, 32)]));
+	assert!(!is_code_reproduced(&deployed, &compiled, &[(3, 31), (36, 32)]));
+	assert!(!is_code_reproduced(&compiled, &compiled, &[(0, 32)]));
+	assert!(!is_code_reproduced(&compiled, &compiled, &[(2, 32)]));
+	assert!(!is_code_reproduced(&deployed, &compiled, &[(3, 32), (u32::MAX, 32)]));
+}
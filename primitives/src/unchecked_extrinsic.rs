@@ -18,18 +18,24 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{evm::EthereumTransactionMessage, signature::SetheumMultiSignature, to_bytes, Address, Balance};
+use crate::{
+	evm::{convert_decimals_from_evm, convert_decimals_to_evm, EthereumTransactionMessage},
+	signature::SetheumMultiSignature,
+	to_bytes, Address, Balance, Nonce,
+};
 use frame_support::{
 	dispatch::{DispatchInfo, GetDispatchInfo},
 	traits::{ExtrinsicCall, Get},
 };
 use module_evm_utility::ethereum::{
-	EIP1559TransactionMessage, EIP2930TransactionMessage, LegacyTransactionMessage, TransactionAction,
+	AccessListItem, EIP1559Transaction, EIP1559TransactionMessage, EIP2930Transaction, EIP2930TransactionMessage,
+	EnvelopedDecodable, EnvelopedEncodable, LegacyTransaction, LegacyTransactionMessage, TransactionAction,
+	TransactionSignature, TransactionV2,
 };
 use module_evm_utility_macro::keccak256;
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
-use sp_core::{H160, H256};
+use sp_core::{H160, H256, U256};
 use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
 use sp_runtime::{
 	generic::{CheckedExtrinsic, SetheumUncheckedExtrinsic},
@@ -43,13 +49,20 @@ use sp_std::{marker::PhantomData, prelude::*};
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 #[scale_info(skip_type_params(ConvertEthTx))]
-pub struct SetheumUncheckedExtrinsic<Call, Extra: SignedExtension, ConvertEthTx, StorageDepositPerByte, TxFeePerGas>(
+pub struct SetheumUncheckedExtrinsic<
+	Call,
+	Extra: SignedExtension,
+	ConvertEthTx,
+	StorageDepositPerByte,
+	TxFeePerGas,
+	BaseFeePerGas,
+>(
 	pub SetheumUncheckedExtrinsic<Address, Call, SetheumMultiSignature, Extra>,
-	PhantomData<(ConvertEthTx, StorageDepositPerByte, TxFeePerGas)>,
+	PhantomData<(ConvertEthTx, StorageDepositPerByte, TxFeePerGas, BaseFeePerGas)>,
 );
 
-impl<Call: TypeInfo, Extra: SignedExtension, ConvertEthTx, StorageDepositPerByte, TxFeePerGas> Extrinsic
-	for SetheumUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas>
+impl<Call: TypeInfo, Extra: SignedExtension, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, BaseFeePerGas> Extrinsic
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, BaseFeePerGas>
 {
 	type Call = Call;
 
@@ -71,29 +84,37 @@ impl<Call: TypeInfo, Extra: SignedExtension, ConvertEthTx, StorageDepositPerByte
 	}
 }
 
-impl<Call, Extra: SignedExtension, ConvertEthTx, StorageDepositPerByte, TxFeePerGas> ExtrinsicMetadata
-	for SetheumUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas>
+impl<Call, Extra: SignedExtension, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, BaseFeePerGas> ExtrinsicMetadata
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, BaseFeePerGas>
 {
 	const VERSION: u8 = SetheumUncheckedExtrinsic::<Address, Call, SetheumMultiSignature, Extra>::VERSION;
 	type SignedExtensions = Extra;
 }
 
-impl<Call: TypeInfo, Extra: SignedExtension, ConvertEthTx, StorageDepositPerByte, TxFeePerGas> ExtrinsicCall
-	for SetheumUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas>
+impl<
+		Call: TypeInfo,
+		Extra: SignedExtension,
+		ConvertEthTx,
+		StorageDepositPerByte,
+		TxFeePerGas,
+		BaseFeePerGas,
+	> ExtrinsicCall
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, BaseFeePerGas>
 {
 	fn call(&self) -> &Self::Call {
 		self.0.call()
 	}
 }
 
-impl<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, Lookup> Checkable<Lookup>
-	for SetheumUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas>
+impl<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, BaseFeePerGas, Lookup> Checkable<Lookup>
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, BaseFeePerGas>
 where
 	Call: Encode + Member,
 	Extra: SignedExtension<AccountId = AccountId32>,
 	ConvertEthTx: Convert<(Call, Extra), Result<(EthereumTransactionMessage, Extra), InvalidTransaction>>,
 	StorageDepositPerByte: Get<Balance>,
 	TxFeePerGas: Get<Balance>,
+	BaseFeePerGas: Get<Balance>,
 	Lookup: traits::Lookup<Source = Address, Target = AccountId32>,
 {
 	type Checked = CheckedExtrinsic<AccountId32, Call, Extra>;
@@ -126,7 +147,8 @@ where
 					gas_price: tx_gas_price.into(),
 					gas_limit: tx_gas_limit.into(),
 					action: eth_msg.action,
-					value: eth_msg.value.into(),
+					// signed in EVM decimals
+					value: convert_decimals_to_evm(eth_msg.value).into(),
 					input: eth_msg.input,
 					chain_id: Some(eth_msg.chain_id),
 				};
@@ -170,7 +192,8 @@ where
 					gas_price: tx_gas_price.into(),
 					gas_limit: tx_gas_limit.into(),
 					action: eth_msg.action,
-					value: eth_msg.value.into(),
+					// signed in EVM decimals
+					value: convert_decimals_to_evm(eth_msg.value).into(),
 					input: eth_msg.input,
 					access_list: eth_msg.access_list,
 				};
@@ -208,8 +231,14 @@ where
 					(eth_msg.gas_price as u128, eth_msg.gas_limit as u128)
 				};
 
-				// tip = priority_fee * gas_limit
-				let priority_fee = eth_msg.tip.checked_div(eth_msg.gas_limit.into()).unwrap_or_default();
+				// tip = priority_fee * gas_limit, in native decimals
+				let priority_fee =
+					convert_decimals_to_evm(eth_msg.tip.checked_div(eth_msg.gas_limit.into()).unwrap_or_default());
+
+				// eth_call_v2, the signed max fee must cover the base fee and the priority fee
+				if !eth_msg.gas_price.is_zero() && tx_gas_price < BaseFeePerGas::get().saturating_add(priority_fee) {
+					return Err(InvalidTransaction::Payment.into());
+				}
 
 				let msg = EIP1559TransactionMessage {
 					chain_id: eth_msg.chain_id,
@@ -218,7 +247,8 @@ where
 					max_fee_per_gas: tx_gas_price.into(),
 					gas_limit: tx_gas_limit.into(),
 					action: eth_msg.action,
-					value: eth_msg.value.into(),
+					// signed in EVM decimals
+					value: convert_decimals_to_evm(eth_msg.value).into(),
 					input: eth_msg.input,
 					access_list: eth_msg.access_list,
 				};
@@ -275,8 +305,8 @@ where
 	}
 }
 
-impl<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas> GetDispatchInfo
-	for SetheumUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas>
+impl<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, BaseFeePerGas> GetDispatchInfo
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, BaseFeePerGas>
 where
 	Call: GetDispatchInfo,
 	Extra: SignedExtension,
@@ -286,8 +316,15 @@ where
 	}
 }
 
-impl<Call: Encode, Extra: SignedExtension, ConvertEthTx, StorageDepositPerByte, TxFeePerGas> serde::Serialize
-	for SetheumUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas>
+impl<
+		Call: Encode,
+		Extra: SignedExtension,
+		ConvertEthTx,
+		StorageDepositPerByte,
+		TxFeePerGas,
+		BaseFeePerGas,
+	> serde::Serialize
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, BaseFeePerGas>
 {
 	fn serialize<S>(&self, seq: S) -> Result<S::Ok, S::Error>
 	where
@@ -297,8 +334,16 @@ impl<Call: Encode, Extra: SignedExtension, ConvertEthTx, StorageDepositPerByte,
 	}
 }
 
-impl<'a, Call: Decode, Extra: SignedExtension, ConvertEthTx, StorageDepositPerByte, TxFeePerGas> serde::Deserialize<'a>
-	for SetheumUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas>
+impl<
+		'a,
+		Call: Decode,
+		Extra: SignedExtension,
+		ConvertEthTx,
+		StorageDepositPerByte,
+		TxFeePerGas,
+		BaseFeePerGas,
+	> serde::Deserialize<'a>
+	for SetheumUncheckedExtrinsic<Call, Extra, ConvertEthTx, StorageDepositPerByte, TxFeePerGas, BaseFeePerGas>
 {
 	fn deserialize<D>(de: D) -> Result<Self, D::Error>
 	where
@@ -315,6 +360,175 @@ fn recover_signer(sig: &[u8; 65], msg_hash: &[u8; 32]) -> Option<H160> {
 		.ok()
 }
 
+/// A signed Ethereum transaction, as submitted to `eth_sendRawTransaction`, decoded into the
+/// arguments of `EVM::eth_call_v2` and the signature `check` verifies for it.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct EthereumTransaction {
+	pub signer: H160,
+	pub signature: SetheumMultiSignature,
+	pub chain_id: u64,
+	pub nonce: Nonce,
+	/// `max_priority_fee_per_gas` of EIP-1559 transactions. `None` for the other types, the tip
+	/// is then encoded in `gas_price`.
+	pub max_priority_fee_per_gas: Option<u64>,
+	pub action: TransactionAction,
+	pub input: Vec<u8>,
+	/// In native decimals.
+	pub value: Balance,
+	/// Encoded gas price, `max_fee_per_gas` for EIP-1559 transactions.
+	pub gas_price: u64,
+	/// Encoded gas limit.
+	pub gas_limit: u64,
+	pub access_list: Vec<AccessListItem>,
+}
+
+impl EthereumTransaction {
+	/// Hash of the signed Ethereum transaction, the `keccak_256` of the raw transaction it was
+	/// decoded from. `None` if the signature is not an Ethereum one.
+	pub fn hash(&self) -> Option<H256> {
+		let (sig, transaction_type) = match self.signature {
+			SetheumMultiSignature::Ethereum(sig) => (sig, 0u8),
+			SetheumMultiSignature::Eip2930(sig) => (sig, 1),
+			SetheumMultiSignature::Eip1559(sig) => (sig, 2),
+			_ => return None,
+		};
+		let r = H256::from_slice(&sig[0..32]);
+		let s = H256::from_slice(&sig[32..64]);
+		let value = U256::from(convert_decimals_to_evm(self.value));
+
+		let transaction = match transaction_type {
+			0 => TransactionV2::Legacy(LegacyTransaction {
+				nonce: self.nonce.into(),
+				gas_price: self.gas_price.into(),
+				gas_limit: self.gas_limit.into(),
+				action: self.action,
+				value,
+				input: self.input.clone(),
+				// EIP-155
+				signature: TransactionSignature::new(
+					self.chain_id.checked_mul(2)?.checked_add(35)?.checked_add(sig[64].into())?,
+					r,
+					s,
+				)?,
+			}),
+			1 => TransactionV2::EIP2930(EIP2930Transaction {
+				chain_id: self.chain_id,
+				nonce: self.nonce.into(),
+				gas_price: self.gas_price.into(),
+				gas_limit: self.gas_limit.into(),
+				action: self.action,
+				value,
+				input: self.input.clone(),
+				access_list: self.access_list.clone(),
+				odd_y_parity: sig[64] != 0,
+				r,
+				s,
+			}),
+			_ => TransactionV2::EIP1559(EIP1559Transaction {
+				chain_id: self.chain_id,
+				nonce: self.nonce.into(),
+				max_priority_fee_per_gas: self.max_priority_fee_per_gas?.into(),
+				max_fee_per_gas: self.gas_price.into(),
+				gas_limit: self.gas_limit.into(),
+				action: self.action,
+				value,
+				input: self.input.clone(),
+				access_list: self.access_list.clone(),
+				odd_y_parity: sig[64] != 0,
+				r,
+				s,
+			}),
+		};
+
+		Some(H256::from(keccak_256(&EnvelopedEncodable::encode(&transaction))))
+	}
+}
+
+/// Decode a signed legacy (EIP-155), EIP-2930 or EIP-1559 transaction and recover its signer.
+pub fn decode_ethereum_transaction(raw: &[u8]) -> Option<EthereumTransaction> {
+	fn to_u64(value: U256) -> Option<u64> {
+		(value <= U256::from(u64::MAX)).then(|| value.low_u64())
+	}
+	fn to_balance(value: U256) -> Option<Balance> {
+		if value > U256::from(Balance::MAX) {
+			return None;
+		}
+		convert_decimals_from_evm(value.low_u128())
+	}
+	fn to_signature(r: &H256, s: &H256, recovery_id: u8) -> [u8; 65] {
+		let mut sig = [0u8; 65];
+		sig[0..32].copy_from_slice(r.as_bytes());
+		sig[32..64].copy_from_slice(s.as_bytes());
+		sig[64] = recovery_id;
+		sig
+	}
+
+	let transaction = TransactionV2::decode(raw).ok()?;
+	let (msg_hash, sig, tx) = match transaction {
+		TransactionV2::Legacy(tx) => {
+			// replay protection is required as `check` signs with the chain id
+			let chain_id = tx.signature.chain_id()?;
+			let sig = to_signature(tx.signature.r(), tx.signature.s(), tx.signature.standard_v());
+			let msg_hash = LegacyTransactionMessage::from(tx.clone()).hash();
+			let tx = EthereumTransaction {
+				signer: Default::default(),
+				signature: SetheumMultiSignature::Ethereum(sig),
+				chain_id,
+				nonce: to_u64(tx.nonce)?.try_into().ok()?,
+				max_priority_fee_per_gas: None,
+				action: tx.action,
+				input: tx.input,
+				value: to_balance(tx.value)?,
+				gas_price: to_u64(tx.gas_price)?,
+				gas_limit: to_u64(tx.gas_limit)?,
+				access_list: Vec::new(),
+			};
+			(msg_hash, sig, tx)
+		}
+		TransactionV2::EIP2930(tx) => {
+			let sig = to_signature(&tx.r, &tx.s, tx.odd_y_parity as u8);
+			let msg_hash = EIP2930TransactionMessage::from(tx.clone()).hash();
+			let tx = EthereumTransaction {
+				signer: Default::default(),
+				signature: SetheumMultiSignature::Eip2930(sig),
+				chain_id: tx.chain_id,
+				nonce: to_u64(tx.nonce)?.try_into().ok()?,
+				max_priority_fee_per_gas: None,
+				action: tx.action,
+				input: tx.input,
+				value: to_balance(tx.value)?,
+				gas_price: to_u64(tx.gas_price)?,
+				gas_limit: to_u64(tx.gas_limit)?,
+				access_list: tx.access_list,
+			};
+			(msg_hash, sig, tx)
+		}
+		TransactionV2::EIP1559(tx) => {
+			let sig = to_signature(&tx.r, &tx.s, tx.odd_y_parity as u8);
+			let msg_hash = EIP1559TransactionMessage::from(tx.clone()).hash();
+			let tx = EthereumTransaction {
+				signer: Default::default(),
+				signature: SetheumMultiSignature::Eip1559(sig),
+				chain_id: tx.chain_id,
+				nonce: to_u64(tx.nonce)?.try_into().ok()?,
+				max_priority_fee_per_gas: Some(to_u64(tx.max_priority_fee_per_gas)?),
+				action: tx.action,
+				input: tx.input,
+				value: to_balance(tx.value)?,
+				gas_price: to_u64(tx.max_fee_per_gas)?,
+				gas_limit: to_u64(tx.gas_limit)?,
+				access_list: tx.access_list,
+			};
+			(msg_hash, sig, tx)
+		}
+	};
+
+	Some(EthereumTransaction {
+		signer: recover_signer(&sig, msg_hash.as_fixed_bytes())?,
+		..tx
+	})
+}
+
 fn verify_eip712_signature(eth_msg: EthereumTransactionMessage, sig: [u8; 65]) -> Option<H160> {
 	let domain_hash = keccak256!("EIP712Domain(string name,string version,uint256 chainId,bytes32 salt)");
 	let access_list_type_hash = keccak256!("AccessList(address address,uint256[] storageKeys)");
@@ -394,6 +608,7 @@ fn recover_sign_data(
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use frame_support::traits::ConstU128;
 	use hex_literal::hex;
 	use module_evm_utility::ethereum::AccessListItem;
 	use sp_core::U256;
@@ -672,4 +887,222 @@ mod tests {
 
 		assert_eq!(recover_sign_data(&msg, u128::MAX, u128::MAX), None);
 	}
+
+	const CHAIN_ID: u64 = 595;
+
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	enum MockCall {
+		EthCallV2(TransactionAction, Vec<u8>, Balance, u64, u64, Vec<AccessListItem>),
+	}
+
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	struct MockExtra(Nonce);
+
+	impl SignedExtension for MockExtra {
+		const IDENTIFIER: &'static str = "MockExtra";
+		type AccountId = AccountId32;
+		type Call = MockCall;
+		type AdditionalSigned = ();
+		type Pre = ();
+
+		fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+			Ok(())
+		}
+
+		fn pre_dispatch(
+			self,
+			_who: &Self::AccountId,
+			_call: &Self::Call,
+			_info: &sp_runtime::traits::DispatchInfoOf<Self::Call>,
+			_len: usize,
+		) -> Result<(), TransactionValidityError> {
+			Ok(())
+		}
+	}
+
+	/// The `eth_call_v2` arm of the runtime `ConvertEthereumTx`.
+	struct MockConvertEthTx;
+
+	impl Convert<(MockCall, MockExtra), Result<(EthereumTransactionMessage, MockExtra), InvalidTransaction>>
+		for MockConvertEthTx
+	{
+		fn convert(
+			(call, extra): (MockCall, MockExtra),
+		) -> Result<(EthereumTransactionMessage, MockExtra), InvalidTransaction> {
+			let MockCall::EthCallV2(action, input, value, gas_price, gas_limit, access_list) = call;
+			Ok((
+				EthereumTransactionMessage {
+					chain_id: CHAIN_ID,
+					genesis: Default::default(),
+					nonce: extra.0,
+					tip: 0,
+					gas_price,
+					gas_limit,
+					storage_limit: 0,
+					action,
+					value,
+					input,
+					valid_until: 0,
+					access_list,
+				},
+				extra,
+			))
+		}
+	}
+
+	type MockUncheckedExtrinsic<BaseFeePerGas> = SetheumUncheckedExtrinsic<
+		MockCall,
+		MockExtra,
+		MockConvertEthTx,
+		ConstU128<100>,
+		ConstU128<100_000_000_000>,
+		BaseFeePerGas,
+	>;
+
+	struct MockLookup;
+
+	impl traits::Lookup for MockLookup {
+		type Source = Address;
+		type Target = AccountId32;
+
+		fn lookup(&self, address: Address) -> Result<AccountId32, sp_runtime::traits::LookupError> {
+			match address {
+				Address::Address20(address) => Ok(account_of(address.into())),
+				_ => Err(sp_runtime::traits::LookupError),
+			}
+		}
+	}
+
+	fn account_of(address: H160) -> AccountId32 {
+		let mut data = [0u8; 32];
+		data[0..20].copy_from_slice(address.as_bytes());
+		AccountId32::new(data)
+	}
+
+	fn sign_legacy_transaction(pair: &sp_core::ecdsa::Pair, value: U256) -> Vec<u8> {
+		let msg = LegacyTransactionMessage {
+			nonce: U256::from(3),
+			gas_price: U256::from(200_000_000_000u64),
+			gas_limit: U256::from(2_100_000),
+			action: TransactionAction::Call(H160::from_str("0x1111111111222222222233333333334444444444").unwrap()),
+			value,
+			input: vec![0x12, 0x34],
+			chain_id: Some(CHAIN_ID),
+		};
+		let sig = pair.sign_prehashed(msg.hash().as_fixed_bytes()).0;
+		let transaction = TransactionV2::Legacy(LegacyTransaction {
+			nonce: msg.nonce,
+			gas_price: msg.gas_price,
+			gas_limit: msg.gas_limit,
+			action: msg.action,
+			value: msg.value,
+			input: msg.input,
+			signature: TransactionSignature::new(
+				CHAIN_ID * 2 + 35 + u64::from(sig[64]),
+				H256::from_slice(&sig[0..32]),
+				H256::from_slice(&sig[32..64]),
+			)
+			.unwrap(),
+		});
+		EnvelopedEncodable::encode(&transaction).to_vec()
+	}
+
+	#[test]
+	fn signed_raw_transaction_should_pass_check() {
+		use sp_core::Pair;
+
+		let pair = sp_core::ecdsa::Pair::from_seed(&[1u8; 32]);
+		// 1 SEE in EVM decimals
+		let raw = sign_legacy_transaction(&pair, U256::from(1_000_000_000_000_000_000u128));
+
+		let tx = decode_ethereum_transaction(&raw).unwrap();
+		assert_eq!(tx.value, 1_000_000_000_000);
+		assert_eq!(tx.hash(), Some(H256::from(keccak_256(&raw))));
+
+		let call = MockCall::EthCallV2(
+			tx.action,
+			tx.input.clone(),
+			tx.value,
+			tx.gas_price,
+			tx.gas_limit,
+			tx.access_list.clone(),
+		);
+		let xt = MockUncheckedExtrinsic::<ConstU128<100_000_000_000>>::new(
+			call.clone(),
+			Some((
+				Address::Address20(tx.signer.into()),
+				tx.signature.clone(),
+				MockExtra(tx.nonce),
+			)),
+		)
+		.unwrap();
+		let checked = xt.check(&MockLookup).unwrap();
+		assert_eq!(checked.signed.map(|(who, _)| who), Some(account_of(tx.signer)));
+		assert_eq!(checked.function, call);
+
+		// the signature does not cover a different value
+		let xt = MockUncheckedExtrinsic::<ConstU128<100_000_000_000>>::new(
+			MockCall::EthCallV2(tx.action, tx.input, tx.value + 1, tx.gas_price, tx.gas_limit, tx.access_list),
+			Some((
+				Address::Address20(tx.signer.into()),
+				tx.signature,
+				MockExtra(tx.nonce),
+			)),
+		)
+		.unwrap();
+		assert!(xt.check(&MockLookup).is_err());
+	}
+
+	#[test]
+	fn eip1559_transaction_should_cover_base_fee() {
+		use sp_core::Pair;
+
+		let pair = sp_core::ecdsa::Pair::from_seed(&[1u8; 32]);
+		let msg = EIP1559TransactionMessage {
+			chain_id: CHAIN_ID,
+			nonce: U256::from(3),
+			max_priority_fee_per_gas: U256::zero(),
+			max_fee_per_gas: U256::from(150_000_000_000u64),
+			gas_limit: U256::from(2_100_000),
+			action: TransactionAction::Call(H160::from_str("0x1111111111222222222233333333334444444444").unwrap()),
+			value: U256::zero(),
+			input: vec![0x12, 0x34],
+			access_list: vec![],
+		};
+		let sig = pair.sign_prehashed(msg.hash().as_fixed_bytes()).0;
+		let transaction = TransactionV2::EIP1559(EIP1559Transaction {
+			chain_id: msg.chain_id,
+			nonce: msg.nonce,
+			max_priority_fee_per_gas: msg.max_priority_fee_per_gas,
+			max_fee_per_gas: msg.max_fee_per_gas,
+			gas_limit: msg.gas_limit,
+			action: msg.action,
+			value: msg.value,
+			input: msg.input,
+			access_list: msg.access_list,
+			odd_y_parity: sig[64] != 0,
+			r: H256::from_slice(&sig[0..32]),
+			s: H256::from_slice(&sig[32..64]),
+		});
+		let tx = decode_ethereum_transaction(&EnvelopedEncodable::encode(&transaction)).unwrap();
+		let call = MockCall::EthCallV2(tx.action, tx.input, tx.value, tx.gas_price, tx.gas_limit, tx.access_list);
+		let signed = Some((
+			Address::Address20(tx.signer.into()),
+			tx.signature,
+			MockExtra(tx.nonce),
+		));
+
+		let xt = MockUncheckedExtrinsic::<ConstU128<150_000_000_000>>::new(call.clone(), signed.clone()).unwrap();
+		assert_eq!(
+			xt.check(&MockLookup).unwrap().signed.map(|(who, _)| who),
+			Some(account_of(tx.signer))
+		);
+
+		// the base fee rose above the max fee
+		let xt = MockUncheckedExtrinsic::<ConstU128<150_000_000_001>>::new(call, signed).unwrap();
+		assert_eq!(
+			xt.check(&MockLookup).map(|_| ()),
+			Err(InvalidTransaction::Payment.into())
+		);
+	}
 }